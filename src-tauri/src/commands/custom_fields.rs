@@ -0,0 +1,114 @@
+//! 自定义字段定义命令边界。
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::db::DbState;
+use crate::repos::custom_field_repo;
+use crate::services::{
+    CustomFieldDefinitionCreateInput, CustomFieldDefinitionUpdatePatch, CustomFieldService,
+};
+use crate::types::{dto::CustomFieldDefinitionDto, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCustomFieldDefinitionsArgs {
+    pub space_id: String,
+    /// 传入时返回对该项目生效的定义（space 级 + 项目级）；不传只返回 space 级定义
+    pub project_id: Option<String>,
+}
+
+/// 列出生效的自定义字段定义。
+#[tauri::command]
+pub async fn list_custom_field_definitions(
+    state: State<'_, DbState>,
+    args: ListCustomFieldDefinitionsArgs,
+) -> Result<Vec<CustomFieldDefinitionDto>, ApiError> {
+    custom_field_repo::list_applicable(&state.conn, &args.space_id, args.project_id.as_deref())
+        .await
+        .map(|items| items.into_iter().map(custom_field_repo::to_dto).collect())
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCustomFieldDefinitionArgs {
+    pub space_id: String,
+    /// 不传表示 space 级定义
+    pub project_id: Option<String>,
+    pub title: String,
+    /// text / number / date / select / multiSelect / checkbox / url
+    pub field_type: String,
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub rank: Option<i64>,
+}
+
+/// 新建自定义字段定义。
+#[tauri::command]
+pub async fn create_custom_field_definition(
+    state: State<'_, DbState>,
+    args: CreateCustomFieldDefinitionArgs,
+) -> Result<CustomFieldDefinitionDto, ApiError> {
+    CustomFieldService::create(
+        &state.conn,
+        CustomFieldDefinitionCreateInput {
+            space_id: args.space_id,
+            project_id: args.project_id,
+            title: args.title,
+            field_type: args.field_type,
+            options: args.options,
+            rank: args.rank,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCustomFieldDefinitionArgs {
+    pub id: String,
+    pub title: Option<String>,
+    pub field_type: Option<String>,
+    /// 传入时整体替换候选项
+    pub options: Option<Vec<String>>,
+    pub rank: Option<i64>,
+}
+
+/// 更新自定义字段定义；改名会同步迁移任务里的取值。
+#[tauri::command]
+pub async fn update_custom_field_definition(
+    state: State<'_, DbState>,
+    args: UpdateCustomFieldDefinitionArgs,
+) -> Result<CustomFieldDefinitionDto, ApiError> {
+    CustomFieldService::update(
+        &state.conn,
+        &args.id,
+        CustomFieldDefinitionUpdatePatch {
+            title: args.title,
+            field_type: args.field_type,
+            options: args.options,
+            rank: args.rank,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldDefinitionIdArgs {
+    pub id: String,
+}
+
+/// 删除自定义字段定义。
+#[tauri::command]
+pub async fn delete_custom_field_definition(
+    state: State<'_, DbState>,
+    args: CustomFieldDefinitionIdArgs,
+) -> Result<(), ApiError> {
+    CustomFieldService::delete(&state.conn, &args.id)
+        .await
+        .map_err(ApiError::from)
+}
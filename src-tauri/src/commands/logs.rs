@@ -5,7 +5,11 @@ use tauri::State;
 
 use crate::db::DbState;
 use crate::repos::activity_log_repo::{ActivityLogRepo, ListActivityLogsInput};
-use crate::types::{dto::ActivityLogDto, error::ApiError};
+use crate::services::UndoService;
+use crate::types::{
+    dto::{ActivityLogDto, UndoResultDto},
+    error::ApiError,
+};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,6 +18,8 @@ pub struct ListActivityLogsArgs {
     pub task_id: Option<String>,
     pub space_id: Option<String>,
     pub project_id: Option<String>,
+    /// 只看某一次写操作写出的日志
+    pub batch_id: Option<String>,
     pub from: Option<i64>,
     pub to: Option<i64>,
     pub limit: Option<u64>,
@@ -32,6 +38,7 @@ pub async fn list_activity_logs(
             task_id: args.task_id,
             space_id: args.space_id,
             project_id: args.project_id,
+            batch_id: args.batch_id,
             from: args.from,
             to: args.to,
             limit: args.limit,
@@ -41,3 +48,17 @@ pub async fn list_activity_logs(
     .await
     .map_err(ApiError::from)
 }
+
+/// 撤销最近一次操作。
+#[tauri::command]
+pub async fn undo_last(state: State<'_, DbState>) -> Result<UndoResultDto, ApiError> {
+    UndoService::undo_last(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 重做最近一次撤销。
+#[tauri::command]
+pub async fn redo(state: State<'_, DbState>) -> Result<UndoResultDto, ApiError> {
+    UndoService::redo(&state.conn).await.map_err(ApiError::from)
+}
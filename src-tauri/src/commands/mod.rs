@@ -11,10 +11,25 @@
 //! - 不管理事务边界
 //! - 不直接操作数据库实体
 
+use serde::Deserialize;
+
 pub mod assets;
+pub mod custom_fields;
 pub mod hello;
 pub mod logs;
 pub mod projects;
+pub mod search;
 pub mod spaces;
 pub mod sync;
 pub mod tasks;
+pub mod trash;
+
+/// 依赖“今天”等本地日期的命令共用的时区参数。
+///
+/// 以 `#[serde(flatten)]` 嵌进各命令的参数结构，前端仍平铺传 `utcOffsetMinutes`。
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalTzArgs {
+    /// 本地时区相对 UTC 的偏移分钟数（东区为正）；不传使用系统时区
+    pub utc_offset_minutes: Option<i32>,
+}
@@ -5,24 +5,39 @@
 //! - `Option<Option<T>>` 在“清空字段 vs 不修改字段”场景的语义
 //! - 纯查询直达 `ProjectRepo`，写命令统一走 `ProjectService`
 
+use std::collections::HashMap;
+
 use serde::Deserialize;
 use tauri::State;
 
-use crate::db::DbState;
-use crate::repos::project_repo::ProjectRepo;
+use super::LocalTzArgs;
+use crate::db::{now_ms, DbState};
+use crate::repos::project_repo::{
+    helpers,
+    schedule::AtRiskQuery,
+    timeline::{TimelineEntryType, TimelineQuery},
+    ProjectRepo,
+};
 use crate::services::{
-    ProjectCreateInput, ProjectService, ProjectUpdateInput,
+    ProjectCloneInput, ProjectCreateInput, ProjectMilestoneCreateInput,
+    ProjectMilestoneUpdatePatch, ProjectReorderInput, ProjectService,
+    ProjectTemplateInstantiateInput, ProjectTemplateSaveInput, ProjectUpdateInput,
     ProjectUpdatePatch as ServiceProjectUpdatePatch,
 };
 use crate::types::{
-    dto::{LinkInputDto, ProjectDto},
-    error::ApiError,
+    dto::{
+        LinkInputDto, ProjectDto, ProjectMilestoneDto, ProjectRiskDto, ProjectTemplateDto,
+        ProjectTimeRollupDto, ProjectTimelinePageDto,
+    },
+    error::{ApiError, AppError},
 };
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListProjectsArgs {
     pub space_id: String,
+    /// 按生命周期状态过滤；不传表示全部
+    pub lifecycle_statuses: Option<Vec<String>>,
 }
 
 /// 列出某个 Space 下的全部 Project（包括 Default Project 与其子节点）。
@@ -31,22 +46,121 @@ pub async fn list_projects(
     state: State<'_, DbState>,
     args: ListProjectsArgs,
 ) -> Result<Vec<ProjectDto>, ApiError> {
-    ProjectRepo::list_by_space(&state.conn, &args.space_id)
+    let lifecycle_statuses = args
+        .lifecycle_statuses
+        .unwrap_or_default()
+        .iter()
+        .map(|value| helpers::parse_lifecycle_status(value))
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    ProjectRepo::list_by_space(&state.conn, &args.space_id, &lifecycle_statuses)
         .await
         .map_err(ApiError::from)
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeletedProjectsArgs {
+    pub space_id: String,
+}
+
 /// 列出某个 Space 下的已软删除 Project。
 #[tauri::command]
 pub async fn list_deleted_projects(
     state: State<'_, DbState>,
-    args: ListProjectsArgs,
+    args: ListDeletedProjectsArgs,
 ) -> Result<Vec<ProjectDto>, ApiError> {
     ProjectRepo::list_deleted_by_space(&state.conn, &args.space_id)
         .await
         .map_err(ApiError::from)
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTimeRollupArgs {
+    pub project_id: String,
+}
+
+/// 汇总项目及其子项目的计时时长。
+#[tauri::command]
+pub async fn get_project_time_rollup(
+    state: State<'_, DbState>,
+    args: ProjectTimeRollupArgs,
+) -> Result<ProjectTimeRollupDto, ApiError> {
+    ProjectRepo::time_rollup(&state.conn, &args.project_id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProjectTimelineArgs {
+    pub project_id: String,
+    /// taskCompleted / taskLog / projectLog / note / snippet / diary；不传表示全部
+    pub entry_types: Option<Vec<String>>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+/// 项目及其子项目的时间线：任务完成、活动日志、笔记、片段与日记按时间倒序合并。
+#[tauri::command]
+pub async fn get_project_timeline(
+    state: State<'_, DbState>,
+    args: GetProjectTimelineArgs,
+) -> Result<ProjectTimelinePageDto, ApiError> {
+    let entry_types = args
+        .entry_types
+        .unwrap_or_default()
+        .iter()
+        .map(|value| TimelineEntryType::parse(value))
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    ProjectRepo::timeline(
+        &state.conn,
+        TimelineQuery {
+            project_id: args.project_id,
+            entry_types,
+            from: args.from,
+            to: args.to,
+            cursor: args.cursor,
+            limit: args.limit,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListProjectsAtRiskArgs {
+    pub space_id: String,
+    /// 截止时间在多少天内算临近，默认 7
+    pub due_within_days: Option<i64>,
+    /// 未完成任务占比达到多少算风险，默认 0.5
+    pub min_open_ratio: Option<f64>,
+}
+
+/// 列出排期有风险的项目，按截止时间升序。
+#[tauri::command]
+pub async fn list_projects_at_risk(
+    state: State<'_, DbState>,
+    args: ListProjectsAtRiskArgs,
+) -> Result<Vec<ProjectRiskDto>, ApiError> {
+    ProjectRepo::list_at_risk(
+        &state.conn,
+        AtRiskQuery {
+            space_id: args.space_id,
+            now: now_ms(),
+            due_within_days: args.due_within_days,
+            min_open_ratio: args.min_open_ratio,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateProjectArgs {
@@ -55,9 +169,12 @@ pub struct CreateProjectArgs {
     pub parent_id: Option<String>,
     pub note: Option<String>,
     pub priority: Option<String>,
-    pub rank: Option<i64>,
+    /// 分数排序键；不传时排到同级末尾
+    pub rank: Option<String>,
     pub tags: Option<Vec<String>>,
     pub links: Option<Vec<LinkInputDto>>,
+    /// planned / active / paused / completed / cancelled；不传为 active
+    pub lifecycle_status: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -83,6 +200,14 @@ pub struct UpdateProjectPatch {
     pub tags: Option<Vec<String>>,
     /// 传空数组表示清空链接。
     pub links: Option<Vec<LinkInputDto>>,
+    /// 生命周期状态，非法流转会被拒绝。
+    pub lifecycle_status: Option<String>,
+    /// Some(None) 表示清空开始时间；需落在父项目日期范围内。
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub start_at: Option<Option<i64>>,
+    /// Some(None) 表示清空截止时间；需落在父项目日期范围内。
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub due_at: Option<Option<i64>>,
 }
 
 impl From<UpdateProjectPatch> for ServiceProjectUpdatePatch {
@@ -96,6 +221,9 @@ impl From<UpdateProjectPatch> for ServiceProjectUpdatePatch {
             parent_id: value.parent_id,
             tags: value.tags,
             links: value.links,
+            lifecycle_status: value.lifecycle_status,
+            start_at: value.start_at,
+            due_at: value.due_at,
         }
     }
 }
@@ -118,6 +246,7 @@ pub async fn create_project(
             rank: args.rank,
             tags: args.tags,
             links: args.links,
+            lifecycle_status: args.lifecycle_status,
         },
     )
     .await
@@ -156,7 +285,12 @@ pub async fn get_default_project(
 #[serde(rename_all = "camelCase")]
 pub struct ReorderProjectArgs {
     pub project_id: String,
-    pub new_rank: i64,
+    /// 显式指定排序键；不传时按前后邻居计算
+    pub new_rank: Option<String>,
+    /// 移动后紧挨在上方的同级项目
+    pub prev_project_id: Option<String>,
+    /// 移动后紧挨在下方的同级项目
+    pub next_project_id: Option<String>,
     /// 可选：如果需要更新父节点（跨层级拖拽）
     /// - None：不修改 parent
     /// - Some(None)：显式设为根节点
@@ -165,17 +299,21 @@ pub struct ReorderProjectArgs {
     pub new_parent_id: Option<Option<String>>,
 }
 
-/// 调整项目排序。
+/// 调整项目排序，返回新的排序键。
 #[tauri::command]
 pub async fn reorder_project(
     state: State<'_, DbState>,
     args: ReorderProjectArgs,
-) -> Result<(), ApiError> {
+) -> Result<String, ApiError> {
     ProjectService::reorder(
         &state.conn,
-        &args.project_id,
-        args.new_rank,
-        args.new_parent_id,
+        ProjectReorderInput {
+            project_id: args.project_id,
+            new_rank: args.new_rank,
+            prev_project_id: args.prev_project_id,
+            next_project_id: args.next_project_id,
+            new_parent_id: args.new_parent_id,
+        },
     )
     .await
     .map_err(ApiError::from)
@@ -186,17 +324,15 @@ pub async fn reorder_project(
 pub struct RebalanceProjectRanksArgs {
     /// 按顺序排列的项目 ID 列表
     pub project_ids: Vec<String>,
-    /// 步长，默认 1024
-    pub step: Option<i64>,
 }
 
-/// 批量重排项目 rank。
+/// 按顺序整体重排项目 rank；只在邻居之间没有空位时才需要。
 #[tauri::command]
 pub async fn rebalance_project_ranks(
     state: State<'_, DbState>,
     args: RebalanceProjectRanksArgs,
 ) -> Result<(), ApiError> {
-    ProjectService::rebalance(&state.conn, &args.project_ids, args.step.unwrap_or(1024))
+    ProjectService::rebalance(&state.conn, &args.project_ids)
         .await
         .map_err(ApiError::from)
 }
@@ -250,3 +386,253 @@ pub async fn unarchive_project(
         .await
         .map_err(ApiError::from)
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneProjectSubtreeArgs {
+    pub project_id: String,
+    /// - 不传：与原项目同级
+    /// - null：放到根节点
+    /// - "x"：放到 x 下面
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub parent_id: Option<Option<String>>,
+    /// 放到根节点时的目标 space；不传沿用原项目所在 space
+    pub space_id: Option<String>,
+    /// 覆盖根项目标题
+    pub title: Option<String>,
+    /// 是否连同子项目一起复制，默认 true
+    pub include_children: Option<bool>,
+    /// 是否复制已完成的任务，默认 false
+    pub include_done_tasks: Option<bool>,
+}
+
+/// 深拷贝项目子树（标签、链接、任务与自定义字段），返回新的根项目。
+#[tauri::command]
+pub async fn clone_project_subtree(
+    state: State<'_, DbState>,
+    args: CloneProjectSubtreeArgs,
+) -> Result<ProjectDto, ApiError> {
+    ProjectService::clone_subtree(
+        &state.conn,
+        ProjectCloneInput {
+            project_id: args.project_id,
+            parent_id: args.parent_id,
+            space_id: args.space_id,
+            title: args.title,
+            include_children: args.include_children.unwrap_or(true),
+            include_done_tasks: args.include_done_tasks.unwrap_or(false),
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+/// 列出项目模板。
+#[tauri::command]
+pub async fn list_project_templates(
+    state: State<'_, DbState>,
+) -> Result<Vec<ProjectTemplateDto>, ApiError> {
+    ProjectRepo::list_templates(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveProjectAsTemplateArgs {
+    pub project_id: String,
+    pub name: String,
+    /// 为 true 时已完成的任务也作为起始任务保存；默认 false
+    pub include_done_tasks: Option<bool>,
+}
+
+/// 把项目及其子树保存为模板。
+#[tauri::command]
+pub async fn save_project_as_template(
+    state: State<'_, DbState>,
+    args: SaveProjectAsTemplateArgs,
+) -> Result<ProjectTemplateDto, ApiError> {
+    ProjectService::save_as_template(
+        &state.conn,
+        ProjectTemplateSaveInput {
+            project_id: args.project_id,
+            name: args.name,
+            include_done_tasks: args.include_done_tasks.unwrap_or(false),
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameProjectTemplateArgs {
+    pub id: String,
+    pub name: String,
+}
+
+/// 重命名项目模板。
+#[tauri::command]
+pub async fn rename_project_template(
+    state: State<'_, DbState>,
+    args: RenameProjectTemplateArgs,
+) -> Result<ProjectTemplateDto, ApiError> {
+    ProjectService::rename_template(&state.conn, &args.id, &args.name)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTemplateIdArgs {
+    pub id: String,
+}
+
+/// 删除项目模板。
+#[tauri::command]
+pub async fn delete_project_template(
+    state: State<'_, DbState>,
+    args: ProjectTemplateIdArgs,
+) -> Result<(), ApiError> {
+    ProjectService::delete_template(&state.conn, &args.id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateProjectTemplateArgs {
+    pub template_id: String,
+    /// 目标 space；传入父项目时可省略
+    pub space_id: Option<String>,
+    pub parent_id: Option<String>,
+    /// 覆盖根项目标题
+    pub title: Option<String>,
+    /// 额外的占位符取值，例如 `{ "client": "ACME" }`
+    pub variables: Option<HashMap<String, String>>,
+    #[serde(flatten)]
+    pub local_tz: LocalTzArgs,
+}
+
+/// 按模板创建整棵项目树，返回根项目。
+#[tauri::command]
+pub async fn instantiate_project_template(
+    state: State<'_, DbState>,
+    args: InstantiateProjectTemplateArgs,
+) -> Result<ProjectDto, ApiError> {
+    ProjectService::instantiate_template(
+        &state.conn,
+        ProjectTemplateInstantiateInput {
+            template_id: args.template_id,
+            space_id: args.space_id,
+            parent_id: args.parent_id,
+            title: args.title,
+            variables: args.variables.unwrap_or_default(),
+            utc_offset_minutes: args.local_tz.utc_offset_minutes,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListProjectMilestonesArgs {
+    pub project_id: String,
+}
+
+/// 列出项目里程碑（含任务进度）。
+#[tauri::command]
+pub async fn list_project_milestones(
+    state: State<'_, DbState>,
+    args: ListProjectMilestonesArgs,
+) -> Result<Vec<ProjectMilestoneDto>, ApiError> {
+    ProjectRepo::list_milestones(&state.conn, &args.project_id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProjectMilestoneArgs {
+    pub project_id: String,
+    pub title: String,
+    pub target_at: Option<i64>,
+    /// 不传时排到项目末尾
+    pub rank: Option<i64>,
+}
+
+/// 新建项目里程碑。
+#[tauri::command]
+pub async fn create_project_milestone(
+    state: State<'_, DbState>,
+    args: CreateProjectMilestoneArgs,
+) -> Result<ProjectMilestoneDto, ApiError> {
+    ProjectService::create_milestone(
+        &state.conn,
+        ProjectMilestoneCreateInput {
+            project_id: args.project_id,
+            title: args.title,
+            target_at: args.target_at,
+            rank: args.rank,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProjectMilestoneArgs {
+    pub milestone_id: String,
+    pub patch: UpdateProjectMilestonePatch,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProjectMilestonePatch {
+    pub title: Option<String>,
+    /// Some(None) 表示清空目标日期。
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub target_at: Option<Option<i64>>,
+    pub rank: Option<i64>,
+    /// true 标记完成，false 重新打开。
+    pub completed: Option<bool>,
+}
+
+/// 更新项目里程碑。
+#[tauri::command]
+pub async fn update_project_milestone(
+    state: State<'_, DbState>,
+    args: UpdateProjectMilestoneArgs,
+) -> Result<ProjectMilestoneDto, ApiError> {
+    ProjectService::update_milestone(
+        &state.conn,
+        &args.milestone_id,
+        ProjectMilestoneUpdatePatch {
+            title: args.patch.title,
+            target_at: args.patch.target_at,
+            rank: args.patch.rank,
+            completed: args.patch.completed,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteProjectMilestoneArgs {
+    pub milestone_id: String,
+}
+
+/// 删除项目里程碑，挂在上面的任务移出里程碑。
+#[tauri::command]
+pub async fn delete_project_milestone(
+    state: State<'_, DbState>,
+    args: DeleteProjectMilestoneArgs,
+) -> Result<(), ApiError> {
+    ProjectService::delete_milestone(&state.conn, &args.milestone_id)
+        .await
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,58 @@
+//! 全文搜索命令边界。
+//! 重点：纯查询命令，直达 `search_repo`，索引维护由各写用例负责。
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::db::DbState;
+use crate::repos::search_repo::{self, SearchEntityType, SearchQuery};
+use crate::types::{
+    dto::SearchHitDto,
+    error::{ApiError, AppError},
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchArgs {
+    pub query: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub tag: Option<String>,
+    /// task / project / note / snippet / diary，不传表示全部类型
+    pub entity_types: Option<Vec<String>>,
+    /// 是否包含已删除记录，默认 false
+    pub include_deleted: Option<bool>,
+    /// 是否包含已归档记录，默认 false
+    pub include_archived: Option<bool>,
+    pub limit: Option<u64>,
+}
+
+/// 跨任务、项目、笔记、代码片段和日记的全文搜索。
+#[tauri::command]
+pub async fn search(
+    state: State<'_, DbState>,
+    args: SearchArgs,
+) -> Result<Vec<SearchHitDto>, ApiError> {
+    let entity_types = args
+        .entity_types
+        .unwrap_or_default()
+        .iter()
+        .map(|value| SearchEntityType::parse(value))
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    search_repo::search(
+        &state.conn,
+        SearchQuery {
+            text: &args.query,
+            space_id: args.space_id.as_deref(),
+            project_id: args.project_id.as_deref(),
+            tag: args.tag.as_deref(),
+            entity_types,
+            include_deleted: args.include_deleted.unwrap_or(false),
+            include_archived: args.include_archived.unwrap_or(false),
+            limit: args.limit,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
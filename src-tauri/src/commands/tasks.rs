@@ -5,17 +5,28 @@
 //! - tri-state（`Option<Option<T>>`）表达“保持/清空/设置”
 //! - 读命令直达 `TaskRepo`，写命令统一走 `TaskService`
 
+use std::collections::HashMap;
+
 use serde::Deserialize;
 use tauri::State;
 
+use super::LocalTzArgs;
 use crate::db::DbState;
-use crate::repos::task_repo::TaskRepo;
+use crate::repos::task_repo::{list::TaskListQuery, TaskRepo};
 use crate::services::{
-    TaskCreateInput, TaskCreatePatch, TaskService, TaskUpdateInput,
-    TaskUpdatePatch as ServiceTaskUpdatePatch,
+    ChecklistItemCreateInput, ChecklistItemUpdateInput, ChecklistItemUpdatePatch, QuickAddInput,
+    ReminderSchedulerHandle, TaskCreateInput, TaskCreatePatch, TaskDuplicateInput,
+    TaskReorderInput, TaskService, TaskTemplateCreateInput, TaskTemplateInstantiateInput,
+    TaskTemplateUpdatePatch, TaskUpdateInput, TaskUpdatePatch as ServiceTaskUpdatePatch,
+    TimerStartInput,
 };
 use crate::types::{
-    dto::{CustomFieldsDto, LinkInputDto, TaskDto},
+    dto::{
+        BulkUpdateResultDto, ChecklistItemDto, CustomFieldAggregateDto, CustomFieldPredicateDto,
+        CustomFieldsDto, LinkInputDto, QuickAddResultDto, RecurrenceRuleDto, TaskCompleteResultDto,
+        TaskDto, TaskPageDto, TaskReminderDto, TaskSortDto, TaskTemplateBodyDto, TaskTemplateDto,
+        TimeEntryDto, TimeRangeDto, TodayViewDto,
+    },
     error::ApiError,
 };
 
@@ -25,6 +36,8 @@ pub struct ListTasksArgs {
     pub space_id: Option<String>,
     pub status: Option<String>,
     pub project_id: Option<String>,
+    /// 为 true 时隐藏仍有未完成前置任务的任务
+    pub exclude_blocked: Option<bool>,
 }
 
 /// 列出任务。
@@ -40,6 +53,132 @@ pub async fn list_tasks(
         args.space_id.as_deref(),
         args.status.as_deref(),
         args.project_id.as_deref(),
+        args.exclude_blocked.unwrap_or(false),
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTasksArgs {
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub statuses: Option<Vec<String>>,
+    pub priorities: Option<Vec<String>>,
+    pub tags_any: Option<Vec<String>>,
+    pub tags_all: Option<Vec<String>>,
+    pub deadline: Option<TimeRangeDto>,
+    pub scheduled: Option<TimeRangeDto>,
+    pub created: Option<TimeRangeDto>,
+    pub completed: Option<TimeRangeDto>,
+    pub text: Option<String>,
+    pub custom_fields: Option<Vec<CustomFieldPredicateDto>>,
+    /// include / exclude / only
+    pub archived: Option<String>,
+    /// include / exclude / only，按当前时间判断是否仍在推迟中
+    pub deferred: Option<String>,
+    pub exclude_blocked: Option<bool>,
+    pub sort: Option<Vec<TaskSortDto>>,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+/// 结构化查询任务（过滤 + 排序 + 游标分页）。
+#[tauri::command]
+pub async fn query_tasks(
+    state: State<'_, DbState>,
+    args: QueryTasksArgs,
+) -> Result<TaskPageDto, ApiError> {
+    TaskRepo::query(
+        &state.conn,
+        TaskListQuery {
+            space_id: args.space_id,
+            project_id: args.project_id,
+            statuses: args.statuses.unwrap_or_default(),
+            priorities: args.priorities.unwrap_or_default(),
+            tags_any: args.tags_any.unwrap_or_default(),
+            tags_all: args.tags_all.unwrap_or_default(),
+            deadline: args.deadline,
+            scheduled: args.scheduled,
+            created: args.created,
+            completed: args.completed,
+            text: args.text,
+            custom_fields: args.custom_fields.unwrap_or_default(),
+            archived: args.archived,
+            deferred: args.deferred,
+            exclude_blocked: args.exclude_blocked.unwrap_or(false),
+            sort: args.sort.unwrap_or_default(),
+            cursor: args.cursor,
+            limit: args.limit,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateTasksByCustomFieldArgs {
+    /// 分组字段标题
+    pub group_by: String,
+    /// 求和字段标题，按数值累加
+    pub sum_field: Option<String>,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub statuses: Option<Vec<String>>,
+    pub priorities: Option<Vec<String>>,
+    pub tags_any: Option<Vec<String>>,
+    pub tags_all: Option<Vec<String>>,
+    pub text: Option<String>,
+    pub custom_fields: Option<Vec<CustomFieldPredicateDto>>,
+    /// include / exclude / only
+    pub archived: Option<String>,
+}
+
+/// 按自定义字段取值分项目统计任务数与数值合计。
+#[tauri::command]
+pub async fn aggregate_tasks_by_custom_field(
+    state: State<'_, DbState>,
+    args: AggregateTasksByCustomFieldArgs,
+) -> Result<Vec<CustomFieldAggregateDto>, ApiError> {
+    TaskRepo::aggregate_by_custom_field(
+        &state.conn,
+        TaskListQuery {
+            space_id: args.space_id,
+            project_id: args.project_id,
+            statuses: args.statuses.unwrap_or_default(),
+            priorities: args.priorities.unwrap_or_default(),
+            tags_any: args.tags_any.unwrap_or_default(),
+            tags_all: args.tags_all.unwrap_or_default(),
+            text: args.text,
+            custom_fields: args.custom_fields.unwrap_or_default(),
+            archived: args.archived,
+            ..Default::default()
+        },
+        &args.group_by,
+        args.sum_field.as_deref(),
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTodayArgs {
+    #[serde(flatten)]
+    pub local_tz: LocalTzArgs,
+}
+
+/// “今天”视图：跨全部 Space 汇总逾期、今天截止、今天计划与进行中的任务。
+#[tauri::command]
+pub async fn list_today(
+    state: State<'_, DbState>,
+    args: Option<ListTodayArgs>,
+) -> Result<TodayViewDto, ApiError> {
+    TaskRepo::list_today(
+        &state.conn,
+        args.unwrap_or_default().local_tz.utc_offset_minutes,
     )
     .await
     .map_err(ApiError::from)
@@ -74,7 +213,7 @@ pub async fn list_deleted_tasks(
 pub struct CreateTaskArgs {
     pub space_id: String,
     pub title: String,
-    /// 前端 settings.autoStart 的值；不传默认 true
+    /// 为 true 时新任务直接进入 doing；不传默认 false
     pub auto_start: Option<bool>,
     /// 项目 ID（可选，如果不提供则关联到默认项目）
     pub project_id: Option<String>,
@@ -94,7 +233,7 @@ pub async fn create_task(
         TaskCreateInput {
             space_id: args.space_id,
             title: args.title,
-            auto_start: args.auto_start.unwrap_or(true),
+            auto_start: args.auto_start.unwrap_or(false),
             project_id: args.project_id,
             patch: TaskCreatePatch::default(),
         },
@@ -115,15 +254,22 @@ pub struct CreateTaskWithPatchArgs {
     pub priority: Option<String>,
     pub note: Option<String>,
     pub deadline_at: Option<i64>,
+    /// 计划处理时间
+    pub scheduled_at: Option<i64>,
+    /// 推迟到该时间之前不在默认列表中出现
+    pub defer_until: Option<i64>,
     pub tags: Option<Vec<String>>,
     pub links: Option<Vec<LinkInputDto>>,
     pub custom_fields: Option<CustomFieldsDto>,
+    pub recurrence: Option<RecurrenceRuleDto>,
+    /// 前置任务 id 列表
+    pub blocked_by: Option<Vec<String>>,
 }
 
 /// 创建带 patch 的任务。
 ///
 /// 与 `create_task` 相比，这个入口允许一次性写入状态、优先级、
-/// 截止时间、标签、链接和自定义字段。
+/// 截止时间、标签、链接、前置任务、自定义字段和重复规则。
 #[tauri::command]
 pub async fn create_task_with_patch(
     state: State<'_, DbState>,
@@ -134,7 +280,7 @@ pub async fn create_task_with_patch(
         TaskCreateInput {
             space_id: args.space_id,
             title: args.title,
-            auto_start: args.auto_start.unwrap_or(true),
+            auto_start: args.auto_start.unwrap_or(false),
             project_id: args.project_id,
             patch: TaskCreatePatch {
                 status: args.status,
@@ -142,9 +288,13 @@ pub async fn create_task_with_patch(
                 priority: args.priority,
                 note: args.note,
                 deadline_at: args.deadline_at,
+                scheduled_at: args.scheduled_at,
+                defer_until: args.defer_until,
                 tags: args.tags,
                 links: args.links,
                 custom_fields: args.custom_fields,
+                recurrence: args.recurrence,
+                blocked_by: args.blocked_by,
             },
         },
     )
@@ -152,6 +302,38 @@ pub async fn create_task_with_patch(
     .map_err(ApiError::from)
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddArgs {
+    /// 例如 `修复登录 #backend !P0 @work/Auth due:fri 5pm every:week`
+    pub text: String,
+    /// 文本里没有写 `@space` 时使用的 space
+    pub space_id: Option<String>,
+    #[serde(flatten)]
+    pub local_tz: LocalTzArgs,
+    /// 为 true 时只返回解析预览，不创建任务
+    pub dry_run: Option<bool>,
+}
+
+/// 快速录入：解析一行文本并创建任务，同时返回解析预览。
+#[tauri::command]
+pub async fn quick_add(
+    state: State<'_, DbState>,
+    args: QuickAddArgs,
+) -> Result<QuickAddResultDto, ApiError> {
+    TaskService::quick_add(
+        &state.conn,
+        QuickAddInput {
+            text: args.text,
+            space_id: args.space_id,
+            utc_offset_minutes: args.local_tz.utc_offset_minutes,
+            dry_run: args.dry_run.unwrap_or(false),
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTaskArgs {
@@ -178,11 +360,20 @@ pub struct UpdateTaskPatch {
     /// Some(None) 表示设为未分类
     #[serde(default, with = "::serde_with::rust::double_option")]
     pub project_id: Option<Option<String>>,
+    /// Some(None) 表示移出里程碑；切换项目且不传时自动移出原项目的里程碑
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub milestone_id: Option<Option<String>>,
     /// Some(None) 表示清空截止日期
     #[serde(default, with = "::serde_with::rust::double_option")]
     pub deadline_at: Option<Option<i64>>,
-    /// 更新排序权重
-    pub rank: Option<i64>,
+    /// Some(None) 表示清空计划时间
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub scheduled_at: Option<Option<i64>>,
+    /// Some(None) 表示取消推迟
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub defer_until: Option<Option<i64>>,
+    /// 更新排序键（分数排序键，只能由 0-9、a-z 组成）
+    pub rank: Option<String>,
     /// 外部链接列表（传空数组表示清空）
     pub links: Option<Vec<LinkInputDto>>,
     /// Some(None) 表示清空 customFields
@@ -194,6 +385,11 @@ pub struct UpdateTaskPatch {
     /// Some(None) 表示清空删除时间
     #[serde(default, with = "::serde_with::rust::double_option")]
     pub deleted_at: Option<Option<i64>>,
+    /// Some(None) 表示取消重复
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub recurrence: Option<Option<RecurrenceRuleDto>>,
+    /// 前置任务 id 列表（传空数组表示清空）
+    pub blocked_by: Option<Vec<String>>,
 }
 
 impl From<UpdateTaskPatch> for ServiceTaskUpdatePatch {
@@ -210,12 +406,17 @@ impl From<UpdateTaskPatch> for ServiceTaskUpdatePatch {
             tags: value.tags,
             space_id: value.space_id,
             project_id: value.project_id,
+            milestone_id: value.milestone_id,
             deadline_at: value.deadline_at,
+            scheduled_at: value.scheduled_at,
+            defer_until: value.defer_until,
             rank: value.rank,
             links: value.links,
             custom_fields: value.custom_fields,
             archived_at: value.archived_at,
             deleted_at: value.deleted_at,
+            recurrence: value.recurrence,
+            blocked_by: value.blocked_by,
         }
     }
 }
@@ -234,18 +435,61 @@ pub async fn update_task(state: State<'_, DbState>, args: UpdateTaskArgs) -> Res
     .map_err(ApiError::from)
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateTasksArgs {
+    pub ids: Vec<String>,
+    pub patch: UpdateTaskPatch,
+}
+
+/// 批量更新任务：同一个 patch 应用到多个任务，逐条返回成功或失败原因。
+#[tauri::command]
+pub async fn bulk_update_tasks(
+    state: State<'_, DbState>,
+    args: BulkUpdateTasksArgs,
+) -> Result<BulkUpdateResultDto, ApiError> {
+    TaskService::bulk_update(&state.conn, &args.ids, args.patch.into())
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateTasksArgs {
+    pub ids: Vec<String>,
+    /// 副本放入的项目；不传时留在原任务所在项目
+    pub project_id: Option<String>,
+}
+
+/// 复制一组任务，返回新建的副本。
+#[tauri::command]
+pub async fn duplicate_tasks(
+    state: State<'_, DbState>,
+    args: DuplicateTasksArgs,
+) -> Result<Vec<TaskDto>, ApiError> {
+    TaskService::duplicate(
+        &state.conn,
+        TaskDuplicateInput {
+            task_ids: args.ids,
+            project_id: args.project_id,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompleteTaskArgs {
     pub id: String,
 }
 
-/// 直接完成任务。
+/// 直接完成任务，返回因此解除阻塞的任务与生成的下一次重复任务。
 #[tauri::command]
 pub async fn complete_task(
     state: State<'_, DbState>,
     args: CompleteTaskArgs,
-) -> Result<(), ApiError> {
+) -> Result<TaskCompleteResultDto, ApiError> {
     TaskService::complete(&state.conn, &args.id)
         .await
         .map_err(ApiError::from)
@@ -285,22 +529,109 @@ pub async fn restore_tasks(
         .map_err(ApiError::from)
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListArchivedTasksArgs {
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+}
+
+/// 列出已归档任务。
+#[tauri::command]
+pub async fn list_archived_tasks(
+    state: State<'_, DbState>,
+    args: ListArchivedTasksArgs,
+) -> Result<Vec<TaskDto>, ApiError> {
+    TaskRepo::list_archived(
+        &state.conn,
+        args.space_id.as_deref(),
+        args.project_id.as_deref(),
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveTasksArgs {
+    pub ids: Vec<String>,
+}
+
+/// 批量归档任务。
+#[tauri::command]
+pub async fn archive_tasks(
+    state: State<'_, DbState>,
+    args: ArchiveTasksArgs,
+) -> Result<usize, ApiError> {
+    TaskService::archive_many(&state.conn, &args.ids)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 批量取消归档任务。
+#[tauri::command]
+pub async fn unarchive_tasks(
+    state: State<'_, DbState>,
+    args: ArchiveTasksArgs,
+) -> Result<usize, ApiError> {
+    TaskService::unarchive_many(&state.conn, &args.ids)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[tauri::command]
+pub async fn get_task_auto_archive_days(state: State<'_, DbState>) -> Result<i64, ApiError> {
+    TaskService::get_auto_archive_days(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTaskAutoArchiveDaysArgs {
+    /// 完成超过这么多天的任务自动归档；0 表示关闭
+    pub days: i64,
+}
+
+#[tauri::command]
+pub async fn set_task_auto_archive_days(
+    state: State<'_, DbState>,
+    args: SetTaskAutoArchiveDaysArgs,
+) -> Result<i64, ApiError> {
+    TaskService::set_auto_archive_days(&state.conn, args.days)
+        .await
+        .map_err(ApiError::from)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReorderTaskArgs {
     pub task_id: String,
-    pub new_rank: i64,
+    /// 显式指定排序键；不传时按前后邻居计算
+    pub new_rank: Option<String>,
+    /// 移动后紧挨在上方的任务
+    pub prev_task_id: Option<String>,
+    /// 移动后紧挨在下方的任务
+    pub next_task_id: Option<String>,
 }
 
-/// 调整单个任务排序。
+/// 调整单个任务排序，返回新的排序键。
 #[tauri::command]
 pub async fn reorder_task(
     state: State<'_, DbState>,
     args: ReorderTaskArgs,
-) -> Result<(), ApiError> {
-    TaskService::reorder(&state.conn, args.task_id, args.new_rank)
-        .await
-        .map_err(ApiError::from)
+) -> Result<String, ApiError> {
+    TaskService::reorder(
+        &state.conn,
+        TaskReorderInput {
+            task_id: args.task_id,
+            new_rank: args.new_rank,
+            prev_task_id: args.prev_task_id,
+            next_task_id: args.next_task_id,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
 }
 
 #[derive(Debug, Deserialize)]
@@ -308,17 +639,451 @@ pub async fn reorder_task(
 pub struct RebalanceRanksArgs {
     /// 按顺序排列的任务 ID 列表
     pub task_ids: Vec<String>,
-    /// 步长，默认 1024
-    pub step: Option<i64>,
 }
 
-/// 按顺序批量重排任务 rank。
+/// 按顺序整体重排任务 rank；只在邻居之间没有空位时才需要。
 #[tauri::command]
 pub async fn rebalance_ranks(
     state: State<'_, DbState>,
     args: RebalanceRanksArgs,
 ) -> Result<(), ApiError> {
-    TaskService::rebalance(&state.conn, &args.task_ids, args.step.unwrap_or(1024))
+    TaskService::rebalance(&state.conn, &args.task_ids)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListChecklistItemsArgs {
+    pub task_id: String,
+}
+
+/// 列出任务的检查清单。
+#[tauri::command]
+pub async fn list_task_checklist_items(
+    state: State<'_, DbState>,
+    args: ListChecklistItemsArgs,
+) -> Result<Vec<ChecklistItemDto>, ApiError> {
+    TaskRepo::list_checklist_items(&state.conn, &args.task_id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChecklistItemArgs {
+    pub task_id: String,
+    pub title: String,
+    /// 不传则追加到清单末尾
+    pub rank: Option<i64>,
+}
+
+/// 新建检查项。
+#[tauri::command]
+pub async fn create_task_checklist_item(
+    state: State<'_, DbState>,
+    args: CreateChecklistItemArgs,
+) -> Result<ChecklistItemDto, ApiError> {
+    TaskService::create_checklist_item(
+        &state.conn,
+        ChecklistItemCreateInput {
+            task_id: args.task_id,
+            title: args.title,
+            rank: args.rank,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChecklistItemArgs {
+    pub id: String,
+    pub patch: UpdateChecklistItemPatch,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChecklistItemPatch {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+    pub rank: Option<i64>,
+}
+
+impl From<UpdateChecklistItemPatch> for ChecklistItemUpdatePatch {
+    fn from(value: UpdateChecklistItemPatch) -> Self {
+        Self {
+            title: value.title,
+            done: value.done,
+            rank: value.rank,
+        }
+    }
+}
+
+/// 更新检查项。
+#[tauri::command]
+pub async fn update_task_checklist_item(
+    state: State<'_, DbState>,
+    args: UpdateChecklistItemArgs,
+) -> Result<ChecklistItemDto, ApiError> {
+    TaskService::update_checklist_item(
+        &state.conn,
+        ChecklistItemUpdateInput {
+            id: args.id,
+            patch: args.patch.into(),
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItemIdArgs {
+    pub id: String,
+}
+
+/// 切换检查项的勾选状态。
+#[tauri::command]
+pub async fn toggle_task_checklist_item(
+    state: State<'_, DbState>,
+    args: ChecklistItemIdArgs,
+) -> Result<ChecklistItemDto, ApiError> {
+    TaskService::toggle_checklist_item(&state.conn, args.id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderChecklistItemArgs {
+    pub id: String,
+    pub new_rank: i64,
+}
+
+/// 调整检查项排序。
+#[tauri::command]
+pub async fn reorder_task_checklist_item(
+    state: State<'_, DbState>,
+    args: ReorderChecklistItemArgs,
+) -> Result<ChecklistItemDto, ApiError> {
+    TaskService::reorder_checklist_item(&state.conn, args.id, args.new_rank)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 删除检查项。
+#[tauri::command]
+pub async fn delete_task_checklist_item(
+    state: State<'_, DbState>,
+    args: ChecklistItemIdArgs,
+) -> Result<(), ApiError> {
+    TaskService::delete_checklist_item(&state.conn, &args.id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartTimerArgs {
+    pub task_id: String,
+    /// manual / pomodoro，默认 manual
+    pub mode: Option<String>,
+    /// 番茄钟时长（分钟），默认 25
+    pub pomodoro_minutes: Option<i64>,
+    pub note: Option<String>,
+}
+
+/// 为任务开始计时；已有进行中的计时器会先被结束。
+#[tauri::command]
+pub async fn start_task_timer(
+    state: State<'_, DbState>,
+    args: StartTimerArgs,
+) -> Result<TimeEntryDto, ApiError> {
+    TaskService::start_timer(
+        &state.conn,
+        TimerStartInput {
+            task_id: args.task_id,
+            mode: args.mode,
+            pomodoro_minutes: args.pomodoro_minutes,
+            note: args.note,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+/// 暂停当前计时器。
+#[tauri::command]
+pub async fn pause_task_timer(state: State<'_, DbState>) -> Result<TimeEntryDto, ApiError> {
+    TaskService::pause_timer(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 恢复当前计时器。
+#[tauri::command]
+pub async fn resume_task_timer(state: State<'_, DbState>) -> Result<TimeEntryDto, ApiError> {
+    TaskService::resume_timer(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 结束当前计时器。
+#[tauri::command]
+pub async fn stop_task_timer(state: State<'_, DbState>) -> Result<Option<TimeEntryDto>, ApiError> {
+    TaskService::stop_timer(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 读取当前计时器（到点的番茄钟会先被结束）。
+#[tauri::command]
+pub async fn get_active_task_timer(
+    state: State<'_, DbState>,
+) -> Result<Option<TimeEntryDto>, ApiError> {
+    TaskService::active_timer(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTimeEntriesArgs {
+    pub task_id: String,
+}
+
+/// 列出任务的计时记录。
+#[tauri::command]
+pub async fn list_task_time_entries(
+    state: State<'_, DbState>,
+    args: ListTimeEntriesArgs,
+) -> Result<Vec<TimeEntryDto>, ApiError> {
+    TaskRepo::list_time_entries(&state.conn, &args.task_id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryIdArgs {
+    pub id: String,
+}
+
+/// 删除计时记录。
+#[tauri::command]
+pub async fn delete_task_time_entry(
+    state: State<'_, DbState>,
+    args: TimeEntryIdArgs,
+) -> Result<(), ApiError> {
+    TaskService::delete_time_entry(&state.conn, &args.id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRemindersArgs {
+    pub task_id: String,
+}
+
+/// 列出任务的提醒配置；为空表示按截止时刻默认提醒。
+#[tauri::command]
+pub async fn list_task_reminders(
+    state: State<'_, DbState>,
+    args: ListRemindersArgs,
+) -> Result<Vec<TaskReminderDto>, ApiError> {
+    TaskRepo::list_reminders(&state.conn, &args.task_id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRemindersArgs {
+    pub task_id: String,
+    /// 提前提醒的分钟数列表；0 表示截止时刻，空列表恢复默认提醒
+    pub offset_minutes: Vec<i64>,
+}
+
+/// 整体替换任务的提醒配置。
+#[tauri::command]
+pub async fn set_task_reminders(
+    state: State<'_, DbState>,
+    scheduler: State<'_, ReminderSchedulerHandle>,
+    args: SetRemindersArgs,
+) -> Result<Vec<TaskReminderDto>, ApiError> {
+    let reminders = TaskService::set_reminders(&state.conn, &args.task_id, args.offset_minutes)
+        .await
+        .map_err(ApiError::from)?;
+    scheduler.wake();
+    Ok(reminders)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnoozeReminderArgs {
+    pub id: String,
+    /// 稍后提醒的分钟数
+    pub minutes: i64,
+}
+
+/// 稍后提醒。
+#[tauri::command]
+pub async fn snooze_task_reminder(
+    state: State<'_, DbState>,
+    scheduler: State<'_, ReminderSchedulerHandle>,
+    args: SnoozeReminderArgs,
+) -> Result<TaskReminderDto, ApiError> {
+    let reminder = TaskService::snooze_reminder(&state.conn, &args.id, args.minutes)
+        .await
+        .map_err(ApiError::from)?;
+    scheduler.wake();
+    Ok(reminder)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderIdArgs {
+    pub id: String,
+}
+
+/// 忽略提醒，当前截止时间内不再触发。
+#[tauri::command]
+pub async fn dismiss_task_reminder(
+    state: State<'_, DbState>,
+    args: ReminderIdArgs,
+) -> Result<TaskReminderDto, ApiError> {
+    TaskService::dismiss_reminder(&state.conn, &args.id)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTaskTemplatesArgs {
+    /// 只看该 space 与未绑定 space 的模板；不传返回全部
+    pub space_id: Option<String>,
+}
+
+/// 列出任务模板。
+#[tauri::command]
+pub async fn list_task_templates(
+    state: State<'_, DbState>,
+    args: ListTaskTemplatesArgs,
+) -> Result<Vec<TaskTemplateDto>, ApiError> {
+    TaskRepo::list_templates(&state.conn, args.space_id.as_deref())
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTaskTemplateArgs {
+    pub name: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub body: TaskTemplateBodyDto,
+}
+
+/// 新建任务模板。
+#[tauri::command]
+pub async fn create_task_template(
+    state: State<'_, DbState>,
+    args: CreateTaskTemplateArgs,
+) -> Result<TaskTemplateDto, ApiError> {
+    TaskService::create_template(
+        &state.conn,
+        TaskTemplateCreateInput {
+            name: args.name,
+            space_id: args.space_id,
+            project_id: args.project_id,
+            body: args.body,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTaskTemplateArgs {
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub space_id: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub project_id: Option<Option<String>>,
+    /// 传入时整体替换模板内容
+    pub body: Option<TaskTemplateBodyDto>,
+}
+
+/// 更新任务模板。
+#[tauri::command]
+pub async fn update_task_template(
+    state: State<'_, DbState>,
+    args: UpdateTaskTemplateArgs,
+) -> Result<TaskTemplateDto, ApiError> {
+    TaskService::update_template(
+        &state.conn,
+        &args.id,
+        TaskTemplateUpdatePatch {
+            name: args.name,
+            space_id: args.space_id,
+            project_id: args.project_id,
+            body: args.body,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTemplateIdArgs {
+    pub id: String,
+}
+
+/// 删除任务模板。
+#[tauri::command]
+pub async fn delete_task_template(
+    state: State<'_, DbState>,
+    args: TaskTemplateIdArgs,
+) -> Result<(), ApiError> {
+    TaskService::delete_template(&state.conn, &args.id)
         .await
         .map_err(ApiError::from)
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTaskTemplateArgs {
+    pub template_id: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    /// 额外的占位符取值，例如 `{ "version": "1.2.0" }`
+    pub variables: Option<HashMap<String, String>>,
+    #[serde(flatten)]
+    pub local_tz: LocalTzArgs,
+}
+
+/// 按模板创建任务。
+#[tauri::command]
+pub async fn instantiate_task_template(
+    state: State<'_, DbState>,
+    args: InstantiateTaskTemplateArgs,
+) -> Result<TaskDto, ApiError> {
+    TaskService::instantiate_template(
+        &state.conn,
+        TaskTemplateInstantiateInput {
+            template_id: args.template_id,
+            space_id: args.space_id,
+            project_id: args.project_id,
+            variables: args.variables.unwrap_or_default(),
+            utc_offset_minutes: args.local_tz.utc_offset_minutes,
+        },
+    )
+    .await
+    .map_err(ApiError::from)
+}
@@ -0,0 +1,68 @@
+//! 回收站命令边界。
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::db::DbState;
+use crate::services::TrashService;
+use crate::types::{dto::TrashPurgeResultDto, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeArgs {
+    pub ids: Vec<String>,
+}
+
+/// 彻底删除回收站里的任务。
+#[tauri::command]
+pub async fn purge_tasks(
+    state: State<'_, DbState>,
+    args: PurgeArgs,
+) -> Result<TrashPurgeResultDto, ApiError> {
+    TrashService::purge_tasks(&state.conn, &args.ids)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 彻底删除回收站里的项目（连同整棵子树）。
+#[tauri::command]
+pub async fn purge_projects(
+    state: State<'_, DbState>,
+    args: PurgeArgs,
+) -> Result<TrashPurgeResultDto, ApiError> {
+    TrashService::purge_projects(&state.conn, &args.ids)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// 清空回收站。
+#[tauri::command]
+pub async fn empty_trash(state: State<'_, DbState>) -> Result<TrashPurgeResultDto, ApiError> {
+    TrashService::empty_trash(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[tauri::command]
+pub async fn get_trash_retention_days(state: State<'_, DbState>) -> Result<i64, ApiError> {
+    TrashService::get_retention_days(&state.conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTrashRetentionDaysArgs {
+    /// 0 表示不自动清除
+    pub days: i64,
+}
+
+#[tauri::command]
+pub async fn set_trash_retention_days(
+    state: State<'_, DbState>,
+    args: SetTrashRetentionDaysArgs,
+) -> Result<i64, ApiError> {
+    TrashService::set_retention_days(&state.conn, args.days)
+        .await
+        .map_err(ApiError::from)
+}
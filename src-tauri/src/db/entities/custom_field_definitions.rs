@@ -0,0 +1,30 @@
+//! SeaORM Entity for custom field definitions.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "custom_field_definitions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub space_id: String,
+    /// 为空时对整个 space 生效，否则只对该项目下的任务生效
+    pub project_id: Option<String>,
+    /// 任务 `custom_fields` 里按标题关联字段值
+    pub title: String,
+    /// text / number / date / select / multiSelect / checkbox / url
+    pub field_type: String,
+    /// 选择类字段的候选项（JSON 字符串数组）
+    #[sea_orm(column_type = "Text", nullable)]
+    pub options: Option<String>,
+    pub rank: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
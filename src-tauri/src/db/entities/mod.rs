@@ -6,14 +6,25 @@ pub mod asset_diary_entries;
 pub mod asset_notes;
 pub mod asset_snippets;
 pub mod asset_vault_entries;
+pub mod custom_field_definitions;
 pub mod links;
+pub mod local_batches;
 pub mod project_activity_logs;
 pub mod project_links;
+pub mod project_milestones;
 pub mod project_tags;
+pub mod project_templates;
 pub mod projects;
+pub mod purged_entities;
 pub mod spaces;
 pub mod tags;
 pub mod task_activity_logs;
+pub mod task_checklist_items;
+pub mod task_dependencies;
 pub mod task_links;
+pub mod task_reminders;
 pub mod task_tags;
+pub mod task_templates;
+pub mod task_time_entries;
 pub mod tasks;
+pub mod undo_records;
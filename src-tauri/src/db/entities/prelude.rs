@@ -6,14 +6,25 @@ pub use super::asset_diary_entries::Entity as AssetDiaryEntries;
 pub use super::asset_notes::Entity as AssetNotes;
 pub use super::asset_snippets::Entity as AssetSnippets;
 pub use super::asset_vault_entries::Entity as AssetVaultEntries;
+pub use super::custom_field_definitions::Entity as CustomFieldDefinitions;
 pub use super::links::Entity as Links;
+pub use super::local_batches::Entity as LocalBatches;
 pub use super::project_activity_logs::Entity as ProjectActivityLogs;
 pub use super::project_links::Entity as ProjectLinks;
+pub use super::project_milestones::Entity as ProjectMilestones;
 pub use super::project_tags::Entity as ProjectTags;
+pub use super::project_templates::Entity as ProjectTemplates;
 pub use super::projects::Entity as Projects;
+pub use super::purged_entities::Entity as PurgedEntities;
 pub use super::spaces::Entity as Spaces;
 pub use super::tags::Entity as Tags;
 pub use super::task_activity_logs::Entity as TaskActivityLogs;
+pub use super::task_checklist_items::Entity as TaskChecklistItems;
+pub use super::task_dependencies::Entity as TaskDependencies;
 pub use super::task_links::Entity as TaskLinks;
+pub use super::task_reminders::Entity as TaskReminders;
 pub use super::task_tags::Entity as TaskTags;
+pub use super::task_templates::Entity as TaskTemplates;
+pub use super::task_time_entries::Entity as TaskTimeEntries;
 pub use super::tasks::Entity as Tasks;
+pub use super::undo_records::Entity as UndoRecords;
@@ -19,6 +19,8 @@ pub struct Model {
     pub detail: String,
     pub create_by: String,
     pub created_at: i64,
+    /// 同一次写操作的批次 id；历史数据为空
+    pub batch_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
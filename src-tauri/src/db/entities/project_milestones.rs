@@ -0,0 +1,45 @@
+//! SeaORM Entity for project milestones.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "project_milestones")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub project_id: String,
+    pub space_id: String,
+    pub title: String,
+    /// 目标日期（毫秒时间戳）
+    pub target_at: Option<i64>,
+    pub rank: i64,
+    /// 为空表示未完成
+    pub completed_at: Option<i64>,
+    /// 挂在该里程碑下的任务数（忽略已归档 / 已删除），由 `stats` 回写
+    pub task_count: i64,
+    pub done_task_count: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::projects::Entity",
+        from = "Column::ProjectId",
+        to = "super::projects::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Projects,
+}
+
+impl Related<super::projects::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Projects.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
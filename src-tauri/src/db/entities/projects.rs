@@ -16,6 +16,9 @@ pub struct Model {
     pub note: Option<String>,
     pub priority: Priority,
     pub todo_task_count: i64,
+    pub doing_task_count: i64,
+    pub waiting_task_count: i64,
+    pub someday_task_count: i64,
     pub done_task_count: i64,
     pub last_task_updated_at: Option<i64>,
     pub created_at: i64,
@@ -23,7 +26,25 @@ pub struct Model {
     pub archived_at: Option<i64>,
     pub deleted_at: Option<i64>,
     pub create_by: String,
-    pub rank: i64,
+    /// 分数排序键，见 `repos::rank`
+    pub rank: String,
+    /// 生命周期状态：planned / active / paused / completed / cancelled
+    #[sea_orm(default_value = "active")]
+    pub lifecycle_status: String,
+    /// 计划开始时间，需落在父项目的日期范围内
+    pub start_at: Option<i64>,
+    /// 计划截止时间，需落在父项目的日期范围内
+    pub due_at: Option<i64>,
+    /// 以下为整棵子树（自身 + 全部后代项目）的汇总，口径同上面的任务计数；只在本地维护，不参与同步
+    #[sea_orm(default_value = 0)]
+    pub subtree_task_count: i64,
+    #[sea_orm(default_value = 0)]
+    pub subtree_done_task_count: i64,
+    /// 子树任务最近一次变化时间
+    pub subtree_last_activity_at: Option<i64>,
+    /// 子树任务已结束计时的累计时长（毫秒）
+    #[sea_orm(default_value = 0)]
+    pub subtree_tracked_ms: i64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
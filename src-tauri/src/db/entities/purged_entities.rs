@@ -0,0 +1,21 @@
+//! SeaORM Entity for purged (hard-deleted) entity tombstones.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "purged_entities")]
+pub struct Model {
+    /// `{entity_type}:{entity_id}`，同一实体在各端只留一条
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// `task` 或 `project`
+    pub entity_type: String,
+    pub entity_id: String,
+    pub purged_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
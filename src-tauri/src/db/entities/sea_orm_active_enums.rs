@@ -19,6 +19,12 @@ pub enum Priority {
 pub enum TaskStatus {
     #[sea_orm(string_value = "todo")]
     Todo,
+    #[sea_orm(string_value = "doing")]
+    Doing,
+    #[sea_orm(string_value = "waiting")]
+    Waiting,
+    #[sea_orm(string_value = "someday")]
+    Someday,
     #[sea_orm(string_value = "done")]
     Done,
 }
@@ -0,0 +1,39 @@
+//! SeaORM Entity for task dependencies (blocked-by graph).
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "task_dependencies")]
+pub struct Model {
+    /// 被阻塞的任务
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub task_id: String,
+    /// 前置任务（完成后 `task_id` 才解除阻塞）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub blocked_by_task_id: String,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tasks::Entity",
+        from = "Column::TaskId",
+        to = "super::tasks::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Tasks,
+    #[sea_orm(
+        belongs_to = "super::tasks::Entity",
+        from = "Column::BlockedByTaskId",
+        to = "super::tasks::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    BlockedByTask,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
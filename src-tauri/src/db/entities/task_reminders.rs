@@ -0,0 +1,42 @@
+//! SeaORM Entity for task reminders.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "task_reminders")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub task_id: String,
+    /// 提前提醒的分钟数；0 表示在截止时刻提醒
+    pub offset_minutes: i64,
+    /// 触发/稍后/忽略状态所对应的截止时间；与任务当前截止时间不一致时状态失效
+    pub anchor_deadline_at: Option<i64>,
+    pub last_fired_at: Option<i64>,
+    pub snoozed_until: Option<i64>,
+    pub dismissed_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tasks::Entity",
+        from = "Column::TaskId",
+        to = "super::tasks::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Tasks,
+}
+
+impl Related<super::tasks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tasks.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,26 @@
+//! SeaORM Entity for task templates.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "task_templates")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub name: String,
+    /// 实例化时默认使用的 space / 项目；项目被删除后模板仍然保留
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    /// 模板内容（`TaskTemplateBodyDto`）的 JSON
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+//! SeaORM Entity for task time entries.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "task_time_entries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub task_id: String,
+    /// manual / pomodoro
+    pub mode: String,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+    /// 本次暂停的开始时间；非空表示计时器处于暂停状态
+    pub paused_at: Option<i64>,
+    /// 已累计的暂停时长（毫秒）
+    pub paused_ms: i64,
+    /// 番茄钟的固定时长（毫秒）；manual 模式为空
+    pub planned_ms: Option<i64>,
+    /// 结束时写入的有效时长（毫秒）
+    pub duration_ms: Option<i64>,
+    pub note: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tasks::Entity",
+        from = "Column::TaskId",
+        to = "super::tasks::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Tasks,
+}
+
+impl Related<super::tasks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tasks.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -16,16 +16,30 @@ pub struct Model {
     pub status: TaskStatus,
     pub done_reason: Option<DoneReason>,
     pub priority: Priority,
-    pub rank: i64,
+    /// 分数排序键，见 `repos::rank`
+    pub rank: String,
     pub created_at: i64,
     pub updated_at: i64,
     pub completed_at: Option<i64>,
     pub deadline_at: Option<i64>,
+    /// 计划处理时间（与截止时间无关，用于“今天”视图）
+    pub scheduled_at: Option<i64>,
+    /// 推迟到该时间之前不在默认列表中出现
+    pub defer_until: Option<i64>,
     pub archived_at: Option<i64>,
     pub deleted_at: Option<i64>,
     #[sea_orm(column_type = "Text", nullable)]
     pub custom_fields: Option<String>, // JSON stored as string for now
     pub create_by: String,
+    /// RRULE 风格的重复规则文本，例如 `FREQ=WEEKLY;INTERVAL=1;BYDAY=MO`
+    #[sea_orm(column_type = "Text", nullable)]
+    pub recurrence_rule: Option<String>,
+    /// 重复系列 id（即系列第一条任务的 id）
+    pub recurrence_series_id: Option<String>,
+    /// 当前任务在重复系列中的序号（从 1 开始）
+    pub recurrence_index: Option<i64>,
+    /// 所属里程碑，只能指向当前项目下的里程碑
+    pub milestone_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
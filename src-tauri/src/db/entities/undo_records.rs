@@ -0,0 +1,24 @@
+//! SeaORM Entity for undo / redo records.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "undo_records")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 被反转的操作（活动日志批次 id）
+    pub batch_id: String,
+    /// 反转时写出的日志批次 id
+    pub inverse_batch_id: String,
+    /// `undo` 或 `redo`
+    pub kind: String,
+    pub action_label: String,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,100 @@
+//! 为任务补充重复规则字段。
+//!
+//! 重点：
+//! - `recurrence_rule` 以 RRULE 文本保存，便于跨端原样同步
+//! - `recurrence_series_id` + `recurrence_index` 用于串起同一系列的各次任务
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column("tasks", "recurrence_rule").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("tasks"))
+                        .add_column(ColumnDef::new(Alias::new("recurrence_rule")).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        if !manager.has_column("tasks", "recurrence_series_id").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("tasks"))
+                        .add_column(
+                            ColumnDef::new(Alias::new("recurrence_series_id"))
+                                .string()
+                                .null(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        if !manager.has_column("tasks", "recurrence_index").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("tasks"))
+                        .add_column(
+                            ColumnDef::new(Alias::new("recurrence_index"))
+                                .big_integer()
+                                .null(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_tasks_recurrence_series_id")
+                    .table(Alias::new("tasks"))
+                    .col(Alias::new("recurrence_series_id"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("idx_tasks_recurrence_series_id")
+                    .table(Alias::new("tasks"))
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            "recurrence_index",
+            "recurrence_series_id",
+            "recurrence_rule",
+        ] {
+            if manager.has_column("tasks", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("tasks"))
+                            .drop_column(Alias::new(column))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+//! 新增任务检查清单表。
+//!
+//! 清单项带 `updated_at` + `deleted_at`，与关系表一样按 tombstone 增量同步。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::task_checklist_items;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(task_checklist_items::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_checklist_items_task_rank")
+                    .table(task_checklist_items::Entity)
+                    .col(task_checklist_items::Column::TaskId)
+                    .col(task_checklist_items::Column::Rank)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_checklist_items_updated_at")
+                    .table(task_checklist_items::Entity)
+                    .col(task_checklist_items::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(task_checklist_items::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! 新增任务依赖（blocked-by）关系表。
+//!
+//! 与其它关系表一样带 `updated_at` + `deleted_at`，删除以 tombstone 传播。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::task_dependencies;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(task_dependencies::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_dependencies_blocked_by_task_id")
+                    .table(task_dependencies::Entity)
+                    .col(task_dependencies::Column::BlockedByTaskId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_dependencies_updated_at")
+                    .table(task_dependencies::Entity)
+                    .col(task_dependencies::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(task_dependencies::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! 新增本地全文搜索索引（SQLite FTS5）。
+//!
+//! 重点：
+//! - 索引是派生数据，只在本地 SQLite 建表，远端 Postgres 跳过
+//! - 使用 trigram 分词，中文按子串也能命中
+//! - 数据回填不放在迁移里，由启动时的 `rebuild_if_empty` 统一处理
+
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Sqlite {
+            return Ok(());
+        }
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DbBackend::Sqlite,
+                "CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                    entity_type UNINDEXED,
+                    entity_id UNINDEXED,
+                    space_id UNINDEXED,
+                    project_id UNINDEXED,
+                    title,
+                    body,
+                    tags,
+                    is_deleted UNINDEXED,
+                    is_archived UNINDEXED,
+                    updated_at UNINDEXED,
+                    tokenize = 'trigram'
+                )",
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Sqlite {
+            return Ok(());
+        }
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DbBackend::Sqlite,
+                "DROP TABLE IF EXISTS search_index",
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! 扩展任务工作流状态：doing / waiting / someday。
+//!
+//! 重点：
+//! - `tasks.status` 本身是文本列，新状态无需改表
+//! - 项目表补充三个状态的计数列，默认 0
+//! - 历史数据里大小写不一或未知的状态统一归一化，避免新枚举解码失败
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COUNT_COLUMNS: [&str; 3] = [
+    "doing_task_count",
+    "waiting_task_count",
+    "someday_task_count",
+];
+
+const KNOWN_STATUSES: [&str; 5] = ["todo", "doing", "waiting", "someday", "done"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in COUNT_COLUMNS {
+            if !manager.has_column("projects", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("projects"))
+                            .add_column(
+                                ColumnDef::new(Alias::new(column))
+                                    .big_integer()
+                                    .not_null()
+                                    .default(0),
+                            )
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        // 先统一成小写，再把仍无法识别的状态回落到 todo。
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Alias::new("tasks"))
+                    .value(
+                        Alias::new("status"),
+                        Func::lower(
+                            Func::cust(Alias::new("TRIM")).arg(Expr::col(Alias::new("status"))),
+                        ),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Alias::new("tasks"))
+                    .value(Alias::new("status"), "todo")
+                    .and_where(Expr::col(Alias::new("status")).is_not_in(KNOWN_STATUSES))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时新状态没有对应值，统一退回 todo。
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Alias::new("tasks"))
+                    .value(Alias::new("status"), "todo")
+                    .and_where(
+                        Expr::col(Alias::new("status")).is_in(["doing", "waiting", "someday"]),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in COUNT_COLUMNS {
+            if manager.has_column("projects", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("projects"))
+                            .drop_column(Alias::new(column))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+//! 新增任务计时记录表。
+//!
+//! 计时记录带 `updated_at` + `deleted_at`，与检查清单一样按 tombstone 增量同步。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::task_time_entries;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(task_time_entries::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_time_entries_task_started")
+                    .table(task_time_entries::Entity)
+                    .col(task_time_entries::Column::TaskId)
+                    .col(task_time_entries::Column::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_time_entries_ended_at")
+                    .table(task_time_entries::Entity)
+                    .col(task_time_entries::Column::EndedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_time_entries_updated_at")
+                    .table(task_time_entries::Entity)
+                    .col(task_time_entries::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(task_time_entries::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+//! 新增任务提醒表。
+//!
+//! 提醒的触发/稍后/忽略状态持久化在这里，应用重启后不会重复提醒；
+//! 同样带 `updated_at` + `deleted_at`，按 tombstone 增量同步。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::task_reminders;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(task_reminders::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_reminders_task_offset")
+                    .table(task_reminders::Entity)
+                    .col(task_reminders::Column::TaskId)
+                    .col(task_reminders::Column::OffsetMinutes)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_reminders_updated_at")
+                    .table(task_reminders::Entity)
+                    .col(task_reminders::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 调度器按截止时间扫描候选任务。
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_tasks_deadline_at")
+                    .table(Alias::new("tasks"))
+                    .col(Alias::new("deadline_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("idx_tasks_deadline_at")
+                    .table(Alias::new("tasks"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(task_reminders::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! 为任务补充计划时间与推迟时间。
+//!
+//! 重点：
+//! - `scheduled_at` 表示“打算哪天做”，与截止时间 `deadline_at` 相互独立
+//! - `defer_until` 之前任务不出现在默认列表中
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMNS: [(&str, &str); 2] = [
+    ("scheduled_at", "idx_tasks_scheduled_at"),
+    ("defer_until", "idx_tasks_defer_until"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (column, index) in COLUMNS {
+            if !manager.has_column("tasks", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("tasks"))
+                            .add_column(ColumnDef::new(Alias::new(column)).big_integer().null())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+
+            manager
+                .create_index(
+                    Index::create()
+                        .if_not_exists()
+                        .name(index)
+                        .table(Alias::new("tasks"))
+                        .col(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (column, index) in COLUMNS {
+            manager
+                .drop_index(
+                    Index::drop()
+                        .if_exists()
+                        .name(index)
+                        .table(Alias::new("tasks"))
+                        .to_owned(),
+                )
+                .await?;
+
+            if manager.has_column("tasks", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("tasks"))
+                            .drop_column(Alias::new(column))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
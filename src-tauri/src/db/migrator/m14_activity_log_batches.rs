@@ -0,0 +1,68 @@
+//! 为任务活动日志补充批次 id。
+//!
+//! 重点：
+//! - 批量操作写出的多条日志共用一个 `batch_id`，前端可按批次折叠展示
+//! - 单条操作的日志保持为空，历史数据无需回填
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLE: &str = "task_activity_logs";
+const COLUMN: &str = "batch_id";
+const INDEX: &str = "idx_task_activity_logs_batch_id";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column(TABLE, COLUMN).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(TABLE))
+                        .add_column(ColumnDef::new(Alias::new(COLUMN)).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name(INDEX)
+                    .table(Alias::new(TABLE))
+                    .col(Alias::new(COLUMN))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name(INDEX)
+                    .table(Alias::new(TABLE))
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.has_column(TABLE, COLUMN).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(TABLE))
+                        .drop_column(Alias::new(COLUMN))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! 任务与项目的 rank 从整数改为分数排序键（字符串）。
+//!
+//! 重点：
+//! - SQLite 不支持直接改列类型，统一走“加新列 -> 回填 -> 删旧列 -> 改名”
+//! - 回填使用 `legacy_key` 的确定性换算，保持原有先后顺序；
+//!   多端各自迁移得到相同结果，因此不刷新 `updated_at`，避免触发整表同步
+//! - 换算规则随迁移冻结在本文件里，不依赖 `repos::rank`，以后调整排序键实现也不会改变迁移结果
+//! - 新库由 m01 按实体直接建成字符串列，这里只会处理空表
+
+use sea_orm::{ConnectionTrait, QueryResult};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: [&str; 2] = ["tasks", "projects"];
+const RANK: &str = "rank";
+const STAGING: &str = "rank_next";
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+/// 旧整数 rank 换算成定长 36 进制时的位数；36^13 > 2^64。
+const LEGACY_WIDTH: usize = 13;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            add_staging_column(
+                manager,
+                table,
+                ColumnDef::new(Alias::new(STAGING))
+                    .string()
+                    .default("")
+                    .to_owned(),
+            )
+            .await?;
+
+            for row in select_ranks(manager, table).await? {
+                let id: String = row.try_get("", "id")?;
+                let next = match row.try_get::<i64>("", RANK) {
+                    Ok(legacy) => legacy_key(legacy),
+                    Err(_) => row.try_get::<String>("", RANK)?,
+                };
+                write_staging(manager, table, &id, next.into()).await?;
+            }
+
+            swap_staging_column(manager, table).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            add_staging_column(
+                manager,
+                table,
+                ColumnDef::new(Alias::new(STAGING))
+                    .big_integer()
+                    .default(0)
+                    .to_owned(),
+            )
+            .await?;
+
+            // 回退时只能保留相对顺序，按排序键先后重新分配整数。
+            for (index, row) in select_ranks(manager, table).await?.into_iter().enumerate() {
+                let id: String = row.try_get("", "id")?;
+                write_staging(manager, table, &id, ((index as i64 + 1) * 1024).into()).await?;
+            }
+
+            swap_staging_column(manager, table).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 把旧整数 rank 按原有大小关系换算成纯小数形式的排序键。
+///
+/// 定长 36 进制编码后去掉末尾的 0；去零不改变小数值，也就不改变排序。
+pub(super) fn legacy_key(rank: i64) -> String {
+    // 平移到正数区间并加 1，保证结果不全为 0。
+    let mut value = (rank as i128 - i64::MIN as i128 + 1) as u128;
+    let mut digits = vec![b'0'; LEGACY_WIDTH];
+    for slot in digits.iter_mut().rev() {
+        *slot = DIGITS[(value % DIGITS.len() as u128) as usize];
+        value /= DIGITS.len() as u128;
+    }
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    digits.into_iter().map(char::from).collect()
+}
+
+async fn add_staging_column(
+    manager: &SchemaManager<'_>,
+    table: &str,
+    mut column: ColumnDef,
+) -> Result<(), DbErr> {
+    if manager.has_column(table, STAGING).await? {
+        return Ok(());
+    }
+    manager
+        .alter_table(
+            Table::alter()
+                .table(Alias::new(table))
+                .add_column(column.not_null())
+                .to_owned(),
+        )
+        .await
+}
+
+async fn select_ranks(manager: &SchemaManager<'_>, table: &str) -> Result<Vec<QueryResult>, DbErr> {
+    let connection = manager.get_connection();
+    let statement = connection.get_database_backend().build(
+        Query::select()
+            .columns([Alias::new("id"), Alias::new(RANK)])
+            .from(Alias::new(table))
+            .order_by(Alias::new(RANK), Order::Asc)
+            .order_by(Alias::new("id"), Order::Asc),
+    );
+    connection.query_all(statement).await
+}
+
+async fn write_staging(
+    manager: &SchemaManager<'_>,
+    table: &str,
+    id: &str,
+    value: SimpleExpr,
+) -> Result<(), DbErr> {
+    manager
+        .exec_stmt(
+            Query::update()
+                .table(Alias::new(table))
+                .value(Alias::new(STAGING), value)
+                .and_where(Expr::col(Alias::new("id")).eq(id))
+                .to_owned(),
+        )
+        .await
+}
+
+async fn swap_staging_column(manager: &SchemaManager<'_>, table: &str) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(Alias::new(table))
+                .drop_column(Alias::new(RANK))
+                .to_owned(),
+        )
+        .await?;
+    manager
+        .alter_table(
+            Table::alter()
+                .table(Alias::new(table))
+                .rename_column(Alias::new(STAGING), Alias::new(RANK))
+                .to_owned(),
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::legacy_key;
+
+    #[test]
+    fn legacy_key_preserves_order() {
+        let ranks = [
+            i64::MIN,
+            i64::MIN + 1,
+            -1_000_000,
+            -1024,
+            -1,
+            0,
+            1,
+            35,
+            36,
+            1024,
+            2048,
+            1_000_000,
+            i64::MAX - 1,
+            i64::MAX,
+        ];
+        let keys: Vec<String> = ranks.iter().map(|rank| legacy_key(*rank)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "{keys:?}");
+        assert!(keys
+            .iter()
+            .all(|key| !key.is_empty() && !key.ends_with('0')));
+        // 冻结的换算结果，改动会让已迁移与未迁移的设备得到不同的键。
+        assert_eq!(legacy_key(0), "1y2p0ij32e8e9");
+    }
+}
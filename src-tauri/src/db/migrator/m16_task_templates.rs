@@ -0,0 +1,49 @@
+//! 新增任务模板表。
+//!
+//! 模板内容整体存成 JSON，字段随 `TaskCreatePatch` 演进时不需要再改表；
+//! 同样带 `updated_at` + `deleted_at`，按 tombstone 增量同步。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::task_templates;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(task_templates::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_task_templates_updated_at")
+                    .table(task_templates::Entity)
+                    .col(task_templates::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(task_templates::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! 新增项目模板表。
+//!
+//! 整棵项目树（子项目、起始任务）嵌套存成一个 JSON，实例化时一次读出；
+//! 与任务模板一样按 tombstone 增量同步。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::project_templates;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(project_templates::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_project_templates_updated_at")
+                    .table(project_templates::Entity)
+                    .col(project_templates::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(project_templates::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
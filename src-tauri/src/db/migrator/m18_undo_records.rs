@@ -0,0 +1,119 @@
+//! 撤销 / 重做所需的结构。
+//!
+//! 重点：
+//! - 项目活动日志补充 `batch_id`，与任务日志一样按“一次写操作”分组
+//! - 新增 `undo_records`，记录哪个批次被撤销 / 重做，以及反转时写出的批次
+//! - 历史日志的批次 id 为空，不参与撤销，无需回填
+//! - `undo_records` 是本机表，只在 SQLite 上创建；`batch_id` 列随日志同步，两端都加
+
+use sea_orm::{DbBackend, DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::undo_records;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const LOG_TABLE: &str = "project_activity_logs";
+const LOG_COLUMN: &str = "batch_id";
+const LOG_INDEX: &str = "idx_project_activity_logs_batch_id";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column(LOG_TABLE, LOG_COLUMN).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(LOG_TABLE))
+                        .add_column(ColumnDef::new(Alias::new(LOG_COLUMN)).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name(LOG_INDEX)
+                    .table(Alias::new(LOG_TABLE))
+                    .col(Alias::new(LOG_COLUMN))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 撤销记录只在本机 SQLite 里使用；同一个 Migrator 也会跑在远端 Postgres 上，那里不建。
+        let builder = manager.get_database_backend();
+        if builder != DbBackend::Sqlite {
+            return Ok(());
+        }
+        let schema = Schema::new(builder);
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(undo_records::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_undo_records_batch_id")
+                    .table(undo_records::Entity)
+                    .col(undo_records::Column::BatchId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_undo_records_inverse_batch_id")
+                    .table(undo_records::Entity)
+                    .col(undo_records::Column::InverseBatchId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(undo_records::Entity)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name(LOG_INDEX)
+                    .table(Alias::new(LOG_TABLE))
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.has_column(LOG_TABLE, LOG_COLUMN).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(LOG_TABLE))
+                        .drop_column(Alias::new(LOG_COLUMN))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
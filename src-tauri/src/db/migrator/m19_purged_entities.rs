@@ -0,0 +1,55 @@
+//! 彻底删除的墓碑表。
+//!
+//! 重点：
+//! - 硬删除后行本身已不存在，只能靠墓碑把“已清除”传播到其他设备
+//! - 墓碑按 `purged_at` 增量同步，目标端收到后同样硬删除对应数据
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::purged_entities;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(purged_entities::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_purged_entities_purged_at")
+                    .table(purged_entities::Entity)
+                    .col(purged_entities::Column::PurgedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(purged_entities::Entity)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
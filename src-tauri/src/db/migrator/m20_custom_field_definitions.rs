@@ -0,0 +1,64 @@
+//! 新增自定义字段定义表。
+//!
+//! 定义挂在 space 或项目上，任务里的字段值按标题与定义关联；
+//! 带 `updated_at` + `deleted_at`，按 tombstone 增量同步。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::custom_field_definitions;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(custom_field_definitions::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_custom_field_definitions_space_id")
+                    .table(custom_field_definitions::Entity)
+                    .col(custom_field_definitions::Column::SpaceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_custom_field_definitions_updated_at")
+                    .table(custom_field_definitions::Entity)
+                    .col(custom_field_definitions::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(custom_field_definitions::Entity)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! 新增项目里程碑表，并给任务补充所属里程碑。
+//!
+//! 里程碑带 `updated_at` + `deleted_at`，按 tombstone 增量同步；
+//! 任务数与完成数只在本地回写，不参与版本比较。
+
+use sea_orm::{DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::project_milestones;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let builder = manager.get_database_backend();
+        let schema = Schema::new(builder);
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(project_milestones::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_project_milestones_project_id")
+                    .table(project_milestones::Entity)
+                    .col(project_milestones::Column::ProjectId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_project_milestones_updated_at")
+                    .table(project_milestones::Entity)
+                    .col(project_milestones::Column::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 新库由 m01 按实体建表时已经带上这一列。
+        if !manager.has_column("tasks", "milestone_id").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("tasks"))
+                        .add_column(ColumnDef::new(Alias::new("milestone_id")).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_tasks_milestone_id")
+                    .table(Alias::new("tasks"))
+                    .col(Alias::new("milestone_id"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("idx_tasks_milestone_id")
+                    .table(Alias::new("tasks"))
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.has_column("tasks", "milestone_id").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("tasks"))
+                        .drop_column(Alias::new("milestone_id"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(project_milestones::Entity).to_owned())
+            .await?;
+        Ok(())
+    }
+}
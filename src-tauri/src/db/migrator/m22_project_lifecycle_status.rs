@@ -0,0 +1,69 @@
+//! 给项目补充显式的生命周期状态。
+//!
+//! 老项目一律视为 `active`；`computed_status` 仍按任务计数推导，只作参考。
+
+use sea_orm::DbErr;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新库由 m01 按实体建表时已经带上这一列。
+        if !manager.has_column("projects", "lifecycle_status").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("projects"))
+                        .add_column(
+                            ColumnDef::new(Alias::new("lifecycle_status"))
+                                .string()
+                                .not_null()
+                                .default("active"),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_projects_space_lifecycle_status")
+                    .table(Alias::new("projects"))
+                    .col(Alias::new("space_id"))
+                    .col(Alias::new("lifecycle_status"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("idx_projects_space_lifecycle_status")
+                    .table(Alias::new("projects"))
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.has_column("projects", "lifecycle_status").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("projects"))
+                        .drop_column(Alias::new("lifecycle_status"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
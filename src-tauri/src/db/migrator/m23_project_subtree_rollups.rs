@@ -0,0 +1,110 @@
+//! 给项目补充整棵子树的任务汇总列。
+//!
+//! 重点：
+//! - 汇总由 `task_repo::stats` 沿父级链回写，这里只负责加列和一次性回填
+//! - 回填用按 `path` 前缀圈子树的关联子查询，SQLite 与 Postgres 通用
+//! - 回填不刷新 `updated_at`，各端各自迁移得到相同结果
+
+use sea_orm::{ConnectionTrait, Statement};
+use sea_orm_migration::prelude::*;
+
+use crate::db::now_ms;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COUNT_COLUMNS: [&str; 4] = [
+    "subtree_task_count",
+    "subtree_done_task_count",
+    "subtree_overdue_task_count",
+    "subtree_tracked_ms",
+];
+const LAST_ACTIVITY_COLUMN: &str = "subtree_last_activity_at";
+
+/// 子树内未删除项目（根节点本身总是算在内）下、未归档未删除任务的过滤条件。
+const SUBTREE_TASKS: &str = "t.archived_at IS NULL AND t.deleted_at IS NULL \
+     AND t.project_id IN (SELECT d.id FROM projects d WHERE d.space_id = projects.space_id \
+     AND (d.id = projects.id OR (d.deleted_at IS NULL AND d.path LIKE projects.path || '/%')))";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新库由 m01 按实体建表时已经带上这些列。
+        for column in COUNT_COLUMNS {
+            add_column(
+                manager,
+                column,
+                ColumnDef::new(Alias::new(column))
+                    .big_integer()
+                    .not_null()
+                    .default(0),
+            )
+            .await?;
+        }
+        add_column(
+            manager,
+            LAST_ACTIVITY_COLUMN,
+            ColumnDef::new(Alias::new(LAST_ACTIVITY_COLUMN))
+                .big_integer()
+                .null(),
+        )
+        .await?;
+
+        let now = now_ms();
+        let sql = format!(
+            "UPDATE projects SET \
+             subtree_task_count = (SELECT COUNT(*) FROM tasks t WHERE {SUBTREE_TASKS}), \
+             subtree_done_task_count = (SELECT COUNT(*) FROM tasks t \
+             WHERE t.status = 'done' AND {SUBTREE_TASKS}), \
+             subtree_overdue_task_count = (SELECT COUNT(*) FROM tasks t \
+             WHERE t.status <> 'done' AND t.deadline_at < {now} AND {SUBTREE_TASKS}), \
+             subtree_last_activity_at = (SELECT MAX(t.updated_at) FROM tasks t WHERE {SUBTREE_TASKS}), \
+             subtree_tracked_ms = COALESCE((SELECT SUM(e.duration_ms) FROM task_time_entries e \
+             JOIN tasks t ON t.id = e.task_id WHERE e.deleted_at IS NULL AND e.ended_at IS NOT NULL \
+             AND t.deleted_at IS NULL AND t.project_id IN (SELECT d.id FROM projects d \
+             WHERE d.space_id = projects.space_id AND (d.id = projects.id \
+             OR (d.deleted_at IS NULL AND d.path LIKE projects.path || '/%')))), 0)"
+        );
+        let backend = manager.get_database_backend();
+        manager
+            .get_connection()
+            .execute(Statement::from_string(backend, sql))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in COUNT_COLUMNS.into_iter().chain([LAST_ACTIVITY_COLUMN]) {
+            if manager.has_column("projects", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("projects"))
+                            .drop_column(Alias::new(column))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn add_column(
+    manager: &SchemaManager<'_>,
+    name: &str,
+    column: &mut ColumnDef,
+) -> Result<(), DbErr> {
+    if manager.has_column("projects", name).await? {
+        return Ok(());
+    }
+    manager
+        .alter_table(
+            Table::alter()
+                .table(Alias::new("projects"))
+                .add_column(column)
+                .to_owned(),
+        )
+        .await
+}
@@ -0,0 +1,47 @@
+//! 给项目补充计划开始 / 截止时间。
+//!
+//! 两列都可为空，老项目视为没有排期，不做回填。
+
+use sea_orm::DbErr;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMNS: [&str; 2] = ["start_at", "due_at"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新库由 m01 按实体建表时已经带上这些列。
+        for column in COLUMNS {
+            if !manager.has_column("projects", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("projects"))
+                            .add_column(ColumnDef::new(Alias::new(column)).big_integer().null())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in COLUMNS {
+            if manager.has_column("projects", column).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new("projects"))
+                            .drop_column(Alias::new(column))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
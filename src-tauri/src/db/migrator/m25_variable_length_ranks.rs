@@ -0,0 +1,74 @@
+//! 排序键改为“变长整数部分 + 小数部分”的格式。
+//!
+//! 重点：
+//! - 旧键是纯小数，统一接到整数 0（`a0`）后面，先后顺序不变
+//! - 换算是确定性的，多端各自迁移得到相同结果，因此不刷新 `updated_at`
+//! - 换算只用本文件和 m15 里冻结的规则，不依赖 `repos::rank`
+//! - 之后追加只给整数部分加一，键长不再随追加次数线性增长
+
+use sea_orm::{ConnectionTrait, QueryResult};
+use sea_orm_migration::prelude::*;
+
+use super::m15_fractional_ranks::legacy_key;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: [&str; 2] = ["tasks", "projects"];
+const RANK: &str = "rank";
+/// 新格式下的整数 0；旧的纯小数键接在它后面。
+const INTEGER_ZERO: &str = "a0";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            for row in select_ranks(manager, table).await? {
+                let id: String = row.try_get("", "id")?;
+                let legacy: String = row.try_get("", RANK)?;
+                write_rank(manager, table, &id, format!("{INTEGER_ZERO}{legacy}")).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新键不一定能还原成纯小数，回退时按现有先后顺序重新换算。
+        for table in TABLES {
+            for (index, row) in select_ranks(manager, table).await?.into_iter().enumerate() {
+                let id: String = row.try_get("", "id")?;
+                write_rank(manager, table, &id, legacy_key(index as i64)).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn select_ranks(manager: &SchemaManager<'_>, table: &str) -> Result<Vec<QueryResult>, DbErr> {
+    let connection = manager.get_connection();
+    let statement = connection.get_database_backend().build(
+        Query::select()
+            .columns([Alias::new("id"), Alias::new(RANK)])
+            .from(Alias::new(table))
+            .order_by(Alias::new(RANK), Order::Asc)
+            .order_by(Alias::new("id"), Order::Asc),
+    );
+    connection.query_all(statement).await
+}
+
+async fn write_rank(
+    manager: &SchemaManager<'_>,
+    table: &str,
+    id: &str,
+    value: String,
+) -> Result<(), DbErr> {
+    manager
+        .exec_stmt(
+            Query::update()
+                .table(Alias::new(table))
+                .value(Alias::new(RANK), value)
+                .and_where(Expr::col(Alias::new("id")).eq(id))
+                .to_owned(),
+        )
+        .await
+}
@@ -0,0 +1,45 @@
+//! 记录本机写出的日志批次。
+//!
+//! 重点：
+//! - 活动日志会随同步拉到其他设备，`batch_id` 也一并带过去
+//! - `local_batches` 不参与同步，撤销只在这里登记过的批次里挑
+//! - 升级前的批次分不清来源，不做回填，因此升级前的操作不能再撤销
+//! - 本机表只在 SQLite 上创建，同一个 Migrator 跑在远端 Postgres 上时跳过
+
+use sea_orm::{DbBackend, DbErr, Schema};
+use sea_orm_migration::prelude::*;
+
+use crate::db::entities::local_batches;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        if backend != DbBackend::Sqlite {
+            return Ok(());
+        }
+        let schema = Schema::new(backend);
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(local_batches::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(local_batches::Entity)
+                    .to_owned(),
+            )
+            .await
+    }
+}
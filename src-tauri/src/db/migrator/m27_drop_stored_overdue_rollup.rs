@@ -0,0 +1,47 @@
+//! 去掉落库的子树逾期任务数。
+//!
+//! 逾期随时间推移变化，写入时落库的值很快就会过期；改为读取项目列表时按当前时间计算。
+
+use sea_orm::DbErr;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMN: &str = "subtree_overdue_task_count";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("projects", COLUMN).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("projects"))
+                        .drop_column(Alias::new(COLUMN))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column("projects", COLUMN).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("projects"))
+                        .add_column(
+                            ColumnDef::new(Alias::new(COLUMN))
+                                .big_integer()
+                                .not_null()
+                                .default(0),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
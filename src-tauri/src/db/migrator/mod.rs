@@ -8,6 +8,28 @@ mod m02_task_activity_logs;
 mod m03_project_activity_logs;
 mod m04_relation_sync_tracking;
 mod m05_assets_library_v2;
+mod m06_task_recurrence;
+mod m07_task_checklist_items;
+mod m08_task_dependencies;
+mod m09_search_index;
+mod m10_task_workflow_statuses;
+mod m11_task_time_entries;
+mod m12_task_reminders;
+mod m13_task_scheduling;
+mod m14_activity_log_batches;
+mod m15_fractional_ranks;
+mod m16_task_templates;
+mod m17_project_templates;
+mod m18_undo_records;
+mod m19_purged_entities;
+mod m20_custom_field_definitions;
+mod m21_project_milestones;
+mod m22_project_lifecycle_status;
+mod m23_project_subtree_rollups;
+mod m24_project_schedule;
+mod m25_variable_length_ranks;
+mod m26_local_batches;
+mod m27_drop_stored_overdue_rollup;
 
 pub struct Migrator;
 
@@ -21,6 +43,28 @@ impl MigratorTrait for Migrator {
             Box::new(m03_project_activity_logs::Migration),
             Box::new(m04_relation_sync_tracking::Migration),
             Box::new(m05_assets_library_v2::Migration),
+            Box::new(m06_task_recurrence::Migration),
+            Box::new(m07_task_checklist_items::Migration),
+            Box::new(m08_task_dependencies::Migration),
+            Box::new(m09_search_index::Migration),
+            Box::new(m10_task_workflow_statuses::Migration),
+            Box::new(m11_task_time_entries::Migration),
+            Box::new(m12_task_reminders::Migration),
+            Box::new(m13_task_scheduling::Migration),
+            Box::new(m14_activity_log_batches::Migration),
+            Box::new(m15_fractional_ranks::Migration),
+            Box::new(m16_task_templates::Migration),
+            Box::new(m17_project_templates::Migration),
+            Box::new(m18_undo_records::Migration),
+            Box::new(m19_purged_entities::Migration),
+            Box::new(m20_custom_field_definitions::Migration),
+            Box::new(m21_project_milestones::Migration),
+            Box::new(m22_project_lifecycle_status::Migration),
+            Box::new(m23_project_subtree_rollups::Migration),
+            Box::new(m24_project_schedule::Migration),
+            Box::new(m25_variable_length_ranks::Migration),
+            Box::new(m26_local_batches::Migration),
+            Box::new(m27_drop_stored_overdue_rollup::Migration),
         ]
     }
 }
@@ -1,7 +1,7 @@
 //! 数据库层入口。
 //!
 //! 学习要点：
-//! - 连接初始化顺序：路径 -> 连接选项 -> 迁移 -> seed
+//! - 连接初始化顺序：路径 -> 连接选项 -> 迁移 -> seed -> 搜索索引回填
 //! - `DbState` 通过 Tauri `State` 注入到命令层
 //! - `now_ms()` 统一时间来源，避免各处自行取时间
 
@@ -59,5 +59,8 @@ pub async fn init_db(app: &AppHandle) -> Result<DbState, AppError> {
     seed::spaces::seed_default_spaces_if_empty(&conn).await?;
     seed::projects::seed_default_projects_and_backfill_tasks(&conn).await?;
 
+    // 5) 搜索索引为空时整体回填（首次升级或索引被清空）。
+    crate::repos::search_repo::rebuild_if_empty(&conn).await?;
+
     Ok(DbState { conn })
 }
@@ -8,7 +8,7 @@ use sea_orm::{
 
 use crate::db::entities::{projects, sea_orm_active_enums::Priority, spaces, tasks};
 use crate::db::now_ms;
-use crate::repos::task_repo::stats;
+use crate::repos::{rank, task_repo::stats};
 use crate::types::error::AppError;
 
 pub async fn seed_default_projects_and_backfill_tasks(
@@ -48,6 +48,9 @@ pub async fn seed_default_projects_and_backfill_tasks(
                 note: Set(None),
                 priority: Set(Priority::P1),
                 todo_task_count: Set(0),
+                doing_task_count: Set(0),
+                waiting_task_count: Set(0),
+                someday_task_count: Set(0),
                 done_task_count: Set(0),
                 last_task_updated_at: Set(None),
                 created_at: Set(now),
@@ -55,7 +58,14 @@ pub async fn seed_default_projects_and_backfill_tasks(
                 archived_at: Set(None),
                 deleted_at: Set(None),
                 create_by: Set("stonefish".to_string()),
-                rank: Set(1024),
+                rank: Set(rank::INITIAL.to_string()),
+                lifecycle_status: Set("active".to_string()),
+                start_at: Set(None),
+                due_at: Set(None),
+                subtree_task_count: Set(0),
+                subtree_done_task_count: Set(0),
+                subtree_last_activity_at: Set(None),
+                subtree_tracked_ms: Set(0),
             };
             active.insert(&txn).await.map_err(AppError::from)?;
         }
@@ -21,30 +21,55 @@ mod services;
 mod types;
 
 use commands::hello::hello;
-use commands::logs::list_activity_logs;
+use commands::logs::{list_activity_logs, redo, undo_last};
 use commands::assets::{
     create_diary_entry, create_note, create_snippet, create_vault_entry, delete_diary_entry,
     delete_note, delete_snippet, delete_vault_entry, get_assets_migration_status,
     import_legacy_assets, list_diary_entries, list_notes, list_snippets, list_vault_entries,
     update_diary_entry, update_note, update_snippet, update_vault_entry,
 };
+use commands::custom_fields::{
+    create_custom_field_definition, delete_custom_field_definition, list_custom_field_definitions,
+    update_custom_field_definition,
+};
 use commands::projects::{
-    archive_project, create_project, delete_project, get_default_project, list_deleted_projects,
-    list_projects, rebalance_project_ranks, reorder_project, restore_project, unarchive_project,
-    update_project,
+    archive_project, clone_project_subtree, create_project, create_project_milestone,
+    delete_project, delete_project_milestone, delete_project_template, get_default_project,
+    get_project_time_rollup, get_project_timeline, instantiate_project_template,
+    list_deleted_projects, list_project_milestones, list_project_templates, list_projects,
+    list_projects_at_risk, rebalance_project_ranks, rename_project_template, reorder_project,
+    restore_project, save_project_as_template, unarchive_project, update_project,
+    update_project_milestone,
 };
+use commands::search::search;
 use commands::spaces::list_spaces;
 use commands::sync::{pull_from_neon, push_to_neon, test_neon_connection};
 use commands::tasks::{
-    complete_task, create_task, create_task_with_patch, delete_tasks, list_deleted_tasks,
-    list_tasks, rebalance_ranks, reorder_task, restore_tasks, update_task,
+    aggregate_tasks_by_custom_field, archive_tasks, bulk_update_tasks, complete_task, create_task,
+    create_task_checklist_item, create_task_template, create_task_with_patch,
+    delete_task_checklist_item, delete_task_template, delete_task_time_entry, delete_tasks,
+    dismiss_task_reminder, duplicate_tasks, get_active_task_timer, get_task_auto_archive_days,
+    instantiate_task_template, list_archived_tasks, list_deleted_tasks, list_task_checklist_items,
+    list_task_reminders, list_task_templates, list_task_time_entries, list_tasks, list_today,
+    pause_task_timer, query_tasks, quick_add, rebalance_ranks, reorder_task,
+    reorder_task_checklist_item, restore_tasks, resume_task_timer, set_task_auto_archive_days,
+    set_task_reminders, snooze_task_reminder, start_task_timer, stop_task_timer,
+    toggle_task_checklist_item, unarchive_tasks, update_task, update_task_checklist_item,
+    update_task_template,
+};
+use commands::trash::{
+    empty_trash, get_trash_retention_days, purge_projects, purge_tasks, set_trash_retention_days,
 };
 use serde_json::Value;
+use services::{ReminderScheduler, SystemClock, TaskService, TrashService};
+use std::sync::Arc;
 use tauri::{
     menu::{MenuBuilder, MenuItemBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WebviewUrl, WebviewWindowBuilder,
+    Emitter, Manager, WebviewUrl, WebviewWindowBuilder,
 };
+use tauri_plugin_notification::NotificationExt;
+use types::dto::ReminderFireDto;
 
 #[cfg(target_os = "macos")]
 use tauri::TitleBarStyle;
@@ -54,6 +79,8 @@ const MAIN_WINDOW_LABEL: &str = "main";
 const TRAY_MENU_SHOW_MAIN: &str = "show_main_window";
 const TRAY_MENU_QUIT_APP: &str = "quit_app";
 
+const REMINDER_FIRED_EVENT: &str = "task-reminder-fired";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TrayLocale {
     ZhCn,
@@ -115,6 +142,75 @@ fn resolve_tray_menu_labels(locale: TrayLocale) -> (&'static str, &'static str)
     }
 }
 
+fn resolve_reminder_body(locale: TrayLocale, remaining_ms: i64) -> String {
+    let minutes = (remaining_ms + 59_999) / 60_000;
+    match locale {
+        TrayLocale::ZhCn => match minutes {
+            i64::MIN..=-1 => "已逾期".to_string(),
+            0 => "已到截止时间".to_string(),
+            1..=59 => format!("{minutes} 分钟后截止"),
+            60..=1439 => format!("{} 小时后截止", minutes / 60),
+            _ => format!("{} 天后截止", minutes / 1440),
+        },
+        TrayLocale::EnUs => match minutes {
+            i64::MIN..=-1 => "Overdue".to_string(),
+            0 => "Due now".to_string(),
+            1..=59 => format!("Due in {minutes} min"),
+            60..=1439 => format!("Due in {} h", minutes / 60),
+            _ => format!("Due in {} d", minutes / 1440),
+        },
+    }
+}
+
+/// 投递一条截止提醒：系统通知不依赖 webview，主窗口隐藏在托盘时同样会弹出。
+fn notify_task_reminder<R: tauri::Runtime>(app: &tauri::AppHandle<R>, fire: &ReminderFireDto) {
+    // 通知文案与托盘菜单共用同一套语言策略。
+    let body = resolve_reminder_body(resolve_tray_locale(app), fire.deadline_at - fire.fired_at);
+    if let Err(error) = app
+        .notification()
+        .builder()
+        .title(&fire.task_title)
+        .body(body)
+        .show()
+    {
+        eprintln!("failed to show reminder notification: {error}");
+    }
+    // 前端据此展示稍后/忽略操作。
+    let _ = app.emit(REMINDER_FIRED_EVENT, fire);
+}
+
+/// 启动截止提醒调度器，并把唤醒句柄注入给命令层。
+fn start_reminder_scheduler(app: &tauri::App) {
+    let conn = app.state::<crate::db::DbState>().conn.clone();
+    let scheduler = ReminderScheduler::new(conn, Arc::new(SystemClock));
+    app.manage(scheduler.handle());
+
+    let app_handle = app.handle().clone();
+    tauri::async_runtime::spawn(
+        scheduler.run(move |fire| notify_task_reminder(&app_handle, &fire)),
+    );
+}
+
+/// 启动时在后台归档完成已久的任务，失败只记日志。
+fn auto_archive_done_tasks(app: &tauri::App) {
+    let conn = app.state::<crate::db::DbState>().conn.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(error) = TaskService::auto_archive_done(&conn, crate::db::now_ms()).await {
+            eprintln!("failed to auto-archive done tasks: {error}");
+        }
+    });
+}
+
+/// 启动时在后台清除超过保留期的回收站内容，失败只记日志。
+fn purge_expired_trash(app: &tauri::App) {
+    let conn = app.state::<crate::db::DbState>().conn.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(error) = TrashService::purge_expired(&conn, crate::db::now_ms()).await {
+            eprintln!("failed to purge expired trash: {error}");
+        }
+    });
+}
+
 fn restore_main_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
     if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
         let _ = window.unminimize();
@@ -172,6 +268,7 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .setup(|app| {
             build_main_window(app)?;
@@ -219,11 +316,17 @@ pub fn run() {
                 .plugin(tauri_plugin_stronghold::Builder::with_argon2(&salt_path).build())?;
 
             // 重点：这里通过 async_runtime 阻塞等待初始化完成，确保命令注册后数据库可用。
-            tauri::async_runtime::block_on(async move {
+            tauri::async_runtime::block_on(async {
                 let db_state = crate::db::init_db(app.handle()).await.map_err(Box::new)?;
                 app.manage(db_state);
-                Ok(())
-            })
+                Ok::<(), Box<dyn std::error::Error>>(())
+            })?;
+
+            // 数据库就绪后再启动提醒调度器，它与窗口生命周期无关。
+            start_reminder_scheduler(app);
+            auto_archive_done_tasks(app);
+            purge_expired_trash(app);
+            Ok(())
         })
         // invoke_handler 把前端可调用的 command 显式列在这里，便于审计接口边界。
         .invoke_handler(tauri::generate_handler![
@@ -247,6 +350,13 @@ pub fn run() {
             get_assets_migration_status,
             import_legacy_assets,
             list_projects,
+            get_project_time_rollup,
+            get_project_timeline,
+            list_projects_at_risk,
+            list_project_milestones,
+            create_project_milestone,
+            update_project_milestone,
+            delete_project_milestone,
             list_deleted_projects,
             create_project,
             update_project,
@@ -257,18 +367,69 @@ pub fn run() {
             unarchive_project,
             list_spaces,
             list_activity_logs,
+            undo_last,
+            redo,
             list_tasks,
+            query_tasks,
+            aggregate_tasks_by_custom_field,
+            list_today,
             list_deleted_tasks,
             create_task,
             create_task_with_patch,
+            quick_add,
             update_task,
+            bulk_update_tasks,
+            duplicate_tasks,
             complete_task,
             delete_tasks,
             restore_tasks,
+            list_archived_tasks,
+            archive_tasks,
+            unarchive_tasks,
+            get_task_auto_archive_days,
+            set_task_auto_archive_days,
+            purge_tasks,
+            purge_projects,
+            empty_trash,
+            get_trash_retention_days,
+            set_trash_retention_days,
+            list_custom_field_definitions,
+            create_custom_field_definition,
+            update_custom_field_definition,
+            delete_custom_field_definition,
             reorder_task,
             rebalance_ranks,
+            list_task_checklist_items,
+            create_task_checklist_item,
+            update_task_checklist_item,
+            toggle_task_checklist_item,
+            reorder_task_checklist_item,
+            delete_task_checklist_item,
+            start_task_timer,
+            pause_task_timer,
+            resume_task_timer,
+            stop_task_timer,
+            get_active_task_timer,
+            list_task_time_entries,
+            delete_task_time_entry,
+            list_task_reminders,
+            set_task_reminders,
+            snooze_task_reminder,
+            dismiss_task_reminder,
+            list_task_templates,
+            create_task_template,
+            update_task_template,
+            delete_task_template,
+            instantiate_task_template,
             reorder_project,
+            clone_project_subtree,
             rebalance_project_ranks,
+            list_project_templates,
+            save_project_as_template,
+            rename_project_template,
+            delete_project_template,
+            instantiate_project_template,
+            search,
             pull_from_neon,
             push_to_neon,
             test_neon_connection
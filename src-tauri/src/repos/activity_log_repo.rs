@@ -13,6 +13,7 @@ use sea_orm::{
 use uuid::Uuid;
 
 use crate::db::entities::{project_activity_logs, projects, task_activity_logs};
+use crate::repos::undo_repo;
 use crate::types::{dto::ActivityLogDto, error::AppError};
 
 pub struct ActivityLogRepo;
@@ -31,6 +32,7 @@ pub struct NewTaskActivityLogInput {
     pub detail: String,
     pub create_by: String,
     pub created_at: i64,
+    pub batch_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,6 +48,7 @@ pub struct NewProjectActivityLogInput {
     pub detail: String,
     pub create_by: String,
     pub created_at: i64,
+    pub batch_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -54,6 +57,7 @@ pub struct ListActivityLogsInput {
     pub task_id: Option<String>,
     pub space_id: Option<String>,
     pub project_id: Option<String>,
+    pub batch_id: Option<String>,
     pub from: Option<i64>,
     pub to: Option<i64>,
     pub limit: Option<u64>,
@@ -68,6 +72,9 @@ impl ActivityLogRepo {
     where
         C: ConnectionTrait,
     {
+        if let Some(batch_id) = input.batch_id.as_deref() {
+            undo_repo::record_local_batch(conn, batch_id, input.created_at).await?;
+        }
         let model = task_activity_logs::ActiveModel {
             id: Set(Uuid::new_v4().to_string()),
             task_id: Set(input.task_id),
@@ -82,6 +89,7 @@ impl ActivityLogRepo {
             detail: Set(input.detail),
             create_by: Set(input.create_by),
             created_at: Set(input.created_at),
+            batch_id: Set(input.batch_id),
         };
 
         model.insert(conn).await.map_err(AppError::from)?;
@@ -95,6 +103,9 @@ impl ActivityLogRepo {
     where
         C: ConnectionTrait,
     {
+        if let Some(batch_id) = input.batch_id.as_deref() {
+            undo_repo::record_local_batch(conn, batch_id, input.created_at).await?;
+        }
         let model = project_activity_logs::ActiveModel {
             id: Set(Uuid::new_v4().to_string()),
             project_id: Set(input.project_id),
@@ -108,6 +119,7 @@ impl ActivityLogRepo {
             detail: Set(input.detail),
             create_by: Set(input.create_by),
             created_at: Set(input.created_at),
+            batch_id: Set(input.batch_id),
         };
 
         model.insert(conn).await.map_err(AppError::from)?;
@@ -143,6 +155,10 @@ impl ActivityLogRepo {
             query = query.filter(task_activity_logs::Column::ProjectId.eq(project_id));
         }
 
+        if let Some(batch_id) = input.batch_id.as_deref() {
+            query = query.filter(task_activity_logs::Column::BatchId.eq(batch_id));
+        }
+
         if let Some(from) = input.from {
             query = query.filter(task_activity_logs::Column::CreatedAt.gte(from));
         }
@@ -192,6 +208,7 @@ impl ActivityLogRepo {
                     space_id: model.space_id,
                     project_id: model.project_id,
                     project_name,
+                    batch_id: model.batch_id,
                 }
             })
             .collect())
@@ -211,6 +228,10 @@ impl ActivityLogRepo {
             query = query.filter(project_activity_logs::Column::SpaceId.eq(space_id));
         }
 
+        if let Some(batch_id) = input.batch_id.as_deref() {
+            query = query.filter(project_activity_logs::Column::BatchId.eq(batch_id));
+        }
+
         if let Some(from) = input.from {
             query = query.filter(project_activity_logs::Column::CreatedAt.gte(from));
         }
@@ -258,6 +279,7 @@ impl ActivityLogRepo {
                     space_id: model.space_id,
                     project_id: Some(project_id),
                     project_name,
+                    batch_id: model.batch_id,
                 }
             })
             .collect())
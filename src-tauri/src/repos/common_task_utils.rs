@@ -1,7 +1,7 @@
 //! Task/Project 共享的轻量校验与规范化工具。
 //! 重点：统一校验规则，避免命令层与 repo 层出现“同名不同义”。
 
-use crate::db::entities::sea_orm_active_enums::Priority;
+use crate::db::entities::sea_orm_active_enums::{Priority, TaskStatus};
 use crate::types::error::AppError;
 
 /// 解析优先级字符串为枚举，支持不区分大小写。
@@ -45,12 +45,32 @@ pub fn normalize_status(status: &str) -> String {
 }
 
 pub fn validate_status(status: &str) -> Result<String, AppError> {
-    let normalized = normalize_status(status);
-    if normalized == "todo" || normalized == "done" {
-        Ok(normalized)
-    } else {
-        Err(AppError::Validation("状态必须为 todo 或 done".to_string()))
+    parse_status(status).map(|status| status_to_string(&status))
+}
+
+/// 解析状态字符串为枚举，支持不区分大小写。
+pub fn parse_status(status: &str) -> Result<TaskStatus, AppError> {
+    match normalize_status(status).as_str() {
+        "todo" => Ok(TaskStatus::Todo),
+        "doing" => Ok(TaskStatus::Doing),
+        "waiting" => Ok(TaskStatus::Waiting),
+        "someday" => Ok(TaskStatus::Someday),
+        "done" => Ok(TaskStatus::Done),
+        _ => Err(AppError::Validation(
+            "状态必须为 todo、doing、waiting、someday 或 done".to_string(),
+        )),
+    }
+}
+
+pub fn status_to_string(status: &TaskStatus) -> String {
+    match status {
+        TaskStatus::Todo => "todo",
+        TaskStatus::Doing => "doing",
+        TaskStatus::Waiting => "waiting",
+        TaskStatus::Someday => "someday",
+        TaskStatus::Done => "done",
     }
+    .to_string()
 }
 
 pub fn normalize_done_reason(reason: &str) -> Result<String, AppError> {
@@ -0,0 +1,177 @@
+//! 自定义字段定义仓储。
+//!
+//! 重点：
+//! - 定义挂在 space（`project_id` 为空）或单个项目上，任务按 space + 所属项目取生效定义
+//! - 任务里的字段值仍存在 `tasks.custom_fields`，按标题与定义关联
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+
+use crate::db::entities::{custom_field_definitions, tasks};
+use crate::types::{dto::CustomFieldDefinitionDto, error::AppError};
+
+pub struct NewDefinitionRecord {
+    pub id: String,
+    pub space_id: String,
+    pub project_id: Option<String>,
+    pub title: String,
+    pub field_type: String,
+    pub options: Option<String>,
+    pub rank: i64,
+    pub created_at: i64,
+}
+
+/// 把定义转换成前端 DTO。
+pub fn to_dto(model: custom_field_definitions::Model) -> CustomFieldDefinitionDto {
+    CustomFieldDefinitionDto {
+        options: parse_options(model.options.as_deref()),
+        id: model.id,
+        space_id: model.space_id,
+        project_id: model.project_id,
+        title: model.title,
+        field_type: model.field_type,
+        rank: model.rank,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 解析候选项 JSON；脏数据回退为空列表。
+pub fn parse_options(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str::<Vec<String>>(raw).ok())
+        .unwrap_or_default()
+}
+
+/// 按 id 读取未删除的定义。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<custom_field_definitions::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    custom_field_definitions::Entity::find_by_id(id)
+        .filter(custom_field_definitions::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("自定义字段不存在".to_string()))
+}
+
+/// 对某个 space / 项目下的任务生效的定义：space 级定义加上该项目自己的定义。
+pub async fn list_applicable<C>(
+    conn: &C,
+    space_id: &str,
+    project_id: Option<&str>,
+) -> Result<Vec<custom_field_definitions::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut scope = Condition::any().add(custom_field_definitions::Column::ProjectId.is_null());
+    if let Some(project_id) = project_id {
+        scope = scope.add(custom_field_definitions::Column::ProjectId.eq(project_id));
+    }
+    custom_field_definitions::Entity::find()
+        .filter(custom_field_definitions::Column::SpaceId.eq(space_id))
+        .filter(custom_field_definitions::Column::DeletedAt.is_null())
+        .filter(scope)
+        .order_by_asc(custom_field_definitions::Column::Rank)
+        .order_by_asc(custom_field_definitions::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// space 内与给定标题重名的未删除定义（任意作用域）。
+pub async fn find_by_title_in_space<C>(
+    conn: &C,
+    space_id: &str,
+    title: &str,
+) -> Result<Vec<custom_field_definitions::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    custom_field_definitions::Entity::find()
+        .filter(custom_field_definitions::Column::SpaceId.eq(space_id))
+        .filter(custom_field_definitions::Column::Title.eq(title))
+        .filter(custom_field_definitions::Column::DeletedAt.is_null())
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 同一作用域内下一个排序值。
+pub async fn next_rank<C>(
+    conn: &C,
+    space_id: &str,
+    project_id: Option<&str>,
+) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    let project_filter = match project_id {
+        Some(project_id) => custom_field_definitions::Column::ProjectId.eq(project_id),
+        None => custom_field_definitions::Column::ProjectId.is_null(),
+    };
+    let max_rank: Option<Option<i64>> = custom_field_definitions::Entity::find()
+        .select_only()
+        .column_as(custom_field_definitions::Column::Rank.max(), "max_rank")
+        .filter(custom_field_definitions::Column::SpaceId.eq(space_id))
+        .filter(custom_field_definitions::Column::DeletedAt.is_null())
+        .filter(project_filter)
+        .into_tuple()
+        .one(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(max_rank.flatten().map_or(0, |rank| rank + 1))
+}
+
+pub async fn insert<C>(
+    conn: &C,
+    record: NewDefinitionRecord,
+) -> Result<custom_field_definitions::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    custom_field_definitions::ActiveModel {
+        id: Set(record.id),
+        space_id: Set(record.space_id),
+        project_id: Set(record.project_id),
+        title: Set(record.title),
+        field_type: Set(record.field_type),
+        options: Set(record.options),
+        rank: Set(record.rank),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.created_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+pub async fn update<C>(
+    conn: &C,
+    active_model: custom_field_definitions::ActiveModel,
+) -> Result<custom_field_definitions::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
+
+/// 定义作用域内带有自定义字段的任务（含已删除任务，改名时一并迁移）。
+pub async fn find_tasks_in_scope<C>(
+    conn: &C,
+    definition: &custom_field_definitions::Model,
+) -> Result<Vec<tasks::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut query = tasks::Entity::find()
+        .filter(tasks::Column::SpaceId.eq(definition.space_id.as_str()))
+        .filter(tasks::Column::CustomFields.is_not_null());
+    if let Some(project_id) = definition.project_id.as_deref() {
+        query = query.filter(tasks::Column::ProjectId.eq(project_id));
+    }
+    query.all(conn).await.map_err(AppError::from)
+}
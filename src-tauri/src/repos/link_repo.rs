@@ -7,8 +7,8 @@
 use std::collections::{HashMap, HashSet};
 
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
-    QuerySelect, RelationTrait, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect,
+    RelationTrait, Set,
 };
 use uuid::Uuid;
 
@@ -37,11 +37,14 @@ struct NormalizedLinkInput {
     rank: i64,
 }
 
-pub async fn load_links(
-    conn: &DatabaseConnection,
+pub async fn load_links<C>(
+    conn: &C,
     entity: LinkEntity,
     owner_ids: &[String],
-) -> Result<HashMap<String, Vec<LinkDto>>, AppError> {
+) -> Result<HashMap<String, Vec<LinkDto>>, AppError>
+where
+    C: ConnectionTrait,
+{
     if owner_ids.is_empty() {
         return Ok(HashMap::new());
     }
@@ -9,12 +9,20 @@
 //! - 不承载完整业务用例
 //! - 不统一管理事务边界
 //! - 不决定跨实体副作用触发时机
+//!
+//! 约定：
+//! - 可同步实体的删除只写 tombstone（`deleted_at`），让删除能随同步传播；读路径自行过滤
 
 pub mod activity_log_repo;
 pub mod asset_repo;
 pub mod common_task_utils;
+pub mod custom_field_repo;
 pub mod link_repo;
 pub mod project_repo;
+pub mod rank;
+pub mod search_repo;
 pub mod space_repo;
 pub mod tag_repo;
 pub mod task_repo;
+pub mod trash_repo;
+pub mod undo_repo;
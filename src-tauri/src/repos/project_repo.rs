@@ -11,13 +11,24 @@ use sea_orm::{
 };
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::db::entities::projects;
-use crate::types::{dto::ProjectDto, error::AppError};
+use crate::db::{entities::projects, now_ms};
+use crate::repos::task_repo::time_entries;
+use crate::types::{
+    dto::{
+        ProjectDto, ProjectMilestoneDto, ProjectRiskDto, ProjectTemplateDto, ProjectTimeRollupDto,
+        ProjectTimelinePageDto,
+    },
+    error::AppError,
+};
 
 pub mod activity_logs;
 pub mod helpers;
+pub mod milestones;
 pub mod mutation;
 pub mod query;
+pub mod schedule;
+pub mod templates;
+pub mod timeline;
 
 pub struct ProjectRepo;
 
@@ -84,10 +95,17 @@ impl ProjectRepo {
     pub async fn list_by_space(
         conn: &DatabaseConnection,
         space_id: &str,
+        lifecycle_statuses: &[&str],
     ) -> Result<Vec<ProjectDto>, AppError> {
-        let models = projects::Entity::find()
+        let mut query = projects::Entity::find()
             .filter(projects::Column::SpaceId.eq(space_id))
-            .filter(projects::Column::DeletedAt.is_null())
+            .filter(projects::Column::DeletedAt.is_null());
+        if !lifecycle_statuses.is_empty() {
+            query = query.filter(
+                projects::Column::LifecycleStatus.is_in(lifecycle_statuses.iter().copied()),
+            );
+        }
+        let models = query
             .order_by_asc(projects::Column::Rank)
             .order_by_asc(projects::Column::CreatedAt)
             .all(conn)
@@ -101,6 +119,7 @@ impl ProjectRepo {
 
         helpers::attach_links(conn, &mut dtos).await?;
         helpers::attach_tags(conn, &mut dtos).await?;
+        helpers::attach_subtree_overdue(conn, space_id, &mut dtos, now_ms()).await?;
 
         Ok(dtos)
     }
@@ -155,4 +174,68 @@ impl ProjectRepo {
             ))),
         }
     }
+
+    /// 列出项目模板。
+    pub async fn list_templates(
+        conn: &DatabaseConnection,
+    ) -> Result<Vec<ProjectTemplateDto>, AppError> {
+        Ok(templates::list(conn)
+            .await?
+            .into_iter()
+            .map(templates::to_dto)
+            .collect())
+    }
+
+    /// 列出项目下的里程碑，按 rank 排序。
+    pub async fn list_milestones(
+        conn: &DatabaseConnection,
+        project_id: &str,
+    ) -> Result<Vec<ProjectMilestoneDto>, AppError> {
+        Ok(milestones::list_for_project(conn, project_id)
+            .await?
+            .into_iter()
+            .map(milestones::to_dto)
+            .collect())
+    }
+
+    /// 汇总项目计时：自身任务 + 整棵子树任务的已结束计时时长。
+    pub async fn time_rollup(
+        conn: &DatabaseConnection,
+        project_id: &str,
+    ) -> Result<ProjectTimeRollupDto, AppError> {
+        // 收集子树时包含已软删除的节点，汇总前要去掉，否则回收站里的子项目也会被计入。
+        let subtree_ids: Vec<String> = query::find_not_deleted_by_ids(
+            conn,
+            &Self::collect_subtree_ids(conn, project_id).await?,
+        )
+        .await?
+        .into_iter()
+        .map(|project| project.id)
+        .collect();
+        let own_ms =
+            time_entries::sum_tracked_for_projects(conn, &[project_id.to_string()]).await?;
+        let subtree_ms = time_entries::sum_tracked_for_projects(conn, &subtree_ids).await?;
+
+        Ok(ProjectTimeRollupDto {
+            project_id: project_id.to_string(),
+            own_ms,
+            subtree_ms,
+        })
+    }
+
+    /// 项目子树的时间线，按时间倒序分页。
+    pub async fn timeline(
+        conn: &DatabaseConnection,
+        query: timeline::TimelineQuery,
+    ) -> Result<ProjectTimelinePageDto, AppError> {
+        timeline::list(conn, query).await
+    }
+
+    /// 列出排期有风险的项目：截止临近但大量任务未完成，或有任务截止晚于项目。
+    pub async fn list_at_risk(
+        conn: &DatabaseConnection,
+        query: schedule::AtRiskQuery,
+    ) -> Result<Vec<ProjectRiskDto>, AppError> {
+        schedule::list_at_risk(conn, query).await
+    }
 }
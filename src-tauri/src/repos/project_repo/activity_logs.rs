@@ -5,12 +5,18 @@ use sea_orm::ConnectionTrait;
 use crate::repos::activity_log_repo::{ActivityLogRepo, NewProjectActivityLogInput};
 use crate::types::error::AppError;
 
-const ACTION_PROJECT_CREATED: &str = "project_created";
-const ACTION_PROJECT_DELETED: &str = "project_deleted";
-const ACTION_PROJECT_RESTORED: &str = "project_restored";
-const ACTION_PROJECT_ARCHIVED: &str = "project_archived";
-const ACTION_PROJECT_UNARCHIVED: &str = "project_unarchived";
-const ACTION_PROJECT_FIELD_UPDATED: &str = "project_field_updated";
+pub const ACTION_PROJECT_CREATED: &str = "project_created";
+pub const ACTION_PROJECT_DELETED: &str = "project_deleted";
+pub const ACTION_PROJECT_RESTORED: &str = "project_restored";
+pub const ACTION_PROJECT_ARCHIVED: &str = "project_archived";
+pub const ACTION_PROJECT_UNARCHIVED: &str = "project_unarchived";
+pub const ACTION_PROJECT_FIELD_UPDATED: &str = "project_field_updated";
+pub const ACTION_PROJECT_CLONED: &str = "project_cloned";
+pub const ACTION_MILESTONE_CREATED: &str = "milestone_created";
+pub const ACTION_MILESTONE_UPDATED: &str = "milestone_updated";
+pub const ACTION_MILESTONE_COMPLETED: &str = "milestone_completed";
+pub const ACTION_MILESTONE_REOPENED: &str = "milestone_reopened";
+pub const ACTION_MILESTONE_DELETED: &str = "milestone_deleted";
 
 /// 项目活动日志写入时复用的上下文。
 #[derive(Debug, Clone)]
@@ -19,6 +25,8 @@ pub struct ProjectLogCtx<'a> {
     pub space_id: &'a str,
     pub create_by: &'a str,
     pub created_at: i64,
+    /// 同一次写操作的日志共用的批次 id
+    pub batch_id: Option<&'a str>,
 }
 
 /// 追加“项目创建”日志。
@@ -44,6 +52,7 @@ where
             detail: format!("创建项目「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -72,6 +81,7 @@ where
             detail: format!("删除项目「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -100,6 +110,7 @@ where
             detail: format!("恢复项目「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -128,6 +139,7 @@ where
             detail: format!("归档项目「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -156,6 +168,37 @@ where
             detail: format!("取消归档项目「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
+        },
+    )
+    .await
+}
+
+/// 追加“克隆项目”日志，记录新项目复制自哪个项目。
+pub async fn append_cloned<C>(
+    conn: &C,
+    ctx: ProjectLogCtx<'_>,
+    source_project_id: &str,
+    source_title: &str,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    ActivityLogRepo::append_project(
+        conn,
+        NewProjectActivityLogInput {
+            project_id: ctx.project_id.to_string(),
+            space_id: ctx.space_id.to_string(),
+            action: ACTION_PROJECT_CLONED.to_string(),
+            action_label: "克隆项目".to_string(),
+            field_key: Some("sourceProjectId".to_string()),
+            field_label: Some("来源项目".to_string()),
+            before_value: Some(source_project_id.to_string()),
+            after_value: Some(ctx.project_id.to_string()),
+            detail: format!("复制自项目「{}」", source_title),
+            create_by: ctx.create_by.to_string(),
+            created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -197,6 +240,47 @@ where
             ),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
+        },
+    )
+    .await
+}
+
+/// 追加里程碑日志，记在所属项目名下。
+///
+/// `field_key` 统一为 `milestones`，`after_value` 记录里程碑 id，便于前端按里程碑筛选。
+pub async fn append_milestone<C>(
+    conn: &C,
+    ctx: ProjectLogCtx<'_>,
+    action: &str,
+    milestone_id: &str,
+    detail: String,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let action_label = match action {
+        ACTION_MILESTONE_CREATED => "新建里程碑",
+        ACTION_MILESTONE_COMPLETED => "完成里程碑",
+        ACTION_MILESTONE_REOPENED => "重新打开里程碑",
+        ACTION_MILESTONE_DELETED => "删除里程碑",
+        _ => "更新里程碑",
+    };
+    ActivityLogRepo::append_project(
+        conn,
+        NewProjectActivityLogInput {
+            project_id: ctx.project_id.to_string(),
+            space_id: ctx.space_id.to_string(),
+            action: action.to_string(),
+            action_label: action_label.to_string(),
+            field_key: Some("milestones".to_string()),
+            field_label: Some("里程碑".to_string()),
+            before_value: None,
+            after_value: Some(milestone_id.to_string()),
+            detail,
+            create_by: ctx.create_by.to_string(),
+            created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -1,9 +1,17 @@
 //! Project 仓储辅助函数。
 //! 重点：将“可复用但不对外暴露”的逻辑集中在 helper，保持主 repo 可读。
 
-use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::HashMap;
 
-use crate::db::entities::{projects, sea_orm_active_enums::Priority};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{
+    projects,
+    sea_orm_active_enums::{Priority, TaskStatus},
+    tasks,
+};
 use crate::repos::{
     link_repo::{self, LinkEntity},
     tag_repo::{self, TagEntity},
@@ -13,7 +21,7 @@ use crate::types::{dto::ProjectDto, error::AppError};
 pub fn compute_status(
     deleted_at: Option<i64>,
     archived_at: Option<i64>,
-    todo_task_count: i64,
+    open_task_count: i64,
     done_task_count: i64,
 ) -> String {
     // deleted 优先级最高，其次 archived，再根据任务计数判断。
@@ -23,7 +31,8 @@ pub fn compute_status(
     if archived_at.is_some() {
         return "archived".to_string();
     }
-    if todo_task_count > 0 {
+    // todo / doing / waiting / someday 都算未完成。
+    if open_task_count > 0 {
         return "inProgress".to_string();
     }
     if done_task_count > 0 {
@@ -32,12 +41,32 @@ pub fn compute_status(
     "inProgress".to_string()
 }
 
+pub const LIFECYCLE_PLANNED: &str = "planned";
+pub const LIFECYCLE_ACTIVE: &str = "active";
+pub const LIFECYCLE_PAUSED: &str = "paused";
+pub const LIFECYCLE_COMPLETED: &str = "completed";
+pub const LIFECYCLE_CANCELLED: &str = "cancelled";
+
+/// 校验并规范化生命周期状态。
+pub fn parse_lifecycle_status(value: &str) -> Result<&'static str, AppError> {
+    [
+        LIFECYCLE_PLANNED,
+        LIFECYCLE_ACTIVE,
+        LIFECYCLE_PAUSED,
+        LIFECYCLE_COMPLETED,
+        LIFECYCLE_CANCELLED,
+    ]
+    .into_iter()
+    .find(|status| *status == value.trim())
+    .ok_or_else(|| AppError::Validation(format!("不支持的项目状态：{value}")))
+}
+
 /// 把项目模型转换成前端 DTO。
 pub fn project_model_to_dto(m: projects::Model) -> ProjectDto {
     let computed_status = compute_status(
         m.deleted_at,
         m.archived_at,
-        m.todo_task_count,
+        m.todo_task_count + m.doing_task_count + m.waiting_task_count + m.someday_task_count,
         m.done_task_count,
     );
     let priority = match m.priority {
@@ -64,10 +93,21 @@ pub fn project_model_to_dto(m: projects::Model) -> ProjectDto {
         archived_at: m.archived_at,
         deleted_at: m.deleted_at,
         create_by: m.create_by,
+        lifecycle_status: m.lifecycle_status,
+        start_at: m.start_at,
+        due_at: m.due_at,
         computed_status,
         todo_task_count: m.todo_task_count,
+        doing_task_count: m.doing_task_count,
+        waiting_task_count: m.waiting_task_count,
+        someday_task_count: m.someday_task_count,
         done_task_count: m.done_task_count,
         last_task_updated_at: m.last_task_updated_at,
+        subtree_task_count: m.subtree_task_count,
+        subtree_done_task_count: m.subtree_done_task_count,
+        subtree_overdue_task_count: 0,
+        subtree_last_activity_at: m.subtree_last_activity_at,
+        subtree_tracked_ms: m.subtree_tracked_ms,
     }
 }
 
@@ -108,6 +148,65 @@ pub async fn attach_tags(
     Ok(())
 }
 
+/// 按读取时刻补上子树逾期任务数；`projects` 需属于同一 Space。
+///
+/// 逾期随时间推移变化，不适合像其他子树汇总那样在写入时落库。
+pub async fn attach_subtree_overdue(
+    conn: &DatabaseConnection,
+    space_id: &str,
+    projects: &mut [ProjectDto],
+    now: i64,
+) -> Result<(), AppError> {
+    if projects.is_empty() {
+        return Ok(());
+    }
+
+    let rows: Vec<(Option<String>, i64)> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::ProjectId)
+        .column_as(tasks::Column::Id.count(), "count")
+        .filter(tasks::Column::SpaceId.eq(space_id))
+        .filter(tasks::Column::Status.ne(TaskStatus::Done))
+        .filter(tasks::Column::DeadlineAt.lt(now))
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .group_by(tasks::Column::ProjectId)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let overdue_by_project: HashMap<String, i64> = rows
+        .into_iter()
+        .filter_map(|(project_id, count)| project_id.map(|id| (id, count)))
+        .collect();
+
+    // 子树按整个 Space 的未删除项目圈定，不受调用方筛选条件影响。
+    let space_projects: Vec<(String, String)> = projects::Entity::find()
+        .select_only()
+        .column(projects::Column::Id)
+        .column(projects::Column::Path)
+        .filter(projects::Column::SpaceId.eq(space_id))
+        .filter(projects::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let own_counts: Vec<(String, i64)> = space_projects
+        .into_iter()
+        .map(|(id, path)| (path, overdue_by_project.get(&id).copied().unwrap_or(0)))
+        .collect();
+    for project in projects.iter_mut() {
+        let prefix = format!("{}/", project.path);
+        project.subtree_overdue_task_count = own_counts
+            .iter()
+            .filter(|(path, _)| *path == project.path || path.starts_with(&prefix))
+            .map(|(_, count)| count)
+            .sum();
+    }
+
+    Ok(())
+}
+
 /// 根据父节点路径构建项目 path。
 ///
 /// path 是冗余字段，但它能显著提高树结构查询和展示效率。
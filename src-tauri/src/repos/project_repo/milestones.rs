@@ -0,0 +1,182 @@
+//! 项目里程碑持久化原语。
+//! 重点：任务通过 `tasks.milestone_id` 挂到里程碑；删除里程碑前先用 `detach_tasks` 把任务摘下来。
+
+use sea_orm::{
+    prelude::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+
+use crate::db::entities::{project_milestones, tasks};
+use crate::types::{dto::ProjectMilestoneDto, error::AppError};
+
+pub struct NewMilestoneRecord {
+    pub id: String,
+    pub project_id: String,
+    pub space_id: String,
+    pub title: String,
+    pub target_at: Option<i64>,
+    pub rank: i64,
+    pub created_at: i64,
+}
+
+/// 把里程碑模型转换成前端 DTO，进度按完成任务数 / 任务总数计算。
+pub fn to_dto(model: project_milestones::Model) -> ProjectMilestoneDto {
+    let progress = if model.task_count > 0 {
+        model.done_task_count as f64 / model.task_count as f64
+    } else {
+        0.0
+    };
+    ProjectMilestoneDto {
+        id: model.id,
+        project_id: model.project_id,
+        space_id: model.space_id,
+        title: model.title,
+        target_at: model.target_at,
+        rank: model.rank,
+        completed_at: model.completed_at,
+        task_count: model.task_count,
+        done_task_count: model.done_task_count,
+        progress,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 按 id 读取未删除的里程碑。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<project_milestones::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_milestones::Entity::find_by_id(id)
+        .filter(project_milestones::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("里程碑不存在".to_string()))
+}
+
+/// 项目下未删除的里程碑，按 rank 排序。
+pub async fn list_for_project<C>(
+    conn: &C,
+    project_id: &str,
+) -> Result<Vec<project_milestones::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_milestones::Entity::find()
+        .filter(project_milestones::Column::ProjectId.eq(project_id))
+        .filter(project_milestones::Column::DeletedAt.is_null())
+        .order_by_asc(project_milestones::Column::Rank)
+        .order_by_asc(project_milestones::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 项目内下一个排序值。
+pub async fn next_rank<C>(conn: &C, project_id: &str) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    let max_rank: Option<Option<i64>> = project_milestones::Entity::find()
+        .select_only()
+        .column_as(project_milestones::Column::Rank.max(), "max_rank")
+        .filter(project_milestones::Column::ProjectId.eq(project_id))
+        .filter(project_milestones::Column::DeletedAt.is_null())
+        .into_tuple()
+        .one(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(max_rank.flatten().map_or(0, |rank| rank + 1))
+}
+
+pub async fn insert<C>(
+    conn: &C,
+    record: NewMilestoneRecord,
+) -> Result<project_milestones::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_milestones::ActiveModel {
+        id: Set(record.id),
+        project_id: Set(record.project_id),
+        space_id: Set(record.space_id),
+        title: Set(record.title),
+        target_at: Set(record.target_at),
+        rank: Set(record.rank),
+        completed_at: Set(None),
+        task_count: Set(0),
+        done_task_count: Set(0),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.created_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+pub async fn update<C>(
+    conn: &C,
+    active_model: project_milestones::ActiveModel,
+) -> Result<project_milestones::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
+
+/// 挂在这些里程碑下的任务 id（含已删除任务）。
+pub async fn task_ids_in_milestones<C>(
+    conn: &C,
+    milestone_ids: &[String],
+) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    if milestone_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Id)
+        .filter(tasks::Column::MilestoneId.is_in(milestone_ids.iter().cloned()))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 把任务从里程碑上摘下来，刷新 `updated_at` 让变化随同步传播。
+pub async fn detach_tasks<C>(conn: &C, task_ids: &[String], now: i64) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+    tasks::Entity::update_many()
+        .col_expr(tasks::Column::MilestoneId, Expr::value(None::<String>))
+        .col_expr(tasks::Column::UpdatedAt, Expr::value(now))
+        .filter(tasks::Column::Id.is_in(task_ids.iter().cloned()))
+        .exec(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(())
+}
+
+/// 彻底删除项目下的全部里程碑（含已删除的），返回删除条数。
+pub async fn hard_delete_for_projects<C>(conn: &C, project_ids: &[String]) -> Result<u64, AppError>
+where
+    C: ConnectionTrait,
+{
+    if project_ids.is_empty() {
+        return Ok(0);
+    }
+    let result = project_milestones::Entity::delete_many()
+        .filter(project_milestones::Column::ProjectId.is_in(project_ids.iter().cloned()))
+        .exec(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(result.rows_affected)
+}
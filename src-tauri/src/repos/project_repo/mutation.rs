@@ -15,7 +15,8 @@ pub struct NewProjectRecord {
     pub title: String,
     pub note: Option<String>,
     pub priority: Priority,
-    pub rank: i64,
+    pub rank: String,
+    pub lifecycle_status: String,
     pub created_at: i64,
     pub updated_at: i64,
     pub create_by: String,
@@ -35,6 +36,9 @@ where
         note: Set(record.note),
         priority: Set(record.priority),
         todo_task_count: Set(0),
+        doing_task_count: Set(0),
+        waiting_task_count: Set(0),
+        someday_task_count: Set(0),
         done_task_count: Set(0),
         last_task_updated_at: Set(None),
         created_at: Set(record.created_at),
@@ -43,6 +47,13 @@ where
         deleted_at: Set(None),
         create_by: Set(record.create_by),
         rank: Set(record.rank),
+        lifecycle_status: Set(record.lifecycle_status),
+        start_at: Set(None),
+        due_at: Set(None),
+        subtree_task_count: Set(0),
+        subtree_done_task_count: Set(0),
+        subtree_last_activity_at: Set(None),
+        subtree_tracked_ms: Set(0),
     }
     .insert(conn)
     .await
@@ -3,6 +3,7 @@
 use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder};
 
 use crate::db::entities::projects;
+use crate::repos::rank;
 use crate::types::error::AppError;
 
 pub async fn find_by_id<C>(conn: &C, project_id: &str) -> Result<projects::Model, AppError>
@@ -51,7 +52,7 @@ pub async fn next_rank_in_scope<C>(
     conn: &C,
     space_id: &str,
     parent_id: Option<&str>,
-) -> Result<i64, AppError>
+) -> Result<String, AppError>
 where
     C: ConnectionTrait,
 {
@@ -71,7 +72,44 @@ where
         .await
         .map_err(AppError::from)?;
 
-    Ok(max_rank_project
-        .map(|project| project.rank + 1024)
-        .unwrap_or(1024))
+    rank::after(
+        max_rank_project
+            .as_ref()
+            .map(|project| project.rank.as_str()),
+    )
+}
+
+pub async fn find_by_path<C>(
+    conn: &C,
+    space_id: &str,
+    path: &str,
+) -> Result<Option<projects::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    // 快速录入按路径定位项目，大小写不敏感；同一 space 下项目数量有限，直接在内存里比较。
+    let models = projects::Entity::find()
+        .filter(projects::Column::SpaceId.eq(space_id))
+        .filter(projects::Column::DeletedAt.is_null())
+        .order_by_asc(projects::Column::Rank)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .find(|project| project.path.to_lowercase() == path.to_lowercase()))
+}
+
+pub async fn find_children<C>(conn: &C, parent_id: &str) -> Result<Vec<projects::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    // 只取直接子项目，用于父级约束类校验；孙辈由子项目自己的约束间接保证。
+    projects::Entity::find()
+        .filter(projects::Column::ParentId.eq(parent_id))
+        .filter(projects::Column::DeletedAt.is_null())
+        .all(conn)
+        .await
+        .map_err(AppError::from)
 }
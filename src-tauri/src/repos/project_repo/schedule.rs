@@ -0,0 +1,134 @@
+//! 项目排期健康度查询。
+//!
+//! 约定：
+//! - 只看设了截止时间、未归档未删除、且仍在进行中（非 completed / cancelled）的项目
+//! - 任务口径是整棵子树，与 `subtree_*` 汇总列一致
+//! - 两类风险：截止临近（含已过期）但未完成任务占比仍高；有未完成任务的截止时间晚于项目截止时间
+
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+
+use crate::db::entities::{projects, sea_orm_active_enums::TaskStatus, tasks};
+use crate::types::{dto::ProjectRiskDto, error::AppError};
+
+use super::helpers::{LIFECYCLE_CANCELLED, LIFECYCLE_COMPLETED};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const DEFAULT_DUE_WITHIN_DAYS: i64 = 7;
+const DEFAULT_MIN_OPEN_RATIO: f64 = 0.5;
+
+pub const REASON_DUE_SOON: &str = "dueSoon";
+pub const REASON_LATE_TASKS: &str = "lateTasks";
+
+#[derive(Debug, Clone)]
+pub struct AtRiskQuery {
+    pub space_id: String,
+    pub now: i64,
+    /// 截止时间落在多少天以内算“临近”，默认 7 天
+    pub due_within_days: Option<i64>,
+    /// 未完成任务占比达到多少算“还有很多没做”，默认 0.5
+    pub min_open_ratio: Option<f64>,
+}
+
+pub async fn list_at_risk<C>(conn: &C, query: AtRiskQuery) -> Result<Vec<ProjectRiskDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let due_within_days = query.due_within_days.unwrap_or(DEFAULT_DUE_WITHIN_DAYS);
+    if due_within_days < 0 {
+        return Err(AppError::Validation("临近天数不能为负数".to_string()));
+    }
+    let min_open_ratio = query.min_open_ratio.unwrap_or(DEFAULT_MIN_OPEN_RATIO);
+    if !(0.0..=1.0).contains(&min_open_ratio) {
+        return Err(AppError::Validation(
+            "未完成占比需在 0 到 1 之间".to_string(),
+        ));
+    }
+    let due_soon_before = query.now + due_within_days * DAY_MS;
+
+    let space_projects = projects::Entity::find()
+        .filter(projects::Column::SpaceId.eq(query.space_id.as_str()))
+        .filter(projects::Column::DeletedAt.is_null())
+        .order_by_asc(projects::Column::Path)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let candidates: Vec<&projects::Model> = space_projects
+        .iter()
+        .filter(|project| {
+            project.due_at.is_some()
+                && project.archived_at.is_none()
+                && project.lifecycle_status != LIFECYCLE_COMPLETED
+                && project.lifecycle_status != LIFECYCLE_CANCELLED
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // 只需要带截止时间的未完成任务，逐个项目在内存里按子树归属比较。
+    let open_deadlines: Vec<(Option<String>, Option<i64>)> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::ProjectId)
+        .column(tasks::Column::DeadlineAt)
+        .filter(tasks::Column::SpaceId.eq(query.space_id.as_str()))
+        .filter(tasks::Column::Status.ne(TaskStatus::Done))
+        .filter(tasks::Column::DeadlineAt.is_not_null())
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut items = Vec::new();
+    for project in candidates {
+        let Some(due_at) = project.due_at else {
+            continue;
+        };
+        let descendant_prefix = format!("{}/", project.path);
+        let subtree_ids: Vec<&str> = space_projects
+            .iter()
+            .filter(|item| item.id == project.id || item.path.starts_with(&descendant_prefix))
+            .map(|item| item.id.as_str())
+            .collect();
+        let late_task_count = open_deadlines
+            .iter()
+            .filter(|(project_id, deadline_at)| {
+                project_id
+                    .as_deref()
+                    .is_some_and(|project_id| subtree_ids.contains(&project_id))
+                    && deadline_at.is_some_and(|deadline_at| deadline_at > due_at)
+            })
+            .count() as i64;
+
+        let task_count = project.subtree_task_count;
+        let open_task_count = task_count - project.subtree_done_task_count;
+        let mut reasons = Vec::new();
+        if due_at <= due_soon_before
+            && open_task_count > 0
+            && open_task_count as f64 >= task_count as f64 * min_open_ratio
+        {
+            reasons.push(REASON_DUE_SOON.to_string());
+        }
+        if late_task_count > 0 {
+            reasons.push(REASON_LATE_TASKS.to_string());
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+        items.push(ProjectRiskDto {
+            project_id: project.id.clone(),
+            title: project.title.clone(),
+            path: project.path.clone(),
+            lifecycle_status: project.lifecycle_status.clone(),
+            due_at,
+            task_count,
+            open_task_count,
+            late_task_count,
+            reasons,
+        });
+    }
+
+    items.sort_by(|a, b| (a.due_at, &a.path).cmp(&(b.due_at, &b.path)));
+    Ok(items)
+}
@@ -0,0 +1,99 @@
+//! 项目模板持久化原语。
+//!
+//! 重点：
+//! - 整棵模板树以 JSON 存在 `body` 列，读写都经过 `ProjectTemplateNodeDto`
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+use crate::db::entities::project_templates;
+use crate::types::{
+    dto::{ProjectTemplateDto, ProjectTemplateNodeDto},
+    error::AppError,
+};
+
+pub struct NewTemplateRecord {
+    pub id: String,
+    pub name: String,
+    pub body: String,
+    pub created_at: i64,
+}
+
+/// 把模板树序列化成数据库中的 JSON 字符串。
+pub fn serialize_body(root: &ProjectTemplateNodeDto) -> Result<String, AppError> {
+    serde_json::to_string(root)
+        .map_err(|e| AppError::Validation(format!("模板内容序列化失败: {e}")))
+}
+
+/// 把模板转换成前端 DTO。
+pub fn to_dto(model: project_templates::Model) -> ProjectTemplateDto {
+    // 反序列化失败时回退为空模板，避免单条脏数据阻断整个列表。
+    let root = serde_json::from_str::<ProjectTemplateNodeDto>(&model.body).unwrap_or_default();
+    ProjectTemplateDto {
+        id: model.id,
+        name: model.name,
+        root,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 按 id 读取未删除的模板。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<project_templates::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_templates::Entity::find_by_id(id)
+        .filter(project_templates::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("项目模板不存在".to_string()))
+}
+
+/// 按名称列出未删除的模板。
+pub async fn list<C>(conn: &C) -> Result<Vec<project_templates::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_templates::Entity::find()
+        .filter(project_templates::Column::DeletedAt.is_null())
+        .order_by_asc(project_templates::Column::Name)
+        .order_by_asc(project_templates::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 插入一条新模板。
+pub async fn insert<C>(
+    conn: &C,
+    record: NewTemplateRecord,
+) -> Result<project_templates::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_templates::ActiveModel {
+        id: Set(record.id),
+        name: Set(record.name),
+        body: Set(record.body),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.created_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+/// 更新模板。
+pub async fn update<C>(
+    conn: &C,
+    active_model: project_templates::ActiveModel,
+) -> Result<project_templates::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
@@ -0,0 +1,428 @@
+//! 项目时间线查询。
+//!
+//! 约定：
+//! - 范围是目标项目及其整棵子树（见 `ProjectRepo::collect_subtree_ids`）
+//! - 任务完成、任务 / 项目活动日志、笔记、代码片段、日记合并成一条按时间倒序的流
+//! - 任务完成取 `completed_at`，已删除任务不出现；笔记、片段、日记取创建时间
+//! - 按（时间, 类型, id）倒序做 keyset 游标分页：各来源各取一页，再在内存里归并
+
+use std::cmp::Ordering;
+
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::db::entities::{
+    asset_diary_entries, asset_notes, asset_snippets, project_activity_logs, task_activity_logs,
+    tasks,
+};
+use crate::types::{
+    dto::{ProjectTimelineEntryDto, ProjectTimelinePageDto},
+    error::AppError,
+};
+
+use super::ProjectRepo;
+
+const DEFAULT_PAGE_SIZE: u64 = 50;
+const MAX_PAGE_SIZE: u64 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineEntryType {
+    TaskCompleted,
+    TaskLog,
+    ProjectLog,
+    Note,
+    Snippet,
+    Diary,
+}
+
+impl TimelineEntryType {
+    pub const ALL: [Self; 6] = [
+        Self::TaskCompleted,
+        Self::TaskLog,
+        Self::ProjectLog,
+        Self::Note,
+        Self::Snippet,
+        Self::Diary,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TaskCompleted => "taskCompleted",
+            Self::TaskLog => "taskLog",
+            Self::ProjectLog => "projectLog",
+            Self::Note => "note",
+            Self::Snippet => "snippet",
+            Self::Diary => "diary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        Self::ALL
+            .into_iter()
+            .find(|item| item.as_str() == value.trim())
+            .ok_or_else(|| AppError::Validation(format!("不支持的时间线类型：{value}")))
+    }
+}
+
+/// 时间线查询条件。
+#[derive(Debug, Clone)]
+pub struct TimelineQuery {
+    pub project_id: String,
+    /// 为空表示不限类型
+    pub entry_types: Vec<TimelineEntryType>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+/// 游标内容：上一页最后一条的时间、类型与 id。
+#[derive(Debug, Serialize, Deserialize)]
+struct TimelineCursor {
+    at: i64,
+    kind: String,
+    id: String,
+}
+
+/// 单个来源的查询窗口。
+struct Window<'a> {
+    project_ids: &'a [String],
+    from: Option<i64>,
+    to: Option<i64>,
+    cursor: Option<&'a TimelineCursor>,
+    limit: u64,
+}
+
+impl Window<'_> {
+    /// 时间范围 + 游标之后的条件；同一来源内类型相同，只需比较时间与 id。
+    fn condition<A, I>(&self, kind: TimelineEntryType, at: A, id: I) -> Condition
+    where
+        A: ColumnTrait,
+        I: ColumnTrait,
+    {
+        let mut condition = Condition::all();
+        if let Some(from) = self.from {
+            condition = condition.add(at.gte(from));
+        }
+        if let Some(to) = self.to {
+            condition = condition.add(at.lte(to));
+        }
+        if let Some(cursor) = self.cursor {
+            condition = condition.add(match kind.as_str().cmp(cursor.kind.as_str()) {
+                Ordering::Less => Condition::all().add(at.lte(cursor.at)),
+                Ordering::Equal => Condition::any().add(at.lt(cursor.at)).add(
+                    Condition::all()
+                        .add(at.eq(cursor.at))
+                        .add(id.lt(cursor.id.clone())),
+                ),
+                Ordering::Greater => Condition::all().add(at.lt(cursor.at)),
+            });
+        }
+        condition
+    }
+}
+
+/// 分页读取项目子树的时间线。
+pub async fn list<C>(conn: &C, query: TimelineQuery) -> Result<ProjectTimelinePageDto, AppError>
+where
+    C: ConnectionTrait,
+{
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Err(AppError::Validation(
+                "时间线的开始时间不能晚于结束时间".to_string(),
+            ));
+        }
+    }
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|raw| {
+            serde_json::from_str::<TimelineCursor>(raw)
+                .map_err(|_| AppError::Validation("cursor 无效".to_string()))
+        })
+        .transpose()?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let entry_types = if query.entry_types.is_empty() {
+        TimelineEntryType::ALL.to_vec()
+    } else {
+        query.entry_types
+    };
+
+    let project_ids = ProjectRepo::collect_subtree_ids(conn, &query.project_id).await?;
+    // 每个来源多取一条，归并后用来判断是否还有下一页。
+    let window = Window {
+        project_ids: &project_ids,
+        from: query.from,
+        to: query.to,
+        cursor: cursor.as_ref(),
+        limit: limit + 1,
+    };
+
+    let mut items = Vec::new();
+    for entry_type in TimelineEntryType::ALL {
+        if !entry_types.contains(&entry_type) {
+            continue;
+        }
+        items.extend(match entry_type {
+            TimelineEntryType::TaskCompleted => task_completions(conn, &window).await?,
+            TimelineEntryType::TaskLog => task_logs(conn, &window).await?,
+            TimelineEntryType::ProjectLog => project_logs(conn, &window).await?,
+            TimelineEntryType::Note => notes(conn, &window).await?,
+            TimelineEntryType::Snippet => snippets(conn, &window).await?,
+            TimelineEntryType::Diary => diary_entries(conn, &window).await?,
+        });
+    }
+    items.sort_by(|a, b| {
+        (b.occurred_at, &b.entry_type, &b.entry_id).cmp(&(
+            a.occurred_at,
+            &a.entry_type,
+            &a.entry_id,
+        ))
+    });
+
+    let next_cursor = if items.len() as u64 > limit {
+        items.truncate(limit as usize);
+        items
+            .last()
+            .map(|entry| {
+                serde_json::to_string(&TimelineCursor {
+                    at: entry.occurred_at,
+                    kind: entry.entry_type.clone(),
+                    id: entry.entry_id.clone(),
+                })
+                .map_err(|e| AppError::Internal(format!("游标序列化失败: {e}")))
+            })
+            .transpose()?
+    } else {
+        None
+    };
+
+    Ok(ProjectTimelinePageDto { items, next_cursor })
+}
+
+async fn task_completions<C>(
+    conn: &C,
+    window: &Window<'_>,
+) -> Result<Vec<ProjectTimelineEntryDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let models = tasks::Entity::find()
+        .filter(tasks::Column::ProjectId.is_in(window.project_ids.iter().cloned()))
+        .filter(tasks::Column::CompletedAt.is_not_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .filter(window.condition(
+            TimelineEntryType::TaskCompleted,
+            tasks::Column::CompletedAt,
+            tasks::Column::Id,
+        ))
+        .order_by_desc(tasks::Column::CompletedAt)
+        .order_by_desc(tasks::Column::Id)
+        .limit(window.limit)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            Some(ProjectTimelineEntryDto {
+                entry_type: TimelineEntryType::TaskCompleted.as_str().to_string(),
+                occurred_at: model.completed_at?,
+                project_id: model.project_id?,
+                task_id: Some(model.id.clone()),
+                entry_id: model.id,
+                title: model.title,
+                detail: None,
+            })
+        })
+        .collect())
+}
+
+async fn task_logs<C>(
+    conn: &C,
+    window: &Window<'_>,
+) -> Result<Vec<ProjectTimelineEntryDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let models = task_activity_logs::Entity::find()
+        .filter(task_activity_logs::Column::ProjectId.is_in(window.project_ids.iter().cloned()))
+        .filter(window.condition(
+            TimelineEntryType::TaskLog,
+            task_activity_logs::Column::CreatedAt,
+            task_activity_logs::Column::Id,
+        ))
+        .order_by_desc(task_activity_logs::Column::CreatedAt)
+        .order_by_desc(task_activity_logs::Column::Id)
+        .limit(window.limit)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            Some(ProjectTimelineEntryDto {
+                entry_type: TimelineEntryType::TaskLog.as_str().to_string(),
+                entry_id: model.id,
+                occurred_at: model.created_at,
+                project_id: model.project_id?,
+                task_id: Some(model.task_id),
+                title: model.action_label,
+                detail: Some(model.detail),
+            })
+        })
+        .collect())
+}
+
+async fn project_logs<C>(
+    conn: &C,
+    window: &Window<'_>,
+) -> Result<Vec<ProjectTimelineEntryDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let models = project_activity_logs::Entity::find()
+        .filter(project_activity_logs::Column::ProjectId.is_in(window.project_ids.iter().cloned()))
+        .filter(window.condition(
+            TimelineEntryType::ProjectLog,
+            project_activity_logs::Column::CreatedAt,
+            project_activity_logs::Column::Id,
+        ))
+        .order_by_desc(project_activity_logs::Column::CreatedAt)
+        .order_by_desc(project_activity_logs::Column::Id)
+        .limit(window.limit)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .map(|model| ProjectTimelineEntryDto {
+            entry_type: TimelineEntryType::ProjectLog.as_str().to_string(),
+            entry_id: model.id,
+            occurred_at: model.created_at,
+            project_id: model.project_id,
+            task_id: None,
+            title: model.action_label,
+            detail: Some(model.detail),
+        })
+        .collect())
+}
+
+async fn notes<C>(conn: &C, window: &Window<'_>) -> Result<Vec<ProjectTimelineEntryDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let models = asset_notes::Entity::find()
+        .filter(asset_notes::Column::LinkedProjectId.is_in(window.project_ids.iter().cloned()))
+        .filter(window.condition(
+            TimelineEntryType::Note,
+            asset_notes::Column::CreatedAt,
+            asset_notes::Column::Id,
+        ))
+        .order_by_desc(asset_notes::Column::CreatedAt)
+        .order_by_desc(asset_notes::Column::Id)
+        .limit(window.limit)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            Some(ProjectTimelineEntryDto {
+                entry_type: TimelineEntryType::Note.as_str().to_string(),
+                entry_id: model.id,
+                occurred_at: model.created_at,
+                project_id: model.linked_project_id?,
+                task_id: model.linked_task_id,
+                title: model.title,
+                detail: model.excerpt,
+            })
+        })
+        .collect())
+}
+
+async fn snippets<C>(
+    conn: &C,
+    window: &Window<'_>,
+) -> Result<Vec<ProjectTimelineEntryDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let models = asset_snippets::Entity::find()
+        .filter(asset_snippets::Column::LinkedProjectId.is_in(window.project_ids.iter().cloned()))
+        .filter(window.condition(
+            TimelineEntryType::Snippet,
+            asset_snippets::Column::CreatedAt,
+            asset_snippets::Column::Id,
+        ))
+        .order_by_desc(asset_snippets::Column::CreatedAt)
+        .order_by_desc(asset_snippets::Column::Id)
+        .limit(window.limit)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            Some(ProjectTimelineEntryDto {
+                entry_type: TimelineEntryType::Snippet.as_str().to_string(),
+                entry_id: model.id,
+                occurred_at: model.created_at,
+                project_id: model.linked_project_id?,
+                task_id: model.linked_task_id,
+                title: model.title,
+                detail: model.description,
+            })
+        })
+        .collect())
+}
+
+async fn diary_entries<C>(
+    conn: &C,
+    window: &Window<'_>,
+) -> Result<Vec<ProjectTimelineEntryDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let models = asset_diary_entries::Entity::find()
+        .filter(
+            asset_diary_entries::Column::LinkedProjectId.is_in(window.project_ids.iter().cloned()),
+        )
+        .filter(window.condition(
+            TimelineEntryType::Diary,
+            asset_diary_entries::Column::CreatedAt,
+            asset_diary_entries::Column::Id,
+        ))
+        .order_by_desc(asset_diary_entries::Column::CreatedAt)
+        .order_by_desc(asset_diary_entries::Column::Id)
+        .limit(window.limit)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            Some(ProjectTimelineEntryDto {
+                entry_type: TimelineEntryType::Diary.as_str().to_string(),
+                entry_id: model.id,
+                occurred_at: model.created_at,
+                project_id: model.linked_project_id?,
+                task_id: None,
+                title: model.title,
+                detail: model.subtitle,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,397 @@
+//! 分数排序键（fractional index）。
+//!
+//! 重点：
+//! - 排序键由“变长整数部分 + 小数部分”组成，字符集 `0-9a-z`，按字节序比较
+//! - 整数部分的首字符决定位数：`a`..`z` 表示 1..26 位的非负整数，`9`..`0` 表示 1..10 位的负整数
+//! - 追加到末尾只给整数部分加一，键长随元素数量对数增长，不会越追加越长
+//! - 小数部分不能以 `0` 结尾，因此任意两个不同的键之间总能再插入一个新键
+//! - 单次拖拽只改被移动的那一行，不需要重排邻居
+//! - 只用数字和小写字母，避免不同数据库排序规则下大小写比较不一致
+
+use crate::types::error::AppError;
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE: usize = 36;
+/// 空列表里的第一个键，也是整数 0。
+pub const INITIAL: &str = "a0";
+/// 最小的整数保留不用，否则就无法在它前面再插入。
+const SMALLEST_INTEGER: &str = "00000000000";
+/// 客户端显式传入排序键时的长度上限，防止异常输入撑大索引。
+pub const MAX_RANK_LEN: usize = 128;
+
+fn digit(byte: u8) -> Option<usize> {
+    DIGITS.iter().position(|d| *d == byte)
+}
+
+fn invalid(key: &str) -> AppError {
+    AppError::Validation(format!("排序键格式不正确：{key}"))
+}
+
+/// 整数部分的总长度（含首字符）；首字符不合法时返回 None。
+fn integer_len(head: u8) -> Option<usize> {
+    match head {
+        b'a'..=b'z' => Some((head - b'a') as usize + 2),
+        b'0'..=b'9' => Some((b'9' - head) as usize + 2),
+        _ => None,
+    }
+}
+
+/// 拆成（整数部分, 小数部分），并校验键的结构。
+fn split(key: &str) -> Result<(&str, &str), AppError> {
+    let head = *key.as_bytes().first().ok_or_else(|| invalid(key))?;
+    let len = integer_len(head).ok_or_else(|| invalid(key))?;
+    if key.len() < len || key.bytes().any(|byte| digit(byte).is_none()) || key == SMALLEST_INTEGER {
+        return Err(invalid(key));
+    }
+    let (integer, fraction) = key.split_at(len);
+    if fraction.ends_with('0') {
+        return Err(invalid(key));
+    }
+    Ok((integer, fraction))
+}
+
+/// 校验客户端传入的排序键。
+pub fn validate(key: &str) -> Result<String, AppError> {
+    let key = key.trim();
+    if key.is_empty() || key.len() > MAX_RANK_LEN {
+        return Err(AppError::Validation(format!(
+            "排序键长度必须在 1 到 {MAX_RANK_LEN} 之间"
+        )));
+    }
+    split(key)?;
+    Ok(key.to_string())
+}
+
+/// 生成严格位于 `before` 与 `after` 之间的排序键；两端为空表示开区间。
+///
+/// `before >= after` 或键格式不正确时返回校验错误，由调用方决定是否整体重排。
+pub fn between(before: Option<&str>, after: Option<&str>) -> Result<String, AppError> {
+    match (before, after) {
+        (None, None) => Ok(INITIAL.to_string()),
+        (None, Some(after)) => {
+            let (integer, fraction) = split(after)?;
+            if integer == SMALLEST_INTEGER {
+                return Ok(join(integer, &midpoint(b"", Some(fraction.as_bytes()))));
+            }
+            if !fraction.is_empty() {
+                return Ok(integer.to_string());
+            }
+            match decrement_integer(integer) {
+                // 减到最小整数时不能直接用它，再往后接一段小数。
+                Some(previous) if previous == SMALLEST_INTEGER => {
+                    Ok(join(&previous, &midpoint(b"", None)))
+                }
+                Some(previous) => Ok(previous),
+                None => Err(no_room(None, Some(after))),
+            }
+        }
+        (Some(before), None) => {
+            let (integer, fraction) = split(before)?;
+            Ok(increment_integer(integer)
+                .unwrap_or_else(|| join(integer, &midpoint(fraction.as_bytes(), None))))
+        }
+        (Some(before), Some(after)) => {
+            let (lower_integer, lower_fraction) = split(before)?;
+            let (upper_integer, upper_fraction) = split(after)?;
+            if before >= after {
+                return Err(no_room(Some(before), Some(after)));
+            }
+            if lower_integer == upper_integer {
+                return Ok(join(
+                    lower_integer,
+                    &midpoint(lower_fraction.as_bytes(), Some(upper_fraction.as_bytes())),
+                ));
+            }
+            match increment_integer(lower_integer) {
+                Some(next) if next.as_str() < after => Ok(next),
+                _ => Ok(join(
+                    lower_integer,
+                    &midpoint(lower_fraction.as_bytes(), None),
+                )),
+            }
+        }
+    }
+}
+
+/// 追加到末尾：位于 `last` 之后的排序键。
+pub fn after(last: Option<&str>) -> Result<String, AppError> {
+    between(last, None)
+}
+
+/// 为 `count` 个元素生成依次递增的排序键，用于整体重排。
+pub fn spread(count: usize) -> Vec<String> {
+    std::iter::successors(Some(INITIAL.to_string()), |key| increment_integer(key))
+        .take(count)
+        .collect()
+}
+
+fn no_room(before: Option<&str>, after: Option<&str>) -> AppError {
+    AppError::Validation(format!(
+        "排序键 {} 与 {} 之间没有空位，需要先重排",
+        before.unwrap_or("-"),
+        after.unwrap_or("-")
+    ))
+}
+
+fn join(integer: &str, fraction: &[u8]) -> String {
+    let mut key = integer.to_string();
+    key.extend(fraction.iter().copied().map(char::from));
+    key
+}
+
+fn with_head(head: u8, digits: &[u8]) -> String {
+    std::iter::once(head)
+        .chain(digits.iter().copied())
+        .map(char::from)
+        .collect()
+}
+
+/// 求两个小数部分 `(lower, upper)` 之间的中点；`upper` 为空表示 1。
+fn midpoint(lower: &[u8], upper: Option<&[u8]>) -> Vec<u8> {
+    let value =
+        |byte: Option<&u8>, fallback: usize| byte.and_then(|b| digit(*b)).unwrap_or(fallback);
+
+    if let Some(upper) = upper {
+        // 公共前缀原样保留（下界不足的位按 0 补齐），只在第一个不同的位上找空位。
+        let shared = upper
+            .iter()
+            .enumerate()
+            .take_while(|(index, byte)| lower.get(*index).copied().unwrap_or(b'0') == **byte)
+            .count();
+        if shared > 0 {
+            let mut result = upper[..shared].to_vec();
+            result.extend(midpoint(
+                lower.get(shared..).unwrap_or_default(),
+                Some(&upper[shared..]),
+            ));
+            return result;
+        }
+    }
+
+    let low = value(lower.first(), 0);
+    let high = upper.map_or(BASE, |upper| value(upper.first(), BASE));
+    if high > low + 1 {
+        return vec![DIGITS[(low + high).div_ceil(2)]];
+    }
+    match upper {
+        // 上界多于一位时，取它的首位就已经严格小于上界、大于下界。
+        Some(upper) if upper.len() > 1 => vec![upper[0]],
+        _ => {
+            let mut result = vec![DIGITS[low]];
+            result.extend(midpoint(lower.get(1..).unwrap_or_default(), None));
+            result
+        }
+    }
+}
+
+/// 整数部分加一；已经是最大整数时返回 None。
+fn increment_integer(integer: &str) -> Option<String> {
+    let (head, digits) = integer.as_bytes().split_first()?;
+    let mut digits = digits.to_vec();
+    for slot in digits.iter_mut().rev() {
+        let next = digit(*slot)? + 1;
+        if next < BASE {
+            *slot = DIGITS[next];
+            return Some(with_head(*head, &digits));
+        }
+        *slot = b'0';
+    }
+    // 进位溢出：换成多一位（正数）或少一位（负数）的首字符。
+    match *head {
+        b'9' => Some(INITIAL.to_string()),
+        b'z' => None,
+        _ => {
+            let head = DIGITS[digit(*head)? + 1];
+            if head > b'a' {
+                digits.push(b'0');
+            } else {
+                digits.pop();
+            }
+            Some(with_head(head, &digits))
+        }
+    }
+}
+
+/// 整数部分减一；已经是最小整数时返回 None。
+fn decrement_integer(integer: &str) -> Option<String> {
+    let (head, digits) = integer.as_bytes().split_first()?;
+    let mut digits = digits.to_vec();
+    for slot in digits.iter_mut().rev() {
+        let current = digit(*slot)?;
+        if current > 0 {
+            *slot = DIGITS[current - 1];
+            return Some(with_head(*head, &digits));
+        }
+        *slot = DIGITS[BASE - 1];
+    }
+    match *head {
+        b'a' => Some(format!("9{}", DIGITS[BASE - 1] as char)),
+        b'0' => None,
+        _ => {
+            let head = DIGITS[digit(*head)? - 1];
+            if head < b'9' {
+                digits.push(DIGITS[BASE - 1]);
+            } else {
+                digits.pop();
+            }
+            Some(with_head(head, &digits))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_between(before: Option<&str>, after: Option<&str>) -> String {
+        let key = between(before, after).unwrap();
+        validate(&key).unwrap();
+        if let Some(before) = before {
+            assert!(before < key.as_str(), "{before} < {key}");
+        }
+        if let Some(after) = after {
+            assert!(key.as_str() < after, "{key} < {after}");
+        }
+        key
+    }
+
+    #[test]
+    fn between_stays_strictly_inside_bounds() {
+        assert_eq!(between(None, None).unwrap(), INITIAL);
+        assert_between(None, Some(INITIAL));
+        assert_between(Some(INITIAL), None);
+
+        let pairs = [
+            ("a0", "a1"),
+            ("a0", "a01"),
+            ("a0", "a0001"),
+            ("a0i", "a0j"),
+            ("a0zz", "a1"),
+            ("az", "b00"),
+            ("9z", "a0"),
+            ("9zzz", "a0"),
+            ("a0", "b00"),
+            ("8zz", "a0001"),
+            ("00000000001", "00000000002"),
+        ];
+        for (before, after) in pairs {
+            assert_between(Some(before), Some(after));
+            assert_between(None, Some(after));
+            assert_between(Some(before), None);
+        }
+    }
+
+    #[test]
+    fn repeated_insertion_at_same_spot() {
+        // 不断插在同一个下界之后
+        let mut upper = "a1".to_string();
+        for _ in 0..200 {
+            upper = assert_between(Some("a0"), Some(&upper));
+        }
+        // 不断插在同一个上界之前
+        let mut lower = "a0".to_string();
+        for _ in 0..200 {
+            lower = assert_between(Some(&lower), Some("a1"));
+        }
+        // 不断插到最前面，跨过负整数一直到最小整数之后
+        let mut first = INITIAL.to_string();
+        for _ in 0..2000 {
+            first = assert_between(None, Some(&first));
+        }
+        // 不断追加到末尾，键长只随数量对数增长
+        let mut last = INITIAL.to_string();
+        for _ in 0..5000 {
+            last = after(Some(&last)).unwrap();
+        }
+        assert!(last.len() <= 4, "{last}");
+    }
+
+    #[test]
+    fn random_insertions_keep_order() {
+        let mut keys: Vec<String> = Vec::new();
+        let mut seed: u64 = 0x5eed;
+        for _ in 0..3000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let index = (seed >> 33) as usize % (keys.len() + 1);
+            let before = index.checked_sub(1).map(|i| keys[i].as_str());
+            let after = keys.get(index).map(String::as_str);
+            let key = assert_between(before, after);
+            keys.insert(index, key);
+        }
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn carry_and_borrow_across_integer_length() {
+        let steps = [
+            ("9z", "a0"),
+            ("az", "b00"),
+            ("azz", "b000"),
+            ("bzz", "c000"),
+            ("8zz", "90"),
+            ("7zzz", "800"),
+            ("a8", "a9"),
+            ("a9", "aa"),
+        ];
+        for (lower, upper) in steps {
+            assert_eq!(
+                increment_integer(lower).as_deref(),
+                Some(upper),
+                "{lower}+1"
+            );
+            assert_eq!(
+                decrement_integer(upper).as_deref(),
+                Some(lower),
+                "{upper}-1"
+            );
+        }
+
+        let largest = "z".repeat(27);
+        assert_eq!(integer_len(b'z'), Some(largest.len()));
+        assert_eq!(increment_integer(&largest), None);
+        assert_eq!(decrement_integer(SMALLEST_INTEGER), None);
+        // 最大整数之后只能接小数部分
+        assert_between(Some(&largest), None);
+        // 最小整数保留不用，减到它时接一段小数
+        let key = assert_between(None, Some("00000000001"));
+        assert!(key.starts_with(SMALLEST_INTEGER));
+    }
+
+    #[test]
+    fn spread_is_monotonic() {
+        let keys = spread(5000);
+        assert_eq!(keys.len(), 5000);
+        assert_eq!(keys[0], INITIAL);
+        for key in &keys {
+            validate(key).unwrap();
+        }
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(spread(0).is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        for key in [
+            "",
+            "a",
+            "A0",
+            "a0-",
+            "b0",
+            "a00",
+            "a010",
+            SMALLEST_INTEGER,
+            "!",
+        ] {
+            assert!(validate(key).is_err(), "{key:?}");
+        }
+        assert!(validate(&format!("a0{}", "1".repeat(MAX_RANK_LEN))).is_err());
+        assert_eq!(validate(" a0i ").unwrap(), "a0i");
+
+        assert!(between(Some("a1"), Some("a0")).is_err());
+        assert!(between(Some("a0"), Some("a0")).is_err());
+        assert!(between(Some(""), None).is_err());
+        assert!(between(None, Some("")).is_err());
+    }
+}
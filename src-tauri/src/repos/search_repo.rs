@@ -0,0 +1,770 @@
+//! 全文搜索仓储（SQLite FTS5）。
+//!
+//! 重点：
+//! - `search_index` 是派生数据，由各写用例在同一事务里按 id 重建对应条目
+//! - 重建语义是“先删后插”：记录已被物理删除时自然就从索引里消失
+//! - 密钥（vault）不进入索引，避免明文出现在搜索结果里
+//! - trigram 分词要求关键词至少 3 个字符，更短的词退化为 LIKE 过滤
+
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, Statement, Value,
+};
+
+use crate::db::entities::{
+    asset_diary_entries, asset_notes, asset_snippets, projects, task_checklist_items, tasks,
+};
+use crate::repos::tag_repo::{self, TagEntity};
+use crate::types::{dto::SearchHitDto, error::AppError};
+
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 200;
+const MAX_TERMS: usize = 8;
+const REBUILD_BATCH_SIZE: usize = 500;
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+const SNIPPET_MAX_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchEntityType {
+    Task,
+    Project,
+    Note,
+    Snippet,
+    Diary,
+}
+
+impl SearchEntityType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::Project => "project",
+            Self::Note => "note",
+            Self::Snippet => "snippet",
+            Self::Diary => "diary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim() {
+            "task" => Ok(Self::Task),
+            "project" => Ok(Self::Project),
+            "note" => Ok(Self::Note),
+            "snippet" => Ok(Self::Snippet),
+            "diary" => Ok(Self::Diary),
+            other => Err(AppError::Validation(format!("不支持的搜索类型：{other}"))),
+        }
+    }
+}
+
+/// 搜索条件。
+pub struct SearchQuery<'a> {
+    pub text: &'a str,
+    pub space_id: Option<&'a str>,
+    pub project_id: Option<&'a str>,
+    pub tag: Option<&'a str>,
+    /// 为空表示不限类型
+    pub entity_types: Vec<SearchEntityType>,
+    pub include_deleted: bool,
+    pub include_archived: bool,
+    pub limit: Option<u64>,
+}
+
+struct IndexRow {
+    entity_id: String,
+    space_id: Option<String>,
+    project_id: Option<String>,
+    title: String,
+    body: String,
+    tags: Vec<String>,
+    deleted: bool,
+    archived: bool,
+    updated_at: i64,
+}
+
+#[derive(FromQueryResult)]
+struct SearchRow {
+    entity_type: String,
+    entity_id: String,
+    space_id: Option<String>,
+    project_id: Option<String>,
+    title: String,
+    body: String,
+    tags: String,
+    is_deleted: i64,
+    is_archived: i64,
+    updated_at: i64,
+    score: f64,
+}
+
+#[derive(FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// 执行全文搜索，按相关度排序返回命中项。
+pub async fn search<C>(conn: &C, query: SearchQuery<'_>) -> Result<Vec<SearchHitDto>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let terms = split_terms(query.text);
+    if terms.is_empty() {
+        return Err(AppError::Validation("搜索关键词不能为空".to_string()));
+    }
+
+    let mut conditions = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    // 足够长的词交给 FTS5 MATCH（可参与 bm25 排序），短词只能做子串过滤。
+    let match_terms = terms
+        .iter()
+        .filter(|term| term.chars().count() >= 3)
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    let has_match = !match_terms.is_empty();
+    if has_match {
+        conditions.push("search_index MATCH ?".to_string());
+        values.push(format!("{{title body tags}} : ({})", match_terms.join(" AND ")).into());
+    }
+    for term in terms.iter().filter(|term| term.chars().count() < 3) {
+        conditions.push(
+            "(title LIKE ? ESCAPE '\\' OR body LIKE ? ESCAPE '\\' OR tags LIKE ? ESCAPE '\\')"
+                .to_string(),
+        );
+        let pattern = format!("%{}%", escape_like(term));
+        for _ in 0..3 {
+            values.push(pattern.clone().into());
+        }
+    }
+
+    if let Some(space_id) = query.space_id {
+        conditions.push("space_id = ?".to_string());
+        values.push(space_id.into());
+    }
+    if let Some(project_id) = query.project_id {
+        conditions.push("project_id = ?".to_string());
+        values.push(project_id.into());
+    }
+    if let Some(tag) = query.tag.map(str::trim).filter(|tag| !tag.is_empty()) {
+        // tags 以换行拼接存储，首尾补换行后做整词匹配。
+        conditions.push("instr(char(10) || tags || char(10), ?) > 0".to_string());
+        values.push(format!("\n{tag}\n").into());
+    }
+    if !query.entity_types.is_empty() {
+        let placeholders = vec!["?"; query.entity_types.len()].join(", ");
+        conditions.push(format!("entity_type IN ({placeholders})"));
+        for entity_type in &query.entity_types {
+            values.push(entity_type.as_str().into());
+        }
+    }
+    if !query.include_deleted {
+        conditions.push("is_deleted = 0".to_string());
+    }
+    if !query.include_archived {
+        conditions.push("is_archived = 0".to_string());
+    }
+
+    // bm25 权重按列顺序给出：标题 > 标签 > 正文，UNINDEXED 列不参与。
+    let score_expr = if has_match {
+        "-bm25(search_index, 0.0, 0.0, 0.0, 0.0, 10.0, 1.0, 5.0, 0.0, 0.0, 0.0)"
+    } else {
+        "0.0"
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    values.push((limit as i64).into());
+
+    let sql = format!(
+        "SELECT entity_type, entity_id, space_id, project_id, title, body, tags, \
+         is_deleted, is_archived, updated_at, {score_expr} AS score \
+         FROM search_index WHERE {} \
+         ORDER BY score DESC, updated_at DESC LIMIT ?",
+        conditions.join(" AND ")
+    );
+
+    let rows = SearchRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        sql,
+        values,
+    ))
+    .all(conn)
+    .await
+    .map_err(AppError::from)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SearchHitDto {
+            highlighted_title: highlight(&row.title, &terms),
+            highlighted_snippet: build_snippet(&row.body, &terms),
+            entity_type: row.entity_type,
+            entity_id: row.entity_id,
+            space_id: row.space_id,
+            project_id: row.project_id,
+            title: row.title,
+            tags: split_tags(&row.tags),
+            score: row.score,
+            deleted: row.is_deleted != 0,
+            archived: row.is_archived != 0,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+/// 重建指定任务的索引条目（标题、备注、检查清单与标签）。
+pub async fn reindex_tasks<C>(conn: &C, task_ids: &[String]) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+
+    let models = tasks::Entity::find()
+        .filter(tasks::Column::Id.is_in(task_ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let mut tags_map = tag_repo::load_tags(conn, TagEntity::Task, task_ids).await?;
+    let mut checklist_map = load_checklist_titles(conn, task_ids).await?;
+
+    let rows = models
+        .into_iter()
+        .map(|model| {
+            let mut body_parts = model.note.into_iter().collect::<Vec<_>>();
+            body_parts.extend(checklist_map.remove(&model.id).unwrap_or_default());
+            IndexRow {
+                tags: tags_map.remove(&model.id).unwrap_or_default(),
+                entity_id: model.id,
+                space_id: Some(model.space_id),
+                project_id: model.project_id,
+                title: model.title,
+                body: body_parts.join("\n"),
+                deleted: model.deleted_at.is_some(),
+                archived: model.archived_at.is_some(),
+                updated_at: model.updated_at,
+            }
+        })
+        .collect();
+
+    replace_rows(conn, SearchEntityType::Task, task_ids, rows).await
+}
+
+/// 重建挂在指定项目下的全部任务索引，供项目级联写入使用。
+pub async fn reindex_tasks_by_project_ids<C>(
+    conn: &C,
+    project_ids: &[String],
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if project_ids.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<String> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Id)
+        .filter(tasks::Column::ProjectId.is_in(project_ids.iter().cloned()))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    for chunk in task_ids.chunks(REBUILD_BATCH_SIZE) {
+        reindex_tasks(conn, chunk).await?;
+    }
+    Ok(())
+}
+
+/// 重建指定项目的索引条目。
+pub async fn reindex_projects<C>(conn: &C, project_ids: &[String]) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if project_ids.is_empty() {
+        return Ok(());
+    }
+
+    let models = projects::Entity::find()
+        .filter(projects::Column::Id.is_in(project_ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let mut tags_map = tag_repo::load_tags(conn, TagEntity::Project, project_ids).await?;
+
+    let rows = models
+        .into_iter()
+        .map(|model| IndexRow {
+            tags: tags_map.remove(&model.id).unwrap_or_default(),
+            project_id: Some(model.id.clone()),
+            entity_id: model.id,
+            space_id: Some(model.space_id),
+            title: model.title,
+            body: model.note.unwrap_or_default(),
+            deleted: model.deleted_at.is_some(),
+            archived: model.archived_at.is_some(),
+            updated_at: model.updated_at,
+        })
+        .collect();
+
+    replace_rows(conn, SearchEntityType::Project, project_ids, rows).await
+}
+
+/// 重建指定笔记的索引条目。
+pub async fn reindex_notes<C>(conn: &C, note_ids: &[String]) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if note_ids.is_empty() {
+        return Ok(());
+    }
+
+    let models = asset_notes::Entity::find()
+        .filter(asset_notes::Column::Id.is_in(note_ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let space_map = load_project_spaces(
+        conn,
+        models
+            .iter()
+            .filter_map(|model| model.linked_project_id.clone()),
+    )
+    .await?;
+
+    let rows = models
+        .into_iter()
+        .map(|model| IndexRow {
+            space_id: linked_space_id(&space_map, model.linked_project_id.as_deref()),
+            tags: parse_json_tags(&model.tags),
+            entity_id: model.id,
+            project_id: model.linked_project_id,
+            title: model.title,
+            body: model.content,
+            deleted: false,
+            archived: false,
+            updated_at: model.updated_at,
+        })
+        .collect();
+
+    replace_rows(conn, SearchEntityType::Note, note_ids, rows).await
+}
+
+/// 重建指定代码片段的索引条目。
+pub async fn reindex_snippets<C>(conn: &C, snippet_ids: &[String]) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if snippet_ids.is_empty() {
+        return Ok(());
+    }
+
+    let models = asset_snippets::Entity::find()
+        .filter(asset_snippets::Column::Id.is_in(snippet_ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let space_map = load_project_spaces(
+        conn,
+        models
+            .iter()
+            .filter_map(|model| model.linked_project_id.clone()),
+    )
+    .await?;
+
+    let rows = models
+        .into_iter()
+        .map(|model| IndexRow {
+            space_id: linked_space_id(&space_map, model.linked_project_id.as_deref()),
+            tags: parse_json_tags(&model.tags),
+            body: model
+                .description
+                .into_iter()
+                .chain(std::iter::once(model.content))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            entity_id: model.id,
+            project_id: model.linked_project_id,
+            title: model.title,
+            deleted: false,
+            archived: false,
+            updated_at: model.updated_at,
+        })
+        .collect();
+
+    replace_rows(conn, SearchEntityType::Snippet, snippet_ids, rows).await
+}
+
+/// 重建指定日记的索引条目。
+pub async fn reindex_diary_entries<C>(conn: &C, entry_ids: &[String]) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if entry_ids.is_empty() {
+        return Ok(());
+    }
+
+    let models = asset_diary_entries::Entity::find()
+        .filter(asset_diary_entries::Column::Id.is_in(entry_ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let space_map = load_project_spaces(
+        conn,
+        models
+            .iter()
+            .filter_map(|model| model.linked_project_id.clone()),
+    )
+    .await?;
+
+    let rows = models
+        .into_iter()
+        .map(|model| IndexRow {
+            space_id: linked_space_id(&space_map, model.linked_project_id.as_deref()),
+            tags: parse_json_tags(&model.tags),
+            body: model
+                .subtitle
+                .into_iter()
+                .chain(std::iter::once(model.content))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            entity_id: model.id,
+            project_id: model.linked_project_id,
+            title: model.title,
+            deleted: false,
+            archived: false,
+            updated_at: model.updated_at,
+        })
+        .collect();
+
+    replace_rows(conn, SearchEntityType::Diary, entry_ids, rows).await
+}
+
+/// 清空并整体重建搜索索引。
+///
+/// 用于首次升级回填，以及同步 pull 之后（pull 直接写表，不经过 service）。
+pub async fn rebuild_all<C>(conn: &C) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if conn.get_database_backend() != DbBackend::Sqlite {
+        return Ok(());
+    }
+
+    conn.execute(Statement::from_string(
+        DbBackend::Sqlite,
+        "DELETE FROM search_index",
+    ))
+    .await
+    .map_err(AppError::from)?;
+
+    let task_ids: Vec<String> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Id)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for chunk in task_ids.chunks(REBUILD_BATCH_SIZE) {
+        reindex_tasks(conn, chunk).await?;
+    }
+
+    let project_ids: Vec<String> = projects::Entity::find()
+        .select_only()
+        .column(projects::Column::Id)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for chunk in project_ids.chunks(REBUILD_BATCH_SIZE) {
+        reindex_projects(conn, chunk).await?;
+    }
+
+    let note_ids: Vec<String> = asset_notes::Entity::find()
+        .select_only()
+        .column(asset_notes::Column::Id)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for chunk in note_ids.chunks(REBUILD_BATCH_SIZE) {
+        reindex_notes(conn, chunk).await?;
+    }
+
+    let snippet_ids: Vec<String> = asset_snippets::Entity::find()
+        .select_only()
+        .column(asset_snippets::Column::Id)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for chunk in snippet_ids.chunks(REBUILD_BATCH_SIZE) {
+        reindex_snippets(conn, chunk).await?;
+    }
+
+    let diary_entry_ids: Vec<String> = asset_diary_entries::Entity::find()
+        .select_only()
+        .column(asset_diary_entries::Column::Id)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for chunk in diary_entry_ids.chunks(REBUILD_BATCH_SIZE) {
+        reindex_diary_entries(conn, chunk).await?;
+    }
+
+    Ok(())
+}
+
+/// 索引为空时整体重建，保证旧库升级后搜索立即可用。
+pub async fn rebuild_if_empty<C>(conn: &C) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if conn.get_database_backend() != DbBackend::Sqlite {
+        return Ok(());
+    }
+
+    let count = CountRow::find_by_statement(Statement::from_string(
+        DbBackend::Sqlite,
+        "SELECT count(*) AS total FROM search_index",
+    ))
+    .one(conn)
+    .await
+    .map_err(AppError::from)?
+    .map(|row| row.total)
+    .unwrap_or_default();
+
+    if count == 0 {
+        rebuild_all(conn).await?;
+    }
+    Ok(())
+}
+
+/// 先删除 ids 对应的旧条目，再写入仍然存在的记录。
+///
+/// FTS5 的 UNINDEXED 列不能走索引，删除会扫描全表；本地数据量下可以接受。
+async fn replace_rows<C>(
+    conn: &C,
+    entity_type: SearchEntityType,
+    entity_ids: &[String],
+    rows: Vec<IndexRow>,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    // 索引只建在本地 SQLite 上，其它后端直接跳过。
+    if conn.get_database_backend() != DbBackend::Sqlite {
+        return Ok(());
+    }
+
+    let placeholders = vec!["?"; entity_ids.len()].join(", ");
+    let mut values: Vec<Value> = vec![entity_type.as_str().into()];
+    values.extend(entity_ids.iter().map(|id| Value::from(id.as_str())));
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        format!("DELETE FROM search_index WHERE entity_type = ? AND entity_id IN ({placeholders})"),
+        values,
+    ))
+    .await
+    .map_err(AppError::from)?;
+
+    for row in rows {
+        conn.execute(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "INSERT INTO search_index (entity_type, entity_id, space_id, project_id, title, \
+             body, tags, is_deleted, is_archived, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            [
+                entity_type.as_str().into(),
+                row.entity_id.into(),
+                row.space_id.into(),
+                row.project_id.into(),
+                row.title.into(),
+                row.body.into(),
+                row.tags.join("\n").into(),
+                i64::from(row.deleted).into(),
+                i64::from(row.archived).into(),
+                row.updated_at.into(),
+            ],
+        ))
+        .await
+        .map_err(AppError::from)?;
+    }
+
+    Ok(())
+}
+
+async fn load_checklist_titles<C>(
+    conn: &C,
+    task_ids: &[String],
+) -> Result<HashMap<String, Vec<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let rows: Vec<(String, String)> = task_checklist_items::Entity::find()
+        .select_only()
+        .column(task_checklist_items::Column::TaskId)
+        .column(task_checklist_items::Column::Title)
+        .filter(task_checklist_items::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .filter(task_checklist_items::Column::DeletedAt.is_null())
+        .order_by_asc(task_checklist_items::Column::Rank)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for (task_id, title) in rows {
+        map.entry(task_id).or_default().push(title);
+    }
+    Ok(map)
+}
+
+/// 资产本身没有 space，按关联项目所在 space 归类，便于按 space 过滤。
+async fn load_project_spaces<C>(
+    conn: &C,
+    project_ids: impl Iterator<Item = String>,
+) -> Result<HashMap<String, String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let project_ids = project_ids.collect::<HashSet<_>>();
+    if project_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(String, String)> = projects::Entity::find()
+        .select_only()
+        .column(projects::Column::Id)
+        .column(projects::Column::SpaceId)
+        .filter(projects::Column::Id.is_in(project_ids))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(rows.into_iter().collect())
+}
+
+fn linked_space_id(
+    space_map: &HashMap<String, String>,
+    project_id: Option<&str>,
+) -> Option<String> {
+    project_id.and_then(|project_id| space_map.get(project_id).cloned())
+}
+
+fn parse_json_tags(raw: &str) -> Vec<String> {
+    serde_json::from_str(raw).unwrap_or_default()
+}
+
+fn split_tags(raw: &str) -> Vec<String> {
+    raw.split('\n')
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 按空白拆分关键词，去重并限制数量。
+fn split_terms(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in text.split_whitespace() {
+        if !terms
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(term))
+        {
+            terms.push(term.to_string());
+        }
+        if terms.len() >= MAX_TERMS {
+            break;
+        }
+    }
+    terms
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// 标记文本里命中关键词的字符（忽略大小写）。
+///
+/// 短词走 LIKE 时 FTS5 的 `highlight()` 不会标记，因此高亮统一在这里计算。
+fn match_mask(chars: &[char], terms: &[String]) -> Vec<bool> {
+    let folded = chars.iter().map(|ch| fold_char(*ch)).collect::<Vec<_>>();
+    let mut mask = vec![false; chars.len()];
+    for term in terms {
+        let needle = term.chars().map(fold_char).collect::<Vec<_>>();
+        if needle.is_empty() || needle.len() > folded.len() {
+            continue;
+        }
+        for start in 0..=(folded.len() - needle.len()) {
+            if folded[start..start + needle.len()] == needle[..] {
+                mask[start..start + needle.len()].fill(true);
+            }
+        }
+    }
+    mask
+}
+
+fn fold_char(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+fn render_marked(chars: &[char], mask: &[bool]) -> String {
+    let mut output = String::new();
+    let mut in_mark = false;
+    for (ch, marked) in chars.iter().zip(mask) {
+        if *marked && !in_mark {
+            output.push_str("<mark>");
+            in_mark = true;
+        } else if !*marked && in_mark {
+            output.push_str("</mark>");
+            in_mark = false;
+        }
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(*ch),
+        }
+    }
+    if in_mark {
+        output.push_str("</mark>");
+    }
+    output
+}
+
+fn highlight(text: &str, terms: &[String]) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mask = match_mask(&chars, terms);
+    render_marked(&chars, &mask)
+}
+
+/// 截取正文里第一处命中附近的片段；正文没有命中时取开头。
+fn build_snippet(body: &str, terms: &[String]) -> Option<String> {
+    let chars = body
+        .chars()
+        .map(|ch| if ch.is_whitespace() { ' ' } else { ch })
+        .collect::<Vec<_>>();
+    if chars.iter().all(|ch| *ch == ' ') {
+        return None;
+    }
+
+    let mask = match_mask(&chars, terms);
+    let first_hit = mask.iter().position(|marked| *marked).unwrap_or(0);
+    let start = first_hit.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let end = (start + SNIPPET_MAX_CHARS).min(chars.len());
+
+    let mut snippet = render_marked(&chars[start..end], &mask[start..end]);
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
@@ -7,8 +7,8 @@
 use std::collections::{HashMap, HashSet};
 
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
-    QueryOrder, QuerySelect, RelationTrait, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Set,
 };
 use uuid::Uuid;
 
@@ -25,11 +25,14 @@ pub struct TagRelationState {
     pub deleted_at: Option<i64>,
 }
 
-pub async fn load_tags(
-    conn: &DatabaseConnection,
+pub async fn load_tags<C>(
+    conn: &C,
     entity: TagEntity,
     owner_ids: &[String],
-) -> Result<HashMap<String, Vec<String>>, AppError> {
+) -> Result<HashMap<String, Vec<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
     if owner_ids.is_empty() {
         return Ok(HashMap::new());
     }
@@ -5,11 +5,15 @@ use sea_orm::ConnectionTrait;
 use crate::repos::activity_log_repo::{ActivityLogRepo, NewTaskActivityLogInput};
 use crate::types::error::AppError;
 
-const ACTION_TASK_CREATED: &str = "task_created";
-const ACTION_TASK_COMPLETED: &str = "task_completed";
-const ACTION_TASK_DELETED: &str = "task_deleted";
-const ACTION_TASK_RESTORED: &str = "task_restored";
-const ACTION_TASK_FIELD_UPDATED: &str = "task_field_updated";
+pub const ACTION_TASK_CREATED: &str = "task_created";
+pub const ACTION_TASK_COMPLETED: &str = "task_completed";
+pub const ACTION_TASK_DELETED: &str = "task_deleted";
+pub const ACTION_TASK_RESTORED: &str = "task_restored";
+pub const ACTION_TASK_FIELD_UPDATED: &str = "task_field_updated";
+pub const ACTION_TASK_RECURRENCE_SPAWNED: &str = "task_recurrence_spawned";
+pub const ACTION_TASK_DUPLICATED: &str = "task_duplicated";
+pub const ACTION_TASK_ARCHIVED: &str = "task_archived";
+pub const ACTION_TASK_UNARCHIVED: &str = "task_unarchived";
 
 /// 任务活动日志写入时需要的公共上下文。
 #[derive(Debug, Clone)]
@@ -19,6 +23,8 @@ pub struct TaskLogCtx<'a> {
     pub project_id: Option<&'a str>,
     pub create_by: &'a str,
     pub created_at: i64,
+    /// 同一次写操作的日志共用的批次 id
+    pub batch_id: Option<&'a str>,
 }
 
 /// 把可选项目 id 转成日志输入结构需要的 `Option<String>`。
@@ -46,13 +52,19 @@ where
             detail: format!("创建任务「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
 }
 
-/// 追加“任务完成”日志。
-pub async fn append_completed<C>(conn: &C, ctx: TaskLogCtx<'_>, title: &str) -> Result<(), AppError>
+/// 追加“任务完成”日志；`previous_status` 记录完成前的状态，供撤销时还原。
+pub async fn append_completed<C>(
+    conn: &C,
+    ctx: TaskLogCtx<'_>,
+    title: &str,
+    previous_status: &str,
+) -> Result<(), AppError>
 where
     C: ConnectionTrait,
 {
@@ -66,11 +78,12 @@ where
             action_label: "完成任务".to_string(),
             field_key: Some("status".to_string()),
             field_label: Some("状态".to_string()),
-            before_value: None,
+            before_value: Some(previous_status.to_string()),
             after_value: Some("done".to_string()),
             detail: format!("完成任务「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -96,6 +109,7 @@ where
             detail: format!("删除任务「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -121,6 +135,128 @@ where
             detail: format!("恢复任务「{}」", title),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
+        },
+    )
+    .await
+}
+
+/// 追加“任务归档”日志。
+pub async fn append_archived<C>(conn: &C, ctx: TaskLogCtx<'_>, title: &str) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    ActivityLogRepo::append_task(
+        conn,
+        NewTaskActivityLogInput {
+            task_id: ctx.task_id.to_string(),
+            space_id: ctx.space_id.to_string(),
+            project_id: project_id_string(ctx.project_id),
+            action: ACTION_TASK_ARCHIVED.to_string(),
+            action_label: "归档任务".to_string(),
+            field_key: Some("archivedAt".to_string()),
+            field_label: Some("归档时间".to_string()),
+            before_value: None,
+            after_value: Some(ctx.created_at.to_string()),
+            detail: format!("归档任务「{}」", title),
+            create_by: ctx.create_by.to_string(),
+            created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
+        },
+    )
+    .await
+}
+
+/// 追加“任务取消归档”日志；`archived_at` 记录原归档时间，供撤销时还原。
+pub async fn append_unarchived<C>(
+    conn: &C,
+    ctx: TaskLogCtx<'_>,
+    title: &str,
+    archived_at: i64,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    ActivityLogRepo::append_task(
+        conn,
+        NewTaskActivityLogInput {
+            task_id: ctx.task_id.to_string(),
+            space_id: ctx.space_id.to_string(),
+            project_id: project_id_string(ctx.project_id),
+            action: ACTION_TASK_UNARCHIVED.to_string(),
+            action_label: "取消归档任务".to_string(),
+            field_key: Some("archivedAt".to_string()),
+            field_label: Some("归档时间".to_string()),
+            before_value: Some(archived_at.to_string()),
+            after_value: None,
+            detail: format!("取消归档任务「{}」", title),
+            create_by: ctx.create_by.to_string(),
+            created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
+        },
+    )
+    .await
+}
+
+/// 追加“按重复规则生成下一次任务”日志。
+///
+/// 日志挂在新生成的任务上，`before_value` 记录来源任务 id。
+pub async fn append_recurrence_spawned<C>(
+    conn: &C,
+    ctx: TaskLogCtx<'_>,
+    title: &str,
+    source_task_id: &str,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    ActivityLogRepo::append_task(
+        conn,
+        NewTaskActivityLogInput {
+            task_id: ctx.task_id.to_string(),
+            space_id: ctx.space_id.to_string(),
+            project_id: project_id_string(ctx.project_id),
+            action: ACTION_TASK_RECURRENCE_SPAWNED.to_string(),
+            action_label: "生成重复任务".to_string(),
+            field_key: Some("recurrenceSeriesId".to_string()),
+            field_label: Some("重复系列".to_string()),
+            before_value: Some(source_task_id.to_string()),
+            after_value: Some(ctx.task_id.to_string()),
+            detail: format!("按重复规则生成下一次任务「{}」", title),
+            create_by: ctx.create_by.to_string(),
+            created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
+        },
+    )
+    .await
+}
+
+/// 追加“复制任务”日志，记录新任务复制自哪条任务。
+pub async fn append_duplicated<C>(
+    conn: &C,
+    ctx: TaskLogCtx<'_>,
+    source_task_id: &str,
+    source_title: &str,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    ActivityLogRepo::append_task(
+        conn,
+        NewTaskActivityLogInput {
+            task_id: ctx.task_id.to_string(),
+            space_id: ctx.space_id.to_string(),
+            project_id: project_id_string(ctx.project_id),
+            action: ACTION_TASK_DUPLICATED.to_string(),
+            action_label: "复制任务".to_string(),
+            field_key: Some("sourceTaskId".to_string()),
+            field_label: Some("来源任务".to_string()),
+            before_value: Some(source_task_id.to_string()),
+            after_value: Some(ctx.task_id.to_string()),
+            detail: format!("复制自任务「{}」", source_title),
+            create_by: ctx.create_by.to_string(),
+            created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -165,6 +301,7 @@ where
             ),
             create_by: ctx.create_by.to_string(),
             created_at: ctx.created_at,
+            batch_id: ctx.batch_id.map(str::to_string),
         },
     )
     .await
@@ -0,0 +1,129 @@
+//! 按自定义字段分组统计任务。
+//!
+//! 约定：
+//! - 过滤条件复用结构化查询的 `TaskListQuery`，排序与分页字段被忽略
+//! - 分组与求和都在 SQL 里用 JSON1 取值完成，按（项目, 原始取值）聚合
+//! - 分组字段声明为多选时，再把数组取值拆到各个选项上；没有取值的任务归入空值分组
+
+use std::collections::BTreeMap;
+
+use sea_orm::{
+    prelude::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{custom_field_definitions, tasks};
+use crate::types::{dto::CustomFieldAggregateDto, error::AppError};
+
+use super::{custom_fields, list};
+
+/// 取任务里某个标题字段的值；历史脏数据不是合法 JSON 时视为没有字段。
+const FIELD_VALUE_SQL: &str = "(SELECT json_extract(field.value, '$.value') \
+     FROM json_each(CASE WHEN json_valid(\"tasks\".\"custom_fields\") \
+     THEN \"tasks\".\"custom_fields\" ELSE '{}' END, '$.fields') AS field \
+     WHERE json_extract(field.value, '$.title') = ? LIMIT 1)";
+
+/// （项目 id, 字段取值）
+type GroupKey = (Option<String>, Option<String>);
+
+/// 按 `group_by` 字段的取值分项目统计任务数，并对 `sum_field` 的数值求和。
+pub async fn aggregate_by_custom_field(
+    conn: &DatabaseConnection,
+    input: list::TaskListQuery,
+    group_by: &str,
+    sum_field: Option<&str>,
+) -> Result<Vec<CustomFieldAggregateDto>, AppError> {
+    let group_by = group_by.trim();
+    if group_by.is_empty() {
+        return Err(AppError::Validation("分组字段不能为空".to_string()));
+    }
+    let sum_field = sum_field.map(str::trim).filter(|title| !title.is_empty());
+
+    let mut select = list::build_filtered_select(&input)?
+        .select_only()
+        .column(tasks::Column::ProjectId)
+        .expr_as(
+            Expr::cust_with_values(FIELD_VALUE_SQL, [group_by]),
+            "group_value",
+        )
+        .expr_as(Expr::cust("COUNT(*)"), "task_count");
+    // 非数字取值按 0 计入，整组都没有该字段时求和为空。
+    select = match sum_field {
+        Some(title) => select.expr_as(
+            Expr::cust_with_values(format!("SUM(CAST({FIELD_VALUE_SQL} AS REAL))"), [title]),
+            "value_sum",
+        ),
+        None => select.expr_as(Expr::cust("NULL"), "value_sum"),
+    };
+    let rows = select
+        .group_by(tasks::Column::ProjectId)
+        .group_by(Expr::cust("group_value"))
+        .into_tuple::<(Option<String>, Option<String>, i64, Option<f64>)>()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let expand = is_multi_select(conn, input.space_id.as_deref(), group_by).await?;
+    let mut groups: BTreeMap<GroupKey, (i64, Option<f64>)> = BTreeMap::new();
+    for (project_id, raw_value, count, sum) in rows {
+        let values = match raw_value.filter(|value| !value.is_empty()) {
+            Some(value) if expand => {
+                let options =
+                    serde_json::from_str::<Vec<String>>(&value).unwrap_or_else(|_| vec![value]);
+                if options.is_empty() {
+                    vec![None]
+                } else {
+                    options.into_iter().map(Some).collect()
+                }
+            }
+            value => vec![value],
+        };
+        for value in values {
+            let entry = groups
+                .entry((project_id.clone(), value))
+                .or_insert((0, None));
+            entry.0 += count;
+            if let Some(sum) = sum {
+                entry.1 = Some(entry.1.unwrap_or(0.0) + sum);
+            }
+        }
+    }
+
+    let mut result = groups
+        .into_iter()
+        .map(
+            |((project_id, value), (count, sum))| CustomFieldAggregateDto {
+                project_id,
+                value,
+                count,
+                sum,
+            },
+        )
+        .collect::<Vec<_>>();
+    // 同一项目内按任务数从多到少，空值分组放最后。
+    result.sort_by(|a, b| {
+        a.project_id
+            .cmp(&b.project_id)
+            .then_with(|| a.value.is_none().cmp(&b.value.is_none()))
+            .then_with(|| b.count.cmp(&a.count))
+            .then_with(|| a.value.cmp(&b.value))
+    });
+    Ok(result)
+}
+
+/// 分组字段是否在（给定 space 内的）任一作用域被声明为多选。
+async fn is_multi_select(
+    conn: &DatabaseConnection,
+    space_id: Option<&str>,
+    title: &str,
+) -> Result<bool, AppError> {
+    let mut query = custom_field_definitions::Entity::find()
+        .filter(custom_field_definitions::Column::Title.eq(title))
+        .filter(
+            custom_field_definitions::Column::FieldType.eq(custom_fields::FIELD_TYPE_MULTI_SELECT),
+        )
+        .filter(custom_field_definitions::Column::DeletedAt.is_null());
+    if let Some(space_id) = space_id {
+        query = query.filter(custom_field_definitions::Column::SpaceId.eq(space_id));
+    }
+    Ok(query.one(conn).await.map_err(AppError::from)?.is_some())
+}
@@ -0,0 +1,66 @@
+//! 任务自动归档的查询与设置。
+//!
+//! 自动归档只针对完成已久的任务，天数保存在 `app_settings`，0 表示关闭。
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, Set,
+};
+
+use crate::db::entities::{app_settings, sea_orm_active_enums::TaskStatus, tasks};
+use crate::types::error::AppError;
+
+const AUTO_ARCHIVE_DAYS_KEY: &str = "task_auto_archive_done_days";
+
+/// 完成时间早于 `before`、仍未归档的已完成任务 id。
+pub async fn find_done_ids_completed_before<C>(
+    conn: &C,
+    before: i64,
+) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Id)
+        .filter(tasks::Column::Status.eq(TaskStatus::Done))
+        .filter(tasks::Column::CompletedAt.lt(before))
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 读取自动归档天数，未设置时为 0（关闭）。
+pub async fn read_auto_archive_days<C>(conn: &C) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    let setting = app_settings::Entity::find_by_id(AUTO_ARCHIVE_DAYS_KEY.to_string())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(setting
+        .and_then(|setting| setting.value.parse::<i64>().ok())
+        .unwrap_or(0))
+}
+
+pub async fn write_auto_archive_days<C>(conn: &C, days: i64) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    app_settings::Entity::insert(app_settings::ActiveModel {
+        key: Set(AUTO_ARCHIVE_DAYS_KEY.to_string()),
+        value: Set(days.to_string()),
+    })
+    .on_conflict(
+        OnConflict::column(app_settings::Column::Key)
+            .update_columns([app_settings::Column::Value])
+            .to_owned(),
+    )
+    .exec_without_returning(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! 任务检查清单持久化原语。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+use crate::db::entities::task_checklist_items;
+use crate::types::{dto::ChecklistItemDto, error::AppError};
+
+pub struct NewChecklistItemRecord {
+    pub id: String,
+    pub task_id: String,
+    pub title: String,
+    pub done: bool,
+    pub rank: i64,
+    pub created_at: i64,
+}
+
+/// 把清单项模型转换成前端 DTO。
+pub fn to_dto(model: task_checklist_items::Model) -> ChecklistItemDto {
+    ChecklistItemDto {
+        id: model.id,
+        task_id: model.task_id,
+        title: model.title,
+        done: model.done,
+        rank: model.rank,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 按 id 读取未删除的清单项。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<task_checklist_items::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_checklist_items::Entity::find_by_id(id)
+        .filter(task_checklist_items::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("检查项不存在".to_string()))
+}
+
+/// 按 rank 顺序列出某个任务的未删除清单项。
+pub async fn list_for_task<C>(
+    conn: &C,
+    task_id: &str,
+) -> Result<Vec<task_checklist_items::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_checklist_items::Entity::find()
+        .filter(task_checklist_items::Column::TaskId.eq(task_id))
+        .filter(task_checklist_items::Column::DeletedAt.is_null())
+        .order_by_asc(task_checklist_items::Column::Rank)
+        .order_by_asc(task_checklist_items::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 批量统计任务的清单进度，返回 `task_id -> (总数, 已完成数)`。
+pub async fn load_progress_for_tasks<C>(
+    conn: &C,
+    task_ids: &[String],
+) -> Result<HashMap<String, (i64, i64)>, AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(String, bool)> = task_checklist_items::Entity::find()
+        .select_only()
+        .column(task_checklist_items::Column::TaskId)
+        .column(task_checklist_items::Column::Done)
+        .filter(task_checklist_items::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .filter(task_checklist_items::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut map: HashMap<String, (i64, i64)> = HashMap::new();
+    for (task_id, done) in rows {
+        let progress = map.entry(task_id).or_default();
+        progress.0 += 1;
+        if done {
+            progress.1 += 1;
+        }
+    }
+
+    Ok(map)
+}
+
+/// 计算任务清单尾部的新 rank。
+pub async fn next_rank<C>(conn: &C, task_id: &str) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    let max_rank_item = task_checklist_items::Entity::find()
+        .filter(task_checklist_items::Column::TaskId.eq(task_id))
+        .filter(task_checklist_items::Column::DeletedAt.is_null())
+        .order_by_desc(task_checklist_items::Column::Rank)
+        .one(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(max_rank_item.map(|item| item.rank + 1024).unwrap_or(1024))
+}
+
+/// 插入一条新清单项。
+pub async fn insert<C>(
+    conn: &C,
+    record: NewChecklistItemRecord,
+) -> Result<task_checklist_items::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_checklist_items::ActiveModel {
+        id: Set(record.id),
+        task_id: Set(record.task_id),
+        title: Set(record.title),
+        done: Set(record.done),
+        rank: Set(record.rank),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.created_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+/// 更新清单项。
+pub async fn update<C>(
+    conn: &C,
+    active_model: task_checklist_items::ActiveModel,
+) -> Result<task_checklist_items::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
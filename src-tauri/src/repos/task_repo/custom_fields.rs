@@ -1,22 +1,150 @@
 //! custom_fields 序列化/反序列化与规范化。
-//! 重点：仅支持 rank/title/value 结构。
+//! 重点：
+//! - 存储结构仍是 rank/title/value，值一律是字符串
+//! - 有同名字段定义时按定义的类型校验并规范化取值；没有定义的字段按纯文本保留
 
+use chrono::NaiveDate;
+
+use crate::db::entities::custom_field_definitions;
+use crate::repos::custom_field_repo;
 use crate::types::{
     dto::{CustomFieldItemDto, CustomFieldsDto},
     error::AppError,
 };
 
+pub const FIELD_TYPE_TEXT: &str = "text";
+pub const FIELD_TYPE_NUMBER: &str = "number";
+pub const FIELD_TYPE_DATE: &str = "date";
+pub const FIELD_TYPE_SELECT: &str = "select";
+pub const FIELD_TYPE_MULTI_SELECT: &str = "multiSelect";
+pub const FIELD_TYPE_CHECKBOX: &str = "checkbox";
+pub const FIELD_TYPE_URL: &str = "url";
+
+const FIELD_TYPES: [&str; 7] = [
+    FIELD_TYPE_TEXT,
+    FIELD_TYPE_NUMBER,
+    FIELD_TYPE_DATE,
+    FIELD_TYPE_SELECT,
+    FIELD_TYPE_MULTI_SELECT,
+    FIELD_TYPE_CHECKBOX,
+    FIELD_TYPE_URL,
+];
+
+/// 校验字段类型名。
+pub fn parse_field_type(value: &str) -> Result<&'static str, AppError> {
+    FIELD_TYPES
+        .iter()
+        .find(|field_type| **field_type == value.trim())
+        .copied()
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "customFields 字段类型仅支持 {}",
+                FIELD_TYPES.join(" / ")
+            ))
+        })
+}
+
+/// 是否为需要候选项的选择类字段。
+pub fn is_select_type(field_type: &str) -> bool {
+    field_type == FIELD_TYPE_SELECT || field_type == FIELD_TYPE_MULTI_SELECT
+}
+
+/// 按字段类型校验并规范化单个取值。
+///
+/// - number 统一成最短十进制表示
+/// - date 使用 `YYYY-MM-DD`
+/// - multiSelect 存成按候选项顺序排列的 JSON 字符串数组，输入也接受逗号分隔
+/// - checkbox 存成 `true` / `false`
+pub fn normalize_value(
+    title: &str,
+    field_type: &str,
+    options: &[String],
+    value: &str,
+) -> Result<String, AppError> {
+    let invalid = |expected: &str| {
+        AppError::Validation(format!(
+            "自定义字段「{title}」的值「{value}」不是有效的{expected}"
+        ))
+    };
+    match field_type {
+        FIELD_TYPE_NUMBER => value
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(|number| number.to_string())
+            .ok_or_else(|| invalid("数字")),
+        FIELD_TYPE_DATE => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .map_err(|_| invalid("日期（YYYY-MM-DD）")),
+        FIELD_TYPE_SELECT => options
+            .iter()
+            .find(|option| option.as_str() == value)
+            .cloned()
+            .ok_or_else(|| invalid("选项")),
+        FIELD_TYPE_MULTI_SELECT => {
+            let selected = if value.starts_with('[') {
+                serde_json::from_str::<Vec<String>>(value).map_err(|_| invalid("选项列表"))?
+            } else {
+                value.split(',').map(str::to_string).collect()
+            };
+            let selected = selected
+                .iter()
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>();
+            if let Some(unknown) = selected
+                .iter()
+                .find(|item| !options.iter().any(|option| option == *item))
+            {
+                return Err(AppError::Validation(format!(
+                    "自定义字段「{title}」没有选项「{unknown}」"
+                )));
+            }
+            let ordered = options
+                .iter()
+                .filter(|option| selected.contains(&option.as_str()))
+                .collect::<Vec<_>>();
+            serde_json::to_string(&ordered).map_err(|_| invalid("选项列表"))
+        }
+        FIELD_TYPE_CHECKBOX => match value.to_ascii_lowercase().as_str() {
+            "true" => Ok("true".to_string()),
+            "false" => Ok("false".to_string()),
+            _ => Err(invalid("勾选值（true / false）")),
+        },
+        FIELD_TYPE_URL => {
+            let rest = value
+                .strip_prefix("https://")
+                .or_else(|| value.strip_prefix("http://"));
+            match rest {
+                Some(rest) if !rest.is_empty() && !value.contains(char::is_whitespace) => {
+                    Ok(value.to_string())
+                }
+                _ => Err(invalid("链接（http / https）")),
+            }
+        }
+        _ => Ok(value.to_string()),
+    }
+}
+
+/// 宽松解析：反序列化失败时回退为 None，只用于列表 / 详情等读取 DTO，避免历史脏数据阻断读取。
 pub fn parse_from_json_string(raw: Option<&str>) -> Option<CustomFieldsDto> {
-    // 反序列化失败时直接回退为 None，避免历史脏数据阻断列表读取。
-    let raw = raw?;
-    let mut parsed = serde_json::from_str::<CustomFieldsDto>(raw).ok()?;
+    parse_stored(raw).ok().flatten()
+}
+
+/// 严格解析：JSON 不合法时返回校验错误，供写入、迁移等不能静默丢数据的路径使用。
+pub fn parse_stored(raw: Option<&str>) -> Result<Option<CustomFieldsDto>, AppError> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    let mut parsed = serde_json::from_str::<CustomFieldsDto>(raw)
+        .map_err(|e| AppError::Validation(format!("customFields 不是合法的 JSON: {e}")))?;
     for item in &mut parsed.fields {
         if item.rank < 0 {
             item.rank = 0;
         }
     }
     parsed.fields.sort_by_key(|item| item.rank);
-    Some(parsed)
+    Ok(Some(parsed))
 }
 
 /// 把自定义字段 DTO 序列化成数据库中的 JSON 字符串。
@@ -31,7 +159,11 @@ pub fn serialize_custom_fields(fields: &CustomFieldsDto) -> Result<String, AppEr
 /// - rank 非负约束
 /// - title 去空白
 /// - value 的空串归一化
-pub fn normalize_custom_fields(input: CustomFieldsDto) -> Result<CustomFieldsDto, AppError> {
+/// - 有同名定义的字段按类型校验取值
+pub fn normalize_custom_fields(
+    input: CustomFieldsDto,
+    definitions: &[custom_field_definitions::Model],
+) -> Result<CustomFieldsDto, AppError> {
     let mut normalized = Vec::with_capacity(input.fields.len());
 
     for item in input.fields {
@@ -52,6 +184,20 @@ pub fn normalize_custom_fields(input: CustomFieldsDto) -> Result<CustomFieldsDto
             .value
             .map(|text| text.trim().to_string())
             .filter(|text| !text.is_empty());
+        let value = match (
+            value,
+            definitions
+                .iter()
+                .find(|definition| definition.title == title),
+        ) {
+            (Some(value), Some(definition)) => Some(normalize_value(
+                &title,
+                &definition.field_type,
+                &custom_field_repo::parse_options(definition.options.as_deref()),
+                &value,
+            )?),
+            (value, _) => value,
+        };
 
         normalized.push(CustomFieldItemDto {
             rank: item.rank,
@@ -0,0 +1,235 @@
+//! 任务依赖（blocked-by）关系原语。
+//!
+//! 重点：
+//! - 关系表沿用 tombstone 集合同步，删除依赖只写 `deleted_at`
+//! - “被阻塞”指至少存在一个未完成且未删除的前置任务
+
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{
+    sea_query::SelectStatement, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait,
+    JoinType, QueryFilter, QuerySelect, QueryTrait, RelationTrait, Set,
+};
+
+use crate::db::entities::{sea_orm_active_enums::TaskStatus, task_dependencies, tasks};
+use crate::types::error::AppError;
+
+/// 批量读取任务的前置任务 id（仅未删除的依赖关系）。
+pub async fn load_blocked_by_for_tasks<C>(
+    conn: &C,
+    task_ids: &[String],
+) -> Result<HashMap<String, Vec<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(String, String)> = task_dependencies::Entity::find()
+        .select_only()
+        .column(task_dependencies::Column::TaskId)
+        .column(task_dependencies::Column::BlockedByTaskId)
+        .filter(task_dependencies::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .filter(task_dependencies::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for (task_id, blocked_by_task_id) in rows {
+        map.entry(task_id).or_default().push(blocked_by_task_id);
+    }
+    for list in map.values_mut() {
+        list.sort();
+    }
+
+    Ok(map)
+}
+
+/// 批量读取任务仍未完成的前置任务 id。
+pub async fn load_open_blockers_for_tasks<C>(
+    conn: &C,
+    task_ids: &[String],
+) -> Result<HashMap<String, Vec<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(String, String)> = open_blocker_query()
+        .select_only()
+        .column(task_dependencies::Column::TaskId)
+        .column(task_dependencies::Column::BlockedByTaskId)
+        .filter(task_dependencies::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for (task_id, blocked_by_task_id) in rows {
+        map.entry(task_id).or_default().push(blocked_by_task_id);
+    }
+
+    Ok(map)
+}
+
+/// 读取直接依赖某个任务的后续任务 id。
+pub async fn find_dependent_task_ids<C>(
+    conn: &C,
+    blocked_by_task_id: &str,
+) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_dependencies::Entity::find()
+        .select_only()
+        .column(task_dependencies::Column::TaskId)
+        .filter(task_dependencies::Column::BlockedByTaskId.eq(blocked_by_task_id))
+        .filter(task_dependencies::Column::DeletedAt.is_null())
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 判断沿 blocked-by 方向能否从 `from_task_id` 走到 `target_task_id`。
+///
+/// 按层批量查询，避免逐条递归。
+pub async fn is_reachable<C>(
+    conn: &C,
+    from_task_id: &str,
+    target_task_id: &str,
+) -> Result<bool, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut visited = HashSet::from([from_task_id.to_string()]);
+    let mut frontier = vec![from_task_id.to_string()];
+
+    while !frontier.is_empty() {
+        let next: Vec<String> = task_dependencies::Entity::find()
+            .select_only()
+            .column(task_dependencies::Column::BlockedByTaskId)
+            .filter(task_dependencies::Column::TaskId.is_in(frontier.clone()))
+            .filter(task_dependencies::Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::from)?;
+
+        frontier.clear();
+        for task_id in next {
+            if task_id == target_task_id {
+                return Ok(true);
+            }
+            if visited.insert(task_id.clone()) {
+                frontier.push(task_id);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// 同步单个任务的前置任务集合。
+///
+/// 与标签/链接一致：缺失的关系写 tombstone，重新出现的关系复活。
+pub async fn sync_dependencies<C>(
+    conn: &C,
+    task_id: &str,
+    blocked_by_task_ids: &[String],
+    now: i64,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let existing_relations: Vec<(String, Option<i64>)> = task_dependencies::Entity::find()
+        .select_only()
+        .column(task_dependencies::Column::BlockedByTaskId)
+        .column(task_dependencies::Column::DeletedAt)
+        .filter(task_dependencies::Column::TaskId.eq(task_id))
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let desired: HashSet<&String> = blocked_by_task_ids.iter().collect();
+
+    for blocked_by_task_id in blocked_by_task_ids {
+        match existing_relations
+            .iter()
+            .find(|(existing_id, _)| existing_id == blocked_by_task_id)
+        {
+            Some((_, Some(_))) => {
+                apply_relation_state(conn, task_id, blocked_by_task_id, now, None).await?;
+            }
+            Some((_, None)) => {}
+            None => {
+                task_dependencies::ActiveModel {
+                    task_id: Set(task_id.to_string()),
+                    blocked_by_task_id: Set(blocked_by_task_id.clone()),
+                    updated_at: Set(now),
+                    deleted_at: Set(None),
+                }
+                .insert(conn)
+                .await
+                .map_err(AppError::from)?;
+            }
+        }
+    }
+
+    for (blocked_by_task_id, deleted_at) in &existing_relations {
+        if deleted_at.is_none() && !desired.contains(blocked_by_task_id) {
+            apply_relation_state(conn, task_id, blocked_by_task_id, now, Some(now)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 构造“存在未完成前置任务的任务 id”子查询，供列表过滤使用。
+pub fn blocked_task_ids_subquery() -> SelectStatement {
+    open_blocker_query()
+        .select_only()
+        .column(task_dependencies::Column::TaskId)
+        .into_query()
+}
+
+/// 未删除的依赖关系 + 前置任务仍未完成且未删除。
+fn open_blocker_query() -> sea_orm::Select<task_dependencies::Entity> {
+    task_dependencies::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            task_dependencies::Relation::BlockedByTask.def(),
+        )
+        .filter(task_dependencies::Column::DeletedAt.is_null())
+        .filter(tasks::Column::Status.ne(TaskStatus::Done))
+        .filter(tasks::Column::DeletedAt.is_null())
+}
+
+async fn apply_relation_state<C>(
+    conn: &C,
+    task_id: &str,
+    blocked_by_task_id: &str,
+    updated_at: i64,
+    deleted_at: Option<i64>,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    task_dependencies::ActiveModel {
+        task_id: Set(task_id.to_string()),
+        blocked_by_task_id: Set(blocked_by_task_id.to_string()),
+        updated_at: Set(updated_at),
+        deleted_at: Set(deleted_at),
+    }
+    .update(conn)
+    .await
+    .map_err(AppError::from)?;
+
+    Ok(())
+}
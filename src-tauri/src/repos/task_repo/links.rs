@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 
-use sea_orm::{ConnectionTrait, DatabaseConnection};
+use sea_orm::ConnectionTrait;
 
 use crate::repos::link_repo::{self, LinkEntity};
 use crate::types::{
@@ -11,10 +11,13 @@ use crate::types::{
     error::AppError,
 };
 
-pub async fn load_links_for_tasks(
-    conn: &DatabaseConnection,
+pub async fn load_links_for_tasks<C>(
+    conn: &C,
     task_ids: &[String],
-) -> Result<HashMap<String, Vec<LinkDto>>, AppError> {
+) -> Result<HashMap<String, Vec<LinkDto>>, AppError>
+where
+    C: ConnectionTrait,
+{
     // 任务链接读取同样是对通用 link_repo 的轻量封装。
     link_repo::load_links(conn, LinkEntity::Task, task_ids).await
 }
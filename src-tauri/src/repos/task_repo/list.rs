@@ -1,114 +1,283 @@
-//! Task 查询逻辑（正常列表 + 回收站列表）。
+//! Task 查询逻辑（正常列表 + 结构化分页查询 + 回收站列表）。
 //! 重点：排序策略与过滤条件在这里集中定义，前端不重复实现。
+//!
+//! 结构化查询的约定：
+//! - 所有枚举类输入（状态、优先级、排序键、谓词）遇到未知值直接报校验错误
+//! - 分页使用 keyset 游标：游标记录上一页最后一行的排序值与 id
+//! - 可空排序列统一“空值排最后”，排序与游标比较使用同一表达式
+//! - 推迟中的任务（`defer_until` 晚于当前时间）在旧列表入口默认隐藏
 
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    prelude::Expr,
+    sea_query::{Func, LikeExpr, Query, SimpleExpr},
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect, Select, Value,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::db::entities::{sea_orm_active_enums::TaskStatus, tasks};
-use crate::types::{dto::TaskDto, error::AppError};
+use crate::db::{
+    entities::{sea_orm_active_enums::TaskStatus, tags as tag_entity, task_tags, tasks},
+    now_ms,
+};
+use crate::repos::common_task_utils;
+use crate::types::{
+    dto::{CustomFieldPredicateDto, TaskDto, TaskPageDto, TaskSortDto, TimeRangeDto},
+    error::AppError,
+};
 
-use super::{custom_fields, links, tags};
+use super::{
+    checklist, custom_fields, dependencies, links, recurrence, tags, time_entries, validations,
+};
 
-pub async fn list(
-    conn: &DatabaseConnection,
-    space_id: Option<&str>,
-    status: Option<&str>,
-    project_id: Option<&str>,
-) -> Result<Vec<TaskDto>, AppError> {
-    // 列表查询只负责构造筛选条件，不承载“任务是否允许展示”的业务编排。
-    let mut query = tasks::Entity::find();
+const DEFAULT_PAGE_SIZE: u64 = 100;
+const MAX_PAGE_SIZE: u64 = 500;
 
-    if let Some(sid) = space_id {
-        query = query.filter(tasks::Column::SpaceId.eq(sid));
+/// 结构化任务查询条件。
+///
+/// 字符串字段保持前端原值，统一在 `query` 内校验。
+#[derive(Debug, Clone, Default)]
+pub struct TaskListQuery {
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub statuses: Vec<String>,
+    pub priorities: Vec<String>,
+    /// 命中任意一个标签即可
+    pub tags_any: Vec<String>,
+    /// 必须同时带有全部标签
+    pub tags_all: Vec<String>,
+    pub deadline: Option<TimeRangeDto>,
+    pub scheduled: Option<TimeRangeDto>,
+    pub created: Option<TimeRangeDto>,
+    pub completed: Option<TimeRangeDto>,
+    /// 标题或备注包含的文本
+    pub text: Option<String>,
+    pub custom_fields: Vec<CustomFieldPredicateDto>,
+    /// include / exclude / only，默认 include
+    pub archived: Option<String>,
+    /// include / exclude / only，默认 include；以当前时间判断是否仍在推迟中
+    pub deferred: Option<String>,
+    pub exclude_blocked: bool,
+    /// 为空时沿用默认排序
+    pub sort: Vec<TaskSortDto>,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Priority,
+    Rank,
+    DeadlineAt,
+    ScheduledAt,
+    CreatedAt,
+    UpdatedAt,
+    CompletedAt,
+    Title,
+}
+
+impl SortKey {
+    fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim() {
+            "priority" => Ok(Self::Priority),
+            "rank" => Ok(Self::Rank),
+            "deadlineAt" => Ok(Self::DeadlineAt),
+            "scheduledAt" => Ok(Self::ScheduledAt),
+            "createdAt" => Ok(Self::CreatedAt),
+            "updatedAt" => Ok(Self::UpdatedAt),
+            "completedAt" => Ok(Self::CompletedAt),
+            "title" => Ok(Self::Title),
+            other => Err(AppError::Validation(format!("不支持的排序字段：{other}"))),
+        }
     }
 
-    let mut is_done = false;
-    if let Some(stat) = status {
-        // Map string to enum
-        let status_enum = match stat {
-            "todo" => TaskStatus::Todo,
-            "done" => {
-                is_done = true;
-                TaskStatus::Done
-            }
-            _ => TaskStatus::Todo, // Fallback or strict? Existing code allows string
-        };
-        query = query.filter(tasks::Column::Status.eq(status_enum));
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Priority => "priority",
+            Self::Rank => "rank",
+            Self::DeadlineAt => "deadlineAt",
+            Self::ScheduledAt => "scheduledAt",
+            Self::CreatedAt => "createdAt",
+            Self::UpdatedAt => "updatedAt",
+            Self::CompletedAt => "completedAt",
+            Self::Title => "title",
+        }
     }
 
-    if let Some(pid) = project_id {
-        query = query.filter(tasks::Column::ProjectId.eq(pid));
+    fn column(self) -> tasks::Column {
+        match self {
+            Self::Priority => tasks::Column::Priority,
+            Self::Rank => tasks::Column::Rank,
+            Self::DeadlineAt => tasks::Column::DeadlineAt,
+            Self::ScheduledAt => tasks::Column::ScheduledAt,
+            Self::CreatedAt => tasks::Column::CreatedAt,
+            Self::UpdatedAt => tasks::Column::UpdatedAt,
+            Self::CompletedAt => tasks::Column::CompletedAt,
+            Self::Title => tasks::Column::Title,
+        }
     }
 
-    query = query.filter(tasks::Column::DeletedAt.is_null());
+    fn is_text(self) -> bool {
+        matches!(self, Self::Priority | Self::Rank | Self::Title)
+    }
 
-    // 重点：done 列表按完成时间倒序；todo 列表按优先级与 rank 排序。
-    if is_done {
-        query = query.order_by_desc(tasks::Column::CompletedAt);
-    } else {
-        query = query
-            .order_by_asc(tasks::Column::Priority)
-            .order_by_asc(tasks::Column::Rank)
-            .order_by_asc(tasks::Column::DeadlineAt)
-            .order_by_asc(tasks::Column::CreatedAt);
+    fn is_nullable(self) -> bool {
+        matches!(
+            self,
+            Self::DeadlineAt | Self::ScheduledAt | Self::CompletedAt
+        )
     }
+}
 
-    let models = query.all(conn).await.map_err(AppError::from)?;
+#[derive(Debug, Clone)]
+struct SortSpec {
+    key: SortKey,
+    order: Order,
+}
 
-    // 先把主表模型转成 DTO，后面再集中回填 tags / links。
-    let mut dtos = Vec::with_capacity(models.len());
-    for m in models {
-        let custom_fields = custom_fields::parse_from_json_string(m.custom_fields.as_deref());
+impl SortSpec {
+    fn asc(key: SortKey) -> Self {
+        Self {
+            key,
+            order: Order::Asc,
+        }
+    }
 
-        dtos.push(TaskDto {
-            id: m.id,
-            space_id: m.space_id,
-            project_id: m.project_id,
-            title: m.title,
-            note: m.note,
-            status: match m.status {
-                TaskStatus::Todo => "todo".to_string(),
-                TaskStatus::Done => "done".to_string(),
-            },
-            done_reason: m.done_reason.map(|r| match r {
-                crate::db::entities::sea_orm_active_enums::DoneReason::Completed => {
-                    "completed".to_string()
-                }
-                crate::db::entities::sea_orm_active_enums::DoneReason::Cancelled => {
-                    "cancelled".to_string()
-                }
-            }),
-            priority: match m.priority {
-                crate::db::entities::sea_orm_active_enums::Priority::P0 => "P0".to_string(),
-                crate::db::entities::sea_orm_active_enums::Priority::P1 => "P1".to_string(),
-                crate::db::entities::sea_orm_active_enums::Priority::P2 => "P2".to_string(),
-                crate::db::entities::sea_orm_active_enums::Priority::P3 => "P3".to_string(),
-            },
-            tags: Vec::new(),
-            rank: m.rank,
-            created_at: m.created_at,
-            updated_at: m.updated_at,
-            completed_at: m.completed_at,
-            deadline_at: m.deadline_at,
-            archived_at: m.archived_at,
-            deleted_at: m.deleted_at,
-            links: Vec::new(),
-            custom_fields,
-            create_by: m.create_by,
-        });
+    fn desc(key: SortKey) -> Self {
+        Self {
+            key,
+            order: Order::Desc,
+        }
     }
 
-    if !dtos.is_empty() {
-        // 重点：先批量查 tags/links，再按 task_id 回填，避免 N+1。
-        let task_ids = dtos.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
-        let tag_map = tags::load_tags_for_tasks(conn, &task_ids).await?;
-        let link_map = links::load_links_for_tasks(conn, &task_ids).await?;
-        for task in &mut dtos {
-            task.links = link_map.get(&task.id).cloned().unwrap_or_default();
-            task.tags = tag_map.get(&task.id).cloned().unwrap_or_default();
+    /// 空值哨兵：升序用最大值、降序用最小值，保证空值总排在最后。
+    fn null_sentinel(&self) -> i64 {
+        match self.order {
+            Order::Desc => i64::MIN,
+            _ => i64::MAX,
         }
     }
 
-    Ok(dtos)
+    fn expr(&self) -> SimpleExpr {
+        let column = Expr::col((tasks::Entity, self.key.column()));
+        if self.key.is_nullable() {
+            Func::coalesce([column.into(), Expr::val(self.null_sentinel()).into()]).into()
+        } else {
+            column.into()
+        }
+    }
+
+    fn cursor_value(&self, model: &tasks::Model) -> serde_json::Value {
+        match self.key {
+            SortKey::Priority => serde_json::json!(priority_to_string(&model.priority)),
+            SortKey::Rank => serde_json::json!(model.rank),
+            SortKey::DeadlineAt => {
+                serde_json::json!(model.deadline_at.unwrap_or(self.null_sentinel()))
+            }
+            SortKey::ScheduledAt => {
+                serde_json::json!(model.scheduled_at.unwrap_or(self.null_sentinel()))
+            }
+            SortKey::CreatedAt => serde_json::json!(model.created_at),
+            SortKey::UpdatedAt => serde_json::json!(model.updated_at),
+            SortKey::CompletedAt => {
+                serde_json::json!(model.completed_at.unwrap_or(self.null_sentinel()))
+            }
+            SortKey::Title => serde_json::json!(model.title),
+        }
+    }
+
+    fn signature(&self) -> String {
+        let direction = match self.order {
+            Order::Desc => "desc",
+            _ => "asc",
+        };
+        format!("{}:{direction}", self.key.as_str())
+    }
+}
+
+/// 游标内容：排序签名 + 上一页最后一行的排序值与 id。
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskCursor {
+    sort: String,
+    values: Vec<serde_json::Value>,
+    id: String,
+}
+
+/// 列出任务（不分页）。
+///
+/// 保留给旧入口使用，过滤与排序规则与 `query` 完全一致；推迟中与已归档的任务不返回。
+pub async fn list(
+    conn: &DatabaseConnection,
+    space_id: Option<&str>,
+    status: Option<&str>,
+    project_id: Option<&str>,
+    exclude_blocked: bool,
+) -> Result<Vec<TaskDto>, AppError> {
+    let input = TaskListQuery {
+        space_id: space_id.map(str::to_string),
+        project_id: project_id.map(str::to_string),
+        statuses: status
+            .map(|value| vec![value.to_string()])
+            .unwrap_or_default(),
+        archived: Some("exclude".to_string()),
+        deferred: Some("exclude".to_string()),
+        exclude_blocked,
+        ..TaskListQuery::default()
+    };
+
+    // 列表查询只负责构造筛选条件，不承载“任务是否允许展示”的业务编排。
+    let select = build_filtered_select(&input)?;
+    let sort = resolve_sort(&input)?;
+    let models = apply_sort(select, &sort)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    to_dtos(conn, models).await
+}
+
+/// 结构化任务查询，按 keyset 游标分页。
+pub async fn query(
+    conn: &DatabaseConnection,
+    input: TaskListQuery,
+) -> Result<TaskPageDto, AppError> {
+    let mut select = build_filtered_select(&input)?;
+    let sort = resolve_sort(&input)?;
+    let signature = sort
+        .iter()
+        .map(SortSpec::signature)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    if let Some(raw) = input.cursor.as_deref() {
+        let cursor = decode_cursor(raw, &signature, &sort)?;
+        select = select.filter(keyset_condition(&sort, &cursor)?);
+    }
+
+    let limit = input
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    // 多取一行用于判断是否还有下一页。
+    let mut models = apply_sort(select, &sort)
+        .limit(limit + 1)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let next_cursor = if models.len() as u64 > limit {
+        models.truncate(limit as usize);
+        models
+            .last()
+            .map(|model| encode_cursor(&signature, &sort, model))
+            .transpose()?
+    } else {
+        None
+    };
+
+    Ok(TaskPageDto {
+        items: to_dtos(conn, models).await?,
+        next_cursor,
+    })
 }
 
 /// 列出已软删除任务。
@@ -127,12 +296,7 @@ pub async fn list_deleted(
     }
 
     if let Some(stat) = status {
-        let status_enum = match stat {
-            "todo" => TaskStatus::Todo,
-            "done" => TaskStatus::Done,
-            _ => TaskStatus::Todo,
-        };
-        query = query.filter(tasks::Column::Status.eq(status_enum));
+        query = query.filter(tasks::Column::Status.eq(parse_status(stat)?));
     }
 
     if let Some(pid) = project_id {
@@ -150,7 +314,517 @@ pub async fn list_deleted(
 
     let models = query.all(conn).await.map_err(AppError::from)?;
 
-    // 回收站列表同样沿用“主表先转 DTO，再批量回填关联数据”的策略。
+    to_dtos(conn, models).await
+}
+
+/// 列出已归档（未删除）任务，最近归档的排在前面。
+pub async fn list_archived(
+    conn: &DatabaseConnection,
+    space_id: Option<&str>,
+    project_id: Option<&str>,
+) -> Result<Vec<TaskDto>, AppError> {
+    let mut query = tasks::Entity::find()
+        .filter(tasks::Column::ArchivedAt.is_not_null())
+        .filter(tasks::Column::DeletedAt.is_null());
+
+    if let Some(sid) = space_id {
+        query = query.filter(tasks::Column::SpaceId.eq(sid));
+    }
+
+    if let Some(pid) = project_id {
+        query = query.filter(tasks::Column::ProjectId.eq(pid));
+    }
+
+    let models = query
+        .order_by_desc(tasks::Column::ArchivedAt)
+        .order_by_desc(tasks::Column::CompletedAt)
+        .order_by_desc(tasks::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    to_dtos(conn, models).await
+}
+
+/// 按 id 读取任务 DTO，结果保持传入顺序；不存在的 id 直接忽略。
+pub async fn list_by_ids(
+    conn: &DatabaseConnection,
+    ids: &[String],
+) -> Result<Vec<TaskDto>, AppError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut models = tasks::Entity::find()
+        .filter(tasks::Column::Id.is_in(ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    models.sort_by_key(|model| ids.iter().position(|id| id == &model.id));
+
+    to_dtos(conn, models).await
+}
+
+/// 构造除排序与分页以外的全部过滤条件。
+pub(super) fn build_filtered_select(
+    input: &TaskListQuery,
+) -> Result<Select<tasks::Entity>, AppError> {
+    let mut query = tasks::Entity::find().filter(tasks::Column::DeletedAt.is_null());
+
+    if let Some(sid) = input.space_id.as_deref() {
+        query = query.filter(tasks::Column::SpaceId.eq(sid));
+    }
+    if let Some(pid) = input.project_id.as_deref() {
+        query = query.filter(tasks::Column::ProjectId.eq(pid));
+    }
+
+    if !input.statuses.is_empty() {
+        let statuses = input
+            .statuses
+            .iter()
+            .map(|value| parse_status(value))
+            .collect::<Result<Vec<_>, _>>()?;
+        query = query.filter(tasks::Column::Status.is_in(statuses));
+    }
+    if !input.priorities.is_empty() {
+        let priorities = input
+            .priorities
+            .iter()
+            .map(|value| common_task_utils::parse_priority(Some(value)))
+            .collect::<Result<Vec<_>, _>>()?;
+        query = query.filter(tasks::Column::Priority.is_in(priorities));
+    }
+
+    let tags_any = normalize_names(&input.tags_any);
+    if !tags_any.is_empty() {
+        query = query.filter(
+            Expr::col((tasks::Entity, tasks::Column::Id))
+                .in_subquery(tag_subquery(&tags_any, false)),
+        );
+    }
+    let tags_all = normalize_names(&input.tags_all);
+    if !tags_all.is_empty() {
+        query = query.filter(
+            Expr::col((tasks::Entity, tasks::Column::Id))
+                .in_subquery(tag_subquery(&tags_all, true)),
+        );
+    }
+
+    query = apply_range(
+        query,
+        tasks::Column::DeadlineAt,
+        input.deadline.as_ref(),
+        "deadline",
+    )?;
+    query = apply_range(
+        query,
+        tasks::Column::ScheduledAt,
+        input.scheduled.as_ref(),
+        "scheduled",
+    )?;
+    query = apply_range(
+        query,
+        tasks::Column::CreatedAt,
+        input.created.as_ref(),
+        "created",
+    )?;
+    query = apply_range(
+        query,
+        tasks::Column::CompletedAt,
+        input.completed.as_ref(),
+        "completed",
+    )?;
+
+    if let Some(text) = input
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+    {
+        let pattern = format!("%{}%", escape_like(text));
+        query = query.filter(
+            Condition::any()
+                .add(
+                    Expr::col((tasks::Entity, tasks::Column::Title))
+                        .like(LikeExpr::new(pattern.clone()).escape('\\')),
+                )
+                .add(
+                    Expr::col((tasks::Entity, tasks::Column::Note))
+                        .like(LikeExpr::new(pattern).escape('\\')),
+                ),
+        );
+    }
+
+    for predicate in &input.custom_fields {
+        query = query.filter(custom_field_condition(predicate)?);
+    }
+
+    match input
+        .archived
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or("include")
+    {
+        "include" => {}
+        "exclude" => query = query.filter(tasks::Column::ArchivedAt.is_null()),
+        "only" => query = query.filter(tasks::Column::ArchivedAt.is_not_null()),
+        _ => {
+            return Err(AppError::Validation(
+                "archived 仅支持 include / exclude / only".to_string(),
+            ))
+        }
+    }
+
+    match input
+        .deferred
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or("include")
+    {
+        "include" => {}
+        "exclude" => query = query.filter(not_deferred_condition(now_ms())),
+        "only" => query = query.filter(tasks::Column::DeferUntil.gt(now_ms())),
+        _ => {
+            return Err(AppError::Validation(
+                "deferred 仅支持 include / exclude / only".to_string(),
+            ))
+        }
+    }
+
+    // 仍有未完成前置任务的任务可按需隐藏，用于“现在能做什么”视图。
+    if input.exclude_blocked {
+        query = query.filter(
+            Expr::col((tasks::Entity, tasks::Column::Id))
+                .not_in_subquery(dependencies::blocked_task_ids_subquery()),
+        );
+    }
+
+    Ok(query)
+}
+
+/// 解析排序键；未指定时沿用默认排序。
+///
+/// 重点：done 列表按完成时间倒序；其它列表按优先级与 rank 排序。
+fn resolve_sort(input: &TaskListQuery) -> Result<Vec<SortSpec>, AppError> {
+    if input.sort.is_empty() {
+        let done_only = !input.statuses.is_empty()
+            && input
+                .statuses
+                .iter()
+                .all(|value| matches!(parse_status(value), Ok(TaskStatus::Done)));
+        return Ok(if done_only {
+            vec![SortSpec::desc(SortKey::CompletedAt)]
+        } else {
+            vec![
+                SortSpec::asc(SortKey::Priority),
+                SortSpec::asc(SortKey::Rank),
+                SortSpec::asc(SortKey::DeadlineAt),
+                SortSpec::asc(SortKey::CreatedAt),
+            ]
+        });
+    }
+
+    let mut specs: Vec<SortSpec> = Vec::with_capacity(input.sort.len());
+    for item in &input.sort {
+        let key = SortKey::parse(&item.key)?;
+        if specs.iter().any(|spec| spec.key == key) {
+            return Err(AppError::Validation(format!(
+                "排序字段重复：{}",
+                key.as_str()
+            )));
+        }
+        let order = match item.direction.as_deref().map(str::trim).unwrap_or("asc") {
+            "asc" => Order::Asc,
+            "desc" => Order::Desc,
+            other => {
+                return Err(AppError::Validation(format!(
+                    "排序方向仅支持 asc / desc：{other}"
+                )))
+            }
+        };
+        specs.push(SortSpec { key, order });
+    }
+    Ok(specs)
+}
+
+/// 按排序键排序，最后用 id 兜底，保证顺序稳定、游标可续。
+fn apply_sort(mut query: Select<tasks::Entity>, sort: &[SortSpec]) -> Select<tasks::Entity> {
+    for spec in sort {
+        query = query.order_by(spec.expr(), spec.order.clone());
+    }
+    query.order_by_asc(tasks::Column::Id)
+}
+
+/// keyset 条件：(k1 > v1) OR (k1 = v1 AND k2 > v2) OR ... OR (全部相等 AND id > last_id)。
+fn keyset_condition(sort: &[SortSpec], cursor: &TaskCursor) -> Result<Condition, AppError> {
+    let values = sort
+        .iter()
+        .zip(&cursor.values)
+        .map(|(spec, value)| cursor_value_to_db(spec.key, value))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut any = Condition::any();
+    for index in 0..=sort.len() {
+        let mut all = Condition::all();
+        for (spec, value) in sort.iter().zip(&values).take(index) {
+            all = all.add(Expr::expr(spec.expr()).eq(value.clone()));
+        }
+        all = match sort.get(index) {
+            Some(spec) => {
+                let expr = Expr::expr(spec.expr());
+                let value = values[index].clone();
+                all.add(match spec.order {
+                    Order::Desc => expr.lt(value),
+                    _ => expr.gt(value),
+                })
+            }
+            None => all.add(Expr::col((tasks::Entity, tasks::Column::Id)).gt(cursor.id.clone())),
+        };
+        any = any.add(all);
+    }
+    Ok(any)
+}
+
+fn encode_cursor(
+    signature: &str,
+    sort: &[SortSpec],
+    model: &tasks::Model,
+) -> Result<String, AppError> {
+    serde_json::to_string(&TaskCursor {
+        sort: signature.to_string(),
+        values: sort.iter().map(|spec| spec.cursor_value(model)).collect(),
+        id: model.id.clone(),
+    })
+    .map_err(|e| AppError::Internal(format!("游标序列化失败: {e}")))
+}
+
+fn decode_cursor(raw: &str, signature: &str, sort: &[SortSpec]) -> Result<TaskCursor, AppError> {
+    let cursor = serde_json::from_str::<TaskCursor>(raw)
+        .map_err(|_| AppError::Validation("cursor 无效".to_string()))?;
+    if cursor.sort != signature || cursor.values.len() != sort.len() {
+        return Err(AppError::Validation(
+            "cursor 与当前排序条件不匹配".to_string(),
+        ));
+    }
+    Ok(cursor)
+}
+
+fn cursor_value_to_db(key: SortKey, value: &serde_json::Value) -> Result<Value, AppError> {
+    let converted = if key.is_text() {
+        value.as_str().map(|text| Value::from(text.to_string()))
+    } else {
+        value.as_i64().map(Value::from)
+    };
+    converted.ok_or_else(|| AppError::Validation("cursor 无效".to_string()))
+}
+
+/// 未推迟或推迟已到期的任务。
+pub(super) fn not_deferred_condition(now: i64) -> Condition {
+    Condition::any()
+        .add(tasks::Column::DeferUntil.is_null())
+        .add(tasks::Column::DeferUntil.lte(now))
+}
+
+fn apply_range(
+    query: Select<tasks::Entity>,
+    column: tasks::Column,
+    range: Option<&TimeRangeDto>,
+    label: &str,
+) -> Result<Select<tasks::Entity>, AppError> {
+    let Some(range) = range else {
+        return Ok(query);
+    };
+    if let (Some(from), Some(to)) = (range.from, range.to) {
+        if from >= to {
+            return Err(AppError::Validation(format!(
+                "{label} 时间范围不合法：from 必须早于 to"
+            )));
+        }
+    }
+
+    let mut query = query;
+    if let Some(from) = range.from {
+        query = query.filter(column.gte(from));
+    }
+    if let Some(to) = range.to {
+        query = query.filter(column.lt(to));
+    }
+    Ok(query)
+}
+
+/// 标签子查询：any 命中任一标签；all 要求去重后的命中数等于标签数。
+fn tag_subquery(names: &[String], require_all: bool) -> sea_orm::sea_query::SelectStatement {
+    let mut subquery = Query::select();
+    subquery
+        .column((task_tags::Entity, task_tags::Column::TaskId))
+        .from(task_tags::Entity)
+        .inner_join(
+            tag_entity::Entity,
+            Expr::col((tag_entity::Entity, tag_entity::Column::Id))
+                .equals((task_tags::Entity, task_tags::Column::TagId)),
+        )
+        .and_where(Expr::col((task_tags::Entity, task_tags::Column::DeletedAt)).is_null())
+        .and_where(Expr::col((tag_entity::Entity, tag_entity::Column::Name)).is_in(names.to_vec()));
+    if require_all {
+        subquery
+            .group_by_col((task_tags::Entity, task_tags::Column::TaskId))
+            .and_having(
+                Expr::expr(Func::count_distinct(Expr::col((
+                    tag_entity::Entity,
+                    tag_entity::Column::Name,
+                ))))
+                .eq(names.len() as i64),
+            );
+    }
+    subquery.to_owned()
+}
+
+/// 自定义字段谓词，基于 SQLite JSON1 展开 `custom_fields.fields`。
+///
+/// 历史脏数据可能不是合法 JSON，先用 `json_valid` 兜底，避免整条查询报错。
+fn custom_field_condition(predicate: &CustomFieldPredicateDto) -> Result<SimpleExpr, AppError> {
+    let title = predicate.title.trim();
+    if title.is_empty() {
+        return Err(AppError::Validation(
+            "customFields 过滤条件的 title 不能为空".to_string(),
+        ));
+    }
+    let value = predicate
+        .value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let require_value = || {
+        value.ok_or_else(|| {
+            AppError::Validation(format!(
+                "customFields 过滤条件 {} 需要提供 value",
+                predicate.op
+            ))
+        })
+    };
+
+    let exists = |negated: bool, value_sql: &str, values: Vec<Value>| {
+        let mut all_values: Vec<Value> = vec![title.into()];
+        all_values.extend(values);
+        Expr::cust_with_values(
+            format!(
+                "{}EXISTS (SELECT 1 FROM json_each(CASE WHEN json_valid(\"tasks\".\"custom_fields\") \
+                 THEN \"tasks\".\"custom_fields\" ELSE '{{}}' END, '$.fields') AS field \
+                 WHERE json_extract(field.value, '$.title') = ? AND {value_sql})",
+                if negated { "NOT " } else { "" }
+            ),
+            all_values,
+        )
+    };
+
+    match predicate.op.trim() {
+        "eq" => Ok(exists(
+            false,
+            "json_extract(field.value, '$.value') = ?",
+            vec![require_value()?.into()],
+        )),
+        "ne" => Ok(exists(
+            true,
+            "json_extract(field.value, '$.value') = ?",
+            vec![require_value()?.into()],
+        )),
+        "contains" => Ok(exists(
+            false,
+            "json_extract(field.value, '$.value') LIKE ? ESCAPE '\\'",
+            vec![format!("%{}%", escape_like(require_value()?)).into()],
+        )),
+        "empty" => Ok(exists(
+            true,
+            "COALESCE(json_extract(field.value, '$.value'), '') <> ''",
+            Vec::new(),
+        )),
+        "notEmpty" => Ok(exists(
+            false,
+            "COALESCE(json_extract(field.value, '$.value'), '') <> ''",
+            Vec::new(),
+        )),
+        // 多选值存成 JSON 数组，单值按只有一个元素处理。
+        "includes" => Ok(exists(
+            false,
+            "EXISTS (SELECT 1 FROM json_each(CASE \
+             WHEN json_valid(json_extract(field.value, '$.value')) \
+             AND json_type(json_extract(field.value, '$.value')) = 'array' \
+             THEN json_extract(field.value, '$.value') \
+             ELSE json_array(json_extract(field.value, '$.value')) END) AS item \
+             WHERE item.value = ?)",
+            vec![require_value()?.into()],
+        )),
+        op @ ("gt" | "gte" | "lt" | "lte") => {
+            let operator = match op {
+                "gt" => ">",
+                "gte" => ">=",
+                "lt" => "<",
+                _ => "<=",
+            };
+            let value = require_value()?;
+            // 能解析成数字时按数值比较（跳过非数字取值），否则按文本比较，适用于 YYYY-MM-DD 日期。
+            match value
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+            {
+                Some(number) => Ok(exists(
+                    false,
+                    &format!(
+                        "(json_extract(field.value, '$.value') GLOB '[0-9]*' \
+                         OR json_extract(field.value, '$.value') GLOB '-[0-9]*') \
+                         AND CAST(json_extract(field.value, '$.value') AS REAL) {operator} ?"
+                    ),
+                    vec![number.into()],
+                )),
+                None => Ok(exists(
+                    false,
+                    &format!("json_extract(field.value, '$.value') {operator} ?"),
+                    vec![value.into()],
+                )),
+            }
+        }
+        other => Err(AppError::Validation(format!(
+            "不支持的 customFields 过滤操作：{other}"
+        ))),
+    }
+}
+
+fn parse_status(value: &str) -> Result<TaskStatus, AppError> {
+    validations::parse_status(value)
+}
+
+fn normalize_names(names: &[String]) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for name in names {
+        let trimmed = name.trim();
+        if !trimmed.is_empty() && !result.iter().any(|item| item == trimmed) {
+            result.push(trimmed.to_string());
+        }
+    }
+    result
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn priority_to_string(priority: &crate::db::entities::sea_orm_active_enums::Priority) -> String {
+    match priority {
+        crate::db::entities::sea_orm_active_enums::Priority::P0 => "P0".to_string(),
+        crate::db::entities::sea_orm_active_enums::Priority::P1 => "P1".to_string(),
+        crate::db::entities::sea_orm_active_enums::Priority::P2 => "P2".to_string(),
+        crate::db::entities::sea_orm_active_enums::Priority::P3 => "P3".to_string(),
+    }
+}
+
+/// 主表模型转 DTO，再批量回填关联数据。
+pub(super) async fn to_dtos(
+    conn: &DatabaseConnection,
+    models: Vec<tasks::Model>,
+) -> Result<Vec<TaskDto>, AppError> {
+    // 先把主表模型转成 DTO，后面再集中回填 tags / links。
     let mut dtos = Vec::with_capacity(models.len());
     for m in models {
         let custom_fields = custom_fields::parse_from_json_string(m.custom_fields.as_deref());
@@ -161,10 +835,7 @@ pub async fn list_deleted(
             project_id: m.project_id,
             title: m.title,
             note: m.note,
-            status: match m.status {
-                TaskStatus::Todo => "todo".to_string(),
-                TaskStatus::Done => "done".to_string(),
-            },
+            status: common_task_utils::status_to_string(&m.status),
             done_reason: m.done_reason.map(|r| match r {
                 crate::db::entities::sea_orm_active_enums::DoneReason::Completed => {
                     "completed".to_string()
@@ -173,33 +844,48 @@ pub async fn list_deleted(
                     "cancelled".to_string()
                 }
             }),
-            priority: match m.priority {
-                crate::db::entities::sea_orm_active_enums::Priority::P0 => "P0".to_string(),
-                crate::db::entities::sea_orm_active_enums::Priority::P1 => "P1".to_string(),
-                crate::db::entities::sea_orm_active_enums::Priority::P2 => "P2".to_string(),
-                crate::db::entities::sea_orm_active_enums::Priority::P3 => "P3".to_string(),
-            },
+            priority: priority_to_string(&m.priority),
             tags: Vec::new(),
             rank: m.rank,
             created_at: m.created_at,
             updated_at: m.updated_at,
             completed_at: m.completed_at,
             deadline_at: m.deadline_at,
+            scheduled_at: m.scheduled_at,
+            defer_until: m.defer_until,
             archived_at: m.archived_at,
             deleted_at: m.deleted_at,
             links: Vec::new(),
             custom_fields,
             create_by: m.create_by,
+            recurrence: recurrence::parse_from_rrule_string(m.recurrence_rule.as_deref()),
+            recurrence_series_id: m.recurrence_series_id,
+            milestone_id: m.milestone_id,
+            checklist_total: 0,
+            checklist_done: 0,
+            blocked_by: Vec::new(),
+            blocked: false,
+            tracked_ms: 0,
         });
     }
 
     if !dtos.is_empty() {
+        // 重点：先批量查 tags/links/清单进度/计时，再按 task_id 回填，避免 N+1。
         let task_ids = dtos.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
         let tag_map = tags::load_tags_for_tasks(conn, &task_ids).await?;
         let link_map = links::load_links_for_tasks(conn, &task_ids).await?;
+        let checklist_map = checklist::load_progress_for_tasks(conn, &task_ids).await?;
+        let blocked_by_map = dependencies::load_blocked_by_for_tasks(conn, &task_ids).await?;
+        let open_blocker_map = dependencies::load_open_blockers_for_tasks(conn, &task_ids).await?;
+        let tracked_map = time_entries::load_tracked_for_tasks(conn, &task_ids).await?;
         for task in &mut dtos {
             task.links = link_map.get(&task.id).cloned().unwrap_or_default();
             task.tags = tag_map.get(&task.id).cloned().unwrap_or_default();
+            (task.checklist_total, task.checklist_done) =
+                checklist_map.get(&task.id).copied().unwrap_or_default();
+            task.blocked_by = blocked_by_map.get(&task.id).cloned().unwrap_or_default();
+            task.blocked = open_blocker_map.contains_key(&task.id);
+            task.tracked_ms = tracked_map.get(&task.id).copied().unwrap_or_default();
         }
     }
 
@@ -7,19 +7,34 @@
 
 use sea_orm::{ConnectionTrait, DatabaseConnection};
 
-use crate::types::{dto::TaskDto, error::AppError};
+use crate::types::{
+    dto::{
+        ChecklistItemDto, CustomFieldAggregateDto, TaskDto, TaskPageDto, TaskReminderDto,
+        TaskTemplateDto, TimeEntryDto, TodayViewDto,
+    },
+    error::AppError,
+};
 
 pub struct TaskRepo;
 
 pub mod activity_logs;
+pub mod aggregate;
+pub mod archive;
+pub mod checklist;
 pub mod custom_fields;
 pub mod delete;
+pub mod dependencies;
 pub mod links;
 pub mod list;
 pub mod mutation;
 pub mod query;
+pub mod recurrence;
+pub mod reminders;
 pub mod stats;
 pub mod tags;
+pub mod templates;
+pub mod time_entries;
+pub mod today;
 pub mod validations;
 
 pub use list::list;
@@ -30,8 +45,32 @@ impl TaskRepo {
         space_id: Option<&str>,
         status: Option<&str>,
         project_id: Option<&str>,
+        exclude_blocked: bool,
     ) -> Result<Vec<TaskDto>, AppError> {
-        list(conn, space_id, status, project_id).await
+        list(conn, space_id, status, project_id, exclude_blocked).await
+    }
+
+    pub async fn query(
+        conn: &DatabaseConnection,
+        input: list::TaskListQuery,
+    ) -> Result<TaskPageDto, AppError> {
+        list::query(conn, input).await
+    }
+
+    pub async fn aggregate_by_custom_field(
+        conn: &DatabaseConnection,
+        input: list::TaskListQuery,
+        group_by: &str,
+        sum_field: Option<&str>,
+    ) -> Result<Vec<CustomFieldAggregateDto>, AppError> {
+        aggregate::aggregate_by_custom_field(conn, input, group_by, sum_field).await
+    }
+
+    pub async fn list_today(
+        conn: &DatabaseConnection,
+        utc_offset_minutes: Option<i32>,
+    ) -> Result<TodayViewDto, AppError> {
+        today::list_today(conn, crate::db::now_ms(), utc_offset_minutes).await
     }
 
     pub async fn list_deleted(
@@ -43,6 +82,60 @@ impl TaskRepo {
         list::list_deleted(conn, space_id, status, project_id).await
     }
 
+    pub async fn list_archived(
+        conn: &DatabaseConnection,
+        space_id: Option<&str>,
+        project_id: Option<&str>,
+    ) -> Result<Vec<TaskDto>, AppError> {
+        list::list_archived(conn, space_id, project_id).await
+    }
+
+    pub async fn list_checklist_items(
+        conn: &DatabaseConnection,
+        task_id: &str,
+    ) -> Result<Vec<ChecklistItemDto>, AppError> {
+        Ok(checklist::list_for_task(conn, task_id)
+            .await?
+            .into_iter()
+            .map(checklist::to_dto)
+            .collect())
+    }
+
+    pub async fn list_time_entries(
+        conn: &DatabaseConnection,
+        task_id: &str,
+    ) -> Result<Vec<TimeEntryDto>, AppError> {
+        let now = crate::db::now_ms();
+        Ok(time_entries::list_for_task(conn, task_id)
+            .await?
+            .into_iter()
+            .map(|model| time_entries::to_dto(model, now))
+            .collect())
+    }
+
+    pub async fn list_reminders(
+        conn: &DatabaseConnection,
+        task_id: &str,
+    ) -> Result<Vec<TaskReminderDto>, AppError> {
+        let task = query::find_by_id(conn, task_id).await?;
+        Ok(reminders::list_for_task(conn, task_id)
+            .await?
+            .into_iter()
+            .map(|model| reminders::to_dto(model, task.deadline_at))
+            .collect())
+    }
+
+    pub async fn list_templates(
+        conn: &DatabaseConnection,
+        space_id: Option<&str>,
+    ) -> Result<Vec<TaskTemplateDto>, AppError> {
+        Ok(templates::list(conn, space_id)
+            .await?
+            .into_iter()
+            .map(templates::to_dto)
+            .collect())
+    }
+
     pub async fn soft_delete_by_project_ids<C>(
         conn: &C,
         project_ids: &[String],
@@ -21,15 +21,21 @@ pub struct NewTaskRecord {
     pub status: TaskStatus,
     pub done_reason: Option<DoneReason>,
     pub priority: Priority,
-    pub rank: i64,
+    pub rank: String,
     pub created_at: i64,
     pub updated_at: i64,
     pub completed_at: Option<i64>,
     pub deadline_at: Option<i64>,
+    pub scheduled_at: Option<i64>,
+    pub defer_until: Option<i64>,
     pub archived_at: Option<i64>,
     pub deleted_at: Option<i64>,
     pub custom_fields: Option<String>,
     pub create_by: String,
+    pub recurrence_rule: Option<String>,
+    pub recurrence_series_id: Option<String>,
+    pub recurrence_index: Option<i64>,
+    pub milestone_id: Option<String>,
 }
 
 /// 插入一条新任务记录。
@@ -51,10 +57,16 @@ where
         updated_at: Set(record.updated_at),
         completed_at: Set(record.completed_at),
         deadline_at: Set(record.deadline_at),
+        scheduled_at: Set(record.scheduled_at),
+        defer_until: Set(record.defer_until),
         archived_at: Set(record.archived_at),
         deleted_at: Set(record.deleted_at),
         custom_fields: Set(record.custom_fields),
         create_by: Set(record.create_by),
+        recurrence_rule: Set(record.recurrence_rule),
+        recurrence_series_id: Set(record.recurrence_series_id),
+        recurrence_index: Set(record.recurrence_index),
+        milestone_id: Set(record.milestone_id),
     }
     .insert(conn)
     .await
@@ -69,6 +81,23 @@ where
     active_model.update(conn).await.map_err(AppError::from)
 }
 
+/// 只刷新任务的 `updated_at`。
+///
+/// 用于清单等子表变化时，让任务本身也体现为一次有效变更。
+pub async fn touch_updated_at<C>(conn: &C, id: &str, now: i64) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    tasks::Entity::update_many()
+        .col_expr(tasks::Column::UpdatedAt, Expr::value(now))
+        .filter(tasks::Column::Id.eq(id))
+        .exec(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
 /// 批量软删除任务。
 ///
 /// repo 层只负责字段更新，不负责日志和统计副作用。
@@ -88,6 +117,42 @@ where
     Ok(result.rows_affected as usize)
 }
 
+/// 批量归档任务，已删除或已归档的任务不受影响。
+pub async fn archive_many<C>(conn: &C, ids: &[String], now: i64) -> Result<usize, AppError>
+where
+    C: ConnectionTrait,
+{
+    let result = tasks::Entity::update_many()
+        .col_expr(tasks::Column::ArchivedAt, Expr::value(Some(now)))
+        .col_expr(tasks::Column::UpdatedAt, Expr::value(now))
+        .filter(tasks::Column::Id.is_in(ids.iter().cloned()))
+        .filter(tasks::Column::DeletedAt.is_null())
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .exec(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(result.rows_affected as usize)
+}
+
+/// 批量取消归档任务。
+pub async fn unarchive_many<C>(conn: &C, ids: &[String], now: i64) -> Result<usize, AppError>
+where
+    C: ConnectionTrait,
+{
+    let result = tasks::Entity::update_many()
+        .col_expr(tasks::Column::ArchivedAt, Expr::value(None::<i64>))
+        .col_expr(tasks::Column::UpdatedAt, Expr::value(now))
+        .filter(tasks::Column::Id.is_in(ids.iter().cloned()))
+        .filter(tasks::Column::DeletedAt.is_null())
+        .filter(tasks::Column::ArchivedAt.is_not_null())
+        .exec(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(result.rows_affected as usize)
+}
+
 /// 批量恢复已软删除任务。
 pub async fn restore_many<C>(conn: &C, ids: &[String], now: i64) -> Result<usize, AppError>
 where
@@ -13,6 +13,7 @@ use crate::db::entities::{
     sea_orm_active_enums::{LinkKind, Priority, TaskStatus},
     tags as tag_entities, task_links, task_tags, tasks,
 };
+use crate::repos::rank;
 use crate::types::error::AppError;
 
 pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<tasks::Model, AppError>
@@ -26,6 +27,17 @@ where
         .ok_or_else(|| AppError::Validation("任务不存在".to_string()))
 }
 
+pub async fn find_optional_by_id<C>(conn: &C, id: &str) -> Result<Option<tasks::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    // 用于批量重排等“找不到就跳过”的场景。
+    tasks::Entity::find_by_id(id)
+        .one(conn)
+        .await
+        .map_err(AppError::from)
+}
+
 pub async fn find_not_deleted_by_ids<C>(
     conn: &C,
     ids: &[String],
@@ -41,6 +53,41 @@ where
         .map_err(AppError::from)
 }
 
+/// 读取一组项目下未删除的任务，按排序键排列。
+pub async fn find_not_deleted_by_project_ids<C>(
+    conn: &C,
+    project_ids: &[String],
+) -> Result<Vec<tasks::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    tasks::Entity::find()
+        .filter(tasks::Column::ProjectId.is_in(project_ids.iter().cloned()))
+        .filter(tasks::Column::DeletedAt.is_null())
+        .order_by_asc(tasks::Column::Rank)
+        .order_by_asc(tasks::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 读取一组项目下在指定时刻被软删除的任务，即随项目删除一起删掉的那批。
+pub async fn find_deleted_by_project_ids_at<C>(
+    conn: &C,
+    project_ids: &[String],
+    deleted_at: i64,
+) -> Result<Vec<tasks::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    tasks::Entity::find()
+        .filter(tasks::Column::ProjectId.is_in(project_ids.iter().cloned()))
+        .filter(tasks::Column::DeletedAt.eq(deleted_at))
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
 pub async fn find_deleted_by_ids<C>(conn: &C, ids: &[String]) -> Result<Vec<tasks::Model>, AppError>
 where
     C: ConnectionTrait,
@@ -58,7 +105,7 @@ pub async fn next_rank_in_bucket<C>(
     space_id: &str,
     status: &TaskStatus,
     priority: &Priority,
-) -> Result<i64, AppError>
+) -> Result<String, AppError>
 where
     C: ConnectionTrait,
 {
@@ -72,7 +119,7 @@ where
         .await
         .map_err(AppError::from)?;
 
-    Ok(max_rank_task.map(|task| task.rank + 1024).unwrap_or(1024))
+    rank::after(max_rank_task.as_ref().map(|task| task.rank.as_str()))
 }
 
 pub async fn load_task_tags_for_log<C>(conn: &C, task_id: &str) -> Result<Vec<String>, AppError>
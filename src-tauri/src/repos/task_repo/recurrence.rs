@@ -0,0 +1,525 @@
+//! 任务重复规则的解析、规范化与下一次时间推算。
+//! 重点：数据库里保存 RRULE 文本，对外统一使用 `RecurrenceRuleDto`。
+
+use chrono::{
+    DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+
+use crate::types::{dto::RecurrenceRuleDto, error::AppError};
+
+const WEEKDAY_CODES: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+const UNTIL_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// 从数据库中的 RRULE 文本解析重复规则。
+///
+/// 解析失败时回退为 `None`，避免历史脏数据阻断列表读取。
+pub fn parse_from_rrule_string(raw: Option<&str>) -> Option<RecurrenceRuleDto> {
+    let raw = raw?.trim();
+    let raw = raw.strip_prefix("RRULE:").unwrap_or(raw);
+    let mut rule = RecurrenceRuleDto {
+        freq: String::new(),
+        interval: None,
+        by_weekday: Vec::new(),
+        until: None,
+        count: None,
+    };
+
+    for part in raw.split(';').filter(|part| !part.is_empty()) {
+        let (key, value) = part.split_once('=')?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => rule.freq = value.to_ascii_lowercase(),
+            "INTERVAL" => rule.interval = Some(value.parse().ok()?),
+            "BYDAY" => {
+                rule.by_weekday = value
+                    .split(',')
+                    .map(|code| code.trim().to_ascii_uppercase())
+                    .collect()
+            }
+            "UNTIL" => {
+                let until = NaiveDateTime::parse_from_str(value, UNTIL_FORMAT).ok()?;
+                rule.until = Some(until.and_utc().timestamp_millis());
+            }
+            "COUNT" => rule.count = Some(value.parse().ok()?),
+            _ => {}
+        }
+    }
+
+    normalize_rule(rule).ok()
+}
+
+/// 把重复规则序列化成 RRULE 文本。
+pub fn serialize_rule(rule: &RecurrenceRuleDto) -> String {
+    let mut parts = vec![
+        format!("FREQ={}", rule.freq.to_ascii_uppercase()),
+        format!("INTERVAL={}", rule.interval.unwrap_or(1)),
+    ];
+    if !rule.by_weekday.is_empty() {
+        parts.push(format!("BYDAY={}", rule.by_weekday.join(",")));
+    }
+    if let Some(until) = rule.until.and_then(DateTime::<Utc>::from_timestamp_millis) {
+        parts.push(format!("UNTIL={}", until.format(UNTIL_FORMAT)));
+    }
+    if let Some(count) = rule.count {
+        parts.push(format!("COUNT={count}"));
+    }
+    parts.join(";")
+}
+
+/// 规范化重复规则输入。
+///
+/// 这里会统一处理：
+/// - freq 小写且只允许 daily / weekly / monthly
+/// - interval 缺省为 1
+/// - byWeekday 大写、去重并按周一到周日排序
+/// - until 截断到秒，与 RRULE 文本精度一致
+pub fn normalize_rule(input: RecurrenceRuleDto) -> Result<RecurrenceRuleDto, AppError> {
+    let freq = input.freq.trim().to_ascii_lowercase();
+    if !matches!(freq.as_str(), "daily" | "weekly" | "monthly") {
+        return Err(AppError::Validation(
+            "recurrence.freq 仅支持 daily / weekly / monthly".to_string(),
+        ));
+    }
+
+    let interval = input.interval.unwrap_or(1);
+    if !(1..=366).contains(&interval) {
+        return Err(AppError::Validation(
+            "recurrence.interval 必须在 1 到 366 之间".to_string(),
+        ));
+    }
+
+    if input.by_weekday.iter().any(|value| {
+        !WEEKDAY_CODES
+            .iter()
+            .any(|(code, _)| value.trim().eq_ignore_ascii_case(code))
+    }) {
+        return Err(AppError::Validation(
+            "recurrence.byWeekday 不合法".to_string(),
+        ));
+    }
+    let by_weekday = WEEKDAY_CODES
+        .iter()
+        .filter(|(code, _)| {
+            input
+                .by_weekday
+                .iter()
+                .any(|value| value.trim().eq_ignore_ascii_case(code))
+        })
+        .map(|(code, _)| code.to_string())
+        .collect::<Vec<_>>();
+    if !by_weekday.is_empty() && freq != "weekly" {
+        return Err(AppError::Validation(
+            "recurrence.byWeekday 仅适用于 weekly".to_string(),
+        ));
+    }
+
+    if input.until.is_some() && input.count.is_some() {
+        return Err(AppError::Validation(
+            "recurrence.until 与 recurrence.count 不能同时设置".to_string(),
+        ));
+    }
+    if matches!(input.count, Some(count) if count < 1) {
+        return Err(AppError::Validation(
+            "recurrence.count 必须为正整数".to_string(),
+        ));
+    }
+
+    Ok(RecurrenceRuleDto {
+        freq,
+        interval: Some(interval),
+        by_weekday,
+        until: input.until.map(|until| until.div_euclid(1000) * 1000),
+        count: input.count,
+    })
+}
+
+/// 系列第 `next_index` 次是否已超出 `count` 限制。
+pub fn is_exhausted(rule: &RecurrenceRuleDto, next_index: i64) -> bool {
+    matches!(rule.count, Some(count) if next_index > count)
+}
+
+/// 推算某次任务之后的下一次发生时间。
+///
+/// `anchor_ms` 是本次任务的基准时间（通常为截止时间），
+/// 日期按本地时区推进，时刻保持不变。
+/// 超过 `until` 时返回 `None`；`count` 见 `is_exhausted`。
+pub fn next_occurrence(rule: &RecurrenceRuleDto, anchor_ms: i64) -> Option<i64> {
+    next_occurrence_in(rule, anchor_ms, &Local)
+}
+
+/// 按指定时区推算下一次发生时间，规则同 `next_occurrence`。
+pub fn next_occurrence_in<Tz: TimeZone>(
+    rule: &RecurrenceRuleDto,
+    anchor_ms: i64,
+    tz: &Tz,
+) -> Option<i64> {
+    let anchor = tz.timestamp_millis_opt(anchor_ms).earliest()?;
+    let interval = rule.interval.unwrap_or(1).max(1);
+    let date = anchor.date_naive();
+    let next_date = match rule.freq.as_str() {
+        "daily" => date.checked_add_days(Days::new(interval as u64))?,
+        "weekly" => next_weekly_date(date, interval, &rule.by_weekday)?,
+        "monthly" => date.checked_add_months(Months::new(interval as u32))?,
+        _ => return None,
+    };
+
+    let next_local = next_date.and_time(anchor.time());
+    // 落在夏令时跳变的空档里时，顺延一小时取一个真实存在的本地时间。
+    let next_ms = tz
+        .from_local_datetime(&next_local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(next_local + chrono::Duration::hours(1)))
+                .earliest()
+        })?
+        .timestamp_millis();
+
+    match rule.until {
+        Some(until) if next_ms > until => None,
+        _ => Some(next_ms),
+    }
+}
+
+/// weekly 规则：未指定星期时按整周推进；指定星期时找下一个命中的日期。
+///
+/// 间隔周数以本次日期所在周（周一开始）为第 0 周计算。
+fn next_weekly_date(date: NaiveDate, interval: i64, by_weekday: &[String]) -> Option<NaiveDate> {
+    let targets = by_weekday
+        .iter()
+        .filter_map(|code| {
+            WEEKDAY_CODES
+                .iter()
+                .find(|(value, _)| value == code)
+                .map(|(_, weekday)| *weekday)
+        })
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return date.checked_add_days(Days::new((interval * 7) as u64));
+    }
+
+    let week_start = date.week(Weekday::Mon).first_day();
+    for offset in 1..=(interval + 1) * 7 {
+        let candidate = date.checked_add_days(Days::new(offset as u64))?;
+        let weeks = (candidate.week(Weekday::Mon).first_day() - week_start).num_days() / 7;
+        if weeks % interval == 0 && targets.contains(&candidate.weekday()) {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, MappedLocalTime, NaiveDateTime};
+
+    use super::*;
+
+    fn rule(freq: &str, interval: i64, by_weekday: &[&str]) -> RecurrenceRuleDto {
+        RecurrenceRuleDto {
+            freq: freq.to_string(),
+            interval: Some(interval),
+            by_weekday: by_weekday.iter().map(|code| code.to_string()).collect(),
+            until: None,
+            count: None,
+        }
+    }
+
+    fn at<Tz: TimeZone>(tz: &Tz, date: (i32, u32, u32), time: (u32, u32)) -> i64 {
+        tz.with_ymd_and_hms(date.0, date.1, date.2, time.0, time.1, 0)
+            .earliest()
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn east8() -> FixedOffset {
+        FixedOffset::east_opt(8 * 3600).unwrap()
+    }
+
+    /// 按 2026 年美国东部规则切换夏令时的测试时区：
+    /// 3 月 8 日 02:00 跳到 03:00，11 月 1 日 02:00 回拨到 01:00。
+    #[derive(Clone, Copy, Debug)]
+    struct Eastern2026;
+
+    impl Eastern2026 {
+        const EST: i32 = -5 * 3600;
+        const EDT: i32 = -4 * 3600;
+
+        fn offset(seconds: i32) -> FixedOffset {
+            FixedOffset::east_opt(seconds).unwrap()
+        }
+    }
+
+    impl TimeZone for Eastern2026 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Eastern2026
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            // EDT 对应的 UTC 时刻更早，放在前面。
+            let matches = [Self::EDT, Self::EST]
+                .into_iter()
+                .map(Self::offset)
+                .filter(|offset| {
+                    let utc = *local - chrono::Duration::seconds(offset.local_minus_utc() as i64);
+                    self.offset_from_utc_datetime(&utc) == *offset
+                })
+                .collect::<Vec<_>>();
+            match matches.as_slice() {
+                [] => MappedLocalTime::None,
+                [offset] => MappedLocalTime::Single(*offset),
+                [earliest, latest, ..] => MappedLocalTime::Ambiguous(*earliest, *latest),
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            let start = NaiveDate::from_ymd_opt(2026, 3, 8)
+                .unwrap()
+                .and_hms_opt(7, 0, 0)
+                .unwrap();
+            let end = NaiveDate::from_ymd_opt(2026, 11, 1)
+                .unwrap()
+                .and_hms_opt(6, 0, 0)
+                .unwrap();
+            if (start..end).contains(utc) {
+                Self::offset(Self::EDT)
+            } else {
+                Self::offset(Self::EST)
+            }
+        }
+    }
+
+    #[test]
+    fn parses_and_serializes_rrule() {
+        let parsed =
+            parse_from_rrule_string(Some("RRULE:FREQ=weekly;INTERVAL=2;BYDAY=fr,MO;COUNT=3"))
+                .unwrap();
+        assert_eq!(
+            parsed,
+            RecurrenceRuleDto {
+                count: Some(3),
+                ..rule("weekly", 2, &["MO", "FR"])
+            }
+        );
+        assert_eq!(
+            serialize_rule(&parsed),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;COUNT=3"
+        );
+
+        let until = at(&Utc, (2026, 12, 31), (23, 59));
+        let text = serialize_rule(&RecurrenceRuleDto {
+            until: Some(until),
+            ..rule("daily", 1, &[])
+        });
+        assert_eq!(text, "FREQ=DAILY;INTERVAL=1;UNTIL=20261231T235900Z");
+        assert_eq!(
+            parse_from_rrule_string(Some(&text)).unwrap().until,
+            Some(until)
+        );
+
+        // 缺省 interval 按 1 处理
+        assert_eq!(
+            parse_from_rrule_string(Some("FREQ=MONTHLY")),
+            Some(rule("monthly", 1, &[]))
+        );
+        for raw in [
+            "FREQ=HOURLY",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=DAILY;INTERVAL=abc",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20261231T235900Z",
+            "FREQ",
+        ] {
+            assert_eq!(parse_from_rrule_string(Some(raw)), None, "{raw}");
+        }
+        assert_eq!(parse_from_rrule_string(None), None);
+    }
+
+    #[test]
+    fn normalizes_rule_input() {
+        let normalized = normalize_rule(RecurrenceRuleDto {
+            freq: " Weekly ".to_string(),
+            interval: None,
+            by_weekday: vec!["su".into(), "MO".into(), "mo".into()],
+            until: Some(1_700_000_000_123),
+            count: None,
+        })
+        .unwrap();
+        assert_eq!(normalized.freq, "weekly");
+        assert_eq!(normalized.interval, Some(1));
+        assert_eq!(normalized.by_weekday, vec!["MO", "SU"]);
+        assert_eq!(normalized.until, Some(1_700_000_000_000));
+
+        for invalid in [
+            rule("yearly", 1, &[]),
+            rule("daily", 0, &[]),
+            rule("daily", 367, &[]),
+            rule("monthly", 1, &["MO"]),
+            RecurrenceRuleDto {
+                count: Some(0),
+                ..rule("daily", 1, &[])
+            },
+        ] {
+            assert!(normalize_rule(invalid.clone()).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn monthly_clamps_to_month_end() {
+        let tz = east8();
+        let monthly = rule("monthly", 1, &[]);
+        let cases = [
+            ((2026, 1, 31), (2026, 2, 28)),
+            ((2028, 1, 31), (2028, 2, 29)),
+            ((2026, 3, 31), (2026, 4, 30)),
+            ((2026, 8, 31), (2026, 9, 30)),
+            ((2026, 12, 31), (2027, 1, 31)),
+        ];
+        for (from, to) in cases {
+            assert_eq!(
+                next_occurrence_in(&monthly, at(&tz, from, (9, 30)), &tz),
+                Some(at(&tz, to, (9, 30))),
+                "{from:?}"
+            );
+        }
+        // interval > 1 同样按月末截断
+        assert_eq!(
+            next_occurrence_in(
+                &rule("monthly", 3, &[]),
+                at(&tz, (2026, 11, 30), (9, 30)),
+                &tz
+            ),
+            Some(at(&tz, (2027, 2, 28), (9, 30)))
+        );
+    }
+
+    #[test]
+    fn steps_daily_and_weekly_intervals() {
+        let tz = east8();
+        // 2026-10-14 是周三
+        let wednesday = at(&tz, (2026, 10, 14), (18, 0));
+        let next = |rule: &RecurrenceRuleDto, anchor: i64| next_occurrence_in(rule, anchor, &tz);
+
+        assert_eq!(
+            next(&rule("daily", 3, &[]), wednesday),
+            Some(at(&tz, (2026, 10, 17), (18, 0)))
+        );
+        assert_eq!(
+            next(&rule("weekly", 1, &[]), wednesday),
+            Some(at(&tz, (2026, 10, 21), (18, 0)))
+        );
+        assert_eq!(
+            next(&rule("weekly", 3, &[]), wednesday),
+            Some(at(&tz, (2026, 11, 4), (18, 0)))
+        );
+
+        // 同一周内还有命中的星期
+        let mon_fri = rule("weekly", 1, &["MO", "FR"]);
+        assert_eq!(
+            next(&mon_fri, wednesday),
+            Some(at(&tz, (2026, 10, 16), (18, 0)))
+        );
+        // 星期集合用完后绕到下一周
+        let friday = at(&tz, (2026, 10, 16), (18, 0));
+        assert_eq!(
+            next(&mon_fri, friday),
+            Some(at(&tz, (2026, 10, 19), (18, 0)))
+        );
+        let sunday = at(&tz, (2026, 10, 18), (18, 0));
+        assert_eq!(
+            next(&rule("weekly", 1, &["SU"]), sunday),
+            Some(at(&tz, (2026, 10, 25), (18, 0)))
+        );
+
+        // 隔周：本周剩余星期先用完，再跳过一整周
+        let biweekly = rule("weekly", 2, &["MO", "FR"]);
+        assert_eq!(
+            next(&biweekly, wednesday),
+            Some(at(&tz, (2026, 10, 16), (18, 0)))
+        );
+        assert_eq!(
+            next(&biweekly, friday),
+            Some(at(&tz, (2026, 10, 26), (18, 0)))
+        );
+    }
+
+    #[test]
+    fn stops_at_until_and_count() {
+        let tz = east8();
+        let anchor = at(&tz, (2026, 10, 14), (9, 0));
+        let next_day = at(&tz, (2026, 10, 15), (9, 0));
+        let until = |until: i64| RecurrenceRuleDto {
+            until: Some(until),
+            ..rule("daily", 1, &[])
+        };
+
+        assert_eq!(
+            next_occurrence_in(&until(next_day), anchor, &tz),
+            Some(next_day)
+        );
+        assert_eq!(next_occurrence_in(&until(next_day - 1), anchor, &tz), None);
+
+        let counted = RecurrenceRuleDto {
+            count: Some(3),
+            ..rule("daily", 1, &[])
+        };
+        assert!(!is_exhausted(&counted, 2));
+        assert!(!is_exhausted(&counted, 3));
+        assert!(is_exhausted(&counted, 4));
+        assert!(!is_exhausted(&rule("daily", 1, &[]), 1000));
+    }
+
+    #[test]
+    fn keeps_local_time_across_dst() {
+        let tz = Eastern2026;
+        let edt = Eastern2026::offset(Eastern2026::EDT);
+        let est = Eastern2026::offset(Eastern2026::EST);
+        let daily = rule("daily", 1, &[]);
+
+        // 跨过春季跳变：本地时刻不变，实际间隔只有 23 小时
+        let anchor = at(&est, (2026, 3, 7), (9, 0));
+        let next = next_occurrence_in(&daily, anchor, &tz).unwrap();
+        assert_eq!(next, at(&edt, (2026, 3, 8), (9, 0)));
+        assert_eq!(next - anchor, 23 * 3_600_000);
+
+        // 落在跳变空档里的本地时间顺延一小时
+        let anchor = at(&est, (2026, 3, 7), (2, 30));
+        assert_eq!(
+            next_occurrence_in(&daily, anchor, &tz),
+            Some(at(&edt, (2026, 3, 8), (3, 30)))
+        );
+
+        // 秋季回拨出现两次的本地时间取较早的一次
+        let anchor = at(&edt, (2026, 10, 31), (1, 30));
+        assert_eq!(
+            next_occurrence_in(&daily, anchor, &tz),
+            Some(at(&edt, (2026, 11, 1), (1, 30)))
+        );
+
+        // 跨过秋季回拨：每周同一本地时刻，实际间隔多一小时
+        let anchor = at(&edt, (2026, 10, 28), (9, 0));
+        let next = next_occurrence_in(&rule("weekly", 1, &[]), anchor, &tz).unwrap();
+        assert_eq!(next, at(&est, (2026, 11, 4), (9, 0)));
+        assert_eq!(next - anchor, (7 * 24 + 1) * 3_600_000);
+    }
+}
@@ -0,0 +1,182 @@
+//! 任务提醒持久化原语。
+//!
+//! 重点：
+//! - 提醒时刻 = 截止时间 - 提前分钟数，截止时间变化后自动重新生效
+//! - 触发/稍后/忽略状态都绑定在 `anchor_deadline_at` 上，锚点与当前截止时间不一致即视为全新提醒
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+use crate::db::entities::{sea_orm_active_enums::TaskStatus, task_reminders, tasks};
+use crate::types::{dto::TaskReminderDto, error::AppError};
+
+/// 提前提醒的上限：30 天。
+pub const MAX_OFFSET_MINUTES: i64 = 30 * 24 * 60;
+
+pub struct NewReminderRecord {
+    pub id: String,
+    pub task_id: String,
+    pub offset_minutes: i64,
+    pub created_at: i64,
+}
+
+/// 默认提醒落库时使用的 id；由任务 id 决定，保证各端一致。
+pub fn default_reminder_id(task_id: &str) -> String {
+    format!("{task_id}:default")
+}
+
+/// 提醒在给定截止时间下的计划触发时刻。
+pub fn scheduled_at(deadline_at: i64, offset_minutes: i64) -> i64 {
+    deadline_at - offset_minutes * 60_000
+}
+
+/// 提醒在当前截止时间下的下一次触发时刻；已触发且未稍后、或已忽略时返回 `None`。
+pub fn next_fire_at(model: &task_reminders::Model, deadline_at: i64) -> Option<i64> {
+    if model.anchor_deadline_at != Some(deadline_at) {
+        return Some(scheduled_at(deadline_at, model.offset_minutes));
+    }
+    if model.dismissed_at.is_some() {
+        return None;
+    }
+    match (model.snoozed_until, model.last_fired_at) {
+        (Some(snoozed_until), Some(last_fired_at)) if last_fired_at >= snoozed_until => None,
+        (Some(snoozed_until), _) => Some(snoozed_until),
+        (None, Some(_)) => None,
+        (None, None) => Some(scheduled_at(deadline_at, model.offset_minutes)),
+    }
+}
+
+/// 把提醒转换成前端 DTO；任务没有截止时间时计划时刻为空。
+pub fn to_dto(model: task_reminders::Model, deadline_at: Option<i64>) -> TaskReminderDto {
+    TaskReminderDto {
+        fire_at: deadline_at.map(|deadline_at| scheduled_at(deadline_at, model.offset_minutes)),
+        next_fire_at: deadline_at.and_then(|deadline_at| next_fire_at(&model, deadline_at)),
+        id: model.id,
+        task_id: model.task_id,
+        offset_minutes: model.offset_minutes,
+        last_fired_at: model.last_fired_at,
+        snoozed_until: model.snoozed_until,
+        dismissed_at: model.dismissed_at,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 按 id 读取未删除的提醒。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<task_reminders::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_reminders::Entity::find_by_id(id)
+        .filter(task_reminders::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("提醒不存在".to_string()))
+}
+
+/// 按提前分钟数从大到小列出某个任务的未删除提醒（即按触发先后）。
+pub async fn list_for_task<C>(
+    conn: &C,
+    task_id: &str,
+) -> Result<Vec<task_reminders::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_reminders::Entity::find()
+        .filter(task_reminders::Column::TaskId.eq(task_id))
+        .filter(task_reminders::Column::DeletedAt.is_null())
+        .order_by_desc(task_reminders::Column::OffsetMinutes)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 列出截止时间不晚于 `deadline_before` 的未完成任务，作为调度候选。
+///
+/// 已归档、已删除、已完成的任务不再提醒。
+pub async fn list_candidate_tasks<C>(
+    conn: &C,
+    deadline_before: i64,
+) -> Result<Vec<tasks::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    tasks::Entity::find()
+        .filter(tasks::Column::DeadlineAt.is_not_null())
+        .filter(tasks::Column::DeadlineAt.lte(deadline_before))
+        .filter(tasks::Column::Status.ne(TaskStatus::Done))
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .order_by_asc(tasks::Column::DeadlineAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 批量读取任务的全部提醒记录（含已删除的 tombstone），返回 `task_id -> 提醒列表`。
+///
+/// 调度器靠 tombstone 区分“从未配置过提醒”和“提醒已被清空”。
+pub async fn load_all_for_tasks<C>(
+    conn: &C,
+    task_ids: &[String],
+) -> Result<HashMap<String, Vec<task_reminders::Model>>, AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = task_reminders::Entity::find()
+        .filter(task_reminders::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .order_by_desc(task_reminders::Column::OffsetMinutes)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut grouped: HashMap<String, Vec<task_reminders::Model>> = HashMap::new();
+    for row in rows {
+        grouped.entry(row.task_id.clone()).or_default().push(row);
+    }
+    Ok(grouped)
+}
+
+/// 插入一条新提醒。
+pub async fn insert<C>(
+    conn: &C,
+    record: NewReminderRecord,
+) -> Result<task_reminders::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_reminders::ActiveModel {
+        id: Set(record.id),
+        task_id: Set(record.task_id),
+        offset_minutes: Set(record.offset_minutes),
+        anchor_deadline_at: Set(None),
+        last_fired_at: Set(None),
+        snoozed_until: Set(None),
+        dismissed_at: Set(None),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.created_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+/// 更新提醒。
+pub async fn update<C>(
+    conn: &C,
+    active_model: task_reminders::ActiveModel,
+) -> Result<task_reminders::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
@@ -1,47 +1,236 @@
 //! Task -> Project 统计回写。
 //! 重点：任何影响任务状态/归属的写操作后，都应调用这里保持项目计数一致。
+//! 项目下里程碑的计数、自身及祖先项目的子树汇总随项目统计一起刷新，调用方不需要单独处理。
+
+use std::collections::HashSet;
 
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter,
+    QuerySelect, Set,
 };
 
-use crate::db::entities::{projects, sea_orm_active_enums::TaskStatus, tasks};
+use crate::db::entities::{project_milestones, projects, sea_orm_active_enums::TaskStatus, tasks};
 use crate::types::error::AppError;
 
+use super::time_entries;
+
 pub async fn refresh_project_stats<C>(conn: &C, project_id: &str, now: i64) -> Result<(), AppError>
 where
     C: ConnectionTrait,
 {
-    // todo / done 分开统计，供项目列表与详情直接展示。
-    // 分别统计 todo/done，且忽略 archived/deleted。
-    let todo_task_count = tasks::Entity::find()
+    // 每个状态分开统计，供项目列表与详情直接展示。
+    // 一次按状态分组计数，且忽略 archived/deleted。
+    let rows: Vec<(TaskStatus, i64)> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Status)
+        .column_as(tasks::Column::Id.count(), "count")
         .filter(tasks::Column::ProjectId.eq(project_id))
-        .filter(tasks::Column::Status.eq(TaskStatus::Todo))
         .filter(tasks::Column::ArchivedAt.is_null())
         .filter(tasks::Column::DeletedAt.is_null())
-        .count(conn)
+        .group_by(tasks::Column::Status)
+        .into_tuple()
+        .all(conn)
         .await
         .map_err(AppError::from)?;
 
-    let done_task_count = tasks::Entity::find()
-        .filter(tasks::Column::ProjectId.eq(project_id))
-        .filter(tasks::Column::Status.eq(TaskStatus::Done))
-        .filter(tasks::Column::ArchivedAt.is_null())
-        .filter(tasks::Column::DeletedAt.is_null())
-        .count(conn)
-        .await
-        .map_err(AppError::from)?;
+    let count_of = |status: TaskStatus| {
+        rows.iter()
+            .find(|(row_status, _)| *row_status == status)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    };
 
     // 重点：只更新统计相关列，其他列走 Default 不变。
     let project = projects::ActiveModel {
         id: Set(project_id.to_string()),
-        todo_task_count: Set(todo_task_count as i64),
-        done_task_count: Set(done_task_count as i64),
+        todo_task_count: Set(count_of(TaskStatus::Todo)),
+        doing_task_count: Set(count_of(TaskStatus::Doing)),
+        waiting_task_count: Set(count_of(TaskStatus::Waiting)),
+        someday_task_count: Set(count_of(TaskStatus::Someday)),
+        done_task_count: Set(count_of(TaskStatus::Done)),
         last_task_updated_at: Set(Some(now)),
         ..Default::default()
     };
 
     project.update(conn).await.map_err(AppError::from)?;
+    refresh_milestone_stats(conn, project_id).await?;
+    refresh_subtree_rollups(conn, project_id).await?;
+
+    Ok(())
+}
+
+/// 沿父级链逐个回写子树汇总：项目自身、父项目、祖父项目……直到根。
+///
+/// 项目挂载关系变化、计时结束等不经过 `refresh_project_stats` 的写操作直接调用这里；
+/// 汇总只在本地维护，不参与同步，也不刷新 `updated_at`。
+pub async fn refresh_subtree_rollups<C>(conn: &C, project_id: &str) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut next = Some(project_id.to_string());
+    let mut visited = HashSet::new();
+    while let Some(current_id) = next.take() {
+        // 防御脏数据里的环。
+        if !visited.insert(current_id.clone()) {
+            break;
+        }
+        let Some(project) = projects::Entity::find_by_id(current_id)
+            .one(conn)
+            .await
+            .map_err(AppError::from)?
+        else {
+            break;
+        };
+        refresh_rollup(conn, &project).await?;
+        next = project.parent_id;
+    }
+    Ok(())
+}
+
+/// 重算全部项目的子树汇总；pull 直接写表后调用。
+pub async fn refresh_all_subtree_rollups<C>(conn: &C) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let projects = projects::Entity::find()
+        .filter(projects::Column::DeletedAt.is_null())
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for project in &projects {
+        refresh_rollup(conn, project).await?;
+    }
+    Ok(())
+}
+
+/// 按 `path` 前缀圈出子树，统计未归档、未删除的任务。
+async fn refresh_rollup<C>(conn: &C, project: &projects::Model) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let subtree_ids: Vec<String> = projects::Entity::find()
+        .select_only()
+        .column(projects::Column::Id)
+        .filter(projects::Column::SpaceId.eq(project.space_id.as_str()))
+        .filter(
+            Condition::any()
+                .add(projects::Column::Id.eq(project.id.as_str()))
+                .add(
+                    Condition::all()
+                        .add(projects::Column::Path.starts_with(format!("{}/", project.path)))
+                        .add(projects::Column::DeletedAt.is_null()),
+                ),
+        )
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let rows: Vec<(TaskStatus, i64, Option<i64>)> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Status)
+        .column_as(tasks::Column::Id.count(), "count")
+        .column_as(tasks::Column::UpdatedAt.max(), "last_activity_at")
+        .filter(tasks::Column::ProjectId.is_in(subtree_ids.iter().cloned()))
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .group_by(tasks::Column::Status)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    let mut task_count = 0;
+    let mut done_task_count = 0;
+    let mut last_activity_at = None;
+    for (status, count, updated_at) in rows {
+        task_count += count;
+        if status == TaskStatus::Done {
+            done_task_count += count;
+        }
+        last_activity_at = last_activity_at.max(updated_at);
+    }
+    let tracked_ms = time_entries::sum_tracked_for_projects(conn, &subtree_ids).await?;
+
+    if project.subtree_task_count == task_count
+        && project.subtree_done_task_count == done_task_count
+        && project.subtree_last_activity_at == last_activity_at
+        && project.subtree_tracked_ms == tracked_ms
+    {
+        return Ok(());
+    }
+    projects::ActiveModel {
+        id: Set(project.id.clone()),
+        subtree_task_count: Set(task_count),
+        subtree_done_task_count: Set(done_task_count),
+        subtree_last_activity_at: Set(last_activity_at),
+        subtree_tracked_ms: Set(tracked_ms),
+        ..Default::default()
+    }
+    .update(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
+
+/// 回写项目下各里程碑的任务数与完成数，口径与项目统计一致。
+///
+/// 计数只在本地维护，不刷新 `updated_at`，避免每次任务变化都触发里程碑同步。
+async fn refresh_milestone_stats<C>(conn: &C, project_id: &str) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let milestones = project_milestones::Entity::find()
+        .filter(project_milestones::Column::ProjectId.eq(project_id))
+        .filter(project_milestones::Column::DeletedAt.is_null())
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    if milestones.is_empty() {
+        return Ok(());
+    }
+
+    let rows: Vec<(String, TaskStatus, i64)> = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::MilestoneId)
+        .column(tasks::Column::Status)
+        .column_as(tasks::Column::Id.count(), "count")
+        .filter(tasks::Column::ProjectId.eq(project_id))
+        .filter(tasks::Column::MilestoneId.is_not_null())
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::DeletedAt.is_null())
+        .group_by(tasks::Column::MilestoneId)
+        .group_by(tasks::Column::Status)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    for milestone in milestones {
+        let (task_count, done_task_count) = rows
+            .iter()
+            .filter(|(milestone_id, _, _)| *milestone_id == milestone.id)
+            .fold((0, 0), |(total, done), (_, status, count)| {
+                let done_count = if *status == TaskStatus::Done {
+                    *count
+                } else {
+                    0
+                };
+                (total + count, done + done_count)
+            });
+        if milestone.task_count == task_count && milestone.done_task_count == done_task_count {
+            continue;
+        }
+        project_milestones::ActiveModel {
+            id: Set(milestone.id),
+            task_count: Set(task_count),
+            done_task_count: Set(done_task_count),
+            ..Default::default()
+        }
+        .update(conn)
+        .await
+        .map_err(AppError::from)?;
+    }
 
     Ok(())
 }
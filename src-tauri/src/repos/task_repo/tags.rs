@@ -3,15 +3,18 @@
 
 use std::collections::HashMap;
 
-use sea_orm::{ConnectionTrait, DatabaseConnection};
+use sea_orm::ConnectionTrait;
 
 use crate::repos::tag_repo::{self, TagEntity};
 use crate::types::error::AppError;
 
-pub async fn load_tags_for_tasks(
-    conn: &DatabaseConnection,
+pub async fn load_tags_for_tasks<C>(
+    conn: &C,
     task_ids: &[String],
-) -> Result<HashMap<String, Vec<String>>, AppError> {
+) -> Result<HashMap<String, Vec<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
     // 任务维度的标签读取只是对通用 tag_repo 的轻量适配。
     tag_repo::load_tags(conn, TagEntity::Task, task_ids).await
 }
@@ -0,0 +1,139 @@
+//! 任务模板持久化原语与占位符展开。
+//!
+//! 重点：
+//! - 模板内容整体以 JSON 存在 `body` 列，读写都经过 `TaskTemplateBodyDto`
+//! - 占位符写作 `{{name}}`，名称两侧允许空白；没有取值的占位符原样保留
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+use crate::db::entities::task_templates;
+use crate::types::{
+    dto::{TaskTemplateBodyDto, TaskTemplateDto},
+    error::AppError,
+};
+
+pub struct NewTemplateRecord {
+    pub id: String,
+    pub name: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub body: String,
+    pub created_at: i64,
+}
+
+/// 把模板内容序列化成数据库中的 JSON 字符串。
+pub fn serialize_body(body: &TaskTemplateBodyDto) -> Result<String, AppError> {
+    serde_json::to_string(body)
+        .map_err(|e| AppError::Validation(format!("模板内容序列化失败: {e}")))
+}
+
+/// 把模板转换成前端 DTO。
+pub fn to_dto(model: task_templates::Model) -> TaskTemplateDto {
+    // 反序列化失败时回退为空模板，避免单条脏数据阻断整个列表。
+    let body = serde_json::from_str::<TaskTemplateBodyDto>(&model.body).unwrap_or_default();
+    TaskTemplateDto {
+        id: model.id,
+        name: model.name,
+        space_id: model.space_id,
+        project_id: model.project_id,
+        body,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 展开文本中的 `{{name}}` 占位符。
+pub fn expand_placeholders(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        result.push_str(&rest[..start]);
+        match variables.get(name) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..start + len + 4]),
+        }
+        rest = &rest[start + len + 4..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 按 id 读取未删除的模板。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<task_templates::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_templates::Entity::find_by_id(id)
+        .filter(task_templates::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("任务模板不存在".to_string()))
+}
+
+/// 按名称列出未删除的模板；传入 space 时只保留该 space 与未绑定 space 的模板。
+pub async fn list<C>(
+    conn: &C,
+    space_id: Option<&str>,
+) -> Result<Vec<task_templates::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut query =
+        task_templates::Entity::find().filter(task_templates::Column::DeletedAt.is_null());
+    if let Some(space_id) = space_id {
+        query = query.filter(
+            task_templates::Column::SpaceId
+                .eq(space_id)
+                .or(task_templates::Column::SpaceId.is_null()),
+        );
+    }
+    query
+        .order_by_asc(task_templates::Column::Name)
+        .order_by_asc(task_templates::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 插入一条新模板。
+pub async fn insert<C>(
+    conn: &C,
+    record: NewTemplateRecord,
+) -> Result<task_templates::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_templates::ActiveModel {
+        id: Set(record.id),
+        name: Set(record.name),
+        space_id: Set(record.space_id),
+        project_id: Set(record.project_id),
+        body: Set(record.body),
+        created_at: Set(record.created_at),
+        updated_at: Set(record.created_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+/// 更新模板。
+pub async fn update<C>(
+    conn: &C,
+    active_model: task_templates::ActiveModel,
+) -> Result<task_templates::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
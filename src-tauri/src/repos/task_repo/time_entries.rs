@@ -0,0 +1,248 @@
+//! 任务计时记录持久化原语。
+//!
+//! 重点：
+//! - 结束时间为空的记录即“进行中的计时器”
+//! - 暂停通过 `paused_at` + 累计 `paused_ms` 表达，有效时长始终扣除暂停
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, JoinType,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
+};
+
+use crate::db::entities::{task_time_entries, tasks};
+use crate::types::{dto::TimeEntryDto, error::AppError};
+
+pub const MODE_MANUAL: &str = "manual";
+pub const MODE_POMODORO: &str = "pomodoro";
+
+pub struct NewTimeEntryRecord {
+    pub id: String,
+    pub task_id: String,
+    pub mode: String,
+    pub planned_ms: Option<i64>,
+    pub note: Option<String>,
+    pub started_at: i64,
+}
+
+/// 计算记录在 `now` 时刻的有效时长。
+pub fn elapsed_ms(model: &task_time_entries::Model, now: i64) -> i64 {
+    if let Some(duration_ms) = model.duration_ms {
+        return duration_ms;
+    }
+    let effective_now = model.paused_at.unwrap_or(now);
+    (effective_now - model.started_at - model.paused_ms).max(0)
+}
+
+/// 番茄钟到点时刻；未到点、暂停中或非番茄钟返回 `None`。
+///
+/// 到点时刻按“开始时间 + 累计暂停 + 固定时长”推算，与何时被发现无关。
+pub fn pomodoro_due_at(model: &task_time_entries::Model, now: i64) -> Option<i64> {
+    let planned_ms = model.planned_ms?;
+    if model.ended_at.is_some() || elapsed_ms(model, now) < planned_ms {
+        return None;
+    }
+    Some(model.started_at + model.paused_ms + planned_ms)
+}
+
+/// 把进行中的记录收口为已结束状态；暂停中结束时，暂停区间同样不计入时长。
+pub fn finish(
+    model: task_time_entries::Model,
+    ended_at: i64,
+    now: i64,
+) -> task_time_entries::ActiveModel {
+    let paused_ms = match model.paused_at {
+        Some(paused_at) => model.paused_ms + (ended_at - paused_at).max(0),
+        None => model.paused_ms,
+    };
+    let mut duration_ms = (ended_at - model.started_at - paused_ms).max(0);
+    if let Some(planned_ms) = model.planned_ms {
+        duration_ms = duration_ms.min(planned_ms);
+    }
+
+    let mut active_model: task_time_entries::ActiveModel = model.into();
+    active_model.ended_at = Set(Some(ended_at));
+    active_model.paused_at = Set(None);
+    active_model.paused_ms = Set(paused_ms);
+    active_model.duration_ms = Set(Some(duration_ms));
+    active_model.updated_at = Set(now);
+    active_model
+}
+
+/// 把计时记录转换成前端 DTO，进行中的记录按 `now` 计算时长。
+pub fn to_dto(model: task_time_entries::Model, now: i64) -> TimeEntryDto {
+    let elapsed = elapsed_ms(&model, now);
+    TimeEntryDto {
+        remaining_ms: model
+            .planned_ms
+            .map(|planned_ms| (planned_ms - elapsed).max(0)),
+        running: model.ended_at.is_none() && model.paused_at.is_none(),
+        elapsed_ms: elapsed,
+        id: model.id,
+        task_id: model.task_id,
+        mode: model.mode,
+        started_at: model.started_at,
+        ended_at: model.ended_at,
+        paused_at: model.paused_at,
+        paused_ms: model.paused_ms,
+        planned_ms: model.planned_ms,
+        note: model.note,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 按 id 读取未删除的计时记录。
+pub async fn find_by_id<C>(conn: &C, id: &str) -> Result<task_time_entries::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_time_entries::Entity::find_by_id(id)
+        .filter(task_time_entries::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::Validation("计时记录不存在".to_string()))
+}
+
+/// 列出所有进行中（含暂停）的计时记录，最新的在前。
+///
+/// 正常情况下最多一条；多端同步后可能出现多条，由 service 收口。
+pub async fn list_active<C>(conn: &C) -> Result<Vec<task_time_entries::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_time_entries::Entity::find()
+        .filter(task_time_entries::Column::EndedAt.is_null())
+        .filter(task_time_entries::Column::DeletedAt.is_null())
+        .order_by_desc(task_time_entries::Column::StartedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 按开始时间倒序列出某个任务的未删除计时记录。
+pub async fn list_for_task<C>(
+    conn: &C,
+    task_id: &str,
+) -> Result<Vec<task_time_entries::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_time_entries::Entity::find()
+        .filter(task_time_entries::Column::TaskId.eq(task_id))
+        .filter(task_time_entries::Column::DeletedAt.is_null())
+        .order_by_desc(task_time_entries::Column::StartedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 批量统计任务已结束计时的累计时长，返回 `task_id -> 毫秒`。
+pub async fn load_tracked_for_tasks<C>(
+    conn: &C,
+    task_ids: &[String],
+) -> Result<HashMap<String, i64>, AppError>
+where
+    C: ConnectionTrait,
+{
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(String, Option<i64>)> = task_time_entries::Entity::find()
+        .select_only()
+        .column(task_time_entries::Column::TaskId)
+        .column_as(
+            Expr::col(task_time_entries::Column::DurationMs).sum(),
+            "tracked_ms",
+        )
+        .filter(task_time_entries::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .filter(task_time_entries::Column::DeletedAt.is_null())
+        .filter(task_time_entries::Column::EndedAt.is_not_null())
+        .group_by(task_time_entries::Column::TaskId)
+        .into_tuple()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(task_id, tracked_ms)| (task_id, tracked_ms.unwrap_or(0)))
+        .collect())
+}
+
+/// 汇总一组项目下未删除任务的已结束计时时长。
+pub async fn sum_tracked_for_projects<C>(conn: &C, project_ids: &[String]) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    if project_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let total: Option<Option<i64>> = task_time_entries::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::col((
+                task_time_entries::Entity,
+                task_time_entries::Column::DurationMs,
+            ))
+            .sum(),
+            "tracked_ms",
+        )
+        .join(
+            JoinType::InnerJoin,
+            task_time_entries::Relation::Tasks.def(),
+        )
+        .filter(tasks::Column::ProjectId.is_in(project_ids.iter().cloned()))
+        .filter(tasks::Column::DeletedAt.is_null())
+        .filter(task_time_entries::Column::DeletedAt.is_null())
+        .filter(task_time_entries::Column::EndedAt.is_not_null())
+        .into_tuple()
+        .one(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(total.flatten().unwrap_or(0))
+}
+
+/// 插入一条新计时记录。
+pub async fn insert<C>(
+    conn: &C,
+    record: NewTimeEntryRecord,
+) -> Result<task_time_entries::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_time_entries::ActiveModel {
+        id: Set(record.id),
+        task_id: Set(record.task_id),
+        mode: Set(record.mode),
+        started_at: Set(record.started_at),
+        ended_at: Set(None),
+        paused_at: Set(None),
+        paused_ms: Set(0),
+        planned_ms: Set(record.planned_ms),
+        duration_ms: Set(None),
+        note: Set(record.note),
+        created_at: Set(record.started_at),
+        updated_at: Set(record.started_at),
+        deleted_at: Set(None),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+/// 更新计时记录。
+pub async fn update<C>(
+    conn: &C,
+    active_model: task_time_entries::ActiveModel,
+) -> Result<task_time_entries::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    active_model.update(conn).await.map_err(AppError::from)
+}
@@ -0,0 +1,143 @@
+//! “今天”视图查询。
+//!
+//! 重点：
+//! - “今天”按本地日历日划分，而不是按 UTC 毫秒整除；默认使用系统时区，也可由前端传入固定偏移
+//! - 跨全部 Space，只看未完成、未归档、未删除且不在推迟中的任务
+//! - 每个任务只归入一个分组，优先级：逾期 > 今天截止 > 今天计划 > 进行中
+
+use chrono::{FixedOffset, Local, NaiveDate, TimeZone};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::db::entities::{sea_orm_active_enums::TaskStatus, tasks};
+use crate::types::{
+    dto::{TaskDto, TodayViewDto},
+    error::AppError,
+};
+
+use super::list::{not_deferred_condition, to_dtos};
+
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TodayGroup {
+    Overdue,
+    DueToday,
+    ScheduledToday,
+    Doing,
+}
+
+/// 计算 `now` 所在本地日的起止时间（毫秒）。
+///
+/// `utc_offset_minutes` 为空时使用系统时区（含夏令时规则）；否则按固定偏移计算，东区为正。
+pub fn day_bounds(now: i64, utc_offset_minutes: Option<i32>) -> Result<(i64, i64), AppError> {
+    match utc_offset_minutes {
+        Some(minutes) => bounds_in(&fixed_offset(minutes)?, now),
+        None => bounds_in(&Local, now),
+    }
+}
+
+/// 校验前端传入的 UTC 偏移分钟数并转成固定时区。
+pub fn fixed_offset(minutes: i32) -> Result<FixedOffset, AppError> {
+    if minutes.abs() > MAX_UTC_OFFSET_MINUTES {
+        return Err(AppError::Validation(format!(
+            "utcOffsetMinutes 必须在 -{MAX_UTC_OFFSET_MINUTES} 到 {MAX_UTC_OFFSET_MINUTES} 之间"
+        )));
+    }
+    FixedOffset::east_opt(minutes * 60)
+        .ok_or_else(|| AppError::Validation("utcOffsetMinutes 无效".to_string()))
+}
+
+fn bounds_in<Tz: TimeZone>(tz: &Tz, now: i64) -> Result<(i64, i64), AppError> {
+    let invalid = || AppError::Internal(format!("无法计算本地日期边界：{now}"));
+    let today = tz
+        .timestamp_millis_opt(now)
+        .earliest()
+        .ok_or_else(invalid)?
+        .date_naive();
+    let start = local_day_start(tz, today).ok_or_else(invalid)?;
+    let end = today
+        .succ_opt()
+        .and_then(|tomorrow| local_day_start(tz, tomorrow))
+        .ok_or_else(invalid)?;
+    Ok((start, end))
+}
+
+/// 本地日的第一个真实存在的时刻；个别时区在夏令时切换当天没有 0 点。
+fn local_day_start<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> Option<i64> {
+    (0..24).find_map(|hour| {
+        tz.from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+            .map(|value| value.timestamp_millis())
+    })
+}
+
+fn classify(task: &tasks::Model, day_start: i64, day_end: i64) -> Option<TodayGroup> {
+    match task.deadline_at {
+        Some(deadline_at) if deadline_at < day_start => return Some(TodayGroup::Overdue),
+        Some(deadline_at) if deadline_at < day_end => return Some(TodayGroup::DueToday),
+        _ => {}
+    }
+    if matches!(task.scheduled_at, Some(scheduled_at) if scheduled_at < day_end) {
+        return Some(TodayGroup::ScheduledToday);
+    }
+    (task.status == TaskStatus::Doing).then_some(TodayGroup::Doing)
+}
+
+/// 组装“今天”视图。
+pub async fn list_today(
+    conn: &DatabaseConnection,
+    now: i64,
+    utc_offset_minutes: Option<i32>,
+) -> Result<TodayViewDto, AppError> {
+    let (day_start, day_end) = day_bounds(now, utc_offset_minutes)?;
+
+    let mut models = tasks::Entity::find()
+        .filter(tasks::Column::DeletedAt.is_null())
+        .filter(tasks::Column::ArchivedAt.is_null())
+        .filter(tasks::Column::Status.ne(TaskStatus::Done))
+        .filter(not_deferred_condition(now))
+        .filter(
+            Condition::any()
+                .add(tasks::Column::DeadlineAt.lt(day_end))
+                .add(tasks::Column::ScheduledAt.lt(day_end))
+                .add(tasks::Column::Status.eq(TaskStatus::Doing)),
+        )
+        .order_by_asc(tasks::Column::Priority)
+        .order_by_asc(tasks::Column::Rank)
+        .order_by_asc(tasks::Column::Id)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+
+    // 组内排序：逾期与今天截止按截止时间，今天计划按计划时间，进行中保持优先级顺序。
+    models.sort_by_key(|task| match classify(task, day_start, day_end) {
+        Some(TodayGroup::Overdue | TodayGroup::DueToday) => task.deadline_at,
+        Some(TodayGroup::ScheduledToday) => task.scheduled_at,
+        _ => None,
+    });
+    let groups: Vec<Option<TodayGroup>> = models
+        .iter()
+        .map(|task| classify(task, day_start, day_end))
+        .collect();
+
+    let mut view = TodayViewDto {
+        day_start,
+        day_end,
+        overdue: Vec::new(),
+        due_today: Vec::new(),
+        scheduled_today: Vec::new(),
+        doing: Vec::new(),
+    };
+    for (task, group) in to_dtos(conn, models).await?.into_iter().zip(groups) {
+        let bucket: &mut Vec<TaskDto> = match group {
+            Some(TodayGroup::Overdue) => &mut view.overdue,
+            Some(TodayGroup::DueToday) => &mut view.due_today,
+            Some(TodayGroup::ScheduledToday) => &mut view.scheduled_today,
+            Some(TodayGroup::Doing) => &mut view.doing,
+            None => continue,
+        };
+        bucket.push(task);
+    }
+
+    Ok(view)
+}
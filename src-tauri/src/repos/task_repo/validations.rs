@@ -1,6 +1,7 @@
 //! Task 更新相关校验器。
 //! 重点：把校验逻辑单独抽离，减少 update 主流程的认知负担。
 
+use crate::db::entities::sea_orm_active_enums::TaskStatus;
 use crate::repos::common_task_utils;
 use crate::types::error::AppError;
 
@@ -13,11 +14,57 @@ pub fn trim_and_validate_title(title: &str) -> Result<String, AppError> {
     }
 }
 
+/// 校验检查项标题，规则与任务标题一致。
+pub fn trim_and_validate_checklist_title(title: &str) -> Result<String, AppError> {
+    let trimmed = title.trim();
+    if trimmed.is_empty() {
+        Err(AppError::Validation("检查项标题不能为空".to_string()))
+    } else {
+        Ok(trimmed.to_string())
+    }
+}
+
 /// 规范化并校验任务状态字符串。
 pub fn normalize_status(status: &str) -> Result<String, AppError> {
     common_task_utils::validate_status(status)
 }
 
+/// 解析任务状态字符串为枚举。
+pub fn parse_status(status: &str) -> Result<TaskStatus, AppError> {
+    common_task_utils::parse_status(status)
+}
+
+/// 校验状态流转是否允许；同状态视为无变化，直接放行。
+///
+/// 流转表：
+/// - todo -> doing / waiting / someday / done
+/// - doing -> todo / waiting / done
+/// - waiting -> todo / doing / someday / done
+/// - someday -> todo / doing / done
+/// - done -> todo（重新打开）
+pub fn ensure_status_transition(from: &TaskStatus, to: &TaskStatus) -> Result<(), AppError> {
+    use TaskStatus::*;
+
+    let allowed = from == to
+        || matches!(
+            (from, to),
+            (Todo, Doing | Waiting | Someday | Done)
+                | (Doing, Todo | Waiting | Done)
+                | (Waiting, Todo | Doing | Someday | Done)
+                | (Someday, Todo | Doing | Done)
+                | (Done, Todo)
+        );
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "任务状态不能从 {} 变更为 {}",
+            common_task_utils::status_to_string(from),
+            common_task_utils::status_to_string(to)
+        )))
+    }
+}
+
 /// 规范化完成原因字符串。
 pub fn normalize_done_reason(reason: &str) -> Result<String, AppError> {
     common_task_utils::normalize_done_reason(reason)
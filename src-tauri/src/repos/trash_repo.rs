@@ -0,0 +1,390 @@
+//! 回收站仓储：彻底删除、墓碑与保留期设置。
+//!
+//! 重点：
+//! - 硬删除按“关系 → 从属记录 → 日志 → 主表”的顺序进行，远端 Postgres 上有外键约束
+//! - 每清除一个实体写一条墓碑，同步时据此在其他设备上重放删除
+//! - 这里不碰搜索索引：远端没有这张表，本地由调用方刷新
+
+use std::collections::HashSet;
+
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait,
+    QueryFilter, QuerySelect, Set,
+};
+
+use crate::db::entities::{
+    app_settings, links, project_activity_logs, project_links, project_tags, projects,
+    purged_entities, task_activity_logs, task_checklist_items, task_dependencies, task_links,
+    task_reminders, task_tags, task_time_entries, tasks,
+};
+use crate::repos::project_repo::{
+    helpers as project_helpers, milestones as project_milestones, mutation as project_mutation,
+};
+use crate::types::error::AppError;
+
+pub const ENTITY_TASK: &str = "task";
+pub const ENTITY_PROJECT: &str = "project";
+
+/// 回收站保留天数；0 表示不自动清除。
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+const RETENTION_DAYS_KEY: &str = "trash_retention_days";
+
+/// 单条语句里 `IN (...)` 的最大 id 数，避免触发 SQLite 参数上限。
+const CHUNK_SIZE: usize = 500;
+
+/// 回收站里的任务 id；`before` 为空时返回全部。
+pub async fn deleted_task_ids<C>(conn: &C, before: Option<i64>) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut query = tasks::Entity::find()
+        .select_only()
+        .column(tasks::Column::Id)
+        .filter(tasks::Column::DeletedAt.is_not_null());
+    if let Some(before) = before {
+        query = query.filter(tasks::Column::DeletedAt.lt(before));
+    }
+    query.into_tuple().all(conn).await.map_err(AppError::from)
+}
+
+/// 回收站里的项目 id；`before` 为空时返回全部。
+pub async fn deleted_project_ids<C>(conn: &C, before: Option<i64>) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut query = projects::Entity::find()
+        .select_only()
+        .column(projects::Column::Id)
+        .filter(projects::Column::DeletedAt.is_not_null());
+    if let Some(before) = before {
+        query = query.filter(projects::Column::DeletedAt.lt(before));
+    }
+    query.into_tuple().all(conn).await.map_err(AppError::from)
+}
+
+/// 硬删除任务及其标签、链接、前置关系、检查清单、提醒、计时与活动日志。
+///
+/// 不存在的 id 会被忽略，返回实际删除的任务数。
+pub async fn hard_delete_tasks<C>(conn: &C, ids: &[String]) -> Result<usize, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut deleted = 0;
+    for chunk in ids.chunks(CHUNK_SIZE) {
+        let ids = chunk.to_vec();
+        let link_ids: Vec<String> = task_links::Entity::find()
+            .select_only()
+            .column(task_links::Column::LinkId)
+            .filter(task_links::Column::TaskId.is_in(ids.clone()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::from)?;
+        task_links::Entity::delete_many()
+            .filter(task_links::Column::TaskId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        delete_links(conn, link_ids).await?;
+
+        task_tags::Entity::delete_many()
+            .filter(task_tags::Column::TaskId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        // 其他任务指向这些任务的前置关系也一并清掉。
+        task_dependencies::Entity::delete_many()
+            .filter(
+                task_dependencies::Column::TaskId
+                    .is_in(ids.clone())
+                    .or(task_dependencies::Column::BlockedByTaskId.is_in(ids.clone())),
+            )
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        task_checklist_items::Entity::delete_many()
+            .filter(task_checklist_items::Column::TaskId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        task_reminders::Entity::delete_many()
+            .filter(task_reminders::Column::TaskId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        task_time_entries::Entity::delete_many()
+            .filter(task_time_entries::Column::TaskId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        task_activity_logs::Entity::delete_many()
+            .filter(task_activity_logs::Column::TaskId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+
+        let result = tasks::Entity::delete_many()
+            .filter(tasks::Column::Id.is_in(ids))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        deleted += result.rows_affected as usize;
+    }
+    Ok(deleted)
+}
+
+/// 硬删除项目及其标签、链接、里程碑与活动日志。
+///
+/// 调用方应先清掉项目下的任务；仍然挂在这些项目下的任务会被移到所属 Space 的默认项目，
+/// 未被一起清除的子项目提升为顶层项目，避免留下悬空引用。
+pub async fn hard_delete_projects<C>(conn: &C, ids: &[String], now: i64) -> Result<usize, AppError>
+where
+    C: ConnectionTrait,
+{
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let purging = ids.iter().cloned().collect::<HashSet<_>>();
+
+    let orphan_tasks = tasks::Entity::find()
+        .filter(tasks::Column::ProjectId.is_in(ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for task in orphan_tasks {
+        let default_project_id = format!("{}_default", task.space_id);
+        tasks::ActiveModel {
+            id: Set(task.id),
+            project_id: Set(Some(default_project_id)),
+            milestone_id: Set(None),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .update(conn)
+        .await
+        .map_err(AppError::from)?;
+    }
+
+    let orphan_children = projects::Entity::find()
+        .filter(projects::Column::ParentId.is_in(ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?
+        .into_iter()
+        .filter(|child| !purging.contains(&child.id));
+    for child in orphan_children {
+        let path =
+            project_helpers::build_project_path(conn, &child.space_id, None, &child.title).await?;
+        project_mutation::rebase_descendant_paths(conn, &child.space_id, &child.path, &path, now)
+            .await?;
+        projects::ActiveModel {
+            id: Set(child.id),
+            parent_id: Set(None),
+            path: Set(path),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .update(conn)
+        .await
+        .map_err(AppError::from)?;
+    }
+
+    // 子项目先于父项目删除，分批时也不会违反父子外键。
+    let mut models = projects::Entity::find()
+        .filter(projects::Column::Id.is_in(ids.iter().cloned()))
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    models.sort_by_key(|model| std::cmp::Reverse(model.path.matches('/').count()));
+    let ordered_ids = models.into_iter().map(|model| model.id).collect::<Vec<_>>();
+
+    let mut deleted = 0;
+    for chunk in ordered_ids.chunks(CHUNK_SIZE) {
+        let ids = chunk.to_vec();
+        let link_ids: Vec<String> = project_links::Entity::find()
+            .select_only()
+            .column(project_links::Column::LinkId)
+            .filter(project_links::Column::ProjectId.is_in(ids.clone()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::from)?;
+        project_links::Entity::delete_many()
+            .filter(project_links::Column::ProjectId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        delete_links(conn, link_ids).await?;
+
+        project_tags::Entity::delete_many()
+            .filter(project_tags::Column::ProjectId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        project_activity_logs::Entity::delete_many()
+            .filter(project_activity_logs::Column::ProjectId.is_in(ids.clone()))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        project_milestones::hard_delete_for_projects(conn, &ids).await?;
+
+        let result = projects::Entity::delete_many()
+            .filter(projects::Column::Id.is_in(ids))
+            .exec(conn)
+            .await
+            .map_err(AppError::from)?;
+        deleted += result.rows_affected as usize;
+    }
+    Ok(deleted)
+}
+
+/// 链接主表记录归属单个实体，关系删掉后主表记录也随之删除。
+async fn delete_links<C>(conn: &C, link_ids: Vec<String>) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if link_ids.is_empty() {
+        return Ok(());
+    }
+    links::Entity::delete_many()
+        .filter(links::Column::Id.is_in(link_ids))
+        .exec(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(())
+}
+
+/// 为已清除的实体写墓碑；重复写入保持第一次的时间。
+pub async fn record_tombstones<C>(
+    conn: &C,
+    entity_type: &str,
+    ids: &[String],
+    now: i64,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    for chunk in ids.chunks(CHUNK_SIZE) {
+        let models = chunk.iter().map(|id| purged_entities::ActiveModel {
+            id: Set(format!("{entity_type}:{id}")),
+            entity_type: Set(entity_type.to_string()),
+            entity_id: Set(id.clone()),
+            purged_at: Set(now),
+        });
+        purged_entities::Entity::insert_many(models)
+            .on_conflict(
+                OnConflict::column(purged_entities::Column::Id)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(conn)
+            .await
+            .map_err(AppError::from)?;
+    }
+    Ok(())
+}
+
+/// 按本端全部墓碑重放硬删除，返回实际删除的任务数与项目数。
+///
+/// 同步把旧数据带回来时（例如另一端还没收到墓碑就改过这条任务），这里会再删一次。
+pub async fn apply_tombstones<C>(conn: &C, now: i64) -> Result<(usize, usize), AppError>
+where
+    C: ConnectionTrait,
+{
+    let tombstones = purged_entities::Entity::find()
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    let ids_of = |entity_type: &str| {
+        tombstones
+            .iter()
+            .filter(|item| item.entity_type == entity_type)
+            .map(|item| item.entity_id.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let mut task_ids = Vec::new();
+    for chunk in ids_of(ENTITY_TASK).chunks(CHUNK_SIZE) {
+        let existing: Vec<String> = tasks::Entity::find()
+            .select_only()
+            .column(tasks::Column::Id)
+            .filter(tasks::Column::Id.is_in(chunk.to_vec()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::from)?;
+        task_ids.extend(existing);
+    }
+    let mut project_ids = Vec::new();
+    for chunk in ids_of(ENTITY_PROJECT).chunks(CHUNK_SIZE) {
+        let existing: Vec<String> = projects::Entity::find()
+            .select_only()
+            .column(projects::Column::Id)
+            .filter(projects::Column::Id.is_in(chunk.to_vec()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::from)?;
+        project_ids.extend(existing);
+    }
+
+    let tasks = hard_delete_tasks(conn, &task_ids).await?;
+    let projects = hard_delete_projects(conn, &project_ids, now).await?;
+    Ok((tasks, projects))
+}
+
+/// 读取回收站保留天数，未设置时使用默认值。
+pub async fn read_retention_days<C>(conn: &C) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    let setting = app_settings::Entity::find_by_id(RETENTION_DAYS_KEY.to_string())
+        .one(conn)
+        .await
+        .map_err(AppError::from)?;
+    Ok(setting
+        .and_then(|setting| setting.value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS))
+}
+
+pub async fn write_retention_days<C>(conn: &C, days: i64) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    app_settings::Entity::insert(app_settings::ActiveModel {
+        key: Set(RETENTION_DAYS_KEY.to_string()),
+        value: Set(days.to_string()),
+    })
+    .on_conflict(
+        OnConflict::column(app_settings::Column::Key)
+            .update_columns([app_settings::Column::Value])
+            .to_owned(),
+    )
+    .exec_without_returning(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
+
+/// 挂在这些项目下的全部任务 id（含已删除）。
+pub async fn task_ids_in_projects<C>(
+    conn: &C,
+    project_ids: &[String],
+) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut ids = Vec::new();
+    for chunk in project_ids.chunks(CHUNK_SIZE) {
+        let chunk_ids: Vec<String> = tasks::Entity::find()
+            .select_only()
+            .column(tasks::Column::Id)
+            .filter(tasks::Column::ProjectId.is_in(chunk.to_vec()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::from)?;
+        ids.extend(chunk_ids);
+    }
+    Ok(ids)
+}
@@ -0,0 +1,183 @@
+//! 撤销 / 重做仓储。
+//!
+//! 重点：
+//! - “一次操作”就是共用同一个 `batch_id` 的任务日志与项目日志
+//! - `undo_records` 记录哪个批次被反转过，以及反转时写出的新批次
+//! - 撤销写出的批次不再参与撤销，只能通过重做再反转一次
+//! - 日志会随同步带着 `batch_id` 拉到其他设备，只有 `local_batches` 里登记过的本机批次才能撤销
+//! - 只面向本机 SQLite：`undo_records` / `local_batches` 不会建到远端库，查询可以直接用 SQLite 语法和 rowid
+
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, Order,
+    QueryFilter, QueryOrder, Set, Statement,
+};
+use uuid::Uuid;
+
+use crate::db::entities::{local_batches, project_activity_logs, task_activity_logs, undo_records};
+use crate::types::error::AppError;
+
+pub const KIND_UNDO: &str = "undo";
+pub const KIND_REDO: &str = "redo";
+
+/// 两张日志表合并后的本机批次视图。
+const BATCHES_SQL: &str = "SELECT batch_id, MAX(created_at) AS created_at FROM ( \
+     SELECT batch_id, created_at FROM task_activity_logs WHERE batch_id IS NOT NULL \
+     UNION ALL \
+     SELECT batch_id, created_at FROM project_activity_logs WHERE batch_id IS NOT NULL \
+     ) WHERE batch_id IN (SELECT batch_id FROM local_batches) GROUP BY batch_id";
+
+#[derive(FromQueryResult)]
+struct BatchRow {
+    batch_id: String,
+}
+
+pub struct NewUndoRecord<'a> {
+    pub batch_id: &'a str,
+    pub inverse_batch_id: &'a str,
+    pub kind: &'a str,
+    pub action_label: &'a str,
+    pub created_at: i64,
+}
+
+/// 最近一次可撤销的操作：既没被反转过，也不是撤销本身写出的批次。
+pub async fn latest_undoable_batch<C>(conn: &C) -> Result<Option<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let sql = format!(
+        "SELECT batch_id FROM ({BATCHES_SQL}) \
+         WHERE batch_id NOT IN (SELECT batch_id FROM undo_records) \
+         AND batch_id NOT IN (SELECT inverse_batch_id FROM undo_records WHERE kind = ?) \
+         ORDER BY created_at DESC LIMIT 1"
+    );
+    let row = BatchRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        sql,
+        [KIND_UNDO.into()],
+    ))
+    .one(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(row.map(|row| row.batch_id))
+}
+
+/// 最近一次还没被重做的撤销记录。
+pub async fn latest_redoable_record<C>(conn: &C) -> Result<Option<undo_records::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let records = undo_records::Entity::find()
+        .filter(undo_records::Column::Kind.eq(KIND_UNDO))
+        .order_by_desc(undo_records::Column::CreatedAt)
+        .all(conn)
+        .await
+        .map_err(AppError::from)?;
+    for record in records {
+        let redone = undo_records::Entity::find()
+            .filter(undo_records::Column::BatchId.eq(record.inverse_batch_id.as_str()))
+            .one(conn)
+            .await
+            .map_err(AppError::from)?;
+        if redone.is_none() {
+            return Ok(Some(record));
+        }
+    }
+    Ok(None)
+}
+
+/// 给定时刻之后是否有新的用户操作（撤销 / 重做写出的批次不算）。
+pub async fn has_fresh_batch_after<C>(conn: &C, after: i64) -> Result<bool, AppError>
+where
+    C: ConnectionTrait,
+{
+    let sql = format!(
+        "SELECT batch_id FROM ({BATCHES_SQL}) \
+         WHERE created_at > ? \
+         AND batch_id NOT IN (SELECT inverse_batch_id FROM undo_records) \
+         LIMIT 1"
+    );
+    let row = BatchRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        sql,
+        [after.into()],
+    ))
+    .one(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(row.is_some())
+}
+
+/// 读取某个批次写出的任务日志，按写入顺序（rowid）排列。
+pub async fn load_task_logs<C>(
+    conn: &C,
+    batch_id: &str,
+) -> Result<Vec<task_activity_logs::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    task_activity_logs::Entity::find()
+        .filter(task_activity_logs::Column::BatchId.eq(batch_id))
+        .order_by(Expr::cust("rowid"), Order::Asc)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 读取某个批次写出的项目日志，按写入顺序（rowid）排列。
+pub async fn load_project_logs<C>(
+    conn: &C,
+    batch_id: &str,
+) -> Result<Vec<project_activity_logs::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    project_activity_logs::Entity::find()
+        .filter(project_activity_logs::Column::BatchId.eq(batch_id))
+        .order_by(Expr::cust("rowid"), Order::Asc)
+        .all(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 登记一个本机写出的批次；同一批次多次写日志时只保留第一次。
+pub async fn record_local_batch<C>(
+    conn: &C,
+    batch_id: &str,
+    created_at: i64,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    local_batches::Entity::insert(local_batches::ActiveModel {
+        batch_id: Set(batch_id.to_string()),
+        created_at: Set(created_at),
+    })
+    .on_conflict(
+        OnConflict::column(local_batches::Column::BatchId)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
+
+pub async fn insert<C>(conn: &C, record: NewUndoRecord<'_>) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    undo_records::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        batch_id: Set(record.batch_id.to_string()),
+        inverse_batch_id: Set(record.inverse_batch_id.to_string()),
+        kind: Set(record.kind.to_string()),
+        action_label: Set(record.action_label.to_string()),
+        created_at: Set(record.created_at),
+    }
+    .insert(conn)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
@@ -3,8 +3,11 @@ use uuid::Uuid;
 
 use crate::{
     db::now_ms,
-    repos::asset_repo::{
-        AssetRepo, NewDiaryEntryRecord, NewNoteRecord, NewSnippetRecord, NewVaultEntryRecord,
+    repos::{
+        asset_repo::{
+            AssetRepo, NewDiaryEntryRecord, NewNoteRecord, NewSnippetRecord, NewVaultEntryRecord,
+        },
+        search_repo,
     },
     types::{
         dto::{
@@ -172,8 +175,9 @@ impl AssetService {
         input: AssetSnippetCreateInput,
     ) -> Result<AssetSnippetDto, AppError> {
         let now = now_ms();
+        let txn = conn.begin().await.map_err(AppError::from)?;
         let model = AssetRepo::insert_snippet(
-            conn,
+            &txn,
             NewSnippetRecord {
                 id: Uuid::new_v4().to_string(),
                 title: normalize_required(&input.title, "代码片段标题")?,
@@ -191,6 +195,8 @@ impl AssetService {
             },
         )
         .await?;
+        search_repo::reindex_snippets(&txn, std::slice::from_ref(&model.id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
         asset_repo_to_snippet_dto(model)
     }
 
@@ -200,7 +206,8 @@ impl AssetService {
         patch: AssetSnippetUpdatePatch,
     ) -> Result<(), AppError> {
         let now = now_ms();
-        let model = AssetRepo::get_snippet_by_id(conn, id).await?;
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let model = AssetRepo::get_snippet_by_id(&txn, id).await?;
         let mut active_model: crate::db::entities::asset_snippets::ActiveModel = model.into();
         if let Some(title) = patch.title {
             active_model.title = Set(normalize_required(&title, "代码片段标题")?);
@@ -234,12 +241,17 @@ impl AssetService {
             active_model.sync_state = Set(normalize_sync_state(Some(sync_state)));
         }
         active_model.updated_at = Set(now);
-        AssetRepo::update_snippet(conn, active_model).await?;
+        AssetRepo::update_snippet(&txn, active_model).await?;
+        search_repo::reindex_snippets(&txn, &[id.to_string()]).await?;
+        txn.commit().await.map_err(AppError::from)?;
         Ok(())
     }
 
     pub async fn delete_snippet(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
-        AssetRepo::delete_snippet(conn, id).await
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        AssetRepo::delete_snippet(&txn, id).await?;
+        search_repo::reindex_snippets(&txn, &[id.to_string()]).await?;
+        txn.commit().await.map_err(AppError::from)
     }
 
     pub async fn create_note(
@@ -250,8 +262,9 @@ impl AssetService {
         let excerpt = input.content.lines().find(|line| !line.trim().is_empty()).map(|line| {
             line.trim().chars().take(120).collect::<String>()
         });
+        let txn = conn.begin().await.map_err(AppError::from)?;
         let model = AssetRepo::insert_note(
-            conn,
+            &txn,
             NewNoteRecord {
                 id: Uuid::new_v4().to_string(),
                 title: normalize_required(&input.title, "笔记标题")?,
@@ -267,6 +280,8 @@ impl AssetService {
             },
         )
         .await?;
+        search_repo::reindex_notes(&txn, std::slice::from_ref(&model.id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
         asset_repo_to_note_dto(model)
     }
 
@@ -276,7 +291,8 @@ impl AssetService {
         patch: AssetNoteUpdatePatch,
     ) -> Result<(), AppError> {
         let now = now_ms();
-        let model = AssetRepo::get_note_by_id(conn, id).await?;
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let model = AssetRepo::get_note_by_id(&txn, id).await?;
         let mut active_model: crate::db::entities::asset_notes::ActiveModel = model.into();
         if let Some(title) = patch.title {
             active_model.title = Set(normalize_required(&title, "笔记标题")?);
@@ -311,12 +327,17 @@ impl AssetService {
             active_model.sync_state = Set(normalize_sync_state(Some(sync_state)));
         }
         active_model.updated_at = Set(now);
-        AssetRepo::update_note(conn, active_model).await?;
+        AssetRepo::update_note(&txn, active_model).await?;
+        search_repo::reindex_notes(&txn, &[id.to_string()]).await?;
+        txn.commit().await.map_err(AppError::from)?;
         Ok(())
     }
 
     pub async fn delete_note(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
-        AssetRepo::delete_note(conn, id).await
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        AssetRepo::delete_note(&txn, id).await?;
+        search_repo::reindex_notes(&txn, &[id.to_string()]).await?;
+        txn.commit().await.map_err(AppError::from)
     }
 
     pub async fn create_diary_entry(
@@ -324,8 +345,9 @@ impl AssetService {
         input: AssetDiaryEntryCreateInput,
     ) -> Result<AssetDiaryEntryDto, AppError> {
         let now = now_ms();
+        let txn = conn.begin().await.map_err(AppError::from)?;
         let model = AssetRepo::insert_diary_entry(
-            conn,
+            &txn,
             NewDiaryEntryRecord {
                 id: Uuid::new_v4().to_string(),
                 date: normalize_required(&input.date, "日记日期")?,
@@ -342,6 +364,8 @@ impl AssetService {
             },
         )
         .await?;
+        search_repo::reindex_diary_entries(&txn, std::slice::from_ref(&model.id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
         asset_repo_to_diary_entry_dto(model)
     }
 
@@ -351,7 +375,8 @@ impl AssetService {
         patch: AssetDiaryEntryUpdatePatch,
     ) -> Result<(), AppError> {
         let now = now_ms();
-        let model = AssetRepo::get_diary_entry_by_id(conn, id).await?;
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let model = AssetRepo::get_diary_entry_by_id(&txn, id).await?;
         let mut active_model: crate::db::entities::asset_diary_entries::ActiveModel = model.into();
         if let Some(date) = patch.date {
             active_model.date = Set(normalize_required(&date, "日记日期")?);
@@ -383,12 +408,17 @@ impl AssetService {
             active_model.sync_state = Set(normalize_sync_state(Some(sync_state)));
         }
         active_model.updated_at = Set(now);
-        AssetRepo::update_diary_entry(conn, active_model).await?;
+        AssetRepo::update_diary_entry(&txn, active_model).await?;
+        search_repo::reindex_diary_entries(&txn, &[id.to_string()]).await?;
+        txn.commit().await.map_err(AppError::from)?;
         Ok(())
     }
 
     pub async fn delete_diary_entry(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
-        AssetRepo::delete_diary_entry(conn, id).await
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        AssetRepo::delete_diary_entry(&txn, id).await?;
+        search_repo::reindex_diary_entries(&txn, &[id.to_string()]).await?;
+        txn.commit().await.map_err(AppError::from)
     }
 
     pub async fn create_vault_entry(
@@ -487,6 +517,17 @@ impl AssetService {
             AssetRepo::upsert_imported_vault_entry(&txn, vault_entry).await?;
         }
 
+        let snippet_ids: Vec<String> = bundle.snippets.iter().map(|item| item.id.clone()).collect();
+        let note_ids: Vec<String> = bundle.notes.iter().map(|item| item.id.clone()).collect();
+        let diary_entry_ids: Vec<String> = bundle
+            .diary_entries
+            .iter()
+            .map(|item| item.id.clone())
+            .collect();
+        search_repo::reindex_snippets(&txn, &snippet_ids).await?;
+        search_repo::reindex_notes(&txn, &note_ids).await?;
+        search_repo::reindex_diary_entries(&txn, &diary_entry_ids).await?;
+
         let migrated_at = now_ms();
         AssetRepo::write_migration_status(&txn, migrated_at).await?;
         txn.commit().await.map_err(AppError::from)?;
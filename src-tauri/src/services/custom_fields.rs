@@ -0,0 +1,331 @@
+//! 自定义字段定义用例。
+//!
+//! 约定：
+//! - 同一 space 内，space 级定义与任何项目级定义不能重名；不同项目可以各自定义同名字段
+//! - 改名会把作用域内任务里的字段值一并改名；改类型或候选项时，已有取值必须能通过新定义的校验
+//! - 已有取值会按新定义重新规范化，改动的任务刷新 `updated_at`，随同步传播
+
+use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{entities::custom_field_definitions, now_ms};
+use crate::repos::{
+    custom_field_repo::{self, NewDefinitionRecord},
+    project_repo::query as project_query,
+    search_repo,
+    task_repo::{activity_logs, custom_fields, mutation as task_mutation},
+};
+use crate::types::{dto::CustomFieldDefinitionDto, error::AppError};
+
+pub struct CustomFieldService;
+
+pub struct CustomFieldDefinitionCreateInput {
+    pub space_id: String,
+    pub project_id: Option<String>,
+    pub title: String,
+    pub field_type: String,
+    pub options: Vec<String>,
+    pub rank: Option<i64>,
+}
+
+#[derive(Default)]
+pub struct CustomFieldDefinitionUpdatePatch {
+    pub title: Option<String>,
+    pub field_type: Option<String>,
+    pub options: Option<Vec<String>>,
+    pub rank: Option<i64>,
+}
+
+impl CustomFieldService {
+    /// 新建字段定义。
+    pub async fn create(
+        conn: &DatabaseConnection,
+        input: CustomFieldDefinitionCreateInput,
+    ) -> Result<CustomFieldDefinitionDto, AppError> {
+        let title = normalize_title(&input.title)?;
+        let field_type = custom_fields::parse_field_type(&input.field_type)?;
+        let options = normalize_options(field_type, input.options)?;
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        if let Some(project_id) = input.project_id.as_deref() {
+            let project = project_query::find_by_id(&txn, project_id).await?;
+            if project.space_id != input.space_id || project.deleted_at.is_some() {
+                return Err(AppError::Validation(format!("项目 {project_id} 不存在")));
+            }
+        }
+        ensure_title_available(
+            &txn,
+            &input.space_id,
+            input.project_id.as_deref(),
+            &title,
+            None,
+        )
+        .await?;
+
+        let now = now_ms();
+        let rank = match input.rank {
+            Some(rank) if rank < 0 => {
+                return Err(AppError::Validation(
+                    "自定义字段排序必须为非负整数".to_string(),
+                ))
+            }
+            Some(rank) => rank,
+            None => {
+                custom_field_repo::next_rank(&txn, &input.space_id, input.project_id.as_deref())
+                    .await?
+            }
+        };
+        let model = custom_field_repo::insert(
+            &txn,
+            NewDefinitionRecord {
+                id: Uuid::new_v4().to_string(),
+                space_id: input.space_id,
+                project_id: input.project_id,
+                title,
+                field_type: field_type.to_string(),
+                options: serialize_options(&options)?,
+                rank,
+                created_at: now,
+            },
+        )
+        .await?;
+        // 作用域里可能早就有同名的纯文本字段，先按新定义校验一遍。
+        migrate_task_values(&txn, &model, &model, now).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(custom_field_repo::to_dto(model))
+    }
+
+    /// 更新字段定义；改名与改类型会迁移任务里已有的取值。
+    pub async fn update(
+        conn: &DatabaseConnection,
+        id: &str,
+        patch: CustomFieldDefinitionUpdatePatch,
+    ) -> Result<CustomFieldDefinitionDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let previous = custom_field_repo::find_by_id(&txn, id).await?;
+
+        let title = match patch.title.as_deref() {
+            Some(title) => normalize_title(title)?,
+            None => previous.title.clone(),
+        };
+        if title != previous.title {
+            ensure_title_available(
+                &txn,
+                &previous.space_id,
+                previous.project_id.as_deref(),
+                &title,
+                Some(id),
+            )
+            .await?;
+        }
+        let field_type = match patch.field_type.as_deref() {
+            Some(field_type) => custom_fields::parse_field_type(field_type)?,
+            None => custom_fields::parse_field_type(&previous.field_type)?,
+        };
+        let options = match patch.options {
+            Some(options) => normalize_options(field_type, options)?,
+            None if custom_fields::is_select_type(field_type) => normalize_options(
+                field_type,
+                custom_field_repo::parse_options(previous.options.as_deref()),
+            )?,
+            None => Vec::new(),
+        };
+        if let Some(rank) = patch.rank {
+            if rank < 0 {
+                return Err(AppError::Validation(
+                    "自定义字段排序必须为非负整数".to_string(),
+                ));
+            }
+        }
+
+        let now = now_ms();
+        let mut active_model = previous.clone().into_active_model();
+        active_model.title = Set(title);
+        active_model.field_type = Set(field_type.to_string());
+        active_model.options = Set(serialize_options(&options)?);
+        if let Some(rank) = patch.rank {
+            active_model.rank = Set(rank);
+        }
+        active_model.updated_at = Set(now);
+        let saved = custom_field_repo::update(&txn, active_model).await?;
+
+        if saved.title != previous.title
+            || saved.field_type != previous.field_type
+            || saved.options != previous.options
+        {
+            migrate_task_values(&txn, &previous, &saved, now).await?;
+        }
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(custom_field_repo::to_dto(saved))
+    }
+
+    /// 删除字段定义；任务里的取值保留为纯文本字段。
+    pub async fn delete(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let model = custom_field_repo::find_by_id(&txn, id).await?;
+        let now = now_ms();
+        let mut active_model = model.into_active_model();
+        active_model.deleted_at = Set(Some(now));
+        active_model.updated_at = Set(now);
+        custom_field_repo::update(&txn, active_model).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+fn normalize_title(title: &str) -> Result<String, AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::Validation("自定义字段名称不能为空".to_string()));
+    }
+    Ok(title.to_string())
+}
+
+/// 选择类字段必须至少有一个候选项，其余类型不保留候选项。
+fn normalize_options(field_type: &str, options: Vec<String>) -> Result<Vec<String>, AppError> {
+    if !custom_fields::is_select_type(field_type) {
+        return Ok(Vec::new());
+    }
+    let mut normalized: Vec<String> = Vec::with_capacity(options.len());
+    for option in options {
+        let option = option.trim();
+        if option.is_empty() || normalized.iter().any(|item| item == option) {
+            continue;
+        }
+        if option.contains(',') {
+            return Err(AppError::Validation(format!(
+                "选项「{option}」不能包含逗号"
+            )));
+        }
+        normalized.push(option.to_string());
+    }
+    if normalized.is_empty() {
+        return Err(AppError::Validation(
+            "选择类字段至少需要一个选项".to_string(),
+        ));
+    }
+    Ok(normalized)
+}
+
+fn serialize_options(options: &[String]) -> Result<Option<String>, AppError> {
+    if options.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(options)
+        .map(Some)
+        .map_err(|e| AppError::Validation(format!("自定义字段选项序列化失败: {e}")))
+}
+
+/// 标题在 space 内是否可用，`exclude_id` 用于更新时排除自身。
+async fn ensure_title_available<C>(
+    conn: &C,
+    space_id: &str,
+    project_id: Option<&str>,
+    title: &str,
+    exclude_id: Option<&str>,
+) -> Result<(), AppError>
+where
+    C: sea_orm::ConnectionTrait,
+{
+    let taken = custom_field_repo::find_by_title_in_space(conn, space_id, title)
+        .await?
+        .into_iter()
+        .filter(|item| Some(item.id.as_str()) != exclude_id)
+        .any(|item| {
+            project_id.is_none()
+                || item.project_id.is_none()
+                || item.project_id.as_deref() == project_id
+        });
+    if taken {
+        return Err(AppError::Validation(format!("自定义字段「{title}」已存在")));
+    }
+    Ok(())
+}
+
+/// 把作用域内任务里 `previous` 对应的字段按 `next` 改名并重新规范化取值。
+///
+/// 任意一个取值不符合新定义时整体报错，事务由调用方回滚。
+/// 改动的任务在同一事务里写字段日志并重建搜索索引；日志不带批次 id，
+/// 单独撤销任务取值会和字段定义对不上。
+async fn migrate_task_values<C>(
+    txn: &C,
+    previous: &custom_field_definitions::Model,
+    next: &custom_field_definitions::Model,
+    now: i64,
+) -> Result<(), AppError>
+where
+    C: sea_orm::ConnectionTrait,
+{
+    let options = custom_field_repo::parse_options(next.options.as_deref());
+    let mut changed_ids = Vec::new();
+    for task in custom_field_repo::find_tasks_in_scope(txn, previous).await? {
+        let Some(mut fields) =
+            custom_fields::parse_stored(task.custom_fields.as_deref()).map_err(|_| {
+                AppError::Validation(format!(
+                    "任务「{}」的自定义字段数据已损坏，无法迁移字段「{}」",
+                    task.title, previous.title
+                ))
+            })?
+        else {
+            continue;
+        };
+        let Some(index) = fields
+            .fields
+            .iter()
+            .position(|item| item.title == previous.title)
+        else {
+            continue;
+        };
+        if next.title != previous.title && fields.fields.iter().any(|item| item.title == next.title)
+        {
+            return Err(AppError::Validation(format!(
+                "任务「{}」已有字段「{}」，无法改名",
+                task.title, next.title
+            )));
+        }
+
+        let item = &mut fields.fields[index];
+        let value = match item.value.as_deref() {
+            Some(value) => Some(
+                custom_fields::normalize_value(&next.title, &next.field_type, &options, value)
+                    .map_err(|_| {
+                        AppError::Validation(format!(
+                            "任务「{}」的字段「{}」取值「{}」不符合新的字段定义",
+                            task.title, previous.title, value
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+        if item.title == next.title && item.value == value {
+            continue;
+        }
+        item.title = next.title.clone();
+        item.value = value;
+
+        let previous_value = task.custom_fields.clone();
+        let mut active_model = task.into_active_model();
+        active_model.custom_fields = Set(Some(custom_fields::serialize_custom_fields(&fields)?));
+        active_model.updated_at = Set(now);
+        let saved = task_mutation::update(txn, active_model).await?;
+
+        activity_logs::append_field_updated(
+            txn,
+            activity_logs::TaskLogCtx {
+                task_id: &saved.id,
+                space_id: &saved.space_id,
+                project_id: saved.project_id.as_deref(),
+                create_by: &saved.create_by,
+                created_at: now,
+                batch_id: None,
+            },
+            "customFields",
+            "自定义字段",
+            previous_value,
+            saved.custom_fields.clone(),
+        )
+        .await?;
+        changed_ids.push(saved.id);
+    }
+    search_repo::reindex_tasks(txn, &changed_ids).await
+}
@@ -8,9 +8,13 @@
 //! 纯查询命令不强制经过 service，避免制造空壳透传层。
 
 pub mod assets;
+pub mod custom_fields;
 pub mod project;
+pub mod reminder_scheduler;
 pub mod sync;
 pub mod task;
+pub mod trash;
+pub mod undo;
 
 #[allow(unused_imports)]
 pub use assets::{
@@ -20,6 +24,21 @@ pub use assets::{
 };
 #[allow(unused_imports)]
 pub use project::ProjectService;
-pub use project::{ProjectCreateInput, ProjectUpdateInput, ProjectUpdatePatch};
+pub use custom_fields::{
+    CustomFieldDefinitionCreateInput, CustomFieldDefinitionUpdatePatch, CustomFieldService,
+};
+pub use project::{
+    ProjectCloneInput, ProjectCreateInput, ProjectMilestoneCreateInput,
+    ProjectMilestoneUpdatePatch, ProjectReorderInput, ProjectTemplateInstantiateInput,
+    ProjectTemplateSaveInput, ProjectUpdateInput, ProjectUpdatePatch,
+};
+pub use reminder_scheduler::{ReminderScheduler, ReminderSchedulerHandle, SystemClock};
 pub use sync::{DatabaseUrlArgs, SyncCommandReport, SyncService};
-pub use task::{TaskCreateInput, TaskCreatePatch, TaskService, TaskUpdateInput, TaskUpdatePatch};
+pub use task::{
+    ChecklistItemCreateInput, ChecklistItemUpdateInput, ChecklistItemUpdatePatch, QuickAddInput,
+    TaskCreateInput, TaskCreatePatch, TaskDuplicateInput, TaskReorderInput, TaskService,
+    TaskTemplateCreateInput, TaskTemplateInstantiateInput, TaskTemplateUpdatePatch,
+    TaskUpdateInput, TaskUpdatePatch, TimerStartInput,
+};
+pub use trash::TrashService;
+pub use undo::UndoService;
@@ -3,10 +3,14 @@
 //! 这里把归档和取消归档放在同一个文件里，
 //! 因为它们共享几乎完全相同的约束和副作用。
 
-use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::{entities::projects, now_ms};
-use crate::repos::project_repo::{activity_logs, mutation, query};
+use crate::repos::{
+    project_repo::{activity_logs, mutation, query},
+    search_repo,
+};
 use crate::types::error::AppError;
 
 use super::{helpers::is_default_project_id, ProjectService};
@@ -14,12 +18,28 @@ use super::{helpers::is_default_project_id, ProjectService};
 impl ProjectService {
     /// 归档项目。
     pub async fn archive(conn: &DatabaseConnection, project_id: &str) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        Self::archive_in(&txn, project_id, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在调用方的事务里归档项目；已归档时不做任何事。
+    pub(crate) async fn archive_in<C>(
+        txn: &C,
+        project_id: &str,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
         if is_default_project_id(project_id) {
             return Err(AppError::Validation("默认项目不允许归档".to_string()));
         }
 
-        let txn = conn.begin().await.map_err(AppError::from)?;
-        let model = query::find_by_id(&txn, project_id).await?;
+        let model = query::find_by_id(txn, project_id).await?;
         if model.deleted_at.is_some() {
             return Err(AppError::Validation("已删除项目不允许归档".to_string()));
         }
@@ -27,34 +47,50 @@ impl ProjectService {
             return Ok(());
         }
 
-        let now = now_ms();
         let mut active_model: projects::ActiveModel = model.into_active_model();
         active_model.archived_at = Set(Some(now));
         active_model.updated_at = Set(now);
-        let saved_model = mutation::update(&txn, active_model).await?;
+        let saved_model = mutation::update(txn, active_model).await?;
         activity_logs::append_archived(
-            &txn,
+            txn,
             activity_logs::ProjectLogCtx {
                 project_id: saved_model.id.as_str(),
                 space_id: saved_model.space_id.as_str(),
                 create_by: saved_model.create_by.as_str(),
                 created_at: now,
+                batch_id,
             },
             saved_model.title.as_str(),
         )
         .await?;
-        txn.commit().await.map_err(AppError::from)?;
+        search_repo::reindex_projects(txn, std::slice::from_ref(&saved_model.id)).await?;
         Ok(())
     }
 
     /// 取消归档项目。
     pub async fn unarchive(conn: &DatabaseConnection, project_id: &str) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        Self::unarchive_in(&txn, project_id, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在调用方的事务里取消归档项目；未归档时不做任何事。
+    pub(crate) async fn unarchive_in<C>(
+        txn: &C,
+        project_id: &str,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
         if is_default_project_id(project_id) {
             return Err(AppError::Validation("默认项目不允许取消归档".to_string()));
         }
 
-        let txn = conn.begin().await.map_err(AppError::from)?;
-        let model = query::find_by_id(&txn, project_id).await?;
+        let model = query::find_by_id(txn, project_id).await?;
         if model.deleted_at.is_some() {
             return Err(AppError::Validation("已删除项目不允许取消归档".to_string()));
         }
@@ -62,23 +98,23 @@ impl ProjectService {
             return Ok(());
         }
 
-        let now = now_ms();
         let mut active_model: projects::ActiveModel = model.into_active_model();
         active_model.archived_at = Set(None);
         active_model.updated_at = Set(now);
-        let saved_model = mutation::update(&txn, active_model).await?;
+        let saved_model = mutation::update(txn, active_model).await?;
         activity_logs::append_unarchived(
-            &txn,
+            txn,
             activity_logs::ProjectLogCtx {
                 project_id: saved_model.id.as_str(),
                 space_id: saved_model.space_id.as_str(),
                 create_by: saved_model.create_by.as_str(),
                 created_at: now,
+                batch_id,
             },
             saved_model.title.as_str(),
         )
         .await?;
-        txn.commit().await.map_err(AppError::from)?;
+        search_repo::reindex_projects(txn, std::slice::from_ref(&saved_model.id)).await?;
         Ok(())
     }
 }
@@ -0,0 +1,224 @@
+//! 项目克隆用例。
+//!
+//! 约定：
+//! - 根项目追加到目标位置末尾；子项目保留原排序键，保持同级顺序不变
+//! - 跳过已删除、已归档的子项目与任务；已完成任务只在调用方要求时复制
+//! - 任务复制规则与 `TaskService::duplicate` 一致，见 `services::task::duplicate`
+//! - 每个新项目写一条“复制自”活动日志，结束后统一刷新项目统计与搜索索引
+
+use std::collections::HashMap;
+
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{
+    entities::{projects, sea_orm_active_enums::TaskStatus},
+    now_ms,
+};
+use crate::repos::{
+    link_repo::{self, LinkEntity},
+    project_repo::{activity_logs, helpers as repo_helpers, mutation, query, ProjectRepo},
+    search_repo,
+    tag_repo::{self, TagEntity},
+    task_repo::{query as task_query, stats},
+};
+use crate::services::task::{copy_task_in, TaskCopyTarget};
+use crate::types::{
+    dto::{LinkInputDto, ProjectDto},
+    error::AppError,
+};
+
+use super::{dto::ProjectCloneInput, ProjectService};
+
+impl ProjectService {
+    /// 深拷贝项目（可选连同子项目），返回新的根项目。
+    pub async fn clone_subtree(
+        conn: &DatabaseConnection,
+        input: ProjectCloneInput,
+    ) -> Result<ProjectDto, AppError> {
+        let source = query::find_by_id(conn, &input.project_id).await?;
+        if source.deleted_at.is_some() {
+            return Err(AppError::Validation("项目已删除，无法复制".to_string()));
+        }
+        let title = match input.title.as_deref().map(str::trim) {
+            Some("") => return Err(AppError::Validation("项目名称不能为空".to_string())),
+            Some(title) => title.to_string(),
+            None => source.title.clone(),
+        };
+
+        // parent_id 未传时与原项目同级。
+        let parent_id = match input.parent_id {
+            Some(parent_id) => parent_id,
+            None => source.parent_id.clone(),
+        };
+        let parent = match parent_id.as_deref() {
+            Some(parent_id) => {
+                let parent = query::find_by_id(conn, parent_id).await?;
+                if parent.deleted_at.is_some() {
+                    return Err(AppError::Validation(format!("父项目 {parent_id} 已删除")));
+                }
+                Some(parent)
+            }
+            None => None,
+        };
+        let space_id = match (&parent, input.space_id) {
+            (Some(parent), Some(space_id)) if parent.space_id != space_id => {
+                return Err(AppError::Validation(format!(
+                    "父项目 {} 不属于 space {space_id}",
+                    parent.id
+                )));
+            }
+            (Some(parent), _) => parent.space_id.clone(),
+            (None, Some(space_id)) => space_id,
+            (None, None) => source.space_id.clone(),
+        };
+
+        // 先在克隆前收集源子树，避免克隆到自身子树下时把新项目也算进来。
+        let mut subtree = if input.include_children {
+            let subtree_ids = ProjectRepo::collect_subtree_ids(conn, &source.id).await?;
+            query::find_not_deleted_by_ids(conn, &subtree_ids)
+                .await?
+                .into_iter()
+                .filter(|project| project.id == source.id || project.archived_at.is_none())
+                .collect::<Vec<_>>()
+        } else {
+            vec![source.clone()]
+        };
+        subtree.sort_by(|a, b| (&a.rank, a.created_at).cmp(&(&b.rank, b.created_at)));
+        let project_ids = subtree
+            .iter()
+            .map(|project| project.id.clone())
+            .collect::<Vec<_>>();
+        let mut project_tags = tag_repo::load_tags(conn, TagEntity::Project, &project_ids).await?;
+        let mut project_links =
+            link_repo::load_links(conn, LinkEntity::Project, &project_ids).await?;
+        let mut tasks_by_project = HashMap::new();
+        for task in task_query::find_not_deleted_by_project_ids(conn, &project_ids).await? {
+            if task.archived_at.is_some()
+                || (!input.include_done_tasks && task.status == TaskStatus::Done)
+            {
+                continue;
+            }
+            if let Some(project_id) = task.project_id.clone() {
+                tasks_by_project
+                    .entry(project_id)
+                    .or_insert_with(Vec::new)
+                    .push(task);
+            }
+        }
+        let mut children_by_parent: HashMap<String, Vec<projects::Model>> = HashMap::new();
+        for project in subtree {
+            if let Some(parent_id) = project.parent_id.clone() {
+                children_by_parent
+                    .entry(parent_id)
+                    .or_default()
+                    .push(project);
+            }
+        }
+
+        let now = now_ms();
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        let root_rank = query::next_rank_in_scope(&txn, &space_id, parent_id.as_deref()).await?;
+        let mut new_project_ids = Vec::new();
+        let mut new_task_ids = Vec::new();
+        // 先序遍历，保证父项目先于子项目插入，子项目 path 才能拼到父路径上。
+        let mut stack = vec![(source, parent_id, title, root_rank)];
+        while let Some((project, new_parent_id, title, rank)) = stack.pop() {
+            let id = Uuid::new_v4().to_string();
+            let path =
+                repo_helpers::build_project_path(&txn, &space_id, new_parent_id.as_deref(), &title)
+                    .await?;
+            let copy = mutation::insert(
+                &txn,
+                mutation::NewProjectRecord {
+                    id: id.clone(),
+                    space_id: space_id.clone(),
+                    parent_id: new_parent_id,
+                    path,
+                    title,
+                    note: project.note.clone(),
+                    priority: project.priority.clone(),
+                    rank,
+                    lifecycle_status: repo_helpers::LIFECYCLE_ACTIVE.to_string(),
+                    created_at: now,
+                    updated_at: now,
+                    create_by: "stonefish".to_string(),
+                },
+            )
+            .await?;
+
+            let tags = project_tags.remove(&project.id).unwrap_or_default();
+            if !tags.is_empty() {
+                tag_repo::sync_tags(&txn, TagEntity::Project, &id, &tags, now).await?;
+            }
+            let links = project_links
+                .remove(&project.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|link| LinkInputDto {
+                    id: None,
+                    title: link.title,
+                    url: link.url,
+                    kind: link.kind,
+                    rank: Some(link.rank),
+                })
+                .collect::<Vec<_>>();
+            if !links.is_empty() {
+                link_repo::sync_links(&txn, LinkEntity::Project, &id, &links).await?;
+            }
+            activity_logs::append_cloned(
+                &txn,
+                activity_logs::ProjectLogCtx {
+                    project_id: &copy.id,
+                    space_id: &copy.space_id,
+                    create_by: &copy.create_by,
+                    created_at: now,
+                    batch_id: Some(&batch_id),
+                },
+                &project.id,
+                &project.title,
+            )
+            .await?;
+
+            for task in tasks_by_project.remove(&project.id).unwrap_or_default() {
+                let task_copy = copy_task_in(
+                    &txn,
+                    &task,
+                    TaskCopyTarget {
+                        space_id: &space_id,
+                        project_id: Some(&id),
+                        keep_done: true,
+                        batch_id: Some(&batch_id),
+                    },
+                    now,
+                )
+                .await?;
+                new_task_ids.push(task_copy.id);
+            }
+            stats::refresh_project_stats(&txn, &id, now).await?;
+
+            for child in children_by_parent
+                .remove(&project.id)
+                .unwrap_or_default()
+                .into_iter()
+                .rev()
+            {
+                let child_title = child.title.clone();
+                let child_rank = child.rank.clone();
+                stack.push((child, Some(id.clone()), child_title, child_rank));
+            }
+            new_project_ids.push(id);
+        }
+        search_repo::reindex_projects(&txn, &new_project_ids).await?;
+        search_repo::reindex_tasks(&txn, &new_task_ids).await?;
+        txn.commit().await.map_err(AppError::from)?;
+
+        // 统计在事务里刷新过，这里重新读一次根项目拿到最新计数。
+        let root = query::find_by_id(conn, &new_project_ids[0]).await?;
+        let mut dto = repo_helpers::project_model_to_dto(root);
+        repo_helpers::attach_links(conn, std::slice::from_mut(&mut dto)).await?;
+        repo_helpers::attach_tags(conn, std::slice::from_mut(&mut dto)).await?;
+        Ok(dto)
+    }
+}
@@ -6,14 +6,15 @@
 //! - 标签 / 链接写入
 //! - 创建活动日志
 
-use sea_orm::{DatabaseConnection, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
 use uuid::Uuid;
 
-use crate::db::now_ms;
+use crate::db::{entities::projects, now_ms};
 use crate::repos::{
     common_task_utils,
     link_repo::{self, LinkEntity},
     project_repo::{activity_logs, helpers as repo_helpers, mutation, query},
+    rank, search_repo,
     tag_repo::{self, TagEntity},
 };
 use crate::types::{dto::ProjectDto, error::AppError};
@@ -27,6 +28,27 @@ impl ProjectService {
         input: ProjectCreateInput,
     ) -> Result<ProjectDto, AppError> {
         let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        let project = Self::create_in(&txn, input, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+
+        // 提交后再补 DTO 扩展字段，避免把查询型拼装塞进事务里。
+        let mut dto = repo_helpers::project_model_to_dto(project);
+        repo_helpers::attach_links(conn, std::slice::from_mut(&mut dto)).await?;
+        repo_helpers::attach_tags(conn, std::slice::from_mut(&mut dto)).await?;
+        Ok(dto)
+    }
+
+    /// 在调用方的事务里创建项目，供需要一次创建多个实体的用例组合。
+    pub(crate) async fn create_in<C>(
+        txn: &C,
+        input: ProjectCreateInput,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<projects::Model, AppError>
+    where
+        C: ConnectionTrait,
+    {
         let title = input.title.trim();
         if title.is_empty() {
             return Err(AppError::Validation("项目名称不能为空".to_string()));
@@ -43,22 +65,28 @@ impl ProjectService {
         let links = input.links.unwrap_or_default();
         // 路径在 service 层构建，因为它依赖父子关系这类业务语义。
         let priority = common_task_utils::parse_priority(input.priority.as_deref())?;
+        let lifecycle_status = match input.lifecycle_status.as_deref() {
+            Some(value) => repo_helpers::parse_lifecycle_status(value)?,
+            None => repo_helpers::LIFECYCLE_ACTIVE,
+        };
         let path = repo_helpers::build_project_path(
-            &txn,
+            txn,
             &input.space_id,
             input.parent_id.as_deref(),
             title,
         )
         .await?;
-        let now = now_ms();
         let id = Uuid::new_v4().to_string();
-        let rank = input.rank.unwrap_or(
-            query::next_rank_in_scope(&txn, &input.space_id, input.parent_id.as_deref()).await?,
-        );
+        let rank = match input.rank.as_deref() {
+            Some(value) => rank::validate(value)?,
+            None => {
+                query::next_rank_in_scope(txn, &input.space_id, input.parent_id.as_deref()).await?
+            }
+        };
 
         // 项目主记录先插入，后面的标签和链接都依赖项目 id。
         let project = mutation::insert(
-            &txn,
+            txn,
             mutation::NewProjectRecord {
                 id: id.clone(),
                 space_id: input.space_id,
@@ -68,6 +96,7 @@ impl ProjectService {
                 note,
                 priority,
                 rank,
+                lifecycle_status: lifecycle_status.to_string(),
                 created_at: now,
                 updated_at: now,
                 create_by: "stonefish".to_string(),
@@ -76,29 +105,25 @@ impl ProjectService {
         .await?;
 
         if !tags.is_empty() {
-            tag_repo::sync_tags(&txn, TagEntity::Project, &id, &tags, now).await?;
+            tag_repo::sync_tags(txn, TagEntity::Project, &id, &tags, now).await?;
         }
         if !links.is_empty() {
-            link_repo::sync_links(&txn, LinkEntity::Project, &id, &links).await?;
+            link_repo::sync_links(txn, LinkEntity::Project, &id, &links).await?;
         }
         activity_logs::append_created(
-            &txn,
+            txn,
             activity_logs::ProjectLogCtx {
                 project_id: project.id.as_str(),
                 space_id: project.space_id.as_str(),
                 create_by: project.create_by.as_str(),
                 created_at: now,
+                batch_id,
             },
             project.title.as_str(),
         )
         .await?;
+        search_repo::reindex_projects(txn, std::slice::from_ref(&id)).await?;
 
-        txn.commit().await.map_err(AppError::from)?;
-
-        // 提交后再补 DTO 扩展字段，避免把查询型拼装塞进事务里。
-        let mut dto = repo_helpers::project_model_to_dto(project);
-        repo_helpers::attach_links(conn, std::slice::from_mut(&mut dto)).await?;
-        repo_helpers::attach_tags(conn, std::slice::from_mut(&mut dto)).await?;
-        Ok(dto)
+        Ok(project)
     }
 }
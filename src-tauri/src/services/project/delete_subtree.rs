@@ -2,11 +2,13 @@
 //!
 //! 删除项目不是只删单个节点，而是要把整棵子树和其下任务一起软删除。
 
-use sea_orm::{DatabaseConnection, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::now_ms;
 use crate::repos::project_repo::{activity_logs, mutation, query, ProjectRepo};
-use crate::repos::task_repo::TaskRepo;
+use crate::repos::search_repo;
+use crate::repos::task_repo::{stats, TaskRepo};
 use crate::types::error::AppError;
 
 use super::ProjectService;
@@ -18,28 +20,50 @@ impl ProjectService {
         project_id: &str,
     ) -> Result<(), AppError> {
         let txn = conn.begin().await.map_err(AppError::from)?;
-        let subtree_project_ids = ProjectRepo::collect_subtree_ids(&txn, project_id).await?;
-        let project_models = query::find_not_deleted_by_ids(&txn, &subtree_project_ids).await?;
-        let now = now_ms();
+        let batch_id = Uuid::new_v4().to_string();
+        Self::delete_subtree_in(&txn, project_id, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在调用方的事务里软删除项目子树；已删除的节点和任务保持原删除时间。
+    pub(crate) async fn delete_subtree_in<C>(
+        txn: &C,
+        project_id: &str,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let parent_id = query::find_by_id(txn, project_id).await?.parent_id;
+        let subtree_project_ids = ProjectRepo::collect_subtree_ids(txn, project_id).await?;
+        let project_models = query::find_not_deleted_by_ids(txn, &subtree_project_ids).await?;
         // 先删任务，再删项目，保持引用关系从叶子向上收敛。
-        TaskRepo::soft_delete_by_project_ids(&txn, &subtree_project_ids, now).await?;
-        mutation::soft_delete_by_ids(&txn, &subtree_project_ids, now).await?;
+        TaskRepo::soft_delete_by_project_ids(txn, &subtree_project_ids, now).await?;
+        mutation::soft_delete_by_ids(txn, &subtree_project_ids, now).await?;
 
         for project in &project_models {
             activity_logs::append_deleted(
-                &txn,
+                txn,
                 activity_logs::ProjectLogCtx {
                     project_id: project.id.as_str(),
                     space_id: project.space_id.as_str(),
                     create_by: project.create_by.as_str(),
                     created_at: now,
+                    batch_id,
                 },
                 project.title.as_str(),
             )
             .await?;
         }
 
-        txn.commit().await.map_err(AppError::from)?;
+        if let Some(parent_id) = parent_id.as_deref() {
+            stats::refresh_subtree_rollups(txn, parent_id).await?;
+        }
+
+        search_repo::reindex_projects(txn, &subtree_project_ids).await?;
+        search_repo::reindex_tasks_by_project_ids(txn, &subtree_project_ids).await?;
         Ok(())
     }
 }
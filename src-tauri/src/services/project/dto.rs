@@ -2,6 +2,8 @@
 //!
 //! 这些结构体位于 service 边界，用来表达项目写用例需要的输入语义。
 
+use std::collections::HashMap;
+
 use crate::types::dto::LinkInputDto;
 
 /// 创建项目用例的输入。
@@ -12,9 +14,11 @@ pub struct ProjectCreateInput {
     pub parent_id: Option<String>,
     pub note: Option<String>,
     pub priority: Option<String>,
-    pub rank: Option<i64>,
+    pub rank: Option<String>,
     pub tags: Option<Vec<String>>,
     pub links: Option<Vec<LinkInputDto>>,
+    /// 初始生命周期状态；不传为 active
+    pub lifecycle_status: Option<String>,
 }
 
 /// 更新项目用例的输入。
@@ -34,4 +38,87 @@ pub struct ProjectUpdatePatch {
     pub parent_id: Option<Option<String>>,
     pub tags: Option<Vec<String>>,
     pub links: Option<Vec<LinkInputDto>>,
+    /// 生命周期状态，按状态机校验流转
+    pub lifecycle_status: Option<String>,
+    /// Some(None) 表示清空开始时间
+    pub start_at: Option<Option<i64>>,
+    /// Some(None) 表示清空截止时间
+    pub due_at: Option<Option<i64>>,
+}
+
+/// 项目拖拽排序用例的输入。
+#[derive(Debug, Clone, Default)]
+pub struct ProjectReorderInput {
+    pub project_id: String,
+    /// 显式指定的排序键；传入时忽略前后邻居
+    pub new_rank: Option<String>,
+    /// 移动后紧挨在上方的同级项目
+    pub prev_project_id: Option<String>,
+    /// 移动后紧挨在下方的同级项目
+    pub next_project_id: Option<String>,
+    /// - None：不修改 parent
+    /// - Some(None)：显式设为根节点
+    /// - Some(Some(x))：移动到 x 下面
+    pub new_parent_id: Option<Option<String>>,
+}
+
+/// 克隆项目子树的输入。
+#[derive(Debug, Clone, Default)]
+pub struct ProjectCloneInput {
+    pub project_id: String,
+    /// - None：与原项目同级
+    /// - Some(None)：放到根节点
+    /// - Some(Some(x))：放到 x 下面
+    pub parent_id: Option<Option<String>>,
+    /// 放到根节点时的目标 space；为空时沿用原项目所在 space
+    pub space_id: Option<String>,
+    /// 覆盖根项目标题
+    pub title: Option<String>,
+    /// 为 true 时连同子项目一起复制
+    pub include_children: bool,
+    /// 为 true 时已完成的任务也一起复制，并保持完成状态
+    pub include_done_tasks: bool,
+}
+
+/// 把现有项目子树保存为模板的输入。
+#[derive(Debug, Clone, Default)]
+pub struct ProjectTemplateSaveInput {
+    pub project_id: String,
+    pub name: String,
+    /// 为 true 时已完成的任务也作为起始任务保存
+    pub include_done_tasks: bool,
+}
+
+/// 按模板创建项目树的输入。
+#[derive(Debug, Clone, Default)]
+pub struct ProjectTemplateInstantiateInput {
+    pub template_id: String,
+    /// 目标 space；挂到父项目下时可以省略，以父项目所在 space 为准
+    pub space_id: Option<String>,
+    pub parent_id: Option<String>,
+    /// 覆盖根项目标题
+    pub title: Option<String>,
+    /// 占位符取值，同名时覆盖内置的 date / space 等
+    pub variables: HashMap<String, String>,
+    /// 展开日期占位符用的 UTC 偏移分钟数；为空时使用系统时区
+    pub utc_offset_minutes: Option<i32>,
+}
+
+/// 新建里程碑用例的输入。
+#[derive(Debug, Clone)]
+pub struct ProjectMilestoneCreateInput {
+    pub project_id: String,
+    pub title: String,
+    pub target_at: Option<i64>,
+    pub rank: Option<i64>,
+}
+
+/// 里程碑 patch 更新模型。
+#[derive(Debug, Clone, Default)]
+pub struct ProjectMilestoneUpdatePatch {
+    pub title: Option<String>,
+    pub target_at: Option<Option<i64>>,
+    pub rank: Option<i64>,
+    /// true 标记完成，false 重新打开
+    pub completed: Option<bool>,
 }
@@ -1,4 +1,14 @@
+use sea_orm::ConnectionTrait;
+
 use crate::db::entities::sea_orm_active_enums::Priority;
+use crate::repos::project_repo::{
+    helpers::{
+        LIFECYCLE_ACTIVE, LIFECYCLE_CANCELLED, LIFECYCLE_COMPLETED, LIFECYCLE_PAUSED,
+        LIFECYCLE_PLANNED,
+    },
+    query,
+};
+use crate::types::error::AppError;
 
 /// 判断一个项目是否是系统默认项目。
 pub(super) fn is_default_project_id(project_id: &str) -> bool {
@@ -15,3 +25,77 @@ pub(super) fn priority_to_string(priority: &Priority) -> String {
     }
     .to_string()
 }
+
+/// 生命周期状态机：完成只能重新激活，取消可以回到计划或直接激活。
+pub(super) fn ensure_lifecycle_transition(from: &str, to: &str) -> Result<(), AppError> {
+    let allowed: &[&str] = match from {
+        LIFECYCLE_PLANNED => &[LIFECYCLE_ACTIVE, LIFECYCLE_CANCELLED],
+        LIFECYCLE_ACTIVE => &[
+            LIFECYCLE_PLANNED,
+            LIFECYCLE_PAUSED,
+            LIFECYCLE_COMPLETED,
+            LIFECYCLE_CANCELLED,
+        ],
+        LIFECYCLE_PAUSED => &[LIFECYCLE_ACTIVE, LIFECYCLE_COMPLETED, LIFECYCLE_CANCELLED],
+        LIFECYCLE_COMPLETED => &[LIFECYCLE_ACTIVE],
+        LIFECYCLE_CANCELLED => &[LIFECYCLE_PLANNED, LIFECYCLE_ACTIVE],
+        _ => &[],
+    };
+    if from == to || allowed.contains(&to) {
+        return Ok(());
+    }
+    Err(AppError::Validation(format!(
+        "项目状态不能从 {from} 变更为 {to}"
+    )))
+}
+
+/// 校验项目排期：开始不晚于截止，落在父项目范围内，并且仍能容下直接子项目。
+///
+/// 任何一端为空都视为不限制。
+pub(super) async fn ensure_schedule_fits<C>(
+    conn: &C,
+    project_id: &str,
+    parent_id: Option<&str>,
+    start_at: Option<i64>,
+    due_at: Option<i64>,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if let (Some(start_at), Some(due_at)) = (start_at, due_at) {
+        if start_at > due_at {
+            return Err(AppError::Validation(
+                "项目开始时间不能晚于截止时间".to_string(),
+            ));
+        }
+    }
+    if let Some(parent_id) = parent_id {
+        let parent = query::find_by_id(conn, parent_id).await?;
+        if !schedule_contains(parent.start_at, parent.due_at, start_at, due_at) {
+            return Err(AppError::Validation(format!(
+                "项目日期超出了父项目「{}」的日期范围",
+                parent.title
+            )));
+        }
+    }
+    for child in query::find_children(conn, project_id).await? {
+        if !schedule_contains(start_at, due_at, child.start_at, child.due_at) {
+            return Err(AppError::Validation(format!(
+                "子项目「{}」的日期超出了该项目的日期范围",
+                child.title
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn schedule_contains(
+    outer_start: Option<i64>,
+    outer_due: Option<i64>,
+    start_at: Option<i64>,
+    due_at: Option<i64>,
+) -> bool {
+    [start_at, due_at].into_iter().flatten().all(|value| {
+        outer_start.is_none_or(|start| start <= value) && outer_due.is_none_or(|due| value <= due)
+    })
+}
@@ -0,0 +1,231 @@
+//! 项目里程碑用例。
+//!
+//! 约定：
+//! - 里程碑只挂在未删除的项目上，任务通过 `TaskUpdatePatch.milestone_id` 归入
+//! - 任务数与完成数随项目统计一起刷新，见 `task_repo::stats`
+//! - 删除里程碑时把任务摘下来，任务本身保留在项目里
+//! - 所有变化都记到项目活动日志，但不进撤销批次
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{
+    entities::{project_milestones, projects},
+    now_ms,
+};
+use crate::repos::{
+    project_repo::{
+        activity_logs::{
+            self, ProjectLogCtx, ACTION_MILESTONE_COMPLETED, ACTION_MILESTONE_CREATED,
+            ACTION_MILESTONE_DELETED, ACTION_MILESTONE_REOPENED, ACTION_MILESTONE_UPDATED,
+        },
+        milestones::{self, NewMilestoneRecord},
+        query,
+    },
+    task_repo::stats,
+};
+use crate::types::{dto::ProjectMilestoneDto, error::AppError};
+
+use super::{
+    dto::{ProjectMilestoneCreateInput, ProjectMilestoneUpdatePatch},
+    ProjectService,
+};
+
+impl ProjectService {
+    /// 新建里程碑。
+    pub async fn create_milestone(
+        conn: &DatabaseConnection,
+        input: ProjectMilestoneCreateInput,
+    ) -> Result<ProjectMilestoneDto, AppError> {
+        let title = normalize_title(&input.title)?;
+        if let Some(rank) = input.rank {
+            validate_rank(rank)?;
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let project = find_active_project(&txn, &input.project_id).await?;
+        let now = now_ms();
+        let rank = match input.rank {
+            Some(rank) => rank,
+            None => milestones::next_rank(&txn, &project.id).await?,
+        };
+        let model = milestones::insert(
+            &txn,
+            NewMilestoneRecord {
+                id: Uuid::new_v4().to_string(),
+                project_id: project.id.clone(),
+                space_id: project.space_id.clone(),
+                title,
+                target_at: input.target_at,
+                rank,
+                created_at: now,
+            },
+        )
+        .await?;
+        activity_logs::append_milestone(
+            &txn,
+            log_ctx(&project, now),
+            ACTION_MILESTONE_CREATED,
+            &model.id,
+            format!("新建里程碑「{}」", model.title),
+        )
+        .await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(milestones::to_dto(model))
+    }
+
+    /// 更新里程碑；标记完成 / 重新打开也走这里。
+    pub async fn update_milestone(
+        conn: &DatabaseConnection,
+        id: &str,
+        patch: ProjectMilestoneUpdatePatch,
+    ) -> Result<ProjectMilestoneDto, AppError> {
+        let title = patch.title.as_deref().map(normalize_title).transpose()?;
+        if let Some(rank) = patch.rank {
+            validate_rank(rank)?;
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let previous = milestones::find_by_id(&txn, id).await?;
+        let project = find_active_project(&txn, &previous.project_id).await?;
+        let now = now_ms();
+
+        let mut active_model = previous.clone().into_active_model();
+        if let Some(title) = title {
+            active_model.title = Set(title);
+        }
+        if let Some(target_at) = patch.target_at {
+            active_model.target_at = Set(target_at);
+        }
+        if let Some(rank) = patch.rank {
+            active_model.rank = Set(rank);
+        }
+        match patch.completed {
+            Some(true) if previous.completed_at.is_none() => {
+                active_model.completed_at = Set(Some(now));
+            }
+            Some(false) => active_model.completed_at = Set(None),
+            _ => {}
+        }
+        active_model.updated_at = Set(now);
+        let saved = milestones::update(&txn, active_model).await?;
+
+        for (action, detail) in describe_changes(&previous, &saved) {
+            activity_logs::append_milestone(
+                &txn,
+                log_ctx(&project, now),
+                action,
+                &saved.id,
+                detail,
+            )
+            .await?;
+        }
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(milestones::to_dto(saved))
+    }
+
+    /// 删除里程碑；挂在上面的任务移出里程碑，仍留在项目里。
+    pub async fn delete_milestone(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let model = milestones::find_by_id(&txn, id).await?;
+        let project = query::find_by_id(&txn, &model.project_id).await?;
+        let now = now_ms();
+
+        let task_ids =
+            milestones::task_ids_in_milestones(&txn, std::slice::from_ref(&model.id)).await?;
+        milestones::detach_tasks(&txn, &task_ids, now).await?;
+
+        let title = model.title.clone();
+        let milestone_id = model.id.clone();
+        let mut active_model = model.into_active_model();
+        active_model.deleted_at = Set(Some(now));
+        active_model.updated_at = Set(now);
+        milestones::update(&txn, active_model).await?;
+
+        activity_logs::append_milestone(
+            &txn,
+            log_ctx(&project, now),
+            ACTION_MILESTONE_DELETED,
+            &milestone_id,
+            format!("删除里程碑「{title}」"),
+        )
+        .await?;
+        if !task_ids.is_empty() {
+            stats::refresh_project_stats(&txn, &project.id, now).await?;
+        }
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+async fn find_active_project<C>(conn: &C, project_id: &str) -> Result<projects::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    let project = query::find_by_id(conn, project_id).await?;
+    if project.deleted_at.is_some() {
+        return Err(AppError::Validation(
+            "已删除项目不允许修改里程碑".to_string(),
+        ));
+    }
+    Ok(project)
+}
+
+/// 里程碑日志不带批次 id：撤销计划不回滚里程碑，
+/// 归入批次只会让撤销报告成功却什么也没改。
+fn log_ctx(project: &projects::Model, now: i64) -> ProjectLogCtx<'_> {
+    ProjectLogCtx {
+        project_id: &project.id,
+        space_id: &project.space_id,
+        create_by: &project.create_by,
+        created_at: now,
+        batch_id: None,
+    }
+}
+
+fn normalize_title(title: &str) -> Result<String, AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::Validation("里程碑名称不能为空".to_string()));
+    }
+    Ok(title.to_string())
+}
+
+fn validate_rank(rank: i64) -> Result<(), AppError> {
+    if rank < 0 {
+        return Err(AppError::Validation("里程碑排序必须为非负整数".to_string()));
+    }
+    Ok(())
+}
+
+/// 按变化生成日志动作与描述；排序调整不记日志。
+fn describe_changes(
+    previous: &project_milestones::Model,
+    saved: &project_milestones::Model,
+) -> Vec<(&'static str, String)> {
+    let mut changes = Vec::new();
+    if previous.title != saved.title {
+        changes.push((
+            ACTION_MILESTONE_UPDATED,
+            format!("里程碑「{}」重命名为「{}」", previous.title, saved.title),
+        ));
+    }
+    if previous.target_at != saved.target_at {
+        changes.push((
+            ACTION_MILESTONE_UPDATED,
+            format!("调整里程碑「{}」的目标日期", saved.title),
+        ));
+    }
+    match (previous.completed_at, saved.completed_at) {
+        (None, Some(_)) => changes.push((
+            ACTION_MILESTONE_COMPLETED,
+            format!("完成里程碑「{}」", saved.title),
+        )),
+        (Some(_), None) => changes.push((
+            ACTION_MILESTONE_REOPENED,
+            format!("重新打开里程碑「{}」", saved.title),
+        )),
+        _ => {}
+    }
+    changes
+}
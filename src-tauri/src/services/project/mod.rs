@@ -1,17 +1,26 @@
 //! 项目写用例服务。
 //!
 //! 本模块将承接项目创建、更新、删除子树、恢复、归档、
-//! 取消归档与排序等写用例，并统一管理跨 repo 事务编排。
+//! 取消归档、排序、子树克隆、项目模板、里程碑与撤销计划等写用例，并统一管理跨 repo 事务编排。
 
 mod archive;
+mod clone;
 mod create;
 mod delete_subtree;
 mod dto;
 mod helpers;
+mod milestones;
 mod reorder;
 mod restore;
+mod templates;
+mod undo;
 mod update;
 
-pub use dto::{ProjectCreateInput, ProjectUpdateInput, ProjectUpdatePatch};
+pub use dto::{
+    ProjectCloneInput, ProjectCreateInput, ProjectMilestoneCreateInput,
+    ProjectMilestoneUpdatePatch, ProjectReorderInput, ProjectTemplateInstantiateInput,
+    ProjectTemplateSaveInput, ProjectUpdateInput, ProjectUpdatePatch,
+};
+pub(crate) use undo::ProjectUndoPlan;
 
 pub struct ProjectService;
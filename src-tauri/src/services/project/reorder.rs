@@ -2,25 +2,49 @@
 //!
 //! 项目排序除了改 rank，还可能伴随父节点变化，
 //! 因此有时还要一起重建 path 并回刷子节点路径。
+//! rank 与任务一样是分数排序键，单次拖拽只根据前后邻居算出新键。
 
 use sea_orm::{DatabaseConnection, Set, TransactionTrait};
 
 use crate::db::{entities::projects, now_ms};
-use crate::repos::project_repo::{helpers as repo_helpers, mutation, query};
+use crate::repos::{
+    project_repo::{helpers as repo_helpers, mutation, query},
+    rank,
+    task_repo::stats,
+};
 use crate::types::error::AppError;
 
-use super::ProjectService;
+use super::{dto::ProjectReorderInput, helpers::ensure_schedule_fits, ProjectService};
 
 impl ProjectService {
-    /// 调整单个项目的 rank，并在必要时同步父节点与路径。
+    /// 调整单个项目的 rank，并在必要时同步父节点与路径；返回新的排序键。
     pub async fn reorder(
         conn: &DatabaseConnection,
-        project_id: &str,
-        new_rank: i64,
-        new_parent_id: Option<Option<String>>,
-    ) -> Result<(), AppError> {
+        input: ProjectReorderInput,
+    ) -> Result<String, AppError> {
+        let ProjectReorderInput {
+            project_id,
+            new_rank,
+            prev_project_id,
+            next_project_id,
+            new_parent_id,
+        } = input;
         let txn = conn.begin().await.map_err(AppError::from)?;
-        let model = query::find_by_id(&txn, project_id).await?;
+        let model = query::find_by_id(&txn, &project_id).await?;
+        let new_rank = match new_rank.as_deref() {
+            Some(value) => rank::validate(value)?,
+            None => {
+                let prev = match prev_project_id.as_deref() {
+                    Some(id) => Some(query::find_by_id(&txn, id).await?.rank),
+                    None => None,
+                };
+                let next = match next_project_id.as_deref() {
+                    Some(id) => Some(query::find_by_id(&txn, id).await?.rank),
+                    None => None,
+                };
+                rank::between(prev.as_deref(), next.as_deref())?
+            }
+        };
         let now = now_ms();
         let old_path = model.path.clone();
         let old_parent_id = model.parent_id.clone();
@@ -28,13 +52,21 @@ impl ProjectService {
         let mut path_changed = false;
 
         let mut active_model: projects::ActiveModel = model.clone().into();
-        active_model.rank = Set(new_rank);
+        active_model.rank = Set(new_rank.clone());
         active_model.updated_at = Set(now);
 
         // 只有父节点真的变化时，才需要重建 path 并回刷后代路径。
         if let Some(parent_id) = new_parent_id {
             active_model.parent_id = Set(parent_id.clone());
             if old_parent_id != parent_id {
+                ensure_schedule_fits(
+                    &txn,
+                    &project_id,
+                    parent_id.as_deref(),
+                    model.start_at,
+                    model.due_at,
+                )
+                .await?;
                 next_path = repo_helpers::build_project_path(
                     &txn,
                     &model.space_id,
@@ -51,25 +83,28 @@ impl ProjectService {
         if path_changed {
             mutation::rebase_descendant_paths(&txn, &model.space_id, &old_path, &next_path, now)
                 .await?;
+            if let Some(old_parent_id) = old_parent_id.as_deref() {
+                stats::refresh_subtree_rollups(&txn, old_parent_id).await?;
+            }
+            stats::refresh_subtree_rollups(&txn, &project_id).await?;
         }
         txn.commit().await.map_err(AppError::from)?;
-        Ok(())
+        Ok(new_rank)
     }
 
-    /// 按给定顺序批量重排项目 rank。
+    /// 按给定顺序为一组项目重新生成均匀分布的排序键。
     pub async fn rebalance(
         conn: &DatabaseConnection,
         project_ids: &[String],
-        step: i64,
     ) -> Result<(), AppError> {
         let txn = conn.begin().await.map_err(AppError::from)?;
         let now = now_ms();
-        for (index, project_id) in project_ids.iter().enumerate() {
+        for (project_id, next_rank) in project_ids.iter().zip(rank::spread(project_ids.len())) {
             let Some(model) = query::find_optional_by_id(&txn, project_id).await? else {
                 continue;
             };
             let mut active_model: projects::ActiveModel = model.into();
-            active_model.rank = Set((index as i64) * step);
+            active_model.rank = Set(next_rank);
             active_model.updated_at = Set(now);
             mutation::update(&txn, active_model).await?;
         }
@@ -1,9 +1,14 @@
 //! 项目恢复用例。
 
-use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::{entities::projects, now_ms};
-use crate::repos::project_repo::{activity_logs, mutation, query};
+use crate::repos::{
+    project_repo::{activity_logs, mutation, query},
+    search_repo,
+    task_repo::stats,
+};
 use crate::types::error::AppError;
 
 use super::ProjectService;
@@ -12,24 +17,41 @@ impl ProjectService {
     /// 恢复已软删除项目。
     pub async fn restore(conn: &DatabaseConnection, project_id: &str) -> Result<(), AppError> {
         let txn = conn.begin().await.map_err(AppError::from)?;
-        let model = query::find_by_id(&txn, project_id).await?;
-        let now = now_ms();
+        let batch_id = Uuid::new_v4().to_string();
+        Self::restore_in(&txn, project_id, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在调用方的事务里恢复单个项目。
+    pub(crate) async fn restore_in<C>(
+        txn: &C,
+        project_id: &str,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let model = query::find_by_id(txn, project_id).await?;
         let mut active_model: projects::ActiveModel = model.into_active_model();
         active_model.deleted_at = Set(None);
         active_model.updated_at = Set(now);
-        let saved_model = mutation::update(&txn, active_model).await?;
+        let saved_model = mutation::update(txn, active_model).await?;
         activity_logs::append_restored(
-            &txn,
+            txn,
             activity_logs::ProjectLogCtx {
                 project_id: saved_model.id.as_str(),
                 space_id: saved_model.space_id.as_str(),
                 create_by: saved_model.create_by.as_str(),
                 created_at: now,
+                batch_id,
             },
             saved_model.title.as_str(),
         )
         .await?;
-        txn.commit().await.map_err(AppError::from)?;
+        stats::refresh_subtree_rollups(txn, &saved_model.id).await?;
+        search_repo::reindex_projects(txn, std::slice::from_ref(&saved_model.id)).await?;
         Ok(())
     }
 }
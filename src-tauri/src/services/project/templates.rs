@@ -0,0 +1,313 @@
+//! 项目模板用例。
+//!
+//! 约定：
+//! - 保存时抓取整棵子树：子项目、未删除未归档的任务，以及它们的标签、链接和自定义字段
+//! - 任务的截止 / 计划时间保存为相对根项目创建时间的偏移，实例化时换算到当前时刻
+//! - 实例化在一个事务里逐个调用项目 / 任务的创建用例，path 与 rank 按正常创建规则生成
+
+use std::collections::HashMap;
+
+use sea_orm::{DatabaseConnection, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{
+    entities::{project_templates, projects, sea_orm_active_enums::TaskStatus},
+    now_ms,
+};
+use crate::repos::{
+    link_repo::{self, LinkEntity},
+    project_repo::{helpers as repo_helpers, query, templates, ProjectRepo},
+    space_repo::SpaceRepo,
+    tag_repo::{self, TagEntity},
+    task_repo::{
+        links as task_links, query as task_query, tags as task_tags, templates::expand_placeholders,
+    },
+};
+use crate::services::task::{body_from_task, body_to_create_input, builtin_variables};
+use crate::services::TaskService;
+use crate::types::{
+    dto::{
+        LinkDto, LinkInputDto, ProjectDto, ProjectTemplateDto, ProjectTemplateNodeDto,
+        TaskTemplateBodyDto,
+    },
+    error::AppError,
+};
+
+use super::{
+    dto::{ProjectCreateInput, ProjectTemplateInstantiateInput, ProjectTemplateSaveInput},
+    helpers::priority_to_string,
+    ProjectService,
+};
+
+impl ProjectService {
+    /// 把现有项目子树保存为模板。
+    pub async fn save_as_template(
+        conn: &DatabaseConnection,
+        input: ProjectTemplateSaveInput,
+    ) -> Result<ProjectTemplateDto, AppError> {
+        let name = normalize_name(&input.name)?;
+        let root = query::find_by_id(conn, &input.project_id).await?;
+        if root.deleted_at.is_some() {
+            return Err(AppError::Validation(
+                "项目已删除，无法保存为模板".to_string(),
+            ));
+        }
+
+        let subtree_ids = ProjectRepo::collect_subtree_ids(conn, &root.id).await?;
+        let mut subtree = query::find_not_deleted_by_ids(conn, &subtree_ids).await?;
+        subtree.sort_by(|a, b| (&a.rank, a.created_at).cmp(&(&b.rank, b.created_at)));
+        let project_ids = subtree
+            .iter()
+            .map(|project| project.id.clone())
+            .collect::<Vec<_>>();
+        let project_tags = tag_repo::load_tags(conn, TagEntity::Project, &project_ids).await?;
+        let project_links = link_repo::load_links(conn, LinkEntity::Project, &project_ids).await?;
+
+        let tasks = task_query::find_not_deleted_by_project_ids(conn, &project_ids)
+            .await?
+            .into_iter()
+            .filter(|task| task.archived_at.is_none())
+            .filter(|task| input.include_done_tasks || task.status != TaskStatus::Done)
+            .collect::<Vec<_>>();
+        let task_ids = tasks.iter().map(|task| task.id.clone()).collect::<Vec<_>>();
+        let mut tags_by_task = task_tags::load_tags_for_tasks(conn, &task_ids).await?;
+        let mut links_by_task = task_links::load_links_for_tasks(conn, &task_ids).await?;
+        let mut tasks_by_project: HashMap<String, Vec<TaskTemplateBodyDto>> = HashMap::new();
+        for task in &tasks {
+            let Some(project_id) = task.project_id.clone() else {
+                continue;
+            };
+            tasks_by_project
+                .entry(project_id)
+                .or_default()
+                .push(body_from_task(
+                    task,
+                    tags_by_task.remove(&task.id).unwrap_or_default(),
+                    links_by_task.remove(&task.id).unwrap_or_default(),
+                    root.created_at,
+                )?);
+        }
+
+        let mut children_by_parent: HashMap<String, Vec<projects::Model>> = HashMap::new();
+        for project in subtree {
+            if let Some(parent_id) = project.parent_id.clone() {
+                children_by_parent
+                    .entry(parent_id)
+                    .or_default()
+                    .push(project);
+            }
+        }
+        let mut context = CaptureContext {
+            children_by_parent,
+            tasks_by_project,
+            project_tags,
+            project_links,
+        };
+        let root_node = context.build_node(&root);
+
+        let model = templates::insert(
+            conn,
+            templates::NewTemplateRecord {
+                id: Uuid::new_v4().to_string(),
+                name,
+                body: templates::serialize_body(&root_node)?,
+                created_at: now_ms(),
+            },
+        )
+        .await?;
+        Ok(templates::to_dto(model))
+    }
+
+    /// 重命名项目模板。
+    pub async fn rename_template(
+        conn: &DatabaseConnection,
+        id: &str,
+        name: &str,
+    ) -> Result<ProjectTemplateDto, AppError> {
+        let model = templates::find_by_id(conn, id).await?;
+        let mut active_model: project_templates::ActiveModel = model.into();
+        active_model.name = Set(normalize_name(name)?);
+        active_model.updated_at = Set(now_ms());
+        Ok(templates::to_dto(
+            templates::update(conn, active_model).await?,
+        ))
+    }
+
+    /// 删除项目模板（写 tombstone）。
+    pub async fn delete_template(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
+        let model = templates::find_by_id(conn, id).await?;
+        let now = now_ms();
+        let mut active_model: project_templates::ActiveModel = model.into();
+        active_model.updated_at = Set(now);
+        active_model.deleted_at = Set(Some(now));
+        templates::update(conn, active_model).await?;
+        Ok(())
+    }
+
+    /// 按模板在指定 space / 父项目下创建整棵项目树，返回根项目。
+    pub async fn instantiate_template(
+        conn: &DatabaseConnection,
+        input: ProjectTemplateInstantiateInput,
+    ) -> Result<ProjectDto, AppError> {
+        let template = templates::to_dto(templates::find_by_id(conn, &input.template_id).await?);
+        let parent = match input.parent_id.as_deref() {
+            Some(parent_id) => {
+                let parent = query::find_by_id(conn, parent_id).await?;
+                if parent.deleted_at.is_some() {
+                    return Err(AppError::Validation(format!("父项目 {parent_id} 已删除")));
+                }
+                Some(parent)
+            }
+            None => None,
+        };
+        let space_id = match (&parent, input.space_id) {
+            (Some(parent), Some(space_id)) if parent.space_id != space_id => {
+                return Err(AppError::Validation(format!(
+                    "父项目 {} 不属于 space {space_id}",
+                    parent.id
+                )));
+            }
+            (Some(parent), _) => parent.space_id.clone(),
+            (None, Some(space_id)) => space_id,
+            (None, None) => {
+                return Err(AppError::Validation("需要指定 space 或父项目".to_string()));
+            }
+        };
+        let space = SpaceRepo::list(conn)
+            .await?
+            .into_iter()
+            .find(|space| space.id == space_id)
+            .ok_or_else(|| AppError::Validation(format!("space {space_id} 不存在")))?;
+
+        let now = now_ms();
+        let mut variables = builtin_variables(input.utc_offset_minutes, now)?;
+        variables.insert("space".to_string(), space.name);
+        variables.extend(input.variables);
+
+        let mut root_node = template.root;
+        if let Some(title) = input.title {
+            root_node.title = title;
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        let mut root = None;
+        // 先序遍历：同级节点按模板顺序创建，rank 依次追加在末尾。
+        let mut stack = vec![(root_node, parent.map(|parent| parent.id))];
+        while let Some((node, parent_id)) = stack.pop() {
+            let expand = |text: &str| expand_placeholders(text, &variables);
+            let project = Self::create_in(
+                &txn,
+                ProjectCreateInput {
+                    space_id: space_id.clone(),
+                    title: expand(&node.title),
+                    parent_id,
+                    note: node.note.as_deref().map(expand),
+                    priority: node.priority,
+                    rank: None,
+                    tags: Some(node.tags.iter().map(|tag| expand(tag)).collect()),
+                    links: Some(
+                        node.links
+                            .into_iter()
+                            .map(|link| LinkInputDto {
+                                id: None,
+                                title: expand(&link.title),
+                                url: expand(&link.url),
+                                kind: link.kind,
+                                rank: link.rank,
+                            })
+                            .collect(),
+                    ),
+                    lifecycle_status: None,
+                },
+                now,
+                Some(&batch_id),
+            )
+            .await?;
+
+            let mut task_variables = variables.clone();
+            task_variables
+                .entry("project".to_string())
+                .or_insert_with(|| project.title.clone());
+            for body in node.tasks {
+                TaskService::create_in(
+                    &txn,
+                    body_to_create_input(
+                        body,
+                        space_id.clone(),
+                        Some(project.id.clone()),
+                        &task_variables,
+                        now,
+                    ),
+                    now,
+                    Some(&batch_id),
+                )
+                .await?;
+            }
+
+            for child in node.children.into_iter().rev() {
+                stack.push((child, Some(project.id.clone())));
+            }
+            root.get_or_insert(project);
+        }
+        txn.commit().await.map_err(AppError::from)?;
+
+        let root = root.ok_or_else(|| AppError::Internal("模板没有根项目".to_string()))?;
+        let mut dto = repo_helpers::project_model_to_dto(root);
+        repo_helpers::attach_links(conn, std::slice::from_mut(&mut dto)).await?;
+        repo_helpers::attach_tags(conn, std::slice::from_mut(&mut dto)).await?;
+        Ok(dto)
+    }
+}
+
+/// 保存模板时已经按项目分好组的子树数据。
+struct CaptureContext {
+    children_by_parent: HashMap<String, Vec<projects::Model>>,
+    tasks_by_project: HashMap<String, Vec<TaskTemplateBodyDto>>,
+    project_tags: HashMap<String, Vec<String>>,
+    project_links: HashMap<String, Vec<LinkDto>>,
+}
+
+impl CaptureContext {
+    fn build_node(&mut self, project: &projects::Model) -> ProjectTemplateNodeDto {
+        let children = self
+            .children_by_parent
+            .remove(&project.id)
+            .unwrap_or_default();
+        ProjectTemplateNodeDto {
+            title: project.title.clone(),
+            note: project.note.clone(),
+            priority: Some(priority_to_string(&project.priority)),
+            tags: self.project_tags.remove(&project.id).unwrap_or_default(),
+            links: self
+                .project_links
+                .remove(&project.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|link| LinkInputDto {
+                    id: None,
+                    title: link.title,
+                    url: link.url,
+                    kind: link.kind,
+                    rank: Some(link.rank),
+                })
+                .collect(),
+            tasks: self
+                .tasks_by_project
+                .remove(&project.id)
+                .unwrap_or_default(),
+            children: children
+                .iter()
+                .map(|child| self.build_node(child))
+                .collect(),
+        }
+    }
+}
+
+fn normalize_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("模板名称不能为空".to_string()));
+    }
+    Ok(name.to_string())
+}
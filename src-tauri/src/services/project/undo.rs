@@ -0,0 +1,292 @@
+//! 项目侧的撤销计划。
+//!
+//! 约定：
+//! - 创建 / 克隆 / 恢复 → 删除子树；删除 → 恢复项目，并恢复随它一起删除的任务
+//! - 归档与取消归档互为反向
+//! - 字段更新按字段合并成“最早旧值 → 最新新值”，用旧值拼出 patch 走正常更新用例
+//! - 要删除的项目下出现了批次之外的新内容时视为冲突，避免误删
+
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::ConnectionTrait;
+
+use crate::db::entities::{project_activity_logs, projects};
+use crate::repos::{
+    project_repo::{
+        activity_logs::{
+            ACTION_PROJECT_ARCHIVED, ACTION_PROJECT_CLONED, ACTION_PROJECT_CREATED,
+            ACTION_PROJECT_DELETED, ACTION_PROJECT_RESTORED, ACTION_PROJECT_UNARCHIVED,
+        },
+        query, ProjectRepo,
+    },
+    task_repo::query as task_query,
+};
+use crate::services::TaskService;
+use crate::types::error::AppError;
+
+use super::{
+    dto::{ProjectUpdateInput, ProjectUpdatePatch},
+    helpers::priority_to_string,
+    ProjectService,
+};
+
+/// 一个批次里项目日志的反向操作。
+#[derive(Debug, Default)]
+pub(crate) struct ProjectUndoPlan {
+    /// 项目 id 与当时的删除时间
+    pub restores: Vec<(String, i64)>,
+    pub delete_ids: Vec<String>,
+    pub archive_ids: Vec<String>,
+    pub unarchive_ids: Vec<String>,
+    pub updates: Vec<ProjectUpdateInput>,
+    pub conflicts: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+impl ProjectUndoPlan {
+    pub fn entity_count(&self) -> usize {
+        self.restores.len()
+            + self.delete_ids.len()
+            + self.archive_ids.len()
+            + self.unarchive_ids.len()
+            + self.updates.len()
+    }
+}
+
+impl ProjectService {
+    /// 根据一个批次的项目日志生成反向操作，只读不写。
+    ///
+    /// `batch_task_ids` 是同一批次里出现过的任务，删除子树前用来判断有没有批次之外的新任务。
+    pub(crate) async fn plan_undo<C>(
+        conn: &C,
+        logs: &[project_activity_logs::Model],
+        batch_task_ids: &HashSet<String>,
+    ) -> Result<ProjectUndoPlan, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let mut project_ids = Vec::new();
+        let mut logs_by_project: HashMap<&str, Vec<&project_activity_logs::Model>> = HashMap::new();
+        for log in logs {
+            let entry = logs_by_project.entry(log.project_id.as_str()).or_default();
+            if entry.is_empty() {
+                project_ids.push(log.project_id.as_str());
+            }
+            entry.push(log);
+        }
+        let batch_project_ids = project_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<HashSet<_>>();
+
+        let mut plan = ProjectUndoPlan::default();
+        for project_id in project_ids {
+            let logs = &logs_by_project[project_id];
+            let Some(project) = query::find_optional_by_id(conn, project_id).await? else {
+                plan.conflicts.push(format!("项目 {project_id} 已不存在"));
+                continue;
+            };
+            let find_action = |actions: &[&str]| {
+                logs.iter()
+                    .find(|log| actions.contains(&log.action.as_str()))
+                    .copied()
+            };
+
+            if find_action(&[
+                ACTION_PROJECT_CREATED,
+                ACTION_PROJECT_CLONED,
+                ACTION_PROJECT_RESTORED,
+            ])
+            .is_some()
+            {
+                if project.deleted_at.is_some() {
+                    plan.conflicts
+                        .push(format!("项目「{}」已被删除", project.title));
+                } else if has_foreign_content(conn, &project, &batch_project_ids, batch_task_ids)
+                    .await?
+                {
+                    plan.conflicts
+                        .push(format!("项目「{}」下已有新的子项目或任务", project.title));
+                } else {
+                    plan.delete_ids.push(project.id.clone());
+                }
+                continue;
+            }
+            if let Some(log) = find_action(&[ACTION_PROJECT_DELETED]) {
+                if project.deleted_at.is_none() {
+                    plan.conflicts
+                        .push(format!("项目「{}」已被恢复", project.title));
+                } else {
+                    plan.restores.push((project.id.clone(), log.created_at));
+                }
+                continue;
+            }
+
+            // 归档状态以最后一条归档类日志为准。
+            let archive_log = logs
+                .iter()
+                .rev()
+                .find(|log| {
+                    log.action == ACTION_PROJECT_ARCHIVED || log.action == ACTION_PROJECT_UNARCHIVED
+                })
+                .copied();
+            if let Some(log) = archive_log {
+                let archived = log.action == ACTION_PROJECT_ARCHIVED;
+                if project.archived_at.is_some() != archived {
+                    plan.conflicts
+                        .push(format!("项目「{}」的归档状态已被修改", project.title));
+                } else if archived {
+                    plan.unarchive_ids.push(project.id.clone());
+                } else {
+                    plan.archive_ids.push(project.id.clone());
+                }
+            }
+
+            let mut patch = ProjectUpdatePatch::default();
+            let mut changed_any = false;
+            let mut seen_keys = HashSet::new();
+            for log in logs.iter().filter(|log| {
+                log.action != ACTION_PROJECT_ARCHIVED && log.action != ACTION_PROJECT_UNARCHIVED
+            }) {
+                let Some(key) = log.field_key.as_deref() else {
+                    continue;
+                };
+                if !seen_keys.insert(key) {
+                    continue;
+                }
+                let label = log.field_label.as_deref().unwrap_or(key);
+                // 同一字段多条日志时取最早的旧值与最新的新值。
+                let after = logs
+                    .iter()
+                    .rev()
+                    .find(|item| item.field_key.as_deref() == Some(key))
+                    .and_then(|item| item.after_value.clone());
+                if log.before_value == after {
+                    continue;
+                }
+                let current = match key {
+                    "spaceId" => Some(project.space_id.clone()),
+                    "title" => Some(project.title.clone()),
+                    "note" => project.note.clone(),
+                    "priority" => Some(priority_to_string(&project.priority)),
+                    "parentId" => project.parent_id.clone(),
+                    "lifecycleStatus" => Some(project.lifecycle_status.clone()),
+                    "startAt" => project.start_at.map(|value| value.to_string()),
+                    "dueAt" => project.due_at.map(|value| value.to_string()),
+                    _ => {
+                        plan.skipped
+                            .push(format!("项目「{}」的{}", project.title, label));
+                        continue;
+                    }
+                };
+                if current != after {
+                    plan.conflicts
+                        .push(format!("项目「{}」的{}已被修改", project.title, label));
+                    continue;
+                }
+                let before = log.before_value.clone();
+                let parse_ms = |value: Option<String>| value.and_then(|value| value.parse().ok());
+                match key {
+                    "spaceId" => patch.space_id = before,
+                    "title" => patch.title = before,
+                    "note" => patch.note = Some(before),
+                    "priority" => patch.priority = before,
+                    "lifecycleStatus" => patch.lifecycle_status = before,
+                    "startAt" => patch.start_at = Some(parse_ms(before)),
+                    "dueAt" => patch.due_at = Some(parse_ms(before)),
+                    _ => patch.parent_id = Some(before),
+                }
+                changed_any = true;
+            }
+            if changed_any {
+                plan.updates.push(ProjectUpdateInput {
+                    project_id: project.id.clone(),
+                    patch,
+                });
+            }
+        }
+        Ok(plan)
+    }
+
+    /// 按计划恢复项目（连同随它删除的任务）、回写字段并切换归档状态。
+    ///
+    /// 删除留给调用方在任务处理完之后单独执行。
+    pub(crate) async fn apply_undo_restores_and_updates<C>(
+        txn: &C,
+        plan: &ProjectUndoPlan,
+        now: i64,
+        batch_id: &str,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let mut task_ids = Vec::new();
+        for (project_id, deleted_at) in &plan.restores {
+            Self::restore_in(txn, project_id, now, Some(batch_id)).await?;
+            task_ids.extend(
+                task_query::find_deleted_by_project_ids_at(
+                    txn,
+                    std::slice::from_ref(project_id),
+                    *deleted_at,
+                )
+                .await?
+                .into_iter()
+                .map(|task| task.id),
+            );
+        }
+        if !task_ids.is_empty() {
+            TaskService::restore_many_in(txn, &task_ids, now, Some(batch_id)).await?;
+        }
+
+        for input in &plan.updates {
+            Self::update_in(txn, input.clone(), now, Some(batch_id)).await?;
+        }
+        for project_id in &plan.archive_ids {
+            Self::archive_in(txn, project_id, now, Some(batch_id)).await?;
+        }
+        for project_id in &plan.unarchive_ids {
+            Self::unarchive_in(txn, project_id, now, Some(batch_id)).await?;
+        }
+        Ok(())
+    }
+
+    /// 按计划删除项目子树。
+    pub(crate) async fn apply_undo_deletes<C>(
+        txn: &C,
+        plan: &ProjectUndoPlan,
+        now: i64,
+        batch_id: &str,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
+        for project_id in &plan.delete_ids {
+            Self::delete_subtree_in(txn, project_id, now, Some(batch_id)).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 子树里是否有不属于本批次、且仍然可见的项目或任务。
+async fn has_foreign_content<C>(
+    conn: &C,
+    project: &projects::Model,
+    batch_project_ids: &HashSet<String>,
+    batch_task_ids: &HashSet<String>,
+) -> Result<bool, AppError>
+where
+    C: ConnectionTrait,
+{
+    let subtree_ids = ProjectRepo::collect_subtree_ids(conn, &project.id).await?;
+    let live_projects = query::find_not_deleted_by_ids(conn, &subtree_ids).await?;
+    if live_projects
+        .iter()
+        .any(|item| !batch_project_ids.contains(&item.id))
+    {
+        return Ok(true);
+    }
+    let live_tasks = task_query::find_not_deleted_by_project_ids(conn, &subtree_ids).await?;
+    Ok(live_tasks
+        .iter()
+        .any(|task| !batch_task_ids.contains(&task.id)))
+}
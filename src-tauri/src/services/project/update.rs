@@ -3,32 +3,63 @@
 //! 和任务更新类似，这个入口负责把 patch 语义、路径重建、
 //! 跨 Space 规则、标签链接同步和活动日志统一收口。
 
-use sea_orm::{DatabaseConnection, Set, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, Set, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::{entities::projects, now_ms};
 use crate::repos::{
     common_task_utils,
     link_repo::{self, LinkEntity},
     project_repo::{activity_logs, helpers as repo_helpers, mutation, query, ProjectRepo},
+    search_repo,
     tag_repo::{self, TagEntity},
+    task_repo::stats,
 };
 use crate::types::error::AppError;
 
 use super::{
     dto::ProjectUpdateInput,
-    helpers::{is_default_project_id, priority_to_string},
+    helpers::{
+        ensure_lifecycle_transition, ensure_schedule_fits, is_default_project_id,
+        priority_to_string,
+    },
     ProjectService,
 };
 
 impl ProjectService {
     /// 更新项目。
+    ///
+    /// 生命周期状态只在这里按状态机校验；撤销经 `update_in` 直接回写旧状态。
     pub async fn update(
         conn: &DatabaseConnection,
         input: ProjectUpdateInput,
     ) -> Result<(), AppError> {
-        let ProjectUpdateInput { project_id, patch } = input;
         let txn = conn.begin().await.map_err(AppError::from)?;
-        let model = query::find_by_id(&txn, project_id.as_str()).await?;
+        if let Some(status) = input.patch.lifecycle_status.as_deref() {
+            let model = query::find_by_id(&txn, &input.project_id).await?;
+            ensure_lifecycle_transition(
+                &model.lifecycle_status,
+                repo_helpers::parse_lifecycle_status(status)?,
+            )?;
+        }
+        let batch_id = Uuid::new_v4().to_string();
+        Self::update_in(&txn, input, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在调用方的事务里更新项目。
+    pub(crate) async fn update_in<C>(
+        txn: &C,
+        input: ProjectUpdateInput,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let ProjectUpdateInput { project_id, patch } = input;
+        let model = query::find_by_id(txn, project_id.as_str()).await?;
 
         // 默认项目作为系统保留节点，不允许修改标题、所属空间和父级。
         if is_default_project_id(project_id.as_str())
@@ -39,19 +70,24 @@ impl ProjectService {
             ));
         }
 
-        let now = now_ms();
         let old_space_id = model.space_id.clone();
         let old_path = model.path.clone();
         let old_title = model.title.clone();
         let old_note = model.note.clone();
         let old_priority = model.priority.clone();
         let old_parent_id = model.parent_id.clone();
+        let old_lifecycle_status = model.lifecycle_status.clone();
+        let old_start_at = model.start_at;
+        let old_due_at = model.due_at;
 
         let mut next_space_id = old_space_id.clone();
         let mut next_title = old_title.clone();
         let mut next_note = old_note.clone();
         let mut next_priority = old_priority.clone();
         let mut next_parent_id = old_parent_id.clone();
+        let mut next_lifecycle_status = old_lifecycle_status.clone();
+        let mut next_start_at = old_start_at;
+        let mut next_due_at = old_due_at;
         let mut changed_any = false;
         let mut path_changed = false;
         let mut space_changed = false;
@@ -101,6 +137,30 @@ impl ProjectService {
             }
         }
 
+        if let Some(status) = patch.lifecycle_status.as_deref() {
+            let parsed = repo_helpers::parse_lifecycle_status(status)?;
+            if parsed != old_lifecycle_status {
+                next_lifecycle_status = parsed.to_string();
+                active_model.lifecycle_status = Set(next_lifecycle_status.clone());
+                changed_any = true;
+            }
+        }
+
+        if let Some(start_at) = patch.start_at {
+            if start_at != old_start_at {
+                next_start_at = start_at;
+                active_model.start_at = Set(start_at);
+                changed_any = true;
+            }
+        }
+        if let Some(due_at) = patch.due_at {
+            if due_at != old_due_at {
+                next_due_at = due_at;
+                active_model.due_at = Set(due_at);
+                changed_any = true;
+            }
+        }
+
         if let Some(space_id) = patch.space_id.as_deref() {
             let normalized = space_id.trim();
             if normalized.is_empty() {
@@ -110,7 +170,7 @@ impl ProjectService {
             if normalized != old_space_id {
                 // 当前实现不支持带子树项目跨 Space 迁移，避免路径和下游任务语义混乱。
                 let subtree_ids =
-                    ProjectRepo::collect_subtree_ids(&txn, project_id.as_str()).await?;
+                    ProjectRepo::collect_subtree_ids(txn, project_id.as_str()).await?;
                 if subtree_ids.len() > 1 {
                     return Err(AppError::Validation(
                         "当前项目存在子项目，暂不支持跨 Space 迁移".to_string(),
@@ -137,7 +197,7 @@ impl ProjectService {
                     return Err(AppError::Validation("项目不能挂载到自身".to_string()));
                 }
                 let subtree_ids =
-                    ProjectRepo::collect_subtree_ids(&txn, project_id.as_str()).await?;
+                    ProjectRepo::collect_subtree_ids(txn, project_id.as_str()).await?;
                 if subtree_ids.iter().any(|id| id == parent_id) {
                     return Err(AppError::Validation("项目不能挂载到自身后代".to_string()));
                 }
@@ -150,10 +210,25 @@ impl ProjectService {
             }
         }
 
+        // 排期或父级变了，都要重新确认和父项目、子项目的日期范围是否相容。
+        if next_start_at != old_start_at
+            || next_due_at != old_due_at
+            || next_parent_id != old_parent_id
+        {
+            ensure_schedule_fits(
+                txn,
+                project_id.as_str(),
+                next_parent_id.as_deref(),
+                next_start_at,
+                next_due_at,
+            )
+            .await?;
+        }
+
         // 标题、父级或 space 变了，都可能导致路径重建。
         if next_title != old_title || next_parent_id != old_parent_id || space_changed {
             let rebuilt_path = repo_helpers::build_project_path(
-                &txn,
+                txn,
                 &next_space_id,
                 next_parent_id.as_deref(),
                 next_title.as_str(),
@@ -172,25 +247,32 @@ impl ProjectService {
         }
 
         active_model.updated_at = Set(now);
-        let saved_model = mutation::update(&txn, active_model).await?;
+        let saved_model = mutation::update(txn, active_model).await?;
 
         if space_changed {
             // 项目迁到新空间后，挂在这个项目下的任务也要同步跟过去。
-            mutation::update_tasks_space_by_project(&txn, project_id.as_str(), &next_space_id, now)
+            mutation::update_tasks_space_by_project(txn, project_id.as_str(), &next_space_id, now)
                 .await?;
         }
 
         if let Some(tags) = patch.tags.as_ref() {
-            tag_repo::sync_tags(&txn, TagEntity::Project, project_id.as_str(), tags, now).await?;
+            tag_repo::sync_tags(txn, TagEntity::Project, project_id.as_str(), tags, now).await?;
         }
         if let Some(links) = patch.links.as_ref() {
-            link_repo::sync_links(&txn, LinkEntity::Project, project_id.as_str(), links).await?;
+            link_repo::sync_links(txn, LinkEntity::Project, project_id.as_str(), links).await?;
         }
 
         if path_changed {
-            mutation::rebase_descendant_paths(&txn, &old_space_id, &old_path, &next_path, now)
+            mutation::rebase_descendant_paths(txn, &old_space_id, &old_path, &next_path, now)
                 .await?;
         }
+        if next_parent_id != old_parent_id || space_changed {
+            // 子树整体挪走：原父级链少了这部分，新父级链（含自身）多了这部分。
+            if let Some(old_parent_id) = old_parent_id.as_deref() {
+                stats::refresh_subtree_rollups(txn, old_parent_id).await?;
+            }
+            stats::refresh_subtree_rollups(txn, project_id.as_str()).await?;
+        }
 
         // 最后补字段级活动日志，便于前端展示项目变更历史。
         let log_ctx = activity_logs::ProjectLogCtx {
@@ -198,9 +280,10 @@ impl ProjectService {
             space_id: saved_model.space_id.as_str(),
             create_by: saved_model.create_by.as_str(),
             created_at: now,
+            batch_id,
         };
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "spaceId",
             "所属 Space",
@@ -209,7 +292,7 @@ impl ProjectService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "title",
             "项目标题",
@@ -218,7 +301,7 @@ impl ProjectService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "note",
             "项目备注",
@@ -227,7 +310,7 @@ impl ProjectService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "priority",
             "优先级",
@@ -236,16 +319,48 @@ impl ProjectService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
-            log_ctx,
+            txn,
+            log_ctx.clone(),
             "parentId",
             "父项目",
             old_parent_id,
             next_parent_id,
         )
         .await?;
+        activity_logs::append_field_updated(
+            txn,
+            log_ctx.clone(),
+            "lifecycleStatus",
+            "项目状态",
+            Some(old_lifecycle_status),
+            Some(next_lifecycle_status),
+        )
+        .await?;
+        activity_logs::append_field_updated(
+            txn,
+            log_ctx.clone(),
+            "startAt",
+            "开始时间",
+            old_start_at.map(|value| value.to_string()),
+            next_start_at.map(|value| value.to_string()),
+        )
+        .await?;
+        activity_logs::append_field_updated(
+            txn,
+            log_ctx,
+            "dueAt",
+            "截止时间",
+            old_due_at.map(|value| value.to_string()),
+            next_due_at.map(|value| value.to_string()),
+        )
+        .await?;
+
+        search_repo::reindex_projects(txn, std::slice::from_ref(&project_id)).await?;
+        if space_changed {
+            search_repo::reindex_tasks_by_project_ids(txn, std::slice::from_ref(&project_id))
+                .await?;
+        }
 
-        txn.commit().await.map_err(AppError::from)?;
         Ok(())
     }
 }
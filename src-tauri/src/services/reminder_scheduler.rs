@@ -0,0 +1,523 @@
+//! 截止提醒调度器。
+//!
+//! 重点：
+//! - 时间来源通过 `Clock` 注入，调度逻辑可以用固定时钟验证
+//! - 每轮先落库（标记已触发）再通知，宁可漏一次也不在重启后重复提醒
+//! - 睡眠时长取“下一条提醒时刻”和兜底间隔中的较小值；
+//!   截止时间被编辑或同步改动时，最迟一个兜底间隔内就会被重新发现
+//! - 错过太久（超过 `STALE_AFTER_MS`）的提醒直接跳过，避免长时间未打开应用后集中轰炸
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sea_orm::{DatabaseConnection, Set, TransactionTrait};
+use tokio::sync::Notify;
+
+use crate::db::entities::{task_reminders, tasks};
+use crate::repos::task_repo::reminders;
+use crate::types::{dto::ReminderFireDto, error::AppError};
+
+/// 兜底轮询间隔。
+const MAX_SLEEP_MS: i64 = 60_000;
+/// 超过这个时长仍未触发的提醒视为过期，不再补发。
+pub const STALE_AFTER_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// 调度器的时间来源。
+pub trait Clock: Send + Sync {
+    fn now_ms(&self) -> i64;
+}
+
+/// 系统时钟。
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        crate::db::now_ms()
+    }
+}
+
+/// 一条到期的提醒；`reminder` 为空表示任务未配置提醒、按截止时刻默认提醒。
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    pub task: tasks::Model,
+    pub deadline_at: i64,
+    pub reminder: Option<task_reminders::Model>,
+    pub fire_at: i64,
+}
+
+/// 单轮调度的计算结果。
+#[derive(Debug, Default)]
+pub struct ReminderPlan {
+    pub due: Vec<DueReminder>,
+    /// 尚未到期的最近一条提醒时刻
+    pub next_fire_at: Option<i64>,
+}
+
+/// 单轮调度的执行结果。
+#[derive(Debug, Default)]
+pub struct ReminderTick {
+    pub fired: Vec<ReminderFireDto>,
+    pub next_fire_at: Option<i64>,
+}
+
+/// 根据候选任务及其提醒计算本轮到期项；纯函数，不读写数据库。
+///
+/// `configured` 包含已删除的 tombstone：任务从未有过提醒记录时才按截止时刻默认提醒，
+/// 提醒被清空（只剩 tombstone）时不再提醒。
+pub fn plan_reminders(
+    now: i64,
+    candidates: Vec<(tasks::Model, Vec<task_reminders::Model>)>,
+) -> ReminderPlan {
+    let mut plan = ReminderPlan::default();
+    for (task, configured) in candidates {
+        let Some(deadline_at) = task.deadline_at else {
+            continue;
+        };
+
+        let entries: Vec<(Option<task_reminders::Model>, Option<i64>)> = if configured.is_empty() {
+            vec![(None, Some(deadline_at))]
+        } else {
+            configured
+                .into_iter()
+                .filter(|reminder| reminder.deleted_at.is_none())
+                .map(|reminder| {
+                    let fire_at = reminders::next_fire_at(&reminder, deadline_at);
+                    (Some(reminder), fire_at)
+                })
+                .collect()
+        };
+
+        for (reminder, fire_at) in entries {
+            let Some(fire_at) = fire_at else {
+                continue;
+            };
+            if fire_at > now {
+                plan.next_fire_at = Some(plan.next_fire_at.map_or(fire_at, |at| at.min(fire_at)));
+            } else if now - fire_at <= STALE_AFTER_MS {
+                plan.due.push(DueReminder {
+                    task: task.clone(),
+                    deadline_at,
+                    reminder,
+                    fire_at,
+                });
+            }
+        }
+    }
+    plan.due.sort_by_key(|due| due.fire_at);
+    plan
+}
+
+/// 唤醒句柄：提醒配置变化后让调度器立即重算，而不是等到下一次兜底轮询。
+#[derive(Clone, Default)]
+pub struct ReminderSchedulerHandle {
+    wake: Arc<Notify>,
+}
+
+impl ReminderSchedulerHandle {
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+}
+
+pub struct ReminderScheduler {
+    conn: DatabaseConnection,
+    clock: Arc<dyn Clock>,
+    handle: ReminderSchedulerHandle,
+}
+
+impl ReminderScheduler {
+    pub fn new(conn: DatabaseConnection, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            conn,
+            clock,
+            handle: ReminderSchedulerHandle::default(),
+        }
+    }
+
+    pub fn handle(&self) -> ReminderSchedulerHandle {
+        self.handle.clone()
+    }
+
+    /// 执行一轮调度：找出到期提醒，标记为已触发并返回。
+    pub async fn tick(&self) -> Result<ReminderTick, AppError> {
+        let now = self.clock.now_ms();
+        let txn = self.conn.begin().await.map_err(AppError::from)?;
+
+        // 提前量最多 30 天，更远的截止时间本轮不可能触发，也不影响下一次唤醒。
+        let candidates = reminders::list_candidate_tasks(
+            &txn,
+            now + reminders::MAX_OFFSET_MINUTES * 60_000 + MAX_SLEEP_MS,
+        )
+        .await?;
+        let task_ids: Vec<String> = candidates.iter().map(|task| task.id.clone()).collect();
+        let mut grouped = reminders::load_all_for_tasks(&txn, &task_ids).await?;
+        let plan = plan_reminders(
+            now,
+            candidates
+                .into_iter()
+                .map(|task| {
+                    let configured = grouped.remove(&task.id).unwrap_or_default();
+                    (task, configured)
+                })
+                .collect(),
+        );
+
+        let mut fired = Vec::with_capacity(plan.due.len());
+        for due in plan.due {
+            let saved = match due.reminder {
+                Some(reminder) => {
+                    let mut active_model: task_reminders::ActiveModel = reminder.into();
+                    active_model.anchor_deadline_at = Set(Some(due.deadline_at));
+                    active_model.last_fired_at = Set(Some(now));
+                    active_model.snoozed_until = Set(None);
+                    active_model.dismissed_at = Set(None);
+                    active_model.updated_at = Set(now);
+                    reminders::update(&txn, active_model).await?
+                }
+                // 默认提醒在首次触发时落一条 offset = 0 的记录，触发状态才能持久化。
+                // id 由任务 id 派生，多端各自触发时同步后落到同一行，不会出现重复提醒。
+                None => {
+                    let id = reminders::default_reminder_id(&due.task.id);
+                    let inserted = reminders::insert(
+                        &txn,
+                        reminders::NewReminderRecord {
+                            id,
+                            task_id: due.task.id.clone(),
+                            offset_minutes: 0,
+                            created_at: now,
+                        },
+                    )
+                    .await?;
+                    let mut active_model: task_reminders::ActiveModel = inserted.into();
+                    active_model.anchor_deadline_at = Set(Some(due.deadline_at));
+                    active_model.last_fired_at = Set(Some(now));
+                    active_model.updated_at = Set(now);
+                    reminders::update(&txn, active_model).await?
+                }
+            };
+            fired.push(ReminderFireDto {
+                reminder_id: saved.id,
+                task_id: due.task.id,
+                task_title: due.task.title,
+                deadline_at: due.deadline_at,
+                offset_minutes: saved.offset_minutes,
+                fired_at: now,
+            });
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(ReminderTick {
+            fired,
+            next_fire_at: plan.next_fire_at,
+        })
+    }
+
+    /// 常驻循环；`on_fire` 负责把提醒投递成桌面通知等外部副作用。
+    pub async fn run<F>(self, on_fire: F)
+    where
+        F: Fn(ReminderFireDto) + Send + Sync + 'static,
+    {
+        loop {
+            let sleep_ms = match self.tick().await {
+                Ok(tick) => {
+                    for fire in tick.fired {
+                        on_fire(fire);
+                    }
+                    tick.next_fire_at
+                        .map(|at| (at - self.clock.now_ms()).clamp(0, MAX_SLEEP_MS))
+                        .unwrap_or(MAX_SLEEP_MS)
+                }
+                Err(error) => {
+                    eprintln!("reminder scheduler tick failed: {error}");
+                    MAX_SLEEP_MS
+                }
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(sleep_ms as u64)) => {}
+                _ = self.handle.wake.notified() => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    use sea_orm::{ActiveModelTrait, Database, EntityTrait, IntoActiveModel};
+    use sea_orm_migration::MigratorTrait;
+
+    use super::*;
+    use crate::db::{entities::sea_orm_active_enums::*, migrator::Migrator, seed};
+    use crate::services::task::TaskService;
+
+    const MINUTE: i64 = 60_000;
+    /// 2026-10-14 10:00:00 UTC
+    const NOW: i64 = 1_791_972_000_000;
+
+    struct FixedClock(AtomicI64);
+
+    impl FixedClock {
+        fn new(now: i64) -> Arc<Self> {
+            Arc::new(Self(AtomicI64::new(now)))
+        }
+
+        fn set(&self, now: i64) {
+            self.0.store(now, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for FixedClock {
+        fn now_ms(&self) -> i64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    fn task(id: &str, deadline_at: Option<i64>) -> tasks::Model {
+        tasks::Model {
+            id: id.to_string(),
+            space_id: "work".to_string(),
+            project_id: None,
+            title: format!("任务 {id}"),
+            note: None,
+            status: TaskStatus::Todo,
+            done_reason: None,
+            priority: Priority::P2,
+            rank: crate::repos::rank::INITIAL.to_string(),
+            created_at: 0,
+            updated_at: 0,
+            completed_at: None,
+            deadline_at,
+            scheduled_at: None,
+            defer_until: None,
+            archived_at: None,
+            deleted_at: None,
+            custom_fields: None,
+            create_by: "test".to_string(),
+            recurrence_rule: None,
+            recurrence_series_id: None,
+            recurrence_index: None,
+            milestone_id: None,
+        }
+    }
+
+    fn reminder(task_id: &str, offset_minutes: i64) -> task_reminders::Model {
+        task_reminders::Model {
+            id: format!("{task_id}:{offset_minutes}"),
+            task_id: task_id.to_string(),
+            offset_minutes,
+            anchor_deadline_at: None,
+            last_fired_at: None,
+            snoozed_until: None,
+            dismissed_at: None,
+            created_at: 0,
+            updated_at: 0,
+            deleted_at: None,
+        }
+    }
+
+    fn due_ids(plan: &ReminderPlan) -> Vec<(String, Option<i64>)> {
+        plan.due
+            .iter()
+            .map(|due| {
+                (
+                    due.task.id.clone(),
+                    due.reminder
+                        .as_ref()
+                        .map(|reminder| reminder.offset_minutes),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plans_due_and_next_fire_at() {
+        let clock = FixedClock::new(NOW);
+        let plan = plan_reminders(
+            clock.now_ms(),
+            vec![
+                // 未配置提醒：按截止时刻默认提醒
+                (task("a", Some(NOW - MINUTE)), Vec::new()),
+                // 提前 30 分钟的已到期，提前 5 分钟的尚未到期
+                (
+                    task("b", Some(NOW + 10 * MINUTE)),
+                    vec![reminder("b", 30), reminder("b", 5)],
+                ),
+                (task("c", Some(NOW + 2 * MINUTE)), Vec::new()),
+                (task("d", None), vec![reminder("d", 0)]),
+            ],
+        );
+
+        assert_eq!(
+            due_ids(&plan),
+            vec![("b".to_string(), Some(30)), ("a".to_string(), None)]
+        );
+        assert_eq!(plan.due[0].fire_at, NOW - 20 * MINUTE);
+        assert_eq!(plan.next_fire_at, Some(NOW + 2 * MINUTE));
+    }
+
+    #[test]
+    fn tombstones_suppress_default_reminder() {
+        let clock = FixedClock::new(NOW);
+        let cleared = task_reminders::Model {
+            deleted_at: Some(NOW - 60 * MINUTE),
+            ..reminder("a", 0)
+        };
+        let plan = plan_reminders(
+            clock.now_ms(),
+            vec![
+                (task("a", Some(NOW - MINUTE)), vec![cleared.clone()]),
+                (task("b", Some(NOW + MINUTE)), vec![cleared]),
+            ],
+        );
+        assert!(plan.due.is_empty());
+        assert_eq!(plan.next_fire_at, None);
+    }
+
+    #[test]
+    fn skips_stale_reminders() {
+        let clock = FixedClock::new(NOW);
+        let plan = plan_reminders(
+            clock.now_ms(),
+            vec![
+                (task("stale", Some(NOW - STALE_AFTER_MS - 1)), Vec::new()),
+                (task("edge", Some(NOW - STALE_AFTER_MS)), Vec::new()),
+            ],
+        );
+
+        assert_eq!(due_ids(&plan), vec![("edge".to_string(), None)]);
+        assert_eq!(plan.next_fire_at, None);
+    }
+
+    #[test]
+    fn honours_snooze_and_dismiss() {
+        let clock = FixedClock::new(NOW);
+        let deadline_at = NOW + 60 * MINUTE;
+        let fired = task_reminders::Model {
+            anchor_deadline_at: Some(deadline_at),
+            last_fired_at: Some(NOW - 70 * MINUTE),
+            ..reminder("a", 120)
+        };
+        let snoozed = task_reminders::Model {
+            snoozed_until: Some(NOW + 15 * MINUTE),
+            ..fired.clone()
+        };
+        let dismissed = task_reminders::Model {
+            dismissed_at: Some(NOW - 60 * MINUTE),
+            ..snoozed.clone()
+        };
+        let candidates =
+            |reminder: task_reminders::Model| vec![(task("a", Some(deadline_at)), vec![reminder])];
+
+        // 已触发且未稍后：不再提醒
+        let plan = plan_reminders(clock.now_ms(), candidates(fired.clone()));
+        assert!(plan.due.is_empty());
+        assert_eq!(plan.next_fire_at, None);
+
+        // 稍后：等到 snoozed_until 再触发
+        let plan = plan_reminders(clock.now_ms(), candidates(snoozed.clone()));
+        assert!(plan.due.is_empty());
+        assert_eq!(plan.next_fire_at, Some(NOW + 15 * MINUTE));
+        clock.set(NOW + 15 * MINUTE);
+        let plan = plan_reminders(clock.now_ms(), candidates(snoozed));
+        assert_eq!(due_ids(&plan), vec![("a".to_string(), Some(120))]);
+        assert_eq!(plan.due[0].fire_at, NOW + 15 * MINUTE);
+
+        // 忽略：本次截止时间内不再提醒
+        let plan = plan_reminders(clock.now_ms(), candidates(dismissed.clone()));
+        assert!(plan.due.is_empty());
+        assert_eq!(plan.next_fire_at, None);
+
+        // 截止时间改动后，旧的触发 / 忽略状态失效
+        let moved = vec![(task("a", Some(deadline_at + 180 * MINUTE)), vec![dismissed])];
+        let plan = plan_reminders(clock.now_ms(), moved);
+        assert_eq!(plan.next_fire_at, Some(deadline_at + 60 * MINUTE));
+    }
+
+    #[tokio::test]
+    async fn tick_persists_default_reminder_once() {
+        let conn = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&conn, None).await.unwrap();
+        seed::spaces::seed_default_spaces_if_empty(&conn)
+            .await
+            .unwrap();
+        task("a", Some(NOW - MINUTE))
+            .into_active_model()
+            .insert(&conn)
+            .await
+            .unwrap();
+
+        let clock = FixedClock::new(NOW);
+        let scheduler = ReminderScheduler::new(conn.clone(), clock.clone());
+
+        let tick = scheduler.tick().await.unwrap();
+        assert_eq!(tick.fired.len(), 1);
+        assert_eq!(
+            tick.fired[0].reminder_id,
+            reminders::default_reminder_id("a")
+        );
+        assert_eq!(tick.fired[0].fired_at, NOW);
+        assert!(scheduler.tick().await.unwrap().fired.is_empty());
+
+        // 清空提醒后只剩 tombstone，截止时间改动后也不再默认提醒。
+        TaskService::set_reminders(&conn, "a", Vec::new())
+            .await
+            .unwrap();
+        let mut active_model = task("a", Some(NOW)).into_active_model();
+        active_model.deadline_at = Set(Some(NOW + 5 * MINUTE));
+        active_model.update(&conn).await.unwrap();
+        clock.set(NOW + 5 * MINUTE);
+        let tick = scheduler.tick().await.unwrap();
+        assert!(tick.fired.is_empty());
+        assert_eq!(tick.next_fire_at, None);
+    }
+
+    #[tokio::test]
+    async fn cleared_reminders_never_fire() {
+        let conn = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&conn, None).await.unwrap();
+        seed::spaces::seed_default_spaces_if_empty(&conn)
+            .await
+            .unwrap();
+        for id in ["never", "cleared"] {
+            task(id, Some(NOW - MINUTE))
+                .into_active_model()
+                .insert(&conn)
+                .await
+                .unwrap();
+        }
+
+        // 从未配置过提醒的任务清空提醒，同样不再默认提醒。
+        assert!(TaskService::set_reminders(&conn, "cleared", Vec::new())
+            .await
+            .unwrap()
+            .is_empty());
+
+        let clock = FixedClock::new(NOW);
+        let scheduler = ReminderScheduler::new(conn.clone(), clock.clone());
+        let tick = scheduler.tick().await.unwrap();
+        assert_eq!(
+            tick.fired
+                .iter()
+                .map(|fire| fire.task_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["never"]
+        );
+
+        let marker = task_reminders::Entity::find_by_id(reminders::default_reminder_id("cleared"))
+            .one(&conn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(marker.deleted_at.is_some());
+
+        // 重新配置提醒后照常触发
+        TaskService::set_reminders(&conn, "cleared", vec![0])
+            .await
+            .unwrap();
+        let tick = scheduler.tick().await.unwrap();
+        assert_eq!(tick.fired.len(), 1);
+        assert_eq!(tick.fired[0].task_id, "cleared");
+    }
+}
@@ -53,12 +53,21 @@ pub struct SyncTablesReport {
     pub tags: SyncTableReport,
     pub links: SyncTableReport,
     pub tasks: SyncTableReport,
+    pub task_checklist_items: SyncTableReport,
+    pub task_time_entries: SyncTableReport,
+    pub task_reminders: SyncTableReport,
+    pub task_templates: SyncTableReport,
+    pub project_templates: SyncTableReport,
+    pub custom_field_definitions: SyncTableReport,
+    pub project_milestones: SyncTableReport,
     pub task_activity_logs: SyncTableReport,
     pub project_activity_logs: SyncTableReport,
     pub task_tags: SyncTableReport,
     pub task_links: SyncTableReport,
     pub project_tags: SyncTableReport,
     pub project_links: SyncTableReport,
+    pub task_dependencies: SyncTableReport,
+    pub purged_entities: SyncTableReport,
 }
 
 /// pull / push 命令最终返回给前端的完整结果。
@@ -4,11 +4,14 @@
 //! 1. 建立远端连接
 //! 2. 读取本地 pull 水位
 //! 3. 按固定顺序同步各表
-//! 4. 全部成功后推进水位
-//! 5. 组装最终报告
+//! 4. 重建本地搜索索引
+//! 5. 全部成功后推进水位
+//! 6. 组装最终报告
 
 use sea_orm::DatabaseConnection;
 
+use crate::repos::{search_repo, task_repo::stats};
+
 use super::{
     connection,
     error::SyncError,
@@ -69,6 +72,62 @@ pub(super) async fn pull(
         SyncDirection::Pull,
     )
     .await?;
+    stats.task_checklist_items = upsert::sync_task_checklist_items(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
+    stats.task_time_entries = upsert::sync_task_time_entries(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
+    stats.task_reminders = upsert::sync_task_reminders(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
+    stats.task_templates = upsert::sync_task_templates(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
+    stats.project_templates = upsert::sync_project_templates(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
+    stats.custom_field_definitions = upsert::sync_custom_field_definitions(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
+    stats.project_milestones = upsert::sync_project_milestones(
+        &remote_db,
+        local_db,
+        last_pulled_at,
+        conflict_guard_enabled,
+        SyncDirection::Pull,
+    )
+    .await?;
     stats.vault_entries = upsert::sync_assets(
         &remote_db,
         local_db,
@@ -97,6 +156,20 @@ pub(super) async fn pull(
     stats.task_links = relations.task_links;
     stats.project_tags = relations.project_tags;
     stats.project_links = relations.project_links;
+    stats.task_dependencies = relations.task_dependencies;
+
+    // 墓碑最后同步，顺带清掉本轮被其他表带回来的已彻底删除数据。
+    stats.purged_entities =
+        upsert::sync_purges(&remote_db, local_db, last_pulled_at, SyncDirection::Pull).await?;
+
+    // pull 直接写表、不经过 service，这里统一重建本地搜索索引。
+    search_repo::rebuild_all(local_db)
+        .await
+        .map_err(|error| SyncError::write_target("pull", "search_index", error))?;
+    // 子树汇总只在本地维护，拉下来的任务和项目变化要在本地重新汇总一遍。
+    stats::refresh_all_subtree_rollups(local_db)
+        .await
+        .map_err(|error| SyncError::write_target("pull", "projects", error))?;
 
     watermarks::write_last_pulled_at(local_db, database_url, current_sync_start).await?;
 
@@ -67,6 +67,62 @@ pub(super) async fn push(
         SyncDirection::Push,
     )
     .await?;
+    stats.task_checklist_items = upsert::sync_task_checklist_items(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
+    stats.task_time_entries = upsert::sync_task_time_entries(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
+    stats.task_reminders = upsert::sync_task_reminders(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
+    stats.task_templates = upsert::sync_task_templates(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
+    stats.project_templates = upsert::sync_project_templates(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
+    stats.custom_field_definitions = upsert::sync_custom_field_definitions(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
+    stats.project_milestones = upsert::sync_project_milestones(
+        local_db,
+        &remote_db,
+        last_pushed_at,
+        conflict_guard_enabled,
+        SyncDirection::Push,
+    )
+    .await?;
     stats.vault_entries = upsert::sync_assets(
         local_db,
         &remote_db,
@@ -95,6 +151,11 @@ pub(super) async fn push(
     stats.task_links = relations.task_links;
     stats.project_tags = relations.project_tags;
     stats.project_links = relations.project_links;
+    stats.task_dependencies = relations.task_dependencies;
+
+    // 墓碑最后同步，顺带清掉本轮被其他表带回来的已彻底删除数据。
+    stats.purged_entities =
+        upsert::sync_purges(local_db, &remote_db, last_pushed_at, SyncDirection::Push).await?;
 
     watermarks::write_last_pushed_at(local_db, database_url, current_sync_start).await?;
 
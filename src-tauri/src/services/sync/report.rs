@@ -30,12 +30,21 @@ pub(crate) struct SyncRunStats {
     pub tags: DedupStats,
     pub links: UpsertStats,
     pub tasks: UpsertStats,
+    pub task_checklist_items: UpsertStats,
+    pub task_time_entries: UpsertStats,
+    pub task_reminders: UpsertStats,
+    pub task_templates: UpsertStats,
+    pub project_templates: UpsertStats,
+    pub custom_field_definitions: UpsertStats,
+    pub project_milestones: UpsertStats,
     pub task_activity_logs: DedupStats,
     pub project_activity_logs: DedupStats,
     pub task_tags: UpsertStats,
     pub task_links: UpsertStats,
     pub project_tags: UpsertStats,
     pub project_links: UpsertStats,
+    pub task_dependencies: UpsertStats,
+    pub purged_entities: DedupStats,
 }
 
 impl From<UpsertStats> for SyncTableReport {
@@ -67,12 +76,21 @@ impl SyncRunStats {
                 tags: self.tags.into(),
                 links: self.links.into(),
                 tasks: self.tasks.into(),
+                task_checklist_items: self.task_checklist_items.into(),
+                task_time_entries: self.task_time_entries.into(),
+                task_reminders: self.task_reminders.into(),
+                task_templates: self.task_templates.into(),
+                project_templates: self.project_templates.into(),
+                custom_field_definitions: self.custom_field_definitions.into(),
+                project_milestones: self.project_milestones.into(),
                 task_activity_logs: self.task_activity_logs.into(),
                 project_activity_logs: self.project_activity_logs.into(),
                 task_tags: self.task_tags.into(),
                 task_links: self.task_links.into(),
                 project_tags: self.project_tags.into(),
                 project_links: self.project_links.into(),
+                task_dependencies: self.task_dependencies.into(),
+                purged_entities: self.purged_entities.into(),
             },
         }
     }
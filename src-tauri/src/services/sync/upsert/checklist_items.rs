@@ -0,0 +1,104 @@
+//! `task_checklist_items` 同步。
+//!
+//! 清单项和关系表一样带 `updated_at` + `deleted_at`：
+//! 删除以 tombstone 形式随增量传播，版本比较沿用 `decide_upsert`。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{prelude::TaskChecklistItems, task_checklist_items};
+use crate::services::sync::{
+    error::SyncError,
+    helpers::{decide_upsert, UpsertDecision},
+    report::UpsertStats,
+};
+
+use super::SyncDirection;
+
+/// 同步 `task_checklist_items` 表。
+pub(super) async fn sync(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = TaskChecklistItems::find()
+        .filter(task_checklist_items::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "TaskChecklistItems", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        TaskChecklistItems::find()
+            .select_only()
+            .columns([
+                task_checklist_items::Column::Id,
+                task_checklist_items::Column::UpdatedAt,
+            ])
+            .filter(
+                task_checklist_items::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "TaskChecklistItems", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        // tombstone 也是普通字段覆盖，删除因此能传播到目标端。
+        let active_model: task_checklist_items::ActiveModel = item.into();
+        task_checklist_items::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(task_checklist_items::Column::Id)
+                    .update_columns([
+                        task_checklist_items::Column::TaskId,
+                        task_checklist_items::Column::Title,
+                        task_checklist_items::Column::Done,
+                        task_checklist_items::Column::Rank,
+                        task_checklist_items::Column::UpdatedAt,
+                        task_checklist_items::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::write_target(direction.as_str(), "TaskChecklistItem", error)
+            })?;
+    }
+
+    Ok(stats)
+}
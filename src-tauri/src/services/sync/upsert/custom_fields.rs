@@ -0,0 +1,105 @@
+//! `custom_field_definitions` 同步。
+//!
+//! 定义带 `updated_at` + `deleted_at`，按版本整体覆盖；任务里的取值随 `tasks` 同步。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{custom_field_definitions, prelude::CustomFieldDefinitions};
+use crate::services::sync::{
+    error::SyncError,
+    helpers::{decide_upsert, UpsertDecision},
+    report::UpsertStats,
+};
+
+use super::SyncDirection;
+
+/// 同步 `custom_field_definitions` 表。
+pub(super) async fn sync(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = CustomFieldDefinitions::find()
+        .filter(custom_field_definitions::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| {
+            SyncError::source_read(direction.as_str(), "CustomFieldDefinitions", error)
+        })?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        CustomFieldDefinitions::find()
+            .select_only()
+            .columns([
+                custom_field_definitions::Column::Id,
+                custom_field_definitions::Column::UpdatedAt,
+            ])
+            .filter(
+                custom_field_definitions::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "CustomFieldDefinitions", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        let active_model: custom_field_definitions::ActiveModel = item.into();
+        custom_field_definitions::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(custom_field_definitions::Column::Id)
+                    .update_columns([
+                        custom_field_definitions::Column::ProjectId,
+                        custom_field_definitions::Column::Title,
+                        custom_field_definitions::Column::FieldType,
+                        custom_field_definitions::Column::Options,
+                        custom_field_definitions::Column::Rank,
+                        custom_field_definitions::Column::UpdatedAt,
+                        custom_field_definitions::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::write_target(direction.as_str(), "CustomFieldDefinition", error)
+            })?;
+    }
+
+    Ok(stats)
+}
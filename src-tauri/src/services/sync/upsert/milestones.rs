@@ -0,0 +1,104 @@
+//! `project_milestones` 同步。
+//!
+//! 里程碑带 `updated_at` + `deleted_at`，按版本覆盖；任务数与完成数由本地统计刷新，更新时不覆盖。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{prelude::ProjectMilestones, project_milestones};
+use crate::services::sync::{
+    error::SyncError,
+    helpers::{decide_upsert, UpsertDecision},
+    report::UpsertStats,
+};
+
+use super::SyncDirection;
+
+/// 同步 `project_milestones` 表。
+pub(super) async fn sync(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = ProjectMilestones::find()
+        .filter(project_milestones::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "ProjectMilestones", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        ProjectMilestones::find()
+            .select_only()
+            .columns([
+                project_milestones::Column::Id,
+                project_milestones::Column::UpdatedAt,
+            ])
+            .filter(
+                project_milestones::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "ProjectMilestones", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        let active_model: project_milestones::ActiveModel = item.into();
+        project_milestones::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(project_milestones::Column::Id)
+                    .update_columns([
+                        project_milestones::Column::ProjectId,
+                        project_milestones::Column::SpaceId,
+                        project_milestones::Column::Title,
+                        project_milestones::Column::TargetAt,
+                        project_milestones::Column::Rank,
+                        project_milestones::Column::CompletedAt,
+                        project_milestones::Column::UpdatedAt,
+                        project_milestones::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::write_target(direction.as_str(), "ProjectMilestone", error)
+            })?;
+    }
+
+    Ok(stats)
+}
@@ -6,11 +6,18 @@
 //! - 给 `pull.rs / push.rs` 暴露统一调用入口
 
 mod append_only;
+mod checklist_items;
+mod custom_fields;
 mod links;
+mod milestones;
 mod projects;
+mod purges;
 mod relations;
+mod reminders;
 mod spaces;
 mod tasks;
+mod templates;
+mod time_entries;
 mod vault_entries;
 
 use sea_orm::DatabaseConnection;
@@ -48,6 +55,7 @@ pub(super) struct RelationSyncStats {
     pub task_links: UpsertStats,
     pub project_tags: UpsertStats,
     pub project_links: UpsertStats,
+    pub task_dependencies: UpsertStats,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -127,6 +135,132 @@ pub(super) async fn sync_tasks(
     .await
 }
 
+/// 同步任务检查清单；清单项依赖任务，需在任务之后执行。
+pub(super) async fn sync_task_checklist_items(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    checklist_items::sync(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
+/// 同步任务计时记录；计时记录依赖任务，需在任务之后执行。
+pub(super) async fn sync_task_time_entries(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    time_entries::sync(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
+/// 同步任务提醒；提醒依赖任务，需在任务之后执行。
+pub(super) async fn sync_task_reminders(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    reminders::sync(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
+/// 同步任务模板。
+pub(super) async fn sync_task_templates(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    templates::sync_tasks(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
+/// 同步项目模板。
+pub(super) async fn sync_project_templates(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    templates::sync_projects(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
+/// 同步自定义字段定义。
+pub(super) async fn sync_custom_field_definitions(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    custom_fields::sync(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
+/// 同步项目里程碑。
+pub(super) async fn sync_project_milestones(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    milestones::sync(
+        source_db,
+        target_db,
+        since_ms,
+        conflict_guard_enabled,
+        direction,
+    )
+    .await
+}
+
 /// append-only 表通常只看“新增了多少”，不统计 updated。
 pub(super) async fn sync_append_only(
     source_db: &DatabaseConnection,
@@ -137,6 +271,16 @@ pub(super) async fn sync_append_only(
     append_only::sync(source_db, target_db, since_ms, direction).await
 }
 
+/// 墓碑同步放在所有表之后，确保重放硬删除时能清掉本轮刚写进来的旧数据。
+pub(super) async fn sync_purges(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    direction: SyncDirection,
+) -> Result<DedupStats, SyncError> {
+    purges::sync(source_db, target_db, since_ms, direction).await
+}
+
 /// 关系表集中放在一起，便于统一解释 tombstone 增量同步策略。
 pub(super) async fn sync_relations(
     source_db: &DatabaseConnection,
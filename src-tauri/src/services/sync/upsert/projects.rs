@@ -89,11 +89,17 @@ pub(super) async fn sync(
                         projects::Column::Note,
                         projects::Column::Priority,
                         projects::Column::TodoTaskCount,
+                        projects::Column::DoingTaskCount,
+                        projects::Column::WaitingTaskCount,
+                        projects::Column::SomedayTaskCount,
                         projects::Column::DoneTaskCount,
                         projects::Column::LastTaskUpdatedAt,
                         projects::Column::ArchivedAt,
                         projects::Column::DeletedAt,
                         projects::Column::Rank,
+                        projects::Column::LifecycleStatus,
+                        projects::Column::StartAt,
+                        projects::Column::DueAt,
                     ])
                     .to_owned(),
             )
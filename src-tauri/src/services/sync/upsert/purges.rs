@@ -0,0 +1,59 @@
+//! 彻底删除的同步。
+//!
+//! 墓碑表本身按 append-only 补齐；补齐之后在目标端按全部墓碑重放硬删除，
+//! 这样本轮被其他表重新带回来的旧数据也会一并清掉。
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    TransactionTrait,
+};
+
+use crate::db::entities::{prelude::PurgedEntities, purged_entities};
+use crate::repos::trash_repo;
+use crate::services::sync::{error::SyncError, report::DedupStats};
+
+use super::SyncDirection;
+
+pub(super) async fn sync(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    direction: SyncDirection,
+) -> Result<DedupStats, SyncError> {
+    let source_items = PurgedEntities::find()
+        .filter(purged_entities::Column::PurgedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "PurgedEntities", error))?;
+
+    let mut stats = DedupStats {
+        total: source_items.len(),
+        ..Default::default()
+    };
+    for item in source_items {
+        let active_model: purged_entities::ActiveModel = item.into();
+        let inserted = purged_entities::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(purged_entities::Column::Id)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(target_db)
+            .await
+            .map_err(|error| SyncError::write_target(direction.as_str(), "PurgedEntity", error))?;
+        stats.inserted += inserted as usize;
+    }
+
+    let txn = target_db
+        .begin()
+        .await
+        .map_err(|error| SyncError::write_target(direction.as_str(), "PurgedEntity", error))?;
+    trash_repo::apply_tombstones(&txn, chrono::Utc::now().timestamp_millis())
+        .await
+        .map_err(|error| SyncError::write_target(direction.as_str(), "PurgedEntity", error))?;
+    txn.commit()
+        .await
+        .map_err(|error| SyncError::write_target(direction.as_str(), "PurgedEntity", error))?;
+
+    Ok(stats)
+}
@@ -10,8 +10,8 @@ use sea_orm::{
 };
 
 use crate::db::entities::{
-    prelude::{ProjectLinks, ProjectTags, TaskLinks, TaskTags},
-    project_links, project_tags, task_links, task_tags,
+    prelude::{ProjectLinks, ProjectTags, TaskDependencies, TaskLinks, TaskTags},
+    project_links, project_tags, task_dependencies, task_links, task_tags,
 };
 use crate::services::sync::{
     error::SyncError,
@@ -62,6 +62,14 @@ pub(super) async fn sync(
             direction,
         )
         .await?,
+        task_dependencies: sync_task_dependencies(
+            source_db,
+            target_db,
+            since_ms,
+            conflict_guard_enabled,
+            direction,
+        )
+        .await?,
     })
 }
 
@@ -346,3 +354,78 @@ async fn sync_project_links(
 
     Ok(stats)
 }
+
+async fn sync_task_dependencies(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = TaskDependencies::find()
+        .filter(task_dependencies::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "TaskDependencies", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<(String, String), i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        TaskDependencies::find()
+            .select_only()
+            .columns([
+                task_dependencies::Column::TaskId,
+                task_dependencies::Column::BlockedByTaskId,
+                task_dependencies::Column::UpdatedAt,
+            ])
+            .filter(task_dependencies::Column::TaskId.is_in(source_items.iter().map(|item| item.task_id.clone())))
+            .filter(task_dependencies::Column::BlockedByTaskId.is_in(source_items.iter().map(|item| item.blocked_by_task_id.clone())))
+            .into_tuple::<(String, String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| SyncError::target_state_read(direction.as_str(), "TaskDependencies", error))?
+            .into_iter()
+            .map(|(task_id, blocked_by_task_id, updated_at)| ((task_id, blocked_by_task_id), updated_at))
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        let relation_key = (item.task_id.clone(), item.blocked_by_task_id.clone());
+        match decide_upsert(
+            existing_versions.get(&relation_key).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        let active_model: task_dependencies::ActiveModel = item.into();
+        task_dependencies::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    task_dependencies::Column::TaskId,
+                    task_dependencies::Column::BlockedByTaskId,
+                ])
+                .update_columns([
+                    task_dependencies::Column::UpdatedAt,
+                    task_dependencies::Column::DeletedAt,
+                ])
+                .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| SyncError::write_target(direction.as_str(), "TaskDependency", error))?;
+    }
+
+    Ok(stats)
+}
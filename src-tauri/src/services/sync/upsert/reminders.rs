@@ -0,0 +1,104 @@
+//! `task_reminders` 同步。
+//!
+//! 提醒带 `updated_at` + `deleted_at`，稍后/忽略状态随增量传播，
+//! 避免在一台设备上忽略的提醒又在另一台设备上弹出。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{prelude::TaskReminders, task_reminders};
+use crate::services::sync::{
+    error::SyncError,
+    helpers::{decide_upsert, UpsertDecision},
+    report::UpsertStats,
+};
+
+use super::SyncDirection;
+
+/// 同步 `task_reminders` 表。
+pub(super) async fn sync(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = TaskReminders::find()
+        .filter(task_reminders::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "TaskReminders", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        TaskReminders::find()
+            .select_only()
+            .columns([
+                task_reminders::Column::Id,
+                task_reminders::Column::UpdatedAt,
+            ])
+            .filter(
+                task_reminders::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "TaskReminders", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        // 触发、稍后、忽略和 tombstone 都是普通字段覆盖，按版本整体传播。
+        let active_model: task_reminders::ActiveModel = item.into();
+        task_reminders::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(task_reminders::Column::Id)
+                    .update_columns([
+                        task_reminders::Column::TaskId,
+                        task_reminders::Column::OffsetMinutes,
+                        task_reminders::Column::AnchorDeadlineAt,
+                        task_reminders::Column::LastFiredAt,
+                        task_reminders::Column::SnoozedUntil,
+                        task_reminders::Column::DismissedAt,
+                        task_reminders::Column::UpdatedAt,
+                        task_reminders::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| SyncError::write_target(direction.as_str(), "TaskReminder", error))?;
+    }
+
+    Ok(stats)
+}
@@ -75,7 +75,8 @@ pub(super) async fn sync(
             }
         }
 
-        // 任务的状态、优先级、归档/删除时间、自定义字段都在这里一并覆盖。
+        // 任务的状态、优先级、归档/删除时间、自定义字段都在这里一并覆盖；
+        // 重复规则与系列字段也要随行同步，保证各端看到的是同一个重复系列。
         let active_model: tasks::ActiveModel = item.into();
         tasks::Entity::insert(active_model)
             .on_conflict(
@@ -90,11 +91,17 @@ pub(super) async fn sync(
                         tasks::Column::Rank,
                         tasks::Column::CompletedAt,
                         tasks::Column::DeadlineAt,
+                        tasks::Column::ScheduledAt,
+                        tasks::Column::DeferUntil,
                         tasks::Column::ArchivedAt,
                         tasks::Column::DeletedAt,
                         tasks::Column::CustomFields,
                         tasks::Column::ProjectId,
                         tasks::Column::SpaceId,
+                        tasks::Column::RecurrenceRule,
+                        tasks::Column::RecurrenceSeriesId,
+                        tasks::Column::MilestoneId,
+                        tasks::Column::RecurrenceIndex,
                     ])
                     .to_owned(),
             )
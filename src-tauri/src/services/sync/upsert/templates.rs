@@ -0,0 +1,185 @@
+//! `task_templates` / `project_templates` 同步。
+//!
+//! 模板不依赖任务和项目，带 `updated_at` + `deleted_at`，按版本整体覆盖。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{
+    prelude::{ProjectTemplates, TaskTemplates},
+    project_templates, task_templates,
+};
+use crate::services::sync::{
+    error::SyncError,
+    helpers::{decide_upsert, UpsertDecision},
+    report::UpsertStats,
+};
+
+use super::SyncDirection;
+
+/// 同步 `task_templates` 表。
+pub(super) async fn sync_tasks(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = TaskTemplates::find()
+        .filter(task_templates::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "TaskTemplates", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        TaskTemplates::find()
+            .select_only()
+            .columns([
+                task_templates::Column::Id,
+                task_templates::Column::UpdatedAt,
+            ])
+            .filter(
+                task_templates::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "TaskTemplates", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        let active_model: task_templates::ActiveModel = item.into();
+        task_templates::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(task_templates::Column::Id)
+                    .update_columns([
+                        task_templates::Column::Name,
+                        task_templates::Column::SpaceId,
+                        task_templates::Column::ProjectId,
+                        task_templates::Column::Body,
+                        task_templates::Column::UpdatedAt,
+                        task_templates::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| SyncError::write_target(direction.as_str(), "TaskTemplate", error))?;
+    }
+
+    Ok(stats)
+}
+
+/// 同步 `project_templates` 表。
+pub(super) async fn sync_projects(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = ProjectTemplates::find()
+        .filter(project_templates::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "ProjectTemplates", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        ProjectTemplates::find()
+            .select_only()
+            .columns([
+                project_templates::Column::Id,
+                project_templates::Column::UpdatedAt,
+            ])
+            .filter(
+                project_templates::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "ProjectTemplates", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        let active_model: project_templates::ActiveModel = item.into();
+        project_templates::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(project_templates::Column::Id)
+                    .update_columns([
+                        project_templates::Column::Name,
+                        project_templates::Column::Body,
+                        project_templates::Column::UpdatedAt,
+                        project_templates::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::write_target(direction.as_str(), "ProjectTemplate", error)
+            })?;
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,107 @@
+//! `task_time_entries` 同步。
+//!
+//! 计时记录和检查清单一样带 `updated_at` + `deleted_at`：
+//! 删除以 tombstone 形式随增量传播，版本比较沿用 `decide_upsert`。
+
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+};
+
+use crate::db::entities::{prelude::TaskTimeEntries, task_time_entries};
+use crate::services::sync::{
+    error::SyncError,
+    helpers::{decide_upsert, UpsertDecision},
+    report::UpsertStats,
+};
+
+use super::SyncDirection;
+
+/// 同步 `task_time_entries` 表。
+pub(super) async fn sync(
+    source_db: &DatabaseConnection,
+    target_db: &DatabaseConnection,
+    since_ms: i64,
+    conflict_guard_enabled: bool,
+    direction: SyncDirection,
+) -> Result<UpsertStats, SyncError> {
+    let source_items = TaskTimeEntries::find()
+        .filter(task_time_entries::Column::UpdatedAt.gt(since_ms))
+        .all(source_db)
+        .await
+        .map_err(|error| SyncError::source_read(direction.as_str(), "TaskTimeEntries", error))?;
+
+    let total = source_items.len();
+    let existing_versions: HashMap<String, i64> = if source_items.is_empty() {
+        HashMap::new()
+    } else {
+        TaskTimeEntries::find()
+            .select_only()
+            .columns([
+                task_time_entries::Column::Id,
+                task_time_entries::Column::UpdatedAt,
+            ])
+            .filter(
+                task_time_entries::Column::Id.is_in(
+                    source_items
+                        .iter()
+                        .map(|item| item.id.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(target_db)
+            .await
+            .map_err(|error| {
+                SyncError::target_state_read(direction.as_str(), "TaskTimeEntries", error)
+            })?
+            .into_iter()
+            .collect()
+    };
+
+    let mut stats = UpsertStats {
+        total,
+        ..Default::default()
+    };
+    for item in source_items {
+        match decide_upsert(
+            existing_versions.get(&item.id).copied(),
+            item.updated_at,
+            conflict_guard_enabled,
+        ) {
+            UpsertDecision::Insert => stats.inserted += 1,
+            UpsertDecision::Update => stats.updated += 1,
+            UpsertDecision::ConflictSkip => {
+                stats.conflicted += 1;
+                continue;
+            }
+        }
+
+        // 暂停、结束和 tombstone 都是普通字段覆盖，按版本整体传播。
+        let active_model: task_time_entries::ActiveModel = item.into();
+        task_time_entries::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(task_time_entries::Column::Id)
+                    .update_columns([
+                        task_time_entries::Column::TaskId,
+                        task_time_entries::Column::Mode,
+                        task_time_entries::Column::StartedAt,
+                        task_time_entries::Column::EndedAt,
+                        task_time_entries::Column::PausedAt,
+                        task_time_entries::Column::PausedMs,
+                        task_time_entries::Column::PlannedMs,
+                        task_time_entries::Column::DurationMs,
+                        task_time_entries::Column::Note,
+                        task_time_entries::Column::UpdatedAt,
+                        task_time_entries::Column::DeletedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(target_db)
+            .await
+            .map_err(|error| SyncError::write_target(direction.as_str(), "TaskTimeEntry", error))?;
+    }
+
+    Ok(stats)
+}
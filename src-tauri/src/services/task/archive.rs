@@ -0,0 +1,187 @@
+//! 任务归档与自动归档用例。
+//!
+//! 归档只是把任务移出日常列表：状态不变，仍可在归档列表里查看和取消归档。
+//! 自动归档按完成时间挑出已完成任务，走同一套批量归档逻辑。
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::now_ms;
+use crate::repos::{
+    search_repo,
+    task_repo::{activity_logs, archive, mutation, query, stats},
+};
+use crate::types::error::AppError;
+
+use super::{helpers::dedup_project_ids, time_tracking::stop_timers_for_tasks, TaskService};
+
+const DAY_MS: i64 = 86_400_000;
+const MAX_AUTO_ARCHIVE_DAYS: i64 = 3650;
+
+impl TaskService {
+    /// 批量归档任务。
+    pub async fn archive_many(
+        conn: &DatabaseConnection,
+        ids: &[String],
+    ) -> Result<usize, AppError> {
+        if ids.is_empty() {
+            return Err(AppError::Validation("请选择要归档的任务".to_string()));
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        let count = Self::archive_many_in(&txn, ids, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(count)
+    }
+
+    /// 在调用方的事务里批量归档任务，已删除或已归档的任务会被跳过。
+    pub(crate) async fn archive_many_in<C>(
+        txn: &C,
+        ids: &[String],
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<usize, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let task_models = query::find_not_deleted_by_ids(txn, ids)
+            .await?
+            .into_iter()
+            .filter(|task| task.archived_at.is_none())
+            .collect::<Vec<_>>();
+        let archived_ids = task_models
+            .iter()
+            .map(|task| task.id.clone())
+            .collect::<Vec<_>>();
+        let count = mutation::archive_many(txn, &archived_ids, now).await?;
+        // 归档任务不再出现在列表里，进行中的计时器一并结束。
+        stop_timers_for_tasks(txn, &archived_ids, now).await?;
+
+        for task in &task_models {
+            activity_logs::append_archived(
+                txn,
+                activity_logs::TaskLogCtx {
+                    task_id: &task.id,
+                    space_id: &task.space_id,
+                    project_id: task.project_id.as_deref(),
+                    create_by: &task.create_by,
+                    created_at: now,
+                    batch_id,
+                },
+                &task.title,
+            )
+            .await?;
+        }
+
+        let project_ids = task_models
+            .iter()
+            .filter_map(|task| task.project_id.clone())
+            .collect();
+        for project_id in dedup_project_ids(project_ids) {
+            stats::refresh_project_stats(txn, &project_id, now).await?;
+        }
+        search_repo::reindex_tasks(txn, &archived_ids).await?;
+        Ok(count)
+    }
+
+    /// 批量取消归档任务。
+    pub async fn unarchive_many(
+        conn: &DatabaseConnection,
+        ids: &[String],
+    ) -> Result<usize, AppError> {
+        if ids.is_empty() {
+            return Err(AppError::Validation("请选择要取消归档的任务".to_string()));
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        let count = Self::unarchive_many_in(&txn, ids, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(count)
+    }
+
+    /// 在调用方的事务里批量取消归档任务，未归档的任务会被跳过。
+    pub(crate) async fn unarchive_many_in<C>(
+        txn: &C,
+        ids: &[String],
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<usize, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let task_models = query::find_not_deleted_by_ids(txn, ids)
+            .await?
+            .into_iter()
+            .filter(|task| task.archived_at.is_some())
+            .collect::<Vec<_>>();
+        let unarchived_ids = task_models
+            .iter()
+            .map(|task| task.id.clone())
+            .collect::<Vec<_>>();
+        let count = mutation::unarchive_many(txn, &unarchived_ids, now).await?;
+
+        for task in &task_models {
+            activity_logs::append_unarchived(
+                txn,
+                activity_logs::TaskLogCtx {
+                    task_id: &task.id,
+                    space_id: &task.space_id,
+                    project_id: task.project_id.as_deref(),
+                    create_by: &task.create_by,
+                    created_at: now,
+                    batch_id,
+                },
+                &task.title,
+                task.archived_at.unwrap_or_default(),
+            )
+            .await?;
+        }
+
+        let project_ids = task_models
+            .iter()
+            .filter_map(|task| task.project_id.clone())
+            .collect();
+        for project_id in dedup_project_ids(project_ids) {
+            stats::refresh_project_stats(txn, &project_id, now).await?;
+        }
+        search_repo::reindex_tasks(txn, &unarchived_ids).await?;
+        Ok(count)
+    }
+
+    /// 归档完成时间超过设定天数的已完成任务；天数为 0 时不做任何事。
+    pub async fn auto_archive_done(conn: &DatabaseConnection, now: i64) -> Result<usize, AppError> {
+        let days = archive::read_auto_archive_days(conn).await?;
+        if days <= 0 {
+            return Ok(0);
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let ids = archive::find_done_ids_completed_before(&txn, now - days * DAY_MS).await?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        // 启动时的自动归档不是用户操作，不进撤销批次，免得“撤销”撤掉它而不是用户刚做的事。
+        let count = Self::archive_many_in(&txn, &ids, now, None).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(count)
+    }
+
+    pub async fn get_auto_archive_days(conn: &DatabaseConnection) -> Result<i64, AppError> {
+        archive::read_auto_archive_days(conn).await
+    }
+
+    pub async fn set_auto_archive_days(
+        conn: &DatabaseConnection,
+        days: i64,
+    ) -> Result<i64, AppError> {
+        if !(0..=MAX_AUTO_ARCHIVE_DAYS).contains(&days) {
+            return Err(AppError::Validation(format!(
+                "自动归档天数需在 0 到 {MAX_AUTO_ARCHIVE_DAYS} 之间"
+            )));
+        }
+        archive::write_auto_archive_days(conn, days).await?;
+        Ok(days)
+    }
+}
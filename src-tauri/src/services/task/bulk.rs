@@ -0,0 +1,90 @@
+//! 任务批量更新用例。
+//!
+//! 约定：
+//! - 整批共用一个外层事务，每个任务再套一层 savepoint；单个任务失败只回滚它自己
+//! - 成功写出的字段日志共用一个 `batch_id`，便于前端按批次折叠与追溯
+//! - 项目统计与搜索索引在整批结束后统一刷新，每个项目只刷新一次
+
+use std::collections::HashSet;
+
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::now_ms;
+use crate::repos::{search_repo, task_repo::stats};
+use crate::types::{
+    dto::{BulkUpdateItemResultDto, BulkUpdateResultDto},
+    error::{ApiError, AppError},
+};
+
+use super::{
+    dto::{TaskUpdateInput, TaskUpdatePatch},
+    TaskService,
+};
+
+impl TaskService {
+    /// 把同一个 patch 应用到多个任务，并逐条返回成功或失败原因。
+    pub async fn bulk_update(
+        conn: &DatabaseConnection,
+        ids: &[String],
+        patch: TaskUpdatePatch,
+    ) -> Result<BulkUpdateResultDto, AppError> {
+        let mut seen = HashSet::new();
+        let ids: Vec<&String> = ids.iter().filter(|id| seen.insert(id.as_str())).collect();
+        if ids.is_empty() {
+            return Err(AppError::Validation("请选择要更新的任务".to_string()));
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let batch_id = Uuid::new_v4().to_string();
+        let mut touched_project_ids = HashSet::new();
+        let mut updated_ids = Vec::new();
+        let mut results = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let savepoint = txn.begin().await.map_err(AppError::from)?;
+            let input = TaskUpdateInput {
+                id: id.clone(),
+                patch: patch.clone(),
+            };
+            match Self::apply_update(&savepoint, input, now, Some(&batch_id)).await {
+                Ok(project_ids) => {
+                    savepoint.commit().await.map_err(AppError::from)?;
+                    touched_project_ids.extend(project_ids);
+                    updated_ids.push(id.clone());
+                    results.push(BulkUpdateItemResultDto {
+                        id: id.clone(),
+                        ok: true,
+                        error_code: None,
+                        error_message: None,
+                    });
+                }
+                Err(error) => {
+                    savepoint.rollback().await.map_err(AppError::from)?;
+                    let error = ApiError::from(error);
+                    results.push(BulkUpdateItemResultDto {
+                        id: id.clone(),
+                        ok: false,
+                        error_code: Some(error.code),
+                        error_message: Some(error.message),
+                    });
+                }
+            }
+        }
+
+        for project_id in &touched_project_ids {
+            stats::refresh_project_stats(&txn, project_id, now).await?;
+        }
+        if !updated_ids.is_empty() {
+            search_repo::reindex_tasks(&txn, &updated_ids).await?;
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(BulkUpdateResultDto {
+            batch_id: (!updated_ids.is_empty()).then_some(batch_id),
+            updated: updated_ids.len(),
+            results,
+        })
+    }
+}
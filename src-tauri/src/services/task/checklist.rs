@@ -0,0 +1,203 @@
+//! 任务检查清单用例。
+//!
+//! 清单项是任务的子实体：
+//! - 每次写入都在事务里同时刷新任务 `updated_at`
+//! - 标题、勾选和删除变化以字段级日志记在所属任务上
+//! - 删除只写 tombstone，交给同步传播
+
+use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{entities::tasks, now_ms};
+use crate::repos::{
+    search_repo,
+    task_repo::{activity_logs, checklist, mutation, query, validations},
+};
+use crate::types::{dto::ChecklistItemDto, error::AppError};
+
+use super::{
+    dto::{ChecklistItemCreateInput, ChecklistItemUpdateInput, ChecklistItemUpdatePatch},
+    helpers::checklist_item_to_value,
+    TaskService,
+};
+
+impl TaskService {
+    /// 为任务追加一条检查项。
+    ///
+    /// 未指定 rank 时追加到清单末尾。
+    pub async fn create_checklist_item(
+        conn: &DatabaseConnection,
+        input: ChecklistItemCreateInput,
+    ) -> Result<ChecklistItemDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let title = validations::trim_and_validate_checklist_title(&input.title)?;
+        let task = query::find_by_id(&txn, &input.task_id).await?;
+        let rank = match input.rank {
+            Some(rank) => rank,
+            None => checklist::next_rank(&txn, &task.id).await?,
+        };
+
+        let item = checklist::insert(
+            &txn,
+            checklist::NewChecklistItemRecord {
+                id: Uuid::new_v4().to_string(),
+                task_id: task.id.clone(),
+                title,
+                done: false,
+                rank,
+                created_at: now,
+            },
+        )
+        .await?;
+        mutation::touch_updated_at(&txn, &task.id, now).await?;
+
+        activity_logs::append_field_updated(
+            &txn,
+            task_log_ctx(&task, now),
+            "checklist",
+            "检查清单",
+            None,
+            Some(checklist_item_to_value(&item.title, item.done)),
+        )
+        .await?;
+        search_repo::reindex_tasks(&txn, std::slice::from_ref(&task.id)).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(checklist::to_dto(item))
+    }
+
+    /// 按 patch 更新检查项的标题、勾选状态或排序。
+    pub async fn update_checklist_item(
+        conn: &DatabaseConnection,
+        input: ChecklistItemUpdateInput,
+    ) -> Result<ChecklistItemDto, AppError> {
+        let ChecklistItemUpdateInput { id, patch } = input;
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let previous_item = checklist::find_by_id(&txn, &id).await?;
+        let task = query::find_by_id(&txn, &previous_item.task_id).await?;
+
+        let mut active_model = previous_item.clone().into_active_model();
+        let mut changed_any = false;
+
+        if let Some(title) = patch.title.as_deref() {
+            active_model.title = Set(validations::trim_and_validate_checklist_title(title)?);
+            changed_any = true;
+        }
+        if let Some(done) = patch.done {
+            active_model.done = Set(done);
+            changed_any = true;
+        }
+        if let Some(rank) = patch.rank {
+            active_model.rank = Set(rank);
+            changed_any = true;
+        }
+
+        if !changed_any {
+            return Err(AppError::Validation("没有可更新的字段".to_string()));
+        }
+
+        active_model.updated_at = Set(now);
+        let saved_item = checklist::update(&txn, active_model).await?;
+        mutation::touch_updated_at(&txn, &task.id, now).await?;
+
+        // 只调整 rank 时前后文本一致，日志会被自动跳过。
+        activity_logs::append_field_updated(
+            &txn,
+            task_log_ctx(&task, now),
+            "checklist",
+            "检查清单",
+            Some(checklist_item_to_value(
+                &previous_item.title,
+                previous_item.done,
+            )),
+            Some(checklist_item_to_value(&saved_item.title, saved_item.done)),
+        )
+        .await?;
+        search_repo::reindex_tasks(&txn, std::slice::from_ref(&task.id)).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(checklist::to_dto(saved_item))
+    }
+
+    /// 切换检查项的完成状态。
+    pub async fn toggle_checklist_item(
+        conn: &DatabaseConnection,
+        id: String,
+    ) -> Result<ChecklistItemDto, AppError> {
+        let item = checklist::find_by_id(conn, &id).await?;
+        Self::update_checklist_item(
+            conn,
+            ChecklistItemUpdateInput {
+                id,
+                patch: ChecklistItemUpdatePatch {
+                    done: Some(!item.done),
+                    ..ChecklistItemUpdatePatch::default()
+                },
+            },
+        )
+        .await
+    }
+
+    /// 调整单个检查项的排序权重。
+    pub async fn reorder_checklist_item(
+        conn: &DatabaseConnection,
+        id: String,
+        new_rank: i64,
+    ) -> Result<ChecklistItemDto, AppError> {
+        Self::update_checklist_item(
+            conn,
+            ChecklistItemUpdateInput {
+                id,
+                patch: ChecklistItemUpdatePatch {
+                    rank: Some(new_rank),
+                    ..ChecklistItemUpdatePatch::default()
+                },
+            },
+        )
+        .await
+    }
+
+    /// 删除检查项（写 tombstone）。
+    pub async fn delete_checklist_item(
+        conn: &DatabaseConnection,
+        id: &str,
+    ) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let item = checklist::find_by_id(&txn, id).await?;
+        let task = query::find_by_id(&txn, &item.task_id).await?;
+
+        let mut active_model = item.clone().into_active_model();
+        active_model.updated_at = Set(now);
+        active_model.deleted_at = Set(Some(now));
+        checklist::update(&txn, active_model).await?;
+        mutation::touch_updated_at(&txn, &task.id, now).await?;
+
+        activity_logs::append_field_updated(
+            &txn,
+            task_log_ctx(&task, now),
+            "checklist",
+            "检查清单",
+            Some(checklist_item_to_value(&item.title, item.done)),
+            None,
+        )
+        .await?;
+        search_repo::reindex_tasks(&txn, std::slice::from_ref(&task.id)).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+fn task_log_ctx(task: &tasks::Model, now: i64) -> activity_logs::TaskLogCtx<'_> {
+    activity_logs::TaskLogCtx {
+        task_id: &task.id,
+        space_id: &task.space_id,
+        project_id: task.project_id.as_deref(),
+        create_by: &task.create_by,
+        created_at: now,
+        batch_id: None,
+    }
+}
@@ -4,24 +4,33 @@
 //! 只负责把任务标记为完成并刷新必要副作用。
 
 use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::{
     entities::sea_orm_active_enums::{DoneReason, TaskStatus},
     now_ms,
 };
-use crate::repos::task_repo::{activity_logs, mutation, query, stats};
-use crate::types::error::AppError;
+use crate::repos::{
+    common_task_utils,
+    task_repo::{activity_logs, mutation, query, stats},
+};
+use crate::types::{dto::TaskCompleteResultDto, error::AppError};
 
-use super::TaskService;
+use super::{dependencies::collect_unblocked_task_ids, TaskService};
 
 impl TaskService {
     /// 将任务标记为完成。
     ///
     /// 如果任务原本已经完成，这里仍会更新完成字段，
-    /// 但不会重复追加“完成”活动日志。
-    pub async fn complete(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
+    /// 但不会重复追加“完成”活动日志，也不会再次生成重复任务的下一次。
+    /// 返回值里带上因此解除阻塞的后续任务，便于前端提示。
+    pub async fn complete(
+        conn: &DatabaseConnection,
+        id: &str,
+    ) -> Result<TaskCompleteResultDto, AppError> {
         let txn = conn.begin().await.map_err(AppError::from)?;
         let now = now_ms();
+        let batch_id = Uuid::new_v4().to_string();
         let task = query::find_by_id(&txn, id).await?;
 
         // 完成动作统一收敛为 status + done_reason + completed_at 三个字段的同步更新。
@@ -32,6 +41,8 @@ impl TaskService {
         active_model.updated_at = Set(now);
         mutation::update(&txn, active_model).await?;
 
+        let mut next_occurrence_task_id = None;
+        let mut unblocked_task_ids = Vec::new();
         if task.status != TaskStatus::Done {
             activity_logs::append_completed(
                 &txn,
@@ -41,10 +52,17 @@ impl TaskService {
                     project_id: task.project_id.as_deref(),
                     create_by: &task.create_by,
                     created_at: now,
+                    batch_id: Some(&batch_id),
                 },
                 &task.title,
+                &common_task_utils::status_to_string(&task.status),
             )
             .await?;
+            next_occurrence_task_id =
+                Self::spawn_next_occurrence(&txn, &task, now, Some(&batch_id))
+                    .await?
+                    .map(|next_task| next_task.id);
+            unblocked_task_ids = collect_unblocked_task_ids(&txn, &task.id).await?;
         }
 
         if let Some(project_id) = task.project_id.as_deref() {
@@ -52,6 +70,9 @@ impl TaskService {
         }
 
         txn.commit().await.map_err(AppError::from)?;
-        Ok(())
+        Ok(TaskCompleteResultDto {
+            unblocked_task_ids,
+            next_occurrence_task_id,
+        })
     }
 }
@@ -3,19 +3,24 @@
 //! 这个文件负责把“创建任务”涉及的默认值、输入归一化、
 //! 初始排序、标签/链接写入和活动日志放进同一个事务里完成。
 
-use sea_orm::{DatabaseConnection, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
 use uuid::Uuid;
 
 use crate::db::{
     entities::sea_orm_active_enums::{DoneReason, Priority, TaskStatus},
     now_ms,
 };
-use crate::repos::task_repo::{
-    activity_logs, custom_fields, links, mutation, query, stats, tags, validations,
+use crate::repos::{
+    common_task_utils, custom_field_repo, search_repo,
+    task_repo::{
+        activity_logs, custom_fields, dependencies, links, mutation, query, recurrence, stats,
+        tags, validations,
+    },
 };
 use crate::types::{dto::TaskDto, error::AppError};
 
 use super::{
+    dependencies::validate_blocked_by,
     dto::TaskCreateInput,
     helpers::{normalize_tags, resolve_default_project_id},
     TaskService,
@@ -27,25 +32,38 @@ impl TaskService {
     /// 这里会在一个事务里完成：
     /// - 标题与 patch 输入归一化
     /// - 主表插入
-    /// - 标签 / 链接 / 自定义字段落库
+    /// - 标签 / 链接 / 前置任务 / 自定义字段 / 重复规则落库
     /// - 活动日志与项目统计刷新
     pub async fn create(
         conn: &DatabaseConnection,
         input: TaskCreateInput,
     ) -> Result<TaskDto, AppError> {
         let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = Uuid::new_v4().to_string();
+        let task = Self::create_in(&txn, input, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(task)
+    }
+
+    /// 在调用方的事务里创建任务，供需要一次创建多个实体的用例组合。
+    pub(crate) async fn create_in<C>(
+        txn: &C,
+        input: TaskCreateInput,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<TaskDto, AppError>
+    where
+        C: ConnectionTrait,
+    {
         let patch = input.patch;
         let title = validations::trim_and_validate_title(&input.title)?;
 
-        let now = now_ms();
         let id = Uuid::new_v4().to_string();
-        let _auto_start = input.auto_start;
-        // 创建时允许通过 patch 指定初始状态；未指定则默认 todo。
+        // 创建时允许通过 patch 指定初始状态；
+        // 未指定时由 auto_start 决定直接进入 doing，否则默认 todo。
         let status = match patch.status.as_deref() {
-            Some(status) => match validations::normalize_status(status)?.as_str() {
-                "done" => TaskStatus::Done,
-                _ => TaskStatus::Todo,
-            },
+            Some(status) => validations::parse_status(status)?,
+            None if input.auto_start => TaskStatus::Doing,
             None => TaskStatus::Todo,
         };
         let done_reason = if status == TaskStatus::Done {
@@ -75,26 +93,47 @@ impl TaskService {
             .filter(|value| !value.is_empty())
             .map(str::to_string);
         let deadline_at = patch.deadline_at;
+        let scheduled_at = patch.scheduled_at;
+        let defer_until = patch.defer_until;
         let normalized_tags = normalize_tags(patch.tags.unwrap_or_default());
         let normalized_links = patch.links.unwrap_or_default();
-        let normalized_custom_fields = patch
-            .custom_fields
-            .map(custom_fields::normalize_custom_fields)
+        let normalized_recurrence = patch
+            .recurrence
+            .map(recurrence::normalize_rule)
             .transpose()?;
+        let recurrence_rule = normalized_recurrence
+            .as_ref()
+            .map(recurrence::serialize_rule);
+        // 带重复规则的新任务就是系列的第一次，系列 id 直接复用任务 id。
+        let recurrence_series_id = recurrence_rule.as_ref().map(|_| id.clone());
+        let project_id = match input.project_id {
+            Some(project_id) => Some(project_id),
+            None => Some(resolve_default_project_id(txn, &input.space_id).await?),
+        };
+        // 自定义字段按任务最终所在的 space / 项目取字段定义校验。
+        let normalized_custom_fields = match patch.custom_fields {
+            Some(value) => {
+                let definitions =
+                    custom_field_repo::list_applicable(txn, &input.space_id, project_id.as_deref())
+                        .await?;
+                Some(custom_fields::normalize_custom_fields(value, &definitions)?)
+            }
+            None => None,
+        };
         let custom_fields_json = normalized_custom_fields
             .as_ref()
             .map(custom_fields::serialize_custom_fields)
             .transpose()?;
-        let project_id = match input.project_id {
-            Some(project_id) => Some(project_id),
-            None => Some(resolve_default_project_id(&txn, &input.space_id).await?),
+        let blocked_by = match patch.blocked_by.as_deref() {
+            Some(ids) => validate_blocked_by(txn, None, ids).await?,
+            None => Vec::new(),
         };
-        let rank = query::next_rank_in_bucket(&txn, &input.space_id, &status, &priority).await?;
+        let rank = query::next_rank_in_bucket(txn, &input.space_id, &status, &priority).await?;
         let create_by = "stonefish".to_string();
 
         // 主表先插入，后面标签、链接和日志都依赖新任务 id。
         let task = mutation::insert(
-            &txn,
+            txn,
             mutation::NewTaskRecord {
                 id: id.clone(),
                 space_id: input.space_id.clone(),
@@ -104,44 +143,56 @@ impl TaskService {
                 status: status.clone(),
                 done_reason: done_reason.clone(),
                 priority: priority.clone(),
-                rank,
+                rank: rank.clone(),
                 created_at: now,
                 updated_at: now,
                 completed_at: done_reason.as_ref().map(|_| now),
                 deadline_at,
+                scheduled_at,
+                defer_until,
                 archived_at: None,
                 deleted_at: None,
                 custom_fields: custom_fields_json,
                 create_by: create_by.clone(),
+                recurrence_rule,
+                recurrence_series_id: recurrence_series_id.clone(),
+                recurrence_index: recurrence_series_id.as_ref().map(|_| 1),
+                milestone_id: None,
             },
         )
         .await?;
 
         if !normalized_tags.is_empty() {
-            tags::sync_tags(&txn, &id, &normalized_tags).await?;
+            tags::sync_tags(txn, &id, &normalized_tags).await?;
         }
         if !normalized_links.is_empty() {
-            links::sync_links(&txn, &id, &normalized_links).await?;
+            links::sync_links(txn, &id, &normalized_links).await?;
         }
+        if !blocked_by.is_empty() {
+            dependencies::sync_dependencies(txn, &id, &blocked_by, now).await?;
+        }
+        let blocked = !dependencies::load_open_blockers_for_tasks(txn, std::slice::from_ref(&id))
+            .await?
+            .is_empty();
 
         activity_logs::append_created(
-            &txn,
+            txn,
             activity_logs::TaskLogCtx {
                 task_id: &task.id,
                 space_id: &task.space_id,
                 project_id: task.project_id.as_deref(),
                 create_by: &task.create_by,
                 created_at: now,
+                batch_id,
             },
             &task.title,
         )
         .await?;
 
         if let Some(project_id) = task.project_id.as_deref() {
-            stats::refresh_project_stats(&txn, project_id, now).await?;
+            stats::refresh_project_stats(txn, project_id, now).await?;
         }
-
-        txn.commit().await.map_err(AppError::from)?;
+        search_repo::reindex_tasks(txn, std::slice::from_ref(&id)).await?;
 
         // 返回前组装成前端可直接消费的 DTO，避免命令层二次拼装。
         Ok(TaskDto {
@@ -150,10 +201,7 @@ impl TaskService {
             project_id: task.project_id,
             title: task.title,
             note,
-            status: match status {
-                TaskStatus::Done => "done".to_string(),
-                TaskStatus::Todo => "todo".to_string(),
-            },
+            status: common_task_utils::status_to_string(&status),
             done_reason: done_reason.as_ref().map(|value| match value {
                 DoneReason::Completed => "completed".to_string(),
                 DoneReason::Cancelled => "cancelled".to_string(),
@@ -171,10 +219,20 @@ impl TaskService {
             updated_at: now,
             completed_at: done_reason.as_ref().map(|_| now),
             deadline_at,
+            scheduled_at,
+            defer_until,
             archived_at: None,
             deleted_at: None,
             custom_fields: normalized_custom_fields,
             create_by,
+            recurrence: normalized_recurrence,
+            recurrence_series_id,
+            milestone_id: None,
+            checklist_total: 0,
+            checklist_done: 0,
+            blocked_by,
+            blocked,
+            tracked_ms: 0,
         })
     }
 }
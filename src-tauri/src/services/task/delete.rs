@@ -3,13 +3,17 @@
 //! 这里处理的是软删除语义，因此删除和恢复本质上都是更新状态，
 //! 同时要补日志并刷新受影响项目的统计。
 
-use sea_orm::{DatabaseConnection, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::now_ms;
-use crate::repos::task_repo::{activity_logs, mutation, query, stats};
+use crate::repos::{
+    search_repo,
+    task_repo::{activity_logs, mutation, query, stats},
+};
 use crate::types::error::AppError;
 
-use super::{helpers::dedup_project_ids, TaskService};
+use super::{helpers::dedup_project_ids, time_tracking::stop_timers_for_tasks, TaskService};
 
 impl TaskService {
     /// 批量软删除任务。
@@ -19,24 +23,42 @@ impl TaskService {
         }
 
         let txn = conn.begin().await.map_err(AppError::from)?;
-        let now = now_ms();
-        let task_models = query::find_not_deleted_by_ids(&txn, ids).await?;
+        let batch_id = Uuid::new_v4().to_string();
+        let count = Self::delete_many_in(&txn, ids, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(count)
+    }
+
+    /// 在调用方的事务里批量软删除任务，已删除的任务会被跳过。
+    pub(crate) async fn delete_many_in<C>(
+        txn: &C,
+        ids: &[String],
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<usize, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let task_models = query::find_not_deleted_by_ids(txn, ids).await?;
         let project_ids: Vec<String> = task_models
             .iter()
             .filter_map(|task| task.project_id.clone())
             .collect();
         // 先批量删，再补日志与统计，避免一条条更新拖慢事务。
-        let count = mutation::soft_delete_many(&txn, ids, now).await?;
+        let count = mutation::soft_delete_many(txn, ids, now).await?;
+        // 已删除任务上的计时器不再可见，直接结束，避免时长在后台继续累计。
+        stop_timers_for_tasks(txn, ids, now).await?;
 
         for task in &task_models {
             activity_logs::append_deleted(
-                &txn,
+                txn,
                 activity_logs::TaskLogCtx {
                     task_id: &task.id,
                     space_id: &task.space_id,
                     project_id: task.project_id.as_deref(),
                     create_by: &task.create_by,
                     created_at: now,
+                    batch_id,
                 },
                 &task.title,
             )
@@ -44,10 +66,9 @@ impl TaskService {
         }
 
         for project_id in dedup_project_ids(project_ids) {
-            stats::refresh_project_stats(&txn, &project_id, now).await?;
+            stats::refresh_project_stats(txn, &project_id, now).await?;
         }
-
-        txn.commit().await.map_err(AppError::from)?;
+        search_repo::reindex_tasks(txn, ids).await?;
         Ok(count)
     }
 
@@ -61,23 +82,39 @@ impl TaskService {
         }
 
         let txn = conn.begin().await.map_err(AppError::from)?;
-        let now = now_ms();
-        let task_models = query::find_deleted_by_ids(&txn, ids).await?;
+        let batch_id = Uuid::new_v4().to_string();
+        let count = Self::restore_many_in(&txn, ids, now_ms(), Some(&batch_id)).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(count)
+    }
+
+    /// 在调用方的事务里批量恢复任务，未删除的任务会被跳过。
+    pub(crate) async fn restore_many_in<C>(
+        txn: &C,
+        ids: &[String],
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<usize, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let task_models = query::find_deleted_by_ids(txn, ids).await?;
         let project_ids: Vec<String> = task_models
             .iter()
             .filter_map(|task| task.project_id.clone())
             .collect();
-        let count = mutation::restore_many(&txn, ids, now).await?;
+        let count = mutation::restore_many(txn, ids, now).await?;
 
         for task in &task_models {
             activity_logs::append_restored(
-                &txn,
+                txn,
                 activity_logs::TaskLogCtx {
                     task_id: &task.id,
                     space_id: &task.space_id,
                     project_id: task.project_id.as_deref(),
                     create_by: &task.create_by,
                     created_at: now,
+                    batch_id,
                 },
                 &task.title,
             )
@@ -85,10 +122,9 @@ impl TaskService {
         }
 
         for project_id in dedup_project_ids(project_ids) {
-            stats::refresh_project_stats(&txn, &project_id, now).await?;
+            stats::refresh_project_stats(txn, &project_id, now).await?;
         }
-
-        txn.commit().await.map_err(AppError::from)?;
+        search_repo::reindex_tasks(txn, ids).await?;
         Ok(count)
     }
 }
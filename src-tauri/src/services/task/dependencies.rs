@@ -0,0 +1,79 @@
+//! 任务依赖（blocked-by）规则。
+//!
+//! 这里集中放置依赖相关的业务校验与派生计算：
+//! - 写入前校验前置任务存在、不指向自身、不形成环
+//! - 完成任务后计算哪些后续任务因此解除阻塞
+
+use sea_orm::ConnectionTrait;
+
+use crate::db::entities::sea_orm_active_enums::TaskStatus;
+use crate::repos::task_repo::{dependencies, query};
+use crate::types::error::AppError;
+
+/// 归一化并校验任务的前置任务列表。
+///
+/// 返回去空、去重后的 id 列表；`task_id` 为 `None` 表示任务尚未创建，
+/// 此时不可能有其它任务依赖它，无需做环检测。
+pub(super) async fn validate_blocked_by<C>(
+    conn: &C,
+    task_id: Option<&str>,
+    blocked_by_task_ids: &[String],
+) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut normalized = Vec::new();
+    for value in blocked_by_task_ids {
+        let id = value.trim();
+        if id.is_empty() || normalized.iter().any(|item: &String| item == id) {
+            continue;
+        }
+        if Some(id) == task_id {
+            return Err(AppError::Validation("任务不能依赖自身".to_string()));
+        }
+        normalized.push(id.to_string());
+    }
+
+    let existing = query::find_not_deleted_by_ids(conn, &normalized).await?;
+    if existing.len() != normalized.len() {
+        return Err(AppError::Validation("前置任务不存在或已删除".to_string()));
+    }
+
+    if let Some(task_id) = task_id {
+        // 新增边 task -> blocker 会成环，当且仅当 blocker 已经（间接）依赖 task。
+        for blocked_by_task_id in &normalized {
+            if dependencies::is_reachable(conn, blocked_by_task_id, task_id).await? {
+                return Err(AppError::Validation("任务依赖不能形成循环".to_string()));
+            }
+        }
+    }
+
+    Ok(normalized)
+}
+
+/// 计算某个任务完成后，直接依赖它且已无未完成前置任务的后续任务。
+///
+/// 调用方需要先把任务写成 done，再调用本函数。
+pub(super) async fn collect_unblocked_task_ids<C>(
+    conn: &C,
+    completed_task_id: &str,
+) -> Result<Vec<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let dependent_ids = dependencies::find_dependent_task_ids(conn, completed_task_id).await?;
+    if dependent_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let open_blockers = dependencies::load_open_blockers_for_tasks(conn, &dependent_ids).await?;
+    let mut unblocked = query::find_not_deleted_by_ids(conn, &dependent_ids)
+        .await?
+        .into_iter()
+        .filter(|task| task.status != TaskStatus::Done && !open_blockers.contains_key(&task.id))
+        .map(|task| task.id)
+        .collect::<Vec<_>>();
+    unblocked.sort();
+
+    Ok(unblocked)
+}
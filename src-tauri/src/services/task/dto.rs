@@ -3,7 +3,9 @@
 //! 这些类型是 service 层自己的“用例输入”，
 //! 不是前端命令参数，也不是 repo 落库结构。
 
-use crate::types::dto::{CustomFieldsDto, LinkInputDto};
+use std::collections::HashMap;
+
+use crate::types::dto::{CustomFieldsDto, LinkInputDto, RecurrenceRuleDto, TaskTemplateBodyDto};
 
 /// 创建任务时允许附带的补丁字段。
 #[derive(Debug, Clone, Default)]
@@ -13,9 +15,13 @@ pub struct TaskCreatePatch {
     pub priority: Option<String>,
     pub note: Option<String>,
     pub deadline_at: Option<i64>,
+    pub scheduled_at: Option<i64>,
+    pub defer_until: Option<i64>,
     pub tags: Option<Vec<String>>,
     pub links: Option<Vec<LinkInputDto>>,
     pub custom_fields: Option<CustomFieldsDto>,
+    pub recurrence: Option<RecurrenceRuleDto>,
+    pub blocked_by: Option<Vec<String>>,
 }
 
 /// 创建任务用例的完整输入。
@@ -28,6 +34,57 @@ pub struct TaskCreateInput {
     pub patch: TaskCreatePatch,
 }
 
+/// 快速录入用例的完整输入。
+#[derive(Debug, Clone, Default)]
+pub struct QuickAddInput {
+    pub text: String,
+    /// 文本里没有写 `@space` 时使用的 space；为空时取排序第一的 space
+    pub space_id: Option<String>,
+    /// 解析相对日期用的 UTC 偏移分钟数；为空时使用系统时区
+    pub utc_offset_minutes: Option<i32>,
+    /// 只解析不创建
+    pub dry_run: bool,
+}
+
+/// 新建任务模板的输入。
+#[derive(Debug, Clone, Default)]
+pub struct TaskTemplateCreateInput {
+    pub name: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub body: TaskTemplateBodyDto,
+}
+
+/// 任务模板 patch；`space_id` / `project_id` 同样是三态语义。
+#[derive(Debug, Clone, Default)]
+pub struct TaskTemplateUpdatePatch {
+    pub name: Option<String>,
+    pub space_id: Option<Option<String>>,
+    pub project_id: Option<Option<String>>,
+    pub body: Option<TaskTemplateBodyDto>,
+}
+
+/// 按模板创建任务的输入。
+#[derive(Debug, Clone, Default)]
+pub struct TaskTemplateInstantiateInput {
+    pub template_id: String,
+    /// 覆盖模板默认的 space / 项目
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    /// 额外的占位符取值，同名时覆盖内置的 date / project / space 等
+    pub variables: HashMap<String, String>,
+    /// 展开日期占位符用的 UTC 偏移分钟数；为空时使用系统时区
+    pub utc_offset_minutes: Option<i32>,
+}
+
+/// 复制任务的输入。
+#[derive(Debug, Clone, Default)]
+pub struct TaskDuplicateInput {
+    pub task_ids: Vec<String>,
+    /// 副本放入的项目；为空时留在原任务所在项目
+    pub project_id: Option<String>,
+}
+
 /// 更新任务用例的完整输入。
 #[derive(Debug, Clone)]
 pub struct TaskUpdateInput {
@@ -51,17 +108,69 @@ pub struct TaskUpdatePatch {
     pub tags: Option<Vec<String>>,
     pub space_id: Option<String>,
     pub project_id: Option<Option<String>>,
+    /// 里程碑必须属于任务（更新后）所在的项目
+    pub milestone_id: Option<Option<String>>,
     pub deadline_at: Option<Option<i64>>,
-    pub rank: Option<i64>,
+    pub scheduled_at: Option<Option<i64>>,
+    pub defer_until: Option<Option<i64>>,
+    pub rank: Option<String>,
     pub links: Option<Vec<LinkInputDto>>,
     pub custom_fields: Option<Option<CustomFieldsDto>>,
     pub archived_at: Option<Option<i64>>,
     pub deleted_at: Option<Option<i64>>,
+    pub recurrence: Option<Option<RecurrenceRuleDto>>,
+    pub blocked_by: Option<Vec<String>>,
+}
+
+/// 拖拽排序用例的完整输入。
+#[derive(Debug, Clone, Default)]
+pub struct TaskReorderInput {
+    pub task_id: String,
+    /// 显式指定的排序键；传入时忽略前后邻居
+    pub new_rank: Option<String>,
+    /// 移动后紧挨在上方的任务
+    pub prev_task_id: Option<String>,
+    /// 移动后紧挨在下方的任务
+    pub next_task_id: Option<String>,
+}
+
+/// 新建检查项用例的完整输入。
+#[derive(Debug, Clone)]
+pub struct ChecklistItemCreateInput {
+    pub task_id: String,
+    pub title: String,
+    pub rank: Option<i64>,
+}
+
+/// 更新检查项用例的完整输入。
+#[derive(Debug, Clone)]
+pub struct ChecklistItemUpdateInput {
+    pub id: String,
+    pub patch: ChecklistItemUpdatePatch,
+}
+
+/// 检查项 patch 更新模型，未传字段保持不变。
+#[derive(Debug, Clone, Default)]
+pub struct ChecklistItemUpdatePatch {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+    pub rank: Option<i64>,
+}
+
+/// 开始计时用例的完整输入。
+#[derive(Debug, Clone)]
+pub struct TimerStartInput {
+    pub task_id: String,
+    /// manual / pomodoro，默认 manual
+    pub mode: Option<String>,
+    /// 番茄钟时长（分钟），默认 25
+    pub pomodoro_minutes: Option<i64>,
+    pub note: Option<String>,
 }
 
 impl TaskUpdatePatch {
     /// 只更新 rank 的快捷构造器，供排序用例复用。
-    pub fn rank_only(new_rank: i64) -> Self {
+    pub fn rank_only(new_rank: String) -> Self {
         Self {
             rank: Some(new_rank),
             ..Self::default()
@@ -0,0 +1,220 @@
+//! 任务复制用例。
+//!
+//! 约定：
+//! - 复制标题、备注、优先级、时间、标签、链接、自定义字段、重复规则和检查清单
+//! - 不复制前置任务、提醒和计时记录，这些都只对原任务有意义
+//! - 副本默认回到 todo；waiting / someday 保留，done 只在调用方要求时保留
+//! - 带重复规则的副本是一个新系列的第一次
+//! - 每条副本写一条“复制自”活动日志
+
+use std::collections::BTreeSet;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{
+    entities::{sea_orm_active_enums::TaskStatus, tasks},
+    now_ms,
+};
+use crate::repos::{
+    project_repo::query as project_query,
+    search_repo,
+    task_repo::{activity_logs, checklist, links, list, mutation, query, stats, tags},
+};
+use crate::types::{
+    dto::{LinkInputDto, TaskDto},
+    error::AppError,
+};
+
+use super::{dto::TaskDuplicateInput, TaskService};
+
+impl TaskService {
+    /// 复制一组任务，返回按传入顺序排列的副本。
+    pub async fn duplicate(
+        conn: &DatabaseConnection,
+        input: TaskDuplicateInput,
+    ) -> Result<Vec<TaskDto>, AppError> {
+        let mut task_ids = Vec::with_capacity(input.task_ids.len());
+        for id in input.task_ids {
+            if !task_ids.contains(&id) {
+                task_ids.push(id);
+            }
+        }
+        if task_ids.is_empty() {
+            return Err(AppError::Validation("至少选择一个任务".to_string()));
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let target = match input.project_id.as_deref() {
+            Some(project_id) => {
+                let project = project_query::find_by_id(&txn, project_id).await?;
+                if project.deleted_at.is_some() {
+                    return Err(AppError::Validation(format!("项目 {project_id} 已删除")));
+                }
+                Some(project)
+            }
+            None => None,
+        };
+
+        let now = now_ms();
+        let batch_id = Uuid::new_v4().to_string();
+        let mut copy_ids = Vec::with_capacity(task_ids.len());
+        let mut touched_projects = BTreeSet::new();
+        for id in &task_ids {
+            let source = query::find_by_id(&txn, id).await?;
+            if source.deleted_at.is_some() {
+                return Err(AppError::Validation(format!("任务 {id} 已删除，无法复制")));
+            }
+            let (space_id, project_id) = match &target {
+                Some(project) => (project.space_id.clone(), Some(project.id.clone())),
+                None => (source.space_id.clone(), source.project_id.clone()),
+            };
+            let copy = copy_task_in(
+                &txn,
+                &source,
+                TaskCopyTarget {
+                    space_id: &space_id,
+                    project_id: project_id.as_deref(),
+                    keep_done: false,
+                    batch_id: Some(&batch_id),
+                },
+                now,
+            )
+            .await?;
+            touched_projects.extend(copy.project_id.clone());
+            copy_ids.push(copy.id);
+        }
+
+        for project_id in &touched_projects {
+            stats::refresh_project_stats(&txn, project_id, now).await?;
+        }
+        search_repo::reindex_tasks(&txn, &copy_ids).await?;
+        txn.commit().await.map_err(AppError::from)?;
+
+        list::list_by_ids(conn, &copy_ids).await
+    }
+}
+
+/// 副本落在哪里，以及状态如何处理。
+pub(crate) struct TaskCopyTarget<'a> {
+    pub space_id: &'a str,
+    pub project_id: Option<&'a str>,
+    /// 为 true 时已完成的任务保持完成状态与完成时间
+    pub keep_done: bool,
+    pub batch_id: Option<&'a str>,
+}
+
+/// 在调用方的事务里复制一条任务。
+///
+/// 项目统计与搜索索引由调用方在全部复制完成后统一刷新。
+pub(crate) async fn copy_task_in<C>(
+    conn: &C,
+    source: &tasks::Model,
+    target: TaskCopyTarget<'_>,
+    now: i64,
+) -> Result<tasks::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    let keep_done = target.keep_done && source.status == TaskStatus::Done;
+    let status = match source.status {
+        TaskStatus::Waiting | TaskStatus::Someday => source.status.clone(),
+        TaskStatus::Done if keep_done => TaskStatus::Done,
+        _ => TaskStatus::Todo,
+    };
+    let id = Uuid::new_v4().to_string();
+    let rank = query::next_rank_in_bucket(conn, target.space_id, &status, &source.priority).await?;
+    let copy = mutation::insert(
+        conn,
+        mutation::NewTaskRecord {
+            id: id.clone(),
+            space_id: target.space_id.to_string(),
+            project_id: target.project_id.map(str::to_string),
+            title: source.title.clone(),
+            note: source.note.clone(),
+            status,
+            done_reason: source.done_reason.clone().filter(|_| keep_done),
+            priority: source.priority.clone(),
+            rank,
+            created_at: now,
+            updated_at: now,
+            completed_at: source.completed_at.filter(|_| keep_done),
+            deadline_at: source.deadline_at,
+            scheduled_at: source.scheduled_at,
+            defer_until: source.defer_until,
+            archived_at: None,
+            deleted_at: None,
+            custom_fields: source.custom_fields.clone(),
+            create_by: "stonefish".to_string(),
+            recurrence_rule: source.recurrence_rule.clone(),
+            recurrence_series_id: source.recurrence_rule.as_ref().map(|_| id.clone()),
+            recurrence_index: source.recurrence_rule.as_ref().map(|_| 1),
+            // 里程碑只在复制到同一项目时保留。
+            milestone_id: source
+                .milestone_id
+                .clone()
+                .filter(|_| target.project_id == source.project_id.as_deref()),
+        },
+    )
+    .await?;
+
+    let source_ids = vec![source.id.clone()];
+    let tag_names = tags::load_tags_for_tasks(conn, &source_ids)
+        .await?
+        .remove(&source.id)
+        .unwrap_or_default();
+    if !tag_names.is_empty() {
+        tags::sync_tags(conn, &id, &tag_names).await?;
+    }
+
+    // 链接主表记录归属单个任务，这里复制成新记录而不是共享原链接 id。
+    let link_inputs = links::load_links_for_tasks(conn, &source_ids)
+        .await?
+        .remove(&source.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|link| LinkInputDto {
+            id: None,
+            title: link.title,
+            url: link.url,
+            kind: link.kind,
+            rank: Some(link.rank),
+        })
+        .collect::<Vec<_>>();
+    if !link_inputs.is_empty() {
+        links::sync_links(conn, &id, &link_inputs).await?;
+    }
+
+    // 检查清单按原顺序复制；副本未完成时勾选状态一并重置。
+    for item in checklist::list_for_task(conn, &source.id).await? {
+        checklist::insert(
+            conn,
+            checklist::NewChecklistItemRecord {
+                id: Uuid::new_v4().to_string(),
+                task_id: id.clone(),
+                title: item.title,
+                done: keep_done && item.done,
+                rank: item.rank,
+                created_at: now,
+            },
+        )
+        .await?;
+    }
+
+    activity_logs::append_duplicated(
+        conn,
+        activity_logs::TaskLogCtx {
+            task_id: &copy.id,
+            space_id: &copy.space_id,
+            project_id: copy.project_id.as_deref(),
+            create_by: &copy.create_by,
+            created_at: now,
+            batch_id: target.batch_id,
+        },
+        &source.id,
+        &source.title,
+    )
+    .await?;
+
+    Ok(copy)
+}
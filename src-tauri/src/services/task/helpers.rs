@@ -65,6 +65,34 @@ pub(super) fn normalize_links_for_log(links: &[LinkInputDto]) -> Vec<String> {
         .collect()
 }
 
+/// 把日志里的链接文本还原成链接输入，格式不符时返回 `None`。
+///
+/// 链接 id 不在日志里，还原出来的都是新记录；排序按日志里的先后顺序重新分配。
+pub(super) fn parse_links_from_log(value: Option<&str>) -> Option<Vec<LinkInputDto>> {
+    let Some(value) = value else {
+        return Some(Vec::new());
+    };
+    let mut links = Vec::new();
+    for (index, item) in value.split(">, ").enumerate() {
+        let item = item.strip_suffix('>').unwrap_or(item);
+        let (head, url) = item.rsplit_once('<')?;
+        let (kind, title) = head.split_once(':')?;
+        links.push(LinkInputDto {
+            id: None,
+            title: title.to_string(),
+            url: url.to_string(),
+            kind: kind.to_string(),
+            rank: Some((index as i64 + 1) * 1024),
+        });
+    }
+    Some(links)
+}
+
+/// 把检查项格式化成日志里稳定展示的文本，例如 `[x] 写周报`。
+pub(super) fn checklist_item_to_value(title: &str, done: bool) -> String {
+    format!("[{}] {}", if done { "x" } else { " " }, title)
+}
+
 /// 把字符串列表按给定分隔符拼接成可选值，空列表返回 `None`。
 pub(super) fn to_optional_join(values: Vec<String>, separator: &str) -> Option<String> {
     if values.is_empty() {
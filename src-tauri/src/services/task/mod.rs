@@ -2,19 +2,49 @@
 //!
 //! 本模块承接所有任务相关的写路径：
 //! - 创建、更新、完成、删除、恢复
+//! - 批量归档 / 取消归档，以及已完成任务的自动归档
+//! - 任务复制（供项目克隆复用）
+//! - 同一 patch 对多个任务的批量更新
+//! - 快速录入文本的解析与创建
+//! - 检查清单项的增改、勾选与排序
+//! - 任务计时（开始 / 暂停 / 恢复 / 结束、番茄钟）
+//! - 截止提醒的配置、稍后与忽略
 //! - 排序与批量重排
+//! - 任务模板的维护与按模板创建
+//! - 重复任务的下一次生成
+//! - 按活动日志批次生成撤销计划
 //! - 事务内的活动日志与项目统计刷新
 //!
 //! 纯查询继续保留在命令层直达 query repo，不在这里创建空壳透传方法。
 
+mod archive;
+mod bulk;
+mod checklist;
 mod complete;
 mod create;
 mod delete;
+mod dependencies;
 mod dto;
+mod duplicate;
 mod helpers;
+mod quick_add;
+mod quick_add_parser;
+mod recurrence;
+mod reminders;
 mod reorder;
+mod templates;
+mod time_tracking;
+mod undo;
 mod update;
 
-pub use dto::{TaskCreateInput, TaskCreatePatch, TaskUpdateInput, TaskUpdatePatch};
+pub use dto::{
+    ChecklistItemCreateInput, ChecklistItemUpdateInput, ChecklistItemUpdatePatch, QuickAddInput,
+    TaskCreateInput, TaskCreatePatch, TaskDuplicateInput, TaskReorderInput,
+    TaskTemplateCreateInput, TaskTemplateInstantiateInput, TaskTemplateUpdatePatch,
+    TaskUpdateInput, TaskUpdatePatch, TimerStartInput,
+};
+pub(crate) use duplicate::{copy_task_in, TaskCopyTarget};
+pub(crate) use templates::{body_from_task, body_to_create_input, builtin_variables};
+pub(crate) use undo::TaskUndoPlan;
 
 pub struct TaskService;
@@ -0,0 +1,125 @@
+//! 快速录入用例。
+//!
+//! 文本解析在 `quick_add_parser` 里完成；这里负责把 `@space/项目` 落到具体 id，
+//! 再交给 `create` 走和普通创建完全相同的事务。
+
+use chrono::{DateTime, Local, TimeZone};
+use sea_orm::DatabaseConnection;
+
+use crate::repos::{project_repo::query as project_query, space_repo::SpaceRepo, task_repo::today};
+use crate::types::{
+    dto::{QuickAddPreviewDto, QuickAddResultDto},
+    error::AppError,
+};
+
+use super::{
+    dto::{QuickAddInput, TaskCreateInput, TaskCreatePatch},
+    quick_add_parser, TaskService,
+};
+
+impl TaskService {
+    /// 解析快速录入文本并创建任务；`dry_run` 时只返回解析预览。
+    pub async fn quick_add(
+        conn: &DatabaseConnection,
+        input: QuickAddInput,
+    ) -> Result<QuickAddResultDto, AppError> {
+        let parsed = match input.utc_offset_minutes {
+            Some(minutes) => parse_at(&input.text, &today::fixed_offset(minutes)?)?,
+            None => parse_at(&input.text, &Local)?,
+        };
+        let preview = resolve_preview(conn, parsed, input.space_id.as_deref()).await?;
+        if input.dry_run {
+            return Ok(QuickAddResultDto {
+                preview,
+                task: None,
+            });
+        }
+
+        let task = Self::create(
+            conn,
+            TaskCreateInput {
+                space_id: preview.space_id.clone(),
+                title: preview.title.clone(),
+                auto_start: false,
+                project_id: preview.project_id.clone(),
+                patch: TaskCreatePatch {
+                    priority: preview.priority.clone(),
+                    deadline_at: preview.deadline_at,
+                    tags: Some(preview.tags.clone()),
+                    recurrence: preview.recurrence.clone(),
+                    ..Default::default()
+                },
+            },
+        )
+        .await?;
+
+        Ok(QuickAddResultDto {
+            preview,
+            task: Some(task),
+        })
+    }
+}
+
+fn parse_at<Tz: TimeZone>(
+    text: &str,
+    tz: &Tz,
+) -> Result<quick_add_parser::QuickAddParsed, AppError> {
+    let now: DateTime<Tz> = tz
+        .timestamp_millis_opt(crate::db::now_ms())
+        .earliest()
+        .ok_or_else(|| AppError::Internal("无法获取当前本地时间".to_string()))?;
+    quick_add_parser::parse(text, &now)
+}
+
+/// 首段命中 space（id 或名称，大小写不敏感）时视为 space，其余各段拼成项目路径。
+async fn resolve_preview(
+    conn: &DatabaseConnection,
+    parsed: quick_add_parser::QuickAddParsed,
+    default_space_id: Option<&str>,
+) -> Result<QuickAddPreviewDto, AppError> {
+    let spaces = SpaceRepo::list(conn).await?;
+    let mut segments = parsed.location.as_slice();
+    let matched_space = segments.first().and_then(|first| {
+        spaces.iter().find(|space| {
+            space.id.eq_ignore_ascii_case(first)
+                || space.name.to_lowercase() == first.to_lowercase()
+        })
+    });
+    let space_id = match matched_space {
+        Some(space) => {
+            segments = &segments[1..];
+            space.id.clone()
+        }
+        None => match default_space_id {
+            Some(space_id) => space_id.to_string(),
+            None => spaces
+                .first()
+                .map(|space| space.id.clone())
+                .ok_or_else(|| AppError::Validation("没有可用的 space".to_string()))?,
+        },
+    };
+
+    let project = if segments.is_empty() {
+        None
+    } else {
+        let path = format!("/{}", segments.join("/"));
+        Some(
+            project_query::find_by_path(conn, &space_id, &path)
+                .await?
+                .ok_or_else(|| {
+                    AppError::Validation(format!("在 space {space_id} 中找不到项目 {path}"))
+                })?,
+        )
+    };
+
+    Ok(QuickAddPreviewDto {
+        title: parsed.title,
+        space_id,
+        project_id: project.as_ref().map(|project| project.id.clone()),
+        project_path: project.map(|project| project.path),
+        tags: parsed.tags,
+        priority: parsed.priority,
+        deadline_at: parsed.deadline_at,
+        recurrence: parsed.recurrence,
+    })
+}
@@ -0,0 +1,419 @@
+//! 快速录入文本解析。
+//!
+//! 支持的记号（其余单词按原顺序拼成标题）：
+//! - `#tag`：标签，可出现多次
+//! - `!P0` ~ `!P3`（或 `!0` ~ `!3`）：优先级
+//! - `@space` / `@space/项目/子项目`：Space 与项目路径；首段不是 Space 时整体视为项目路径
+//! - `due:<日期> [时间]`：截止时间，日期支持 today / tomorrow / 星期 / `2026-10-20` / `10-20` / `10/20` / `+3d` / `+2w`，
+//!   时间支持 `5pm` / `5:30pm` / `17:30`，不写时间按当天 23:59
+//! - `every:<规则>`：重复，支持 day / week / month / weekday / `2w` / `3d` / `mon,wed`
+//!
+//! 重点：这里只做纯文本解析，不访问数据库；“当前时间”由调用方注入，便于单独验证。
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, TimeZone, Weekday};
+
+use crate::types::{dto::RecurrenceRuleDto, error::AppError};
+
+use crate::repos::task_repo::recurrence;
+
+/// 解析结果；Space 与项目尚未落到具体 id，由 service 结合数据库解析。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuickAddParsed {
+    pub title: String,
+    /// `@` 后按 `/` 切分的各段
+    pub location: Vec<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<String>,
+    pub deadline_at: Option<i64>,
+    pub recurrence: Option<RecurrenceRuleDto>,
+}
+
+const WEEKDAY_NAMES: [(&str, Weekday, &str); 7] = [
+    ("mon", Weekday::Mon, "MO"),
+    ("tue", Weekday::Tue, "TU"),
+    ("wed", Weekday::Wed, "WE"),
+    ("thu", Weekday::Thu, "TH"),
+    ("fri", Weekday::Fri, "FR"),
+    ("sat", Weekday::Sat, "SA"),
+    ("sun", Weekday::Sun, "SU"),
+];
+
+/// 解析快速录入文本。
+pub fn parse<Tz: TimeZone>(text: &str, now: &DateTime<Tz>) -> Result<QuickAddParsed, AppError> {
+    let mut parsed = QuickAddParsed::default();
+    let mut title_words: Vec<&str> = Vec::new();
+    let mut tokens = text.split_whitespace().peekable();
+
+    while let Some(token) = tokens.next() {
+        if let Some(tag) = token.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+            if !parsed.tags.iter().any(|item| item == tag) {
+                parsed.tags.push(tag.to_string());
+            }
+        } else if let Some(priority) = token.strip_prefix('!').and_then(parse_priority) {
+            parsed.priority = Some(priority);
+        } else if let Some(location) = token.strip_prefix('@').filter(|value| !value.is_empty()) {
+            parsed.location = location
+                .split('/')
+                .map(str::trim)
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_string)
+                .collect();
+        } else if let Some(value) = strip_prefix_ignore_case(token, "due:") {
+            let date = parse_date(value, now)?;
+            // 紧跟的时间记号属于同一个截止时间。
+            let time = match tokens.peek().and_then(|next| parse_time(next)) {
+                Some(time) => {
+                    tokens.next();
+                    time
+                }
+                None => NaiveTime::from_hms_opt(23, 59, 0).expect("valid time"),
+            };
+            parsed.deadline_at = Some(to_timestamp(&now.timezone(), date, time)?);
+        } else if let Some(value) = strip_prefix_ignore_case(token, "every:") {
+            parsed.recurrence = Some(parse_recurrence(value)?);
+        } else {
+            title_words.push(token);
+        }
+    }
+
+    parsed.title = title_words.join(" ");
+    if parsed.title.is_empty() {
+        return Err(AppError::Validation("快速录入缺少任务标题".to_string()));
+    }
+    Ok(parsed)
+}
+
+fn strip_prefix_ignore_case<'a>(token: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = token.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &token[prefix.len()..])
+}
+
+fn parse_priority(value: &str) -> Option<String> {
+    let digit = value.strip_prefix(['p', 'P']).unwrap_or(value);
+    matches!(digit, "0" | "1" | "2" | "3").then(|| format!("P{digit}"))
+}
+
+fn parse_weekday(value: &str) -> Option<(Weekday, &'static str)> {
+    let value = value.to_ascii_lowercase();
+    WEEKDAY_NAMES
+        .iter()
+        .find(|(short, weekday, _)| value == *short || value == weekday_full_name(*weekday))
+        .map(|(_, weekday, code)| (*weekday, *code))
+}
+
+fn weekday_full_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// 解析日期；星期取今天起最近的一次（含今天），不带年份的日期已过则顺延到明年。
+fn parse_date<Tz: TimeZone>(value: &str, now: &DateTime<Tz>) -> Result<NaiveDate, AppError> {
+    let invalid = || AppError::Validation(format!("无法识别的日期：{value}"));
+    let today = now.date_naive();
+    let lower = value.to_ascii_lowercase();
+
+    match lower.as_str() {
+        "today" | "tod" => return Ok(today),
+        "tomorrow" | "tmr" => return today.succ_opt().ok_or_else(invalid),
+        _ => {}
+    }
+    if let Some((weekday, _)) = parse_weekday(&lower) {
+        let ahead =
+            (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+        return today
+            .checked_add_days(Days::new(ahead as u64))
+            .ok_or_else(invalid);
+    }
+    if let Some(relative) = lower.strip_prefix('+') {
+        let (amount, unit) = relative.split_at(relative.len().saturating_sub(1));
+        let amount: u64 = amount.parse().map_err(|_| invalid())?;
+        return match unit {
+            "d" => today.checked_add_days(Days::new(amount)),
+            "w" => today.checked_add_days(Days::new(amount * 7)),
+            "m" => today.checked_add_months(Months::new(amount as u32)),
+            _ => None,
+        }
+        .ok_or_else(invalid);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&lower, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let (month, day) = lower
+        .split_once(['-', '/'])
+        .and_then(|(month, day)| Some((month.parse::<u32>().ok()?, day.parse::<u32>().ok()?)))
+        .ok_or_else(invalid)?;
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day).ok_or_else(invalid)?;
+    if this_year >= today {
+        Ok(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day).ok_or_else(invalid)
+    }
+}
+
+/// 解析时间：`5pm` / `5:30pm` / `17:30`；不是时间记号时返回 `None`。
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    let lower = value.to_ascii_lowercase();
+    let (clock, meridiem) = if let Some(clock) = lower.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = lower.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (lower.as_str(), None)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        // 不带冒号也不带 am/pm 的纯数字不当作时间，避免吞掉标题里的数字。
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(false) => hour % 12,
+        Some(true) => hour % 12 + 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn to_timestamp<Tz: TimeZone>(tz: &Tz, date: NaiveDate, time: NaiveTime) -> Result<i64, AppError> {
+    let local = date.and_time(time);
+    // 落在夏令时跳变的空档里时，顺延一小时取一个真实存在的本地时间。
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + chrono::Duration::hours(1)))
+                .earliest()
+        })
+        .map(|value| value.timestamp_millis())
+        .ok_or_else(|| AppError::Validation(format!("无效的本地时间：{local}")))
+}
+
+/// 解析重复规则，最终仍经过 `recurrence::normalize_rule` 统一校验。
+fn parse_recurrence(value: &str) -> Result<RecurrenceRuleDto, AppError> {
+    let invalid = || AppError::Validation(format!("无法识别的重复规则：{value}"));
+    let lower = value.to_ascii_lowercase();
+    let rule = |freq: &str, interval: i64, by_weekday: Vec<String>| RecurrenceRuleDto {
+        freq: freq.to_string(),
+        interval: Some(interval),
+        by_weekday,
+        until: None,
+        count: None,
+    };
+
+    let parsed = match lower.as_str() {
+        "day" | "daily" => rule("daily", 1, Vec::new()),
+        "week" | "weekly" => rule("weekly", 1, Vec::new()),
+        "month" | "monthly" => rule("monthly", 1, Vec::new()),
+        "weekday" | "weekdays" => rule(
+            "weekly",
+            1,
+            ["MO", "TU", "WE", "TH", "FR"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        ),
+        _ if lower.contains(',') || parse_weekday(&lower).is_some() => {
+            let codes = lower
+                .split(',')
+                .map(|day| parse_weekday(day.trim()).map(|(_, code)| code.to_string()))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+            rule("weekly", 1, codes)
+        }
+        _ => {
+            let digits_end = lower
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(invalid)?;
+            let interval: i64 = lower[..digits_end].parse().map_err(|_| invalid())?;
+            let freq = match &lower[digits_end..] {
+                "d" | "day" | "days" => "daily",
+                "w" | "week" | "weeks" => "weekly",
+                "m" | "month" | "months" => "monthly",
+                _ => return Err(invalid()),
+            };
+            rule(freq, interval, Vec::new())
+        }
+    };
+
+    recurrence::normalize_rule(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+    use super::*;
+
+    /// 2026-10-14 10:00（周三），东八区。
+    fn now() -> DateTime<FixedOffset> {
+        tz().with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap()
+    }
+
+    fn tz() -> FixedOffset {
+        FixedOffset::east_opt(8 * 3600).unwrap()
+    }
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+        tz().with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn weekly(interval: i64, by_weekday: &[&str]) -> RecurrenceRuleDto {
+        RecurrenceRuleDto {
+            freq: "weekly".to_string(),
+            interval: Some(interval),
+            by_weekday: by_weekday.iter().map(|code| code.to_string()).collect(),
+            until: None,
+            count: None,
+        }
+    }
+
+    #[test]
+    fn parses_full_example() {
+        let parsed = parse(
+            "Fix login bug #backend !P0 @work/Auth due:fri 5pm every:week",
+            &now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed,
+            QuickAddParsed {
+                title: "Fix login bug".to_string(),
+                location: vec!["work".to_string(), "Auth".to_string()],
+                tags: vec!["backend".to_string()],
+                priority: Some("P0".to_string()),
+                deadline_at: Some(at(2026, 10, 16, 17, 0)),
+                recurrence: Some(weekly(1, &[])),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_due_dates() {
+        let cases = [
+            ("today", NaiveDate::from_ymd_opt(2026, 10, 14)),
+            ("tomorrow", NaiveDate::from_ymd_opt(2026, 10, 15)),
+            // 星期取含今天在内最近的一次。
+            ("wed", NaiveDate::from_ymd_opt(2026, 10, 14)),
+            ("Monday", NaiveDate::from_ymd_opt(2026, 10, 19)),
+            ("2026-12-01", NaiveDate::from_ymd_opt(2026, 12, 1)),
+            ("11/03", NaiveDate::from_ymd_opt(2026, 11, 3)),
+            // 不带年份且已过的日期顺延到明年。
+            ("10-01", NaiveDate::from_ymd_opt(2027, 10, 1)),
+            ("+3d", NaiveDate::from_ymd_opt(2026, 10, 17)),
+            ("+2w", NaiveDate::from_ymd_opt(2026, 10, 28)),
+            ("+1m", NaiveDate::from_ymd_opt(2026, 11, 14)),
+        ];
+        for (value, expected) in cases {
+            let date = expected.unwrap();
+            let parsed = parse(&format!("写周报 due:{value}"), &now()).unwrap();
+            assert_eq!(
+                parsed.deadline_at,
+                Some(at(date.year(), date.month(), date.day(), 23, 59)),
+                "due:{value}"
+            );
+            assert_eq!(parsed.title, "写周报");
+        }
+
+        let error = parse("写周报 due:someday", &now()).unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn parses_due_times() {
+        let cases = [
+            ("5pm", (17, 0)),
+            ("5:30pm", (17, 30)),
+            ("12am", (0, 0)),
+            ("12pm", (12, 0)),
+            ("9am", (9, 0)),
+            ("17:45", (17, 45)),
+        ];
+        for (value, (hour, minute)) in cases {
+            let parsed = parse(&format!("开会 due:tomorrow {value}"), &now()).unwrap();
+            assert_eq!(
+                parsed.deadline_at,
+                Some(at(2026, 10, 15, hour, minute)),
+                "{value}"
+            );
+            assert_eq!(parsed.title, "开会");
+        }
+
+        // 纯数字不当作时间，留在标题里。
+        let parsed = parse("买 due:today 3 箱水", &now()).unwrap();
+        assert_eq!(parsed.deadline_at, Some(at(2026, 10, 14, 23, 59)));
+        assert_eq!(parsed.title, "买 3 箱水");
+    }
+
+    #[test]
+    fn parses_recurrence_rules() {
+        let rule = |value: &str| {
+            parse(&format!("浇花 every:{value}"), &now())
+                .unwrap()
+                .recurrence
+        };
+        let simple = |freq: &str, interval: i64| RecurrenceRuleDto {
+            freq: freq.to_string(),
+            ..weekly(interval, &[])
+        };
+
+        assert_eq!(rule("day"), Some(simple("daily", 1)));
+        assert_eq!(rule("weekly"), Some(simple("weekly", 1)));
+        assert_eq!(rule("month"), Some(simple("monthly", 1)));
+        assert_eq!(rule("3d"), Some(simple("daily", 3)));
+        assert_eq!(rule("2w"), Some(simple("weekly", 2)));
+        assert_eq!(
+            rule("weekday"),
+            Some(weekly(1, &["MO", "TU", "WE", "TH", "FR"]))
+        );
+        // 星期按周一到周日排序。
+        assert_eq!(rule("fri,mon"), Some(weekly(1, &["MO", "FR"])));
+
+        for value in ["fortnight", "mon,funday", "0d"] {
+            assert!(
+                parse(&format!("浇花 every:{value}"), &now()).is_err(),
+                "every:{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_priority_and_location() {
+        let parsed = parse("整理 !2 #a #b #a @inbox", &now()).unwrap();
+        assert_eq!(parsed.priority.as_deref(), Some("P2"));
+        assert_eq!(parsed.tags, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(parsed.location, vec!["inbox".to_string()]);
+
+        let parsed = parse("整理 !p3 @work/客户/ 官网 /", &now()).unwrap();
+        assert_eq!(parsed.priority.as_deref(), Some("P3"));
+        assert_eq!(
+            parsed.location,
+            vec!["work".to_string(), "客户".to_string()]
+        );
+        assert_eq!(parsed.title, "整理 官网 /");
+
+        // 不合法的优先级与空记号保留在标题里。
+        let parsed = parse("修复 !P9 # @", &now()).unwrap();
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.title, "修复 !P9 # @");
+    }
+
+    #[test]
+    fn rejects_missing_title() {
+        let error = parse("#backend !P1 @work due:fri 5pm", &now()).unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)));
+        assert!(parse("   ", &now()).is_err());
+    }
+}
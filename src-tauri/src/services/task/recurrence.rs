@@ -0,0 +1,186 @@
+//! 重复任务用例。
+//!
+//! 完成一条带重复规则的任务时，在同一事务里生成系列的下一次任务：
+//! - 沿用原任务的项目、优先级、备注、标签、链接、自定义字段和检查清单
+//! - 下一次任务 id 由“系列 id + 序号”确定，多端各自完成同一次任务时
+//!   生成的是同一条记录，同步后不会出现重复的下一次
+//! - 下一次任务被删除或撤销后只剩 tombstone，再次完成时换用带尝试序号的 id 重新生成
+
+use sea_orm::{ConnectionTrait, EntityTrait};
+
+use crate::db::entities::{sea_orm_active_enums::TaskStatus, tasks};
+use crate::repos::{
+    search_repo,
+    task_repo::{activity_logs, checklist, links, mutation, query, recurrence, tags},
+};
+use crate::types::{dto::LinkInputDto, error::AppError};
+
+use super::TaskService;
+
+impl TaskService {
+    /// 按重复规则为刚完成的任务生成下一次任务。
+    ///
+    /// 没有规则、规则已用尽（until / count）或下一次任务已存在时返回 `None`。
+    /// 项目统计由调用方统一刷新，这里不重复处理。
+    pub(super) async fn spawn_next_occurrence<C>(
+        conn: &C,
+        task: &tasks::Model,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<Option<tasks::Model>, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let Some(rule) = recurrence::parse_from_rrule_string(task.recurrence_rule.as_deref())
+        else {
+            return Ok(None);
+        };
+
+        let series_id = task
+            .recurrence_series_id
+            .clone()
+            .unwrap_or_else(|| task.id.clone());
+        let next_index = task.recurrence_index.unwrap_or(1) + 1;
+        if recurrence::is_exhausted(&rule, next_index) {
+            return Ok(None);
+        }
+
+        // 有截止时间时沿截止时间推进，保证系列节奏不受“晚完成”影响。
+        let anchor = task.deadline_at.unwrap_or(now);
+        let Some(next_deadline_at) = recurrence::next_occurrence(&rule, anchor) else {
+            return Ok(None);
+        };
+
+        // 计划时间与推迟时间跟随截止时间整体平移，保持与截止时间的相对间隔。
+        let shift = |value: Option<i64>| value.map(|value| value + (next_deadline_at - anchor));
+
+        let Some(next_id) = next_occurrence_id(conn, &series_id, next_index).await? else {
+            return Ok(None);
+        };
+
+        let rank =
+            query::next_rank_in_bucket(conn, &task.space_id, &TaskStatus::Todo, &task.priority)
+                .await?;
+        let next_task = mutation::insert(
+            conn,
+            mutation::NewTaskRecord {
+                id: next_id.clone(),
+                space_id: task.space_id.clone(),
+                project_id: task.project_id.clone(),
+                title: task.title.clone(),
+                note: task.note.clone(),
+                status: TaskStatus::Todo,
+                done_reason: None,
+                priority: task.priority.clone(),
+                rank,
+                created_at: now,
+                updated_at: now,
+                completed_at: None,
+                deadline_at: Some(next_deadline_at),
+                scheduled_at: shift(task.scheduled_at),
+                defer_until: shift(task.defer_until),
+                archived_at: None,
+                deleted_at: None,
+                custom_fields: task.custom_fields.clone(),
+                create_by: task.create_by.clone(),
+                recurrence_rule: Some(recurrence::serialize_rule(&rule)),
+                recurrence_series_id: Some(series_id),
+                recurrence_index: Some(next_index),
+                milestone_id: task.milestone_id.clone(),
+            },
+        )
+        .await?;
+
+        let task_ids = vec![task.id.clone()];
+        let tag_names = tags::load_tags_for_tasks(conn, &task_ids)
+            .await?
+            .remove(&task.id)
+            .unwrap_or_default();
+        if !tag_names.is_empty() {
+            tags::sync_tags(conn, &next_id, &tag_names).await?;
+        }
+
+        // 链接主表记录归属单个任务，这里复制成新记录而不是共享原链接 id。
+        let link_inputs = links::load_links_for_tasks(conn, &task_ids)
+            .await?
+            .remove(&task.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|link| LinkInputDto {
+                id: None,
+                title: link.title,
+                url: link.url,
+                kind: link.kind,
+                rank: Some(link.rank),
+            })
+            .collect::<Vec<_>>();
+        if !link_inputs.is_empty() {
+            links::sync_links(conn, &next_id, &link_inputs).await?;
+        }
+
+        // 检查清单按原顺序复制，并全部重置为未完成。
+        // 条目 id 与下一次任务一样是确定性的，多端同时生成时同步后不会出现重复条目；
+        // 源条目本身若是上一次复制来的，先去掉旧前缀，避免 id 逐次变长。
+        let source_prefix = format!("{}:", task.id);
+        for item in checklist::list_for_task(conn, &task.id).await? {
+            let origin_id = item.id.strip_prefix(&source_prefix).unwrap_or(&item.id);
+            checklist::insert(
+                conn,
+                checklist::NewChecklistItemRecord {
+                    id: format!("{next_id}:{origin_id}"),
+                    task_id: next_id.clone(),
+                    title: item.title,
+                    done: false,
+                    rank: item.rank,
+                    created_at: now,
+                },
+            )
+            .await?;
+        }
+
+        activity_logs::append_recurrence_spawned(
+            conn,
+            activity_logs::TaskLogCtx {
+                task_id: &next_task.id,
+                space_id: &next_task.space_id,
+                project_id: next_task.project_id.as_deref(),
+                create_by: &next_task.create_by,
+                created_at: now,
+                batch_id,
+            },
+            &next_task.title,
+            &task.id,
+        )
+        .await?;
+        search_repo::reindex_tasks(conn, std::slice::from_ref(&next_id)).await?;
+
+        Ok(Some(next_task))
+    }
+}
+
+/// 选出下一次任务的 id；未删除的下一次任务已存在时返回 `None`。
+///
+/// 依次尝试 `系列 id:序号`、`系列 id:序号.2`……，跳过只剩 tombstone 的 id。
+/// 候选顺序固定，多端基于同步后的同一份数据会选出同一个 id。
+async fn next_occurrence_id<C>(
+    conn: &C,
+    series_id: &str,
+    next_index: i64,
+) -> Result<Option<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut next_id = format!("{series_id}:{next_index}");
+    for attempt in 2.. {
+        let existing = tasks::Entity::find_by_id(&next_id)
+            .one(conn)
+            .await
+            .map_err(AppError::from)?;
+        match existing {
+            None => break,
+            Some(model) if model.deleted_at.is_none() => return Ok(None),
+            Some(_) => next_id = format!("{series_id}:{next_index}.{attempt}"),
+        }
+    }
+    Ok(Some(next_id))
+}
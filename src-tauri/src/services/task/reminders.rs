@@ -0,0 +1,169 @@
+//! 任务提醒用例。
+//!
+//! 约定：
+//! - 提醒以“提前分钟数”配置，一个任务可配多条；从未配置时调度器按截止时刻默认提醒
+//! - 清空提醒会留下 tombstone，调度器据此不再默认提醒
+//! - 稍后/忽略只作用于当前截止时间，截止时间改动后提醒重新生效
+//! - 提醒独立同步，不刷新任务 `updated_at`
+
+use std::collections::BTreeSet;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{entities::task_reminders, now_ms};
+use crate::repos::task_repo::{query, reminders};
+use crate::types::{dto::TaskReminderDto, error::AppError};
+
+use super::TaskService;
+
+const MAX_SNOOZE_MINUTES: i64 = 7 * 24 * 60;
+
+impl TaskService {
+    /// 整体替换任务的提醒配置；保留下来的提前量沿用原有触发状态。
+    pub async fn set_reminders(
+        conn: &DatabaseConnection,
+        task_id: &str,
+        offset_minutes: Vec<i64>,
+    ) -> Result<Vec<TaskReminderDto>, AppError> {
+        let offsets = normalize_offsets(offset_minutes)?;
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let task = query::find_by_id(&txn, task_id).await?;
+        if task.deleted_at.is_some() {
+            return Err(AppError::Validation("任务已删除，无法设置提醒".to_string()));
+        }
+
+        let mut kept = Vec::new();
+        let mut existing_offsets = BTreeSet::new();
+        for reminder in reminders::list_for_task(&txn, &task.id).await? {
+            // 同一提前量在多端同步后可能重复，只保留第一条。
+            if offsets.contains(&reminder.offset_minutes)
+                && existing_offsets.insert(reminder.offset_minutes)
+            {
+                kept.push(reminder);
+                continue;
+            }
+            let mut active_model: task_reminders::ActiveModel = reminder.into();
+            active_model.updated_at = Set(now);
+            active_model.deleted_at = Set(Some(now));
+            reminders::update(&txn, active_model).await?;
+        }
+
+        for offset in offsets.difference(&existing_offsets) {
+            kept.push(
+                reminders::insert(
+                    &txn,
+                    reminders::NewReminderRecord {
+                        id: Uuid::new_v4().to_string(),
+                        task_id: task.id.clone(),
+                        offset_minutes: *offset,
+                        created_at: now,
+                    },
+                )
+                .await?,
+            );
+        }
+
+        // 从未有过提醒记录的任务清空提醒时，落一条已删除的默认提醒作为“不提醒”标记。
+        if offsets.is_empty()
+            && reminders::load_all_for_tasks(&txn, std::slice::from_ref(&task.id))
+                .await?
+                .is_empty()
+        {
+            let marker = reminders::insert(
+                &txn,
+                reminders::NewReminderRecord {
+                    id: reminders::default_reminder_id(&task.id),
+                    task_id: task.id.clone(),
+                    offset_minutes: 0,
+                    created_at: now,
+                },
+            )
+            .await?;
+            let mut active_model: task_reminders::ActiveModel = marker.into();
+            active_model.deleted_at = Set(Some(now));
+            reminders::update(&txn, active_model).await?;
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+        kept.sort_by_key(|reminder| std::cmp::Reverse(reminder.offset_minutes));
+        Ok(kept
+            .into_iter()
+            .map(|model| reminders::to_dto(model, task.deadline_at))
+            .collect())
+    }
+
+    /// 稍后提醒：在 `minutes` 分钟后再次触发。
+    pub async fn snooze_reminder(
+        conn: &DatabaseConnection,
+        id: &str,
+        minutes: i64,
+    ) -> Result<TaskReminderDto, AppError> {
+        if !(1..=MAX_SNOOZE_MINUTES).contains(&minutes) {
+            return Err(AppError::Validation(format!(
+                "稍后提醒时长必须在 1 到 {MAX_SNOOZE_MINUTES} 分钟之间"
+            )));
+        }
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let reminder = reminders::find_by_id(&txn, id).await?;
+        let deadline_at = require_deadline(&txn, &reminder.task_id).await?;
+
+        let mut active_model: task_reminders::ActiveModel = reminder.into();
+        active_model.anchor_deadline_at = Set(Some(deadline_at));
+        active_model.snoozed_until = Set(Some(now + minutes * 60_000));
+        active_model.dismissed_at = Set(None);
+        active_model.updated_at = Set(now);
+        let saved = reminders::update(&txn, active_model).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(reminders::to_dto(saved, Some(deadline_at)))
+    }
+
+    /// 忽略提醒：当前截止时间内不再触发。
+    pub async fn dismiss_reminder(
+        conn: &DatabaseConnection,
+        id: &str,
+    ) -> Result<TaskReminderDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let reminder = reminders::find_by_id(&txn, id).await?;
+        let deadline_at = require_deadline(&txn, &reminder.task_id).await?;
+
+        let mut active_model: task_reminders::ActiveModel = reminder.into();
+        active_model.anchor_deadline_at = Set(Some(deadline_at));
+        active_model.snoozed_until = Set(None);
+        active_model.dismissed_at = Set(Some(now));
+        active_model.updated_at = Set(now);
+        let saved = reminders::update(&txn, active_model).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(reminders::to_dto(saved, Some(deadline_at)))
+    }
+}
+
+/// 校验并去重提前分钟数。
+fn normalize_offsets(offset_minutes: Vec<i64>) -> Result<BTreeSet<i64>, AppError> {
+    if let Some(invalid) = offset_minutes
+        .iter()
+        .find(|offset| !(0..=reminders::MAX_OFFSET_MINUTES).contains(*offset))
+    {
+        return Err(AppError::Validation(format!(
+            "提前提醒分钟数必须在 0 到 {} 之间，收到 {invalid}",
+            reminders::MAX_OFFSET_MINUTES
+        )));
+    }
+    Ok(offset_minutes.into_iter().collect())
+}
+
+/// 稍后/忽略都绑定在当前截止时间上，任务没有截止时间时无从提醒。
+async fn require_deadline<C>(conn: &C, task_id: &str) -> Result<i64, AppError>
+where
+    C: ConnectionTrait,
+{
+    query::find_by_id(conn, task_id)
+        .await?
+        .deadline_at
+        .ok_or_else(|| AppError::Validation("任务没有截止时间，无法调整提醒".to_string()))
+}
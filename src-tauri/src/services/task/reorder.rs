@@ -1,46 +1,81 @@
 //! 任务排序用例。
 //!
-//! 这里把“单条改 rank”和“批量重排 rank”单独抽出来，
+//! 这里把“单条拖拽”和“整体重排”单独抽出来，
 //! 这样命令层看到的就是明确的排序语义，而不是手动构造 update patch。
+//!
+//! 约定：
+//! - rank 是分数排序键，单次拖拽只根据前后邻居算出新键，不动其它任务
+//! - 只有邻居键相同（例如多端同时拖到同一位置）时才需要整体重排
+//! - 整体重排在一个事务内完成，不写字段日志、不刷新项目统计
 
-use sea_orm::DatabaseConnection;
+use sea_orm::{DatabaseConnection, Set, TransactionTrait};
 
+use crate::db::{entities::tasks, now_ms};
+use crate::repos::{
+    rank,
+    task_repo::{mutation, query},
+};
 use crate::types::error::AppError;
 
 use super::{
-    dto::{TaskUpdateInput, TaskUpdatePatch},
+    dto::{TaskReorderInput, TaskUpdateInput, TaskUpdatePatch},
     TaskService,
 };
 
 impl TaskService {
-    /// 更新单个任务的排序权重。
+    /// 把任务移动到 `prev_task_id` 与 `next_task_id` 之间，返回新的排序键。
     ///
-    /// 实现上复用 `update`，避免再维护一套单独的写入逻辑。
+    /// 显式传入 `new_rank` 时直接使用；两端邻居都为空表示移动到空列表。
     pub async fn reorder(
         conn: &DatabaseConnection,
-        task_id: String,
-        new_rank: i64,
-    ) -> Result<(), AppError> {
-        Self::update(
-            conn,
+        input: TaskReorderInput,
+    ) -> Result<String, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let new_rank = match input.new_rank.as_deref() {
+            Some(value) => rank::validate(value)?,
+            None => {
+                let prev = match input.prev_task_id.as_deref() {
+                    Some(id) => Some(query::find_by_id(&txn, id).await?.rank),
+                    None => None,
+                };
+                let next = match input.next_task_id.as_deref() {
+                    Some(id) => Some(query::find_by_id(&txn, id).await?.rank),
+                    None => None,
+                };
+                rank::between(prev.as_deref(), next.as_deref())?
+            }
+        };
+
+        // 复用 update 的写入与日志；rank 不影响统计与搜索，这里不再额外刷新。
+        Self::apply_update(
+            &txn,
             TaskUpdateInput {
-                id: task_id,
-                patch: TaskUpdatePatch::rank_only(new_rank),
+                id: input.task_id,
+                patch: TaskUpdatePatch::rank_only(new_rank.clone()),
             },
+            now_ms(),
+            None,
         )
-        .await
+        .await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(new_rank)
     }
 
-    /// 按给定顺序重新分配一组任务的 rank。
-    pub async fn rebalance(
-        conn: &DatabaseConnection,
-        task_ids: &[String],
-        step: i64,
-    ) -> Result<(), AppError> {
-        for (index, task_id) in task_ids.iter().enumerate() {
-            let new_rank = (index as i64) * step;
-            Self::reorder(conn, task_id.clone(), new_rank).await?;
+    /// 按给定顺序为一组任务重新生成均匀分布的排序键。
+    pub async fn rebalance(conn: &DatabaseConnection, task_ids: &[String]) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        for (task_id, next_rank) in task_ids.iter().zip(rank::spread(task_ids.len())) {
+            let Some(model) = query::find_optional_by_id(&txn, task_id).await? else {
+                continue;
+            };
+            let mut active_model: tasks::ActiveModel = model.into();
+            active_model.rank = Set(next_rank);
+            active_model.updated_at = Set(now);
+            mutation::update(&txn, active_model).await?;
         }
+        txn.commit().await.map_err(AppError::from)?;
         Ok(())
     }
 }
@@ -0,0 +1,383 @@
+//! 任务模板用例。
+//!
+//! 约定：
+//! - 模板保存时就做一遍与创建任务相同的归一化，实例化时不再因为模板内容报错
+//! - 实例化只负责展开占位符、换算相对时间和确定落点，最终统一交给 `create`
+//! - 截止 / 计划时间按“实例化时刻 + 偏移分钟数”计算
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, TimeZone};
+use sea_orm::{DatabaseConnection, Set};
+use uuid::Uuid;
+
+use crate::db::{
+    entities::{sea_orm_active_enums::TaskStatus, task_templates, tasks},
+    now_ms,
+};
+use crate::repos::{
+    common_task_utils,
+    project_repo::query as project_query,
+    space_repo::SpaceRepo,
+    task_repo::{custom_fields, recurrence, templates, today, validations},
+};
+use crate::types::{
+    dto::{CustomFieldsDto, LinkDto, LinkInputDto, TaskDto, TaskTemplateBodyDto, TaskTemplateDto},
+    error::AppError,
+};
+
+use super::{
+    dto::{
+        TaskCreateInput, TaskCreatePatch, TaskTemplateCreateInput, TaskTemplateInstantiateInput,
+        TaskTemplateUpdatePatch,
+    },
+    helpers::{normalize_tags, priority_to_value},
+    TaskService,
+};
+
+/// 相对时间偏移的上限：约 10 年。
+const MAX_OFFSET_MINUTES: i64 = 10 * 366 * 24 * 60;
+
+impl TaskService {
+    /// 新建任务模板。
+    pub async fn create_template(
+        conn: &DatabaseConnection,
+        input: TaskTemplateCreateInput,
+    ) -> Result<TaskTemplateDto, AppError> {
+        let name = normalize_name(&input.name)?;
+        let body = normalize_body(input.body)?;
+        let (space_id, project_id) =
+            validate_target(conn, input.space_id, input.project_id).await?;
+
+        let model = templates::insert(
+            conn,
+            templates::NewTemplateRecord {
+                id: Uuid::new_v4().to_string(),
+                name,
+                space_id,
+                project_id,
+                body: templates::serialize_body(&body)?,
+                created_at: now_ms(),
+            },
+        )
+        .await?;
+        Ok(templates::to_dto(model))
+    }
+
+    /// 按 patch 更新任务模板。
+    pub async fn update_template(
+        conn: &DatabaseConnection,
+        id: &str,
+        patch: TaskTemplateUpdatePatch,
+    ) -> Result<TaskTemplateDto, AppError> {
+        let model = templates::find_by_id(conn, id).await?;
+        let space_id = patch.space_id.unwrap_or_else(|| model.space_id.clone());
+        let project_id = patch.project_id.unwrap_or_else(|| model.project_id.clone());
+        let (space_id, project_id) = validate_target(conn, space_id, project_id).await?;
+
+        let mut active_model: task_templates::ActiveModel = model.into();
+        if let Some(name) = patch.name {
+            active_model.name = Set(normalize_name(&name)?);
+        }
+        if let Some(body) = patch.body {
+            active_model.body = Set(templates::serialize_body(&normalize_body(body)?)?);
+        }
+        active_model.space_id = Set(space_id);
+        active_model.project_id = Set(project_id);
+        active_model.updated_at = Set(now_ms());
+        Ok(templates::to_dto(
+            templates::update(conn, active_model).await?,
+        ))
+    }
+
+    /// 删除任务模板（写 tombstone）。
+    pub async fn delete_template(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
+        let model = templates::find_by_id(conn, id).await?;
+        let now = now_ms();
+        let mut active_model: task_templates::ActiveModel = model.into();
+        active_model.updated_at = Set(now);
+        active_model.deleted_at = Set(Some(now));
+        templates::update(conn, active_model).await?;
+        Ok(())
+    }
+
+    /// 按模板创建任务。
+    ///
+    /// 落点优先级：显式项目 > 显式 space（模板项目不在该 space 时忽略）> 模板项目 > 模板 space。
+    pub async fn instantiate_template(
+        conn: &DatabaseConnection,
+        input: TaskTemplateInstantiateInput,
+    ) -> Result<TaskDto, AppError> {
+        let template = templates::to_dto(templates::find_by_id(conn, &input.template_id).await?);
+        let spaces = SpaceRepo::list(conn).await?;
+
+        let explicit_project = match input.project_id.as_deref() {
+            Some(project_id) => Some(project_query::find_by_id(conn, project_id).await?),
+            None => None,
+        };
+        let template_project = match template.project_id.as_deref() {
+            Some(project_id) => project_query::find_optional_by_id(conn, project_id)
+                .await?
+                .filter(|project| project.deleted_at.is_none()),
+            None => None,
+        };
+        let project = match (explicit_project, input.space_id.as_deref()) {
+            (Some(project), Some(space_id)) if project.space_id != space_id => {
+                return Err(AppError::Validation(format!(
+                    "项目 {} 不属于 space {space_id}",
+                    project.id
+                )));
+            }
+            (Some(project), _) => Some(project),
+            (None, Some(space_id)) => {
+                template_project.filter(|project| project.space_id == space_id)
+            }
+            (None, None) => template_project,
+        };
+        let space_id = match (&project, input.space_id.or(template.space_id)) {
+            (Some(project), _) => project.space_id.clone(),
+            (None, Some(space_id)) => space_id,
+            (None, None) => spaces
+                .first()
+                .map(|space| space.id.clone())
+                .ok_or_else(|| AppError::Validation("没有可用的 space".to_string()))?,
+        };
+        let space = spaces
+            .iter()
+            .find(|space| space.id == space_id)
+            .ok_or_else(|| AppError::Validation(format!("space {space_id} 不存在")))?;
+
+        let now = now_ms();
+        let mut variables = builtin_variables(input.utc_offset_minutes, now)?;
+        variables.insert("space".to_string(), space.name.clone());
+        variables.insert(
+            "project".to_string(),
+            project
+                .as_ref()
+                .map(|project| project.title.clone())
+                .unwrap_or_default(),
+        );
+        variables.extend(input.variables);
+
+        Self::create(
+            conn,
+            body_to_create_input(
+                template.body,
+                space.id.clone(),
+                project.map(|project| project.id),
+                &variables,
+                now,
+            ),
+        )
+        .await
+    }
+}
+
+/// 把模板内容展开成创建任务的输入；相对时间以 `now` 为基准。
+pub(crate) fn body_to_create_input(
+    body: TaskTemplateBodyDto,
+    space_id: String,
+    project_id: Option<String>,
+    variables: &HashMap<String, String>,
+    now: i64,
+) -> TaskCreateInput {
+    let expand = |text: &str| templates::expand_placeholders(text, variables);
+    TaskCreateInput {
+        space_id,
+        title: expand(&body.title),
+        auto_start: false,
+        project_id,
+        patch: TaskCreatePatch {
+            status: body.status,
+            priority: body.priority,
+            note: body.note.as_deref().map(expand),
+            deadline_at: body
+                .deadline_offset_minutes
+                .map(|minutes| now + minutes * 60_000),
+            scheduled_at: body
+                .scheduled_offset_minutes
+                .map(|minutes| now + minutes * 60_000),
+            tags: Some(body.tags.iter().map(|tag| expand(tag)).collect()),
+            links: Some(
+                body.links
+                    .into_iter()
+                    .map(|link| LinkInputDto {
+                        id: None,
+                        title: expand(&link.title),
+                        url: expand(&link.url),
+                        kind: link.kind,
+                        rank: link.rank,
+                    })
+                    .collect(),
+            ),
+            custom_fields: body.custom_fields.map(|fields| CustomFieldsDto {
+                fields: fields
+                    .fields
+                    .into_iter()
+                    .map(|mut item| {
+                        item.title = expand(&item.title);
+                        item.value = item.value.as_deref().map(expand);
+                        item
+                    })
+                    .collect(),
+            }),
+            recurrence: body.recurrence,
+            ..Default::default()
+        },
+    }
+}
+
+/// 把现有任务抓取成模板内容；截止 / 计划时间换算成相对 `anchor` 的偏移。
+///
+/// 进行中和已完成的状态不保留，按模板创建的任务重新从待办开始。
+pub(crate) fn body_from_task(
+    task: &tasks::Model,
+    tags: Vec<String>,
+    links: Vec<LinkDto>,
+    anchor: i64,
+) -> Result<TaskTemplateBodyDto, AppError> {
+    let offset = |at: Option<i64>| {
+        at.map(|at| (at - anchor) / 60_000)
+            .filter(|minutes| minutes.abs() <= MAX_OFFSET_MINUTES)
+    };
+    Ok(TaskTemplateBodyDto {
+        title: task.title.clone(),
+        note: task.note.clone(),
+        status: match task.status {
+            TaskStatus::Waiting | TaskStatus::Someday => {
+                Some(common_task_utils::status_to_string(&task.status))
+            }
+            _ => None,
+        },
+        priority: Some(priority_to_value(&task.priority)),
+        tags,
+        links: links
+            .into_iter()
+            .map(|link| LinkInputDto {
+                id: None,
+                title: link.title,
+                url: link.url,
+                kind: link.kind,
+                rank: Some(link.rank),
+            })
+            .collect(),
+        custom_fields: custom_fields::parse_stored(task.custom_fields.as_deref())?,
+        recurrence: recurrence::parse_from_rrule_string(task.recurrence_rule.as_deref()),
+        deadline_offset_minutes: offset(task.deadline_at),
+        scheduled_offset_minutes: offset(task.scheduled_at),
+    })
+}
+
+/// 与时间相关的内置占位符：date / time / datetime / weekday。
+pub(crate) fn builtin_variables(
+    utc_offset_minutes: Option<i32>,
+    now: i64,
+) -> Result<HashMap<String, String>, AppError> {
+    match utc_offset_minutes {
+        Some(minutes) => time_variables(&today::fixed_offset(minutes)?, now),
+        None => time_variables(&Local, now),
+    }
+}
+
+fn normalize_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("模板名称不能为空".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// 按创建任务的规则归一化模板内容；标题里的占位符在实例化时才展开。
+fn normalize_body(body: TaskTemplateBodyDto) -> Result<TaskTemplateBodyDto, AppError> {
+    for minutes in [body.deadline_offset_minutes, body.scheduled_offset_minutes]
+        .into_iter()
+        .flatten()
+    {
+        if minutes.abs() > MAX_OFFSET_MINUTES {
+            return Err(AppError::Validation(format!(
+                "时间偏移不能超过 {MAX_OFFSET_MINUTES} 分钟"
+            )));
+        }
+    }
+
+    Ok(TaskTemplateBodyDto {
+        title: validations::trim_and_validate_title(&body.title)?,
+        note: body
+            .note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty()),
+        status: body
+            .status
+            .as_deref()
+            .map(validations::normalize_status)
+            .transpose()?,
+        priority: body
+            .priority
+            .as_deref()
+            .map(validations::normalize_priority)
+            .transpose()?,
+        tags: normalize_tags(body.tags),
+        links: body.links,
+        // 模板不绑定具体任务，字段类型在按模板创建任务时再校验。
+        custom_fields: body
+            .custom_fields
+            .map(|value| custom_fields::normalize_custom_fields(value, &[]))
+            .transpose()?,
+        recurrence: body
+            .recurrence
+            .map(recurrence::normalize_rule)
+            .transpose()?,
+        deadline_offset_minutes: body.deadline_offset_minutes,
+        scheduled_offset_minutes: body.scheduled_offset_minutes,
+    })
+}
+
+/// 校验模板默认落点；绑定项目时 space 以项目为准。
+async fn validate_target(
+    conn: &DatabaseConnection,
+    space_id: Option<String>,
+    project_id: Option<String>,
+) -> Result<(Option<String>, Option<String>), AppError> {
+    if let Some(project_id) = project_id {
+        let project = project_query::find_by_id(conn, &project_id).await?;
+        if space_id
+            .as_deref()
+            .is_some_and(|space_id| space_id != project.space_id)
+        {
+            return Err(AppError::Validation(format!(
+                "项目 {project_id} 不属于 space {}",
+                space_id.unwrap_or_default()
+            )));
+        }
+        return Ok((Some(project.space_id), Some(project_id)));
+    }
+    if let Some(space_id) = space_id.as_deref() {
+        if !SpaceRepo::list(conn)
+            .await?
+            .iter()
+            .any(|space| space.id == space_id)
+        {
+            return Err(AppError::Validation(format!("space {space_id} 不存在")));
+        }
+    }
+    Ok((space_id, None))
+}
+
+fn time_variables<Tz: TimeZone>(tz: &Tz, now: i64) -> Result<HashMap<String, String>, AppError>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let local: DateTime<Tz> = tz
+        .timestamp_millis_opt(now)
+        .earliest()
+        .ok_or_else(|| AppError::Internal("无法获取当前本地时间".to_string()))?;
+    Ok(HashMap::from([
+        ("date".to_string(), local.format("%Y-%m-%d").to_string()),
+        ("time".to_string(), local.format("%H:%M").to_string()),
+        (
+            "datetime".to_string(),
+            local.format("%Y-%m-%d %H:%M").to_string(),
+        ),
+        ("weekday".to_string(), local.format("%A").to_string()),
+    ]))
+}
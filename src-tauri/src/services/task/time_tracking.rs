@@ -0,0 +1,258 @@
+//! 任务计时用例。
+//!
+//! 约定：
+//! - 全局同一时刻只允许一个进行中的计时器，开始新计时会先结束旧的
+//! - 番茄钟到点后在下一次访问时按到点时刻自动结束
+//! - 计时记录独立同步，不刷新任务 `updated_at`，避免计时频繁制造任务冲突
+//! - 计时结束或删除后回写所属项目链上的子树计时汇总
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{entities::task_time_entries, now_ms};
+use crate::repos::task_repo::{query, stats, time_entries};
+use crate::types::{dto::TimeEntryDto, error::AppError};
+
+use super::{dto::TimerStartInput, TaskService};
+
+const DEFAULT_POMODORO_MINUTES: i64 = 25;
+const MAX_POMODORO_MINUTES: i64 = 180;
+
+impl TaskService {
+    /// 为任务开始计时；已有进行中的计时器会先被结束。
+    pub async fn start_timer(
+        conn: &DatabaseConnection,
+        input: TimerStartInput,
+    ) -> Result<TimeEntryDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let task = query::find_by_id(&txn, &input.task_id).await?;
+        if task.deleted_at.is_some() {
+            return Err(AppError::Validation("任务已删除，无法开始计时".to_string()));
+        }
+        let (mode, planned_ms) = resolve_mode(input.mode.as_deref(), input.pomodoro_minutes)?;
+        let note = input
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+
+        stop_active_entries(&txn, now).await?;
+        let entry = time_entries::insert(
+            &txn,
+            time_entries::NewTimeEntryRecord {
+                id: Uuid::new_v4().to_string(),
+                task_id: task.id,
+                mode,
+                planned_ms,
+                note,
+                started_at: now,
+            },
+        )
+        .await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(time_entries::to_dto(entry, now))
+    }
+
+    /// 暂停当前计时器。
+    pub async fn pause_timer(conn: &DatabaseConnection) -> Result<TimeEntryDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let entry = settle_active_entry(&txn, now)
+            .await?
+            .ok_or_else(|| AppError::Validation("当前没有进行中的计时".to_string()))?;
+        if entry.paused_at.is_some() {
+            return Err(AppError::Validation("计时已处于暂停状态".to_string()));
+        }
+
+        let mut active_model: task_time_entries::ActiveModel = entry.into();
+        active_model.paused_at = Set(Some(now));
+        active_model.updated_at = Set(now);
+        let saved = time_entries::update(&txn, active_model).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(time_entries::to_dto(saved, now))
+    }
+
+    /// 恢复已暂停的计时器，暂停区间累计进 `paused_ms`。
+    pub async fn resume_timer(conn: &DatabaseConnection) -> Result<TimeEntryDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let entry = settle_active_entry(&txn, now)
+            .await?
+            .ok_or_else(|| AppError::Validation("当前没有进行中的计时".to_string()))?;
+        let Some(paused_at) = entry.paused_at else {
+            return Err(AppError::Validation("计时未处于暂停状态".to_string()));
+        };
+
+        let paused_ms = entry.paused_ms + (now - paused_at).max(0);
+        let mut active_model: task_time_entries::ActiveModel = entry.into();
+        active_model.paused_at = Set(None);
+        active_model.paused_ms = Set(paused_ms);
+        active_model.updated_at = Set(now);
+        let saved = time_entries::update(&txn, active_model).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(time_entries::to_dto(saved, now))
+    }
+
+    /// 结束当前计时器；没有进行中的计时时返回 `None`。
+    pub async fn stop_timer(conn: &DatabaseConnection) -> Result<Option<TimeEntryDto>, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let stopped = stop_active_entries(&txn, now).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(stopped.map(|entry| time_entries::to_dto(entry, now)))
+    }
+
+    /// 读取当前计时器；读取前会先收口已到点的番茄钟，因此走写事务。
+    pub async fn active_timer(conn: &DatabaseConnection) -> Result<Option<TimeEntryDto>, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let entry = settle_active_entry(&txn, now).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(entry.map(|entry| time_entries::to_dto(entry, now)))
+    }
+
+    /// 删除计时记录（写 tombstone）；进行中的记录会一并停止。
+    pub async fn delete_time_entry(conn: &DatabaseConnection, id: &str) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let entry = time_entries::find_by_id(&txn, id).await?;
+        let task_id = entry.task_id.clone();
+
+        let mut active_model = if entry.ended_at.is_none() {
+            time_entries::finish(entry, now, now)
+        } else {
+            entry.into()
+        };
+        active_model.updated_at = Set(now);
+        active_model.deleted_at = Set(Some(now));
+        time_entries::update(&txn, active_model).await?;
+        refresh_tracked_rollup(&txn, &task_id).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+/// 结束一组任务上进行中的计时器，供删除任务等用例复用。
+pub(super) async fn stop_timers_for_tasks<C>(
+    conn: &C,
+    task_ids: &[String],
+    now: i64,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    for entry in time_entries::list_active(conn).await? {
+        if task_ids.contains(&entry.task_id) {
+            let ended_at = time_entries::pomodoro_due_at(&entry, now).unwrap_or(now);
+            finish_entry(conn, entry, ended_at, now).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 结束全部进行中的计时器，返回最新的一条（通常也是唯一一条）。
+async fn stop_active_entries<C>(
+    conn: &C,
+    now: i64,
+) -> Result<Option<task_time_entries::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut latest = None;
+    for entry in time_entries::list_active(conn).await? {
+        let ended_at = time_entries::pomodoro_due_at(&entry, now).unwrap_or(now);
+        let saved = finish_entry(conn, entry, ended_at, now).await?;
+        latest.get_or_insert(saved);
+    }
+    Ok(latest)
+}
+
+/// 收口进行中的计时器：到点的番茄钟自动结束；多端同步留下的多余计时器只保留最新一条。
+async fn settle_active_entry<C>(
+    conn: &C,
+    now: i64,
+) -> Result<Option<task_time_entries::Model>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut current = None;
+    for entry in time_entries::list_active(conn).await? {
+        match time_entries::pomodoro_due_at(&entry, now) {
+            Some(due_at) => {
+                finish_entry(conn, entry, due_at, now).await?;
+            }
+            None if current.is_none() => current = Some(entry),
+            None => {
+                finish_entry(conn, entry, now, now).await?;
+            }
+        }
+    }
+    Ok(current)
+}
+
+/// 结束一条计时记录，并刷新所属项目链上的计时汇总。
+async fn finish_entry<C>(
+    conn: &C,
+    entry: task_time_entries::Model,
+    ended_at: i64,
+    now: i64,
+) -> Result<task_time_entries::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    let task_id = entry.task_id.clone();
+    let saved = time_entries::update(conn, time_entries::finish(entry, ended_at, now)).await?;
+    refresh_tracked_rollup(conn, &task_id).await?;
+    Ok(saved)
+}
+
+async fn refresh_tracked_rollup<C>(conn: &C, task_id: &str) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let task = query::find_by_id(conn, task_id).await?;
+    if let Some(project_id) = task.project_id.as_deref() {
+        stats::refresh_subtree_rollups(conn, project_id).await?;
+    }
+    Ok(())
+}
+
+/// 解析计时模式；番茄钟未指定时长时默认 25 分钟。
+fn resolve_mode(
+    mode: Option<&str>,
+    pomodoro_minutes: Option<i64>,
+) -> Result<(String, Option<i64>), AppError> {
+    match mode.map(str::trim).unwrap_or(time_entries::MODE_MANUAL) {
+        time_entries::MODE_MANUAL => {
+            if pomodoro_minutes.is_some() {
+                return Err(AppError::Validation(
+                    "仅番茄钟模式可设置 pomodoroMinutes".to_string(),
+                ));
+            }
+            Ok((time_entries::MODE_MANUAL.to_string(), None))
+        }
+        time_entries::MODE_POMODORO => {
+            let minutes = pomodoro_minutes.unwrap_or(DEFAULT_POMODORO_MINUTES);
+            if !(1..=MAX_POMODORO_MINUTES).contains(&minutes) {
+                return Err(AppError::Validation(format!(
+                    "番茄钟时长必须在 1 到 {MAX_POMODORO_MINUTES} 分钟之间"
+                )));
+            }
+            Ok((
+                time_entries::MODE_POMODORO.to_string(),
+                Some(minutes * 60_000),
+            ))
+        }
+        _ => Err(AppError::Validation(
+            "计时模式必须为 manual 或 pomodoro".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,343 @@
+//! 任务侧的撤销计划。
+//!
+//! 约定：
+//! - 创建 / 复制 / 重复生成 / 恢复 → 删除；删除 → 恢复
+//! - 完成与字段更新按字段合并成“最早旧值 → 最新新值”，用旧值拼出 patch 走正常更新用例
+//! - 当前值与日志里的新值不一致视为冲突，由调用方决定放弃整次撤销
+//! - 无法从日志还原的字段（例如格式异常的链接）记为跳过
+
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::ConnectionTrait;
+
+use crate::db::entities::{task_activity_logs, tasks};
+use crate::repos::{
+    common_task_utils, search_repo,
+    task_repo::{
+        activity_logs::{
+            ACTION_TASK_CREATED, ACTION_TASK_DELETED, ACTION_TASK_DUPLICATED,
+            ACTION_TASK_RECURRENCE_SPAWNED, ACTION_TASK_RESTORED,
+        },
+        custom_fields, dependencies, query, recurrence, stats, validations,
+    },
+};
+use crate::types::error::AppError;
+
+use super::{
+    dto::{TaskUpdateInput, TaskUpdatePatch},
+    helpers::{done_reason_to_value, parse_links_from_log, priority_to_value, to_optional_join},
+    TaskService,
+};
+
+/// 一个批次里任务日志的反向操作。
+#[derive(Debug, Default)]
+pub(crate) struct TaskUndoPlan {
+    pub restore_ids: Vec<String>,
+    pub delete_ids: Vec<String>,
+    pub updates: Vec<TaskUndoUpdate>,
+    pub conflicts: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+impl TaskUndoPlan {
+    pub fn entity_count(&self) -> usize {
+        self.restore_ids.len() + self.delete_ids.len() + self.updates.len()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TaskUndoUpdate {
+    id: String,
+    /// 目标状态不能从当前状态直接到达时，先回到 todo 再切过去
+    reset_status_first: bool,
+    patch: TaskUpdatePatch,
+}
+
+/// 同一任务同一字段在批次里的净变化。
+struct FieldChange {
+    key: String,
+    label: String,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+impl TaskService {
+    /// 根据一个批次的任务日志生成反向操作，只读不写。
+    pub(crate) async fn plan_undo<C>(
+        conn: &C,
+        logs: &[task_activity_logs::Model],
+    ) -> Result<TaskUndoPlan, AppError>
+    where
+        C: ConnectionTrait,
+    {
+        let mut task_ids = Vec::new();
+        let mut logs_by_task: HashMap<&str, Vec<&task_activity_logs::Model>> = HashMap::new();
+        for log in logs {
+            let entry = logs_by_task.entry(log.task_id.as_str()).or_default();
+            if entry.is_empty() {
+                task_ids.push(log.task_id.as_str());
+            }
+            entry.push(log);
+        }
+
+        let mut plan = TaskUndoPlan::default();
+        for task_id in task_ids {
+            let logs = &logs_by_task[task_id];
+            let Some(task) = query::find_optional_by_id(conn, task_id).await? else {
+                plan.conflicts.push(format!("任务 {task_id} 已不存在"));
+                continue;
+            };
+            let has_action = |actions: &[&str]| {
+                logs.iter()
+                    .any(|log| actions.contains(&log.action.as_str()))
+            };
+
+            if has_action(&[
+                ACTION_TASK_CREATED,
+                ACTION_TASK_DUPLICATED,
+                ACTION_TASK_RECURRENCE_SPAWNED,
+                ACTION_TASK_RESTORED,
+            ]) {
+                if task.deleted_at.is_some() {
+                    plan.conflicts
+                        .push(format!("任务「{}」已被删除", task.title));
+                } else {
+                    plan.delete_ids.push(task.id.clone());
+                }
+                continue;
+            }
+            if has_action(&[ACTION_TASK_DELETED]) {
+                if task.deleted_at.is_none() {
+                    plan.conflicts
+                        .push(format!("任务「{}」已被恢复", task.title));
+                } else {
+                    plan.restore_ids.push(task.id.clone());
+                }
+                continue;
+            }
+
+            // 剩下的是完成与字段更新日志，按写入顺序合并成每个字段的净变化。
+            let mut changes: Vec<FieldChange> = Vec::new();
+            for log in logs {
+                let Some(key) = log.field_key.as_deref() else {
+                    continue;
+                };
+                match changes.iter_mut().find(|change| change.key == key) {
+                    Some(change) => change.after = log.after_value.clone(),
+                    None => changes.push(FieldChange {
+                        key: key.to_string(),
+                        label: log.field_label.clone().unwrap_or_else(|| key.to_string()),
+                        before: log.before_value.clone(),
+                        after: log.after_value.clone(),
+                    }),
+                }
+            }
+
+            let mut patch = TaskUpdatePatch::default();
+            let mut changed_any = false;
+            for change in changes {
+                if change.before == change.after {
+                    continue;
+                }
+                let Some(current) = current_value_for_log(conn, &task, &change.key).await? else {
+                    plan.skipped
+                        .push(format!("任务「{}」的{}", task.title, change.label));
+                    continue;
+                };
+                if !same_log_value(&change.key, current.as_deref(), change.after.as_deref()) {
+                    plan.conflicts
+                        .push(format!("任务「{}」的{}已被修改", task.title, change.label));
+                    continue;
+                }
+                if apply_before_value(&mut patch, &change.key, change.before).is_some() {
+                    changed_any = true;
+                } else {
+                    plan.skipped
+                        .push(format!("任务「{}」的{}", task.title, change.label));
+                }
+            }
+            if !changed_any {
+                continue;
+            }
+
+            let mut reset_status_first = false;
+            if let Some(status) = patch.status.as_deref() {
+                let target = validations::parse_status(status)?;
+                reset_status_first =
+                    validations::ensure_status_transition(&task.status, &target).is_err();
+                if status == "done" && patch.done_reason.is_none() {
+                    patch.done_reason = Some(Some("completed".to_string()));
+                }
+            }
+            plan.updates.push(TaskUndoUpdate {
+                id: task.id.clone(),
+                reset_status_first,
+                patch,
+            });
+        }
+        Ok(plan)
+    }
+
+    /// 按计划恢复 / 回写任务，删除留给调用方在项目删除之前单独执行。
+    pub(crate) async fn apply_undo_restores_and_updates<C>(
+        txn: &C,
+        plan: &TaskUndoPlan,
+        now: i64,
+        batch_id: &str,
+    ) -> Result<(), AppError>
+    where
+        C: ConnectionTrait,
+    {
+        if !plan.restore_ids.is_empty() {
+            Self::restore_many_in(txn, &plan.restore_ids, now, Some(batch_id)).await?;
+        }
+
+        let mut touched_project_ids = HashSet::new();
+        for update in &plan.updates {
+            if update.reset_status_first {
+                touched_project_ids.extend(
+                    Self::apply_update(
+                        txn,
+                        TaskUpdateInput {
+                            id: update.id.clone(),
+                            patch: TaskUpdatePatch {
+                                status: Some("todo".to_string()),
+                                ..Default::default()
+                            },
+                        },
+                        now,
+                        Some(batch_id),
+                    )
+                    .await?,
+                );
+            }
+            touched_project_ids.extend(
+                Self::apply_update(
+                    txn,
+                    TaskUpdateInput {
+                        id: update.id.clone(),
+                        patch: update.patch.clone(),
+                    },
+                    now,
+                    Some(batch_id),
+                )
+                .await?,
+            );
+        }
+        for project_id in touched_project_ids {
+            stats::refresh_project_stats(txn, &project_id, now).await?;
+        }
+        let updated_ids = plan
+            .updates
+            .iter()
+            .map(|update| update.id.clone())
+            .collect::<Vec<_>>();
+        search_repo::reindex_tasks(txn, &updated_ids).await?;
+        Ok(())
+    }
+}
+
+/// 按日志格式读出任务某个字段的当前值；不支持的字段返回 `None`。
+async fn current_value_for_log<C>(
+    conn: &C,
+    task: &tasks::Model,
+    key: &str,
+) -> Result<Option<Option<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let value = match key {
+        "status" => Some(common_task_utils::status_to_string(&task.status)),
+        "title" => Some(task.title.clone()),
+        "priority" => Some(priority_to_value(&task.priority)),
+        "note" => task.note.clone(),
+        "spaceId" => Some(task.space_id.clone()),
+        "projectId" => task.project_id.clone(),
+        "milestoneId" => task.milestone_id.clone(),
+        "deadlineAt" => task.deadline_at.map(|value| value.to_string()),
+        "scheduledAt" => task.scheduled_at.map(|value| value.to_string()),
+        "deferUntil" => task.defer_until.map(|value| value.to_string()),
+        "rank" => Some(task.rank.clone()),
+        "doneReason" => done_reason_to_value(&task.done_reason),
+        "archivedAt" => task.archived_at.map(|value| value.to_string()),
+        "deletedAt" => task.deleted_at.map(|value| value.to_string()),
+        "customFields" => task.custom_fields.clone(),
+        "recurrence" => task.recurrence_rule.clone(),
+        "tags" => to_optional_join(query::load_task_tags_for_log(conn, &task.id).await?, ","),
+        "links" => to_optional_join(query::load_task_links_for_log(conn, &task.id).await?, ", "),
+        "blockedBy" => to_optional_join(
+            dependencies::load_blocked_by_for_tasks(conn, std::slice::from_ref(&task.id))
+                .await?
+                .remove(&task.id)
+                .unwrap_or_default(),
+            ",",
+        ),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// 标签与前置任务按集合比较，日志里的顺序取决于写入时的输入顺序。
+fn same_log_value(key: &str, current: Option<&str>, expected: Option<&str>) -> bool {
+    match key {
+        "tags" | "blockedBy" => split_log_list(current) == split_log_list(expected),
+        _ => current == expected,
+    }
+}
+
+fn split_log_list(value: Option<&str>) -> Vec<String> {
+    let mut items = value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    items.sort();
+    items
+}
+
+/// 把日志旧值写回 patch；旧值无法还原时返回 `None`。
+fn apply_before_value(
+    patch: &mut TaskUpdatePatch,
+    key: &str,
+    before: Option<String>,
+) -> Option<()> {
+    let parse_ms = |value: Option<String>| match value {
+        Some(value) => value.parse::<i64>().ok().map(Some),
+        None => Some(None),
+    };
+    match key {
+        "status" => patch.status = Some(before?),
+        "title" => patch.title = Some(before?),
+        "priority" => patch.priority = Some(before?),
+        "note" => patch.note = Some(before),
+        "spaceId" => patch.space_id = Some(before?),
+        "projectId" => patch.project_id = Some(before),
+        "milestoneId" => patch.milestone_id = Some(before),
+        "deadlineAt" => patch.deadline_at = Some(parse_ms(before)?),
+        "scheduledAt" => patch.scheduled_at = Some(parse_ms(before)?),
+        "deferUntil" => patch.defer_until = Some(parse_ms(before)?),
+        "archivedAt" => patch.archived_at = Some(parse_ms(before)?),
+        "deletedAt" => patch.deleted_at = Some(parse_ms(before)?),
+        "rank" => patch.rank = Some(before?),
+        "doneReason" => patch.done_reason = Some(before),
+        "customFields" => {
+            patch.custom_fields = Some(match before.as_deref() {
+                Some(raw) => Some(custom_fields::parse_stored(Some(raw)).ok()??),
+                None => None,
+            })
+        }
+        "recurrence" => {
+            patch.recurrence = Some(match before.as_deref() {
+                Some(raw) => Some(recurrence::parse_from_rrule_string(Some(raw))?),
+                None => None,
+            })
+        }
+        "tags" => patch.tags = Some(split_log_list(before.as_deref())),
+        "blockedBy" => patch.blocked_by = Some(split_log_list(before.as_deref())),
+        "links" => patch.links = Some(parse_links_from_log(before.as_deref())?),
+        _ => return None,
+    }
+    Some(())
+}
@@ -3,23 +3,31 @@
 //! 这是任务写路径里最重的一个入口：
 //! - 负责 patch 语义解析
 //! - 处理状态/优先级/所属项目变化
-//! - 同步标签、链接和自定义字段
+//! - 同步标签、链接、前置任务、自定义字段和重复规则
 //! - 追加字段级活动日志
 
 use std::collections::HashSet;
 
-use sea_orm::{DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, IntoActiveModel, Set, TransactionTrait};
+use uuid::Uuid;
 
 use crate::db::{
     entities::sea_orm_active_enums::{DoneReason, Priority, TaskStatus},
     now_ms,
 };
-use crate::repos::task_repo::{
-    activity_logs, custom_fields, links, mutation, query, stats, tags, validations,
+use crate::repos::{
+    common_task_utils, custom_field_repo,
+    project_repo::milestones,
+    rank, search_repo,
+    task_repo::{
+        activity_logs, custom_fields, dependencies, links, mutation, query, recurrence, stats,
+        tags, validations,
+    },
 };
 use crate::types::error::AppError;
 
 use super::{
+    dependencies::validate_blocked_by,
     dto::TaskUpdateInput,
     helpers::{
         done_reason_to_value, normalize_links_for_log, normalize_tags_for_log, priority_to_value,
@@ -34,6 +42,33 @@ impl TaskService {
     /// 这个方法的核心价值不是“写字段”，而是把任务相关的业务规则、
     /// 自动重排、日志记录和项目统计刷新统一收口在一个事务里。
     pub async fn update(conn: &DatabaseConnection, input: TaskUpdateInput) -> Result<(), AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let now = now_ms();
+        let id = input.id.clone();
+        let batch_id = Uuid::new_v4().to_string();
+        let touched_project_ids = Self::apply_update(&txn, input, now, Some(&batch_id)).await?;
+
+        for project_id in touched_project_ids {
+            stats::refresh_project_stats(&txn, &project_id, now).await?;
+        }
+        search_repo::reindex_tasks(&txn, std::slice::from_ref(&id)).await?;
+
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 在调用方事务内应用一次 patch，返回需要刷新统计的项目 id。
+    ///
+    /// 项目统计与搜索索引留给调用方统一处理，批量更新时每个项目只刷新一次。
+    pub(crate) async fn apply_update<C>(
+        txn: &C,
+        input: TaskUpdateInput,
+        now: i64,
+        batch_id: Option<&str>,
+    ) -> Result<HashSet<String>, AppError>
+    where
+        C: ConnectionTrait,
+    {
         let TaskUpdateInput { id, patch } = input;
         let title = patch.title;
         let status = patch.status;
@@ -43,25 +78,29 @@ impl TaskService {
         let tags_input = patch.tags;
         let space_id = patch.space_id;
         let requested_project_id = patch.project_id;
+        let milestone_input = patch.milestone_id;
         let deadline_at = patch.deadline_at;
+        let scheduled_at = patch.scheduled_at;
+        let defer_until = patch.defer_until;
         let rank = patch.rank;
         let links_input = patch.links;
         let custom_fields_input = patch.custom_fields;
         let archived_at = patch.archived_at;
         let deleted_at = patch.deleted_at;
+        let recurrence_input = patch.recurrence;
+        let blocked_by_input = patch.blocked_by;
         let tags_input_for_log = tags_input.clone();
         let links_input_for_log = links_input.clone();
 
-        let txn = conn.begin().await.map_err(AppError::from)?;
-        let task_model = query::find_by_id(&txn, &id).await?;
+        let task_model = query::find_by_id(txn, &id).await?;
         let previous_task = task_model.clone();
         let previous_tags_for_log = if tags_input_for_log.is_some() {
-            to_optional_join(query::load_task_tags_for_log(&txn, &id).await?, ",")
+            to_optional_join(query::load_task_tags_for_log(txn, &id).await?, ",")
         } else {
             None
         };
         let previous_links_for_log = if links_input_for_log.is_some() {
-            to_optional_join(query::load_task_links_for_log(&txn, &id).await?, ", ")
+            to_optional_join(query::load_task_links_for_log(txn, &id).await?, ", ")
         } else {
             None
         };
@@ -73,6 +112,25 @@ impl TaskService {
             .as_ref()
             .map(|items| to_optional_join(normalize_links_for_log(items), ", "))
             .unwrap_or(None);
+        let (previous_blocked_by_for_log, next_blocked_by) = match blocked_by_input.as_deref() {
+            Some(ids) => {
+                let next_ids = validate_blocked_by(txn, Some(&id), ids).await?;
+                let previous_ids =
+                    dependencies::load_blocked_by_for_tasks(txn, std::slice::from_ref(&id))
+                        .await?
+                        .remove(&id)
+                        .unwrap_or_default();
+                (to_optional_join(previous_ids, ","), Some(next_ids))
+            }
+            None => (None, None),
+        };
+        let next_blocked_by_for_log = next_blocked_by.as_ref().and_then(|ids| {
+            let mut sorted = ids.clone();
+            sorted.sort();
+            to_optional_join(sorted, ",")
+        });
+        let blocked_by_changed =
+            next_blocked_by.is_some() && previous_blocked_by_for_log != next_blocked_by_for_log;
         let tags_changed =
             tags_input_for_log.is_some() && previous_tags_for_log != next_tags_for_log;
         let links_changed =
@@ -86,11 +144,11 @@ impl TaskService {
         let effective_project_patch = match (space_id.as_deref(), requested_project_id) {
             (Some(_next_space_id), Some(Some(project_id))) => Some(Some(project_id)),
             (Some(next_space_id), Some(None)) | (Some(next_space_id), None) => {
-                Some(Some(resolve_default_project_id(&txn, next_space_id).await?))
+                Some(Some(resolve_default_project_id(txn, next_space_id).await?))
             }
             (None, Some(Some(project_id))) => Some(Some(project_id)),
             (None, Some(None)) => Some(Some(
-                resolve_default_project_id(&txn, previous_space_id.as_str()).await?,
+                resolve_default_project_id(txn, previous_space_id.as_str()).await?,
             )),
             (None, None) => None,
         };
@@ -98,18 +156,17 @@ impl TaskService {
         let mut effective_priority = task_model.priority.clone();
         let mut effective_space_id = task_model.space_id.clone();
         let mut priority_changed = false;
-        let mut status_changed_to_todo = false;
+        let mut status_bucket_changed = false;
         let mut space_changed = false;
         let mut project_changed = false;
         let mut rank_changed_by_auto_bucket = false;
 
         let mut active_model = task_model.into_active_model();
-        let now = now_ms();
         let mut touch_updated_at = false;
         let mut changed_any = false;
 
-        // tags / links 存在于独立关系表，但只更新它们时也应视为一次有效任务变更。
-        if tags_changed || links_changed {
+        // tags / links / 前置任务存在于独立关系表，但只更新它们时也应视为一次有效任务变更。
+        if tags_changed || links_changed || blocked_by_changed {
             touch_updated_at = true;
             changed_any = true;
         }
@@ -123,12 +180,12 @@ impl TaskService {
         }
 
         if let Some(status_str) = status.as_deref() {
-            let status_str = validations::normalize_status(status_str)?;
-            let is_done = status_str == "done";
+            let next_status = validations::parse_status(status_str)?;
+            validations::ensure_status_transition(&previous_status, &next_status)?;
 
             // 进入 done 时必须同步完成原因与完成时间；
-            // 回到 todo 时则要清掉这组字段。
-            if is_done {
+            // 回到未完成状态时则要清掉这组字段。
+            if next_status == TaskStatus::Done {
                 let reason_str =
                     validations::require_done_reason(done_reason.as_ref().map(|v| v.as_deref()))?;
                 let reason_enum = match reason_str.as_str() {
@@ -140,13 +197,11 @@ impl TaskService {
                 active_model.completed_at = Set(Some(now));
                 effective_status = TaskStatus::Done;
             } else {
-                active_model.status = Set(TaskStatus::Todo);
+                active_model.status = Set(next_status.clone());
                 active_model.done_reason = Set(None);
                 active_model.completed_at = Set(None);
-                effective_status = TaskStatus::Todo;
-                if previous_status == TaskStatus::Done {
-                    status_changed_to_todo = true;
-                }
+                status_bucket_changed = previous_status != next_status;
+                effective_status = next_status;
             }
             touch_updated_at = true;
             changed_any = true;
@@ -211,24 +266,67 @@ impl TaskService {
             changed_any = true;
         }
 
+        // 里程碑跟着项目走：切换项目且没有显式指定时，移出原项目的里程碑。
+        let effective_project_id = effective_project_patch
+            .clone()
+            .unwrap_or_else(|| previous_project_id.clone());
+        match milestone_input {
+            Some(Some(milestone_id)) => {
+                let milestone = milestones::find_by_id(txn, &milestone_id).await?;
+                if effective_project_id.as_deref() != Some(milestone.project_id.as_str()) {
+                    return Err(AppError::Validation(format!(
+                        "里程碑「{}」不属于任务所在的项目",
+                        milestone.title
+                    )));
+                }
+                active_model.milestone_id = Set(Some(milestone_id));
+                touch_updated_at = true;
+                changed_any = true;
+            }
+            Some(None) => {
+                active_model.milestone_id = Set(None);
+                touch_updated_at = true;
+                changed_any = true;
+            }
+            None => {
+                if previous_task.milestone_id.is_some()
+                    && effective_project_id != previous_project_id
+                {
+                    active_model.milestone_id = Set(None);
+                }
+            }
+        }
+
         if let Some(deadline_opt) = deadline_at {
             active_model.deadline_at = Set(deadline_opt);
             touch_updated_at = true;
             changed_any = true;
         }
 
-        if let Some(rank) = rank {
-            active_model.rank = Set(rank);
+        if let Some(scheduled_opt) = scheduled_at {
+            active_model.scheduled_at = Set(scheduled_opt);
+            touch_updated_at = true;
+            changed_any = true;
+        }
+
+        if let Some(defer_opt) = defer_until {
+            active_model.defer_until = Set(defer_opt);
+            touch_updated_at = true;
+            changed_any = true;
+        }
+
+        if let Some(next_rank) = rank.as_deref() {
+            active_model.rank = Set(rank::validate(next_rank)?);
             touch_updated_at = true;
             changed_any = true;
-        } else if effective_status == TaskStatus::Todo
-            && (priority_changed || status_changed_to_todo || space_changed || project_changed)
+        } else if effective_status != TaskStatus::Done
+            && (priority_changed || status_bucket_changed || space_changed || project_changed)
         {
-            // 任务重新回到 todo 桶，或者桶维度发生变化时，自动分配一个新的尾部 rank。
+            // 任务进入新的未完成状态桶，或者桶维度发生变化时，自动分配一个新的尾部 rank。
             let new_rank = query::next_rank_in_bucket(
-                &txn,
+                txn,
                 &effective_space_id,
-                &TaskStatus::Todo,
+                &effective_status,
                 &effective_priority,
             )
             .await?;
@@ -253,7 +351,13 @@ impl TaskService {
         if let Some(custom_fields_opt) = custom_fields_input {
             match custom_fields_opt {
                 Some(value) => {
-                    let normalized = custom_fields::normalize_custom_fields(value)?;
+                    let definitions = custom_field_repo::list_applicable(
+                        txn,
+                        &effective_space_id,
+                        effective_project_id.as_deref(),
+                    )
+                    .await?;
+                    let normalized = custom_fields::normalize_custom_fields(value, &definitions)?;
                     let payload = custom_fields::serialize_custom_fields(&normalized)?;
                     active_model.custom_fields = Set(Some(payload));
                 }
@@ -265,6 +369,27 @@ impl TaskService {
             changed_any = true;
         }
 
+        if let Some(recurrence_opt) = recurrence_input {
+            match recurrence_opt {
+                Some(value) => {
+                    let normalized = recurrence::normalize_rule(value)?;
+                    active_model.recurrence_rule =
+                        Set(Some(recurrence::serialize_rule(&normalized)));
+                    // 首次设置规则时，以当前任务作为系列的第一次。
+                    if previous_task.recurrence_series_id.is_none() {
+                        active_model.recurrence_series_id = Set(Some(previous_task.id.clone()));
+                        active_model.recurrence_index = Set(Some(1));
+                    }
+                }
+                None => {
+                    // 清空规则只停止后续生成，保留系列信息以便追溯历史。
+                    active_model.recurrence_rule = Set(None);
+                }
+            }
+            touch_updated_at = true;
+            changed_any = true;
+        }
+
         if !changed_any {
             return Err(AppError::Validation("没有可更新的字段".to_string()));
         }
@@ -273,19 +398,25 @@ impl TaskService {
             active_model.updated_at = Set(now);
         }
 
-        let saved_model = mutation::update(&txn, active_model).await?;
+        let saved_model = mutation::update(txn, active_model).await?;
         let new_project_id = saved_model.project_id.clone();
 
         // 主表更新之后再同步标签/链接，确保日志里拿到的是最终状态。
         if links_changed {
             if let Some(links_input) = links_input.as_ref() {
-                links::sync_links(&txn, &id, links_input).await?;
+                links::sync_links(txn, &id, links_input).await?;
             }
         }
 
         if tags_changed {
             if let Some(tags_input) = tags_input.as_ref() {
-                tags::sync_tags(&txn, &id, tags_input).await?;
+                tags::sync_tags(txn, &id, tags_input).await?;
+            }
+        }
+
+        if blocked_by_changed {
+            if let Some(blocked_by) = next_blocked_by.as_ref() {
+                dependencies::sync_dependencies(txn, &id, blocked_by, now).await?;
             }
         }
 
@@ -296,14 +427,33 @@ impl TaskService {
             project_id: saved_model.project_id.as_deref(),
             create_by: &saved_model.create_by,
             created_at: now,
+            batch_id,
         };
 
         if previous_task.status != TaskStatus::Done && saved_model.status == TaskStatus::Done {
-            activity_logs::append_completed(&txn, log_ctx.clone(), &saved_model.title).await?;
+            activity_logs::append_completed(
+                txn,
+                log_ctx.clone(),
+                &saved_model.title,
+                &common_task_utils::status_to_string(&previous_task.status),
+            )
+            .await?;
+            Self::spawn_next_occurrence(txn, &saved_model, now, batch_id).await?;
+        } else {
+            // 进入 done 已由完成日志表达，其余状态流转记为字段变更。
+            activity_logs::append_field_updated(
+                txn,
+                log_ctx.clone(),
+                "status",
+                "状态",
+                Some(common_task_utils::status_to_string(&previous_task.status)),
+                Some(common_task_utils::status_to_string(&saved_model.status)),
+            )
+            .await?;
         }
 
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "title",
             "标题",
@@ -312,7 +462,7 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "priority",
             "优先级",
@@ -321,7 +471,7 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "note",
             "备注",
@@ -330,7 +480,7 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "spaceId",
             "所属 Space",
@@ -339,7 +489,7 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "projectId",
             "所属 Project",
@@ -348,7 +498,16 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
+            log_ctx.clone(),
+            "milestoneId",
+            "里程碑",
+            previous_task.milestone_id.clone(),
+            saved_model.milestone_id.clone(),
+        )
+        .await?;
+        activity_logs::append_field_updated(
+            txn,
             log_ctx.clone(),
             "deadlineAt",
             "截止时间",
@@ -356,22 +515,40 @@ impl TaskService {
             saved_model.deadline_at.map(|value| value.to_string()),
         )
         .await?;
+        activity_logs::append_field_updated(
+            txn,
+            log_ctx.clone(),
+            "scheduledAt",
+            "计划时间",
+            previous_task.scheduled_at.map(|value| value.to_string()),
+            saved_model.scheduled_at.map(|value| value.to_string()),
+        )
+        .await?;
+        activity_logs::append_field_updated(
+            txn,
+            log_ctx.clone(),
+            "deferUntil",
+            "推迟到",
+            previous_task.defer_until.map(|value| value.to_string()),
+            saved_model.defer_until.map(|value| value.to_string()),
+        )
+        .await?;
 
         if !rank_changed_by_auto_bucket {
             activity_logs::append_field_updated(
-                &txn,
+                txn,
                 log_ctx.clone(),
                 "rank",
                 "排序权重",
-                Some(previous_task.rank.to_string()),
-                Some(saved_model.rank.to_string()),
+                Some(previous_task.rank.clone()),
+                Some(saved_model.rank.clone()),
             )
             .await?;
         }
 
         if previous_task.status == TaskStatus::Done && saved_model.status == TaskStatus::Done {
             activity_logs::append_field_updated(
-                &txn,
+                txn,
                 log_ctx.clone(),
                 "doneReason",
                 "完成原因",
@@ -382,7 +559,7 @@ impl TaskService {
         }
 
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "archivedAt",
             "归档时间",
@@ -391,7 +568,7 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "deletedAt",
             "删除时间",
@@ -400,7 +577,7 @@ impl TaskService {
         )
         .await?;
         activity_logs::append_field_updated(
-            &txn,
+            txn,
             log_ctx.clone(),
             "customFields",
             "自定义字段",
@@ -409,9 +586,19 @@ impl TaskService {
         )
         .await?;
 
+        activity_logs::append_field_updated(
+            txn,
+            log_ctx.clone(),
+            "recurrence",
+            "重复规则",
+            previous_task.recurrence_rule.clone(),
+            saved_model.recurrence_rule.clone(),
+        )
+        .await?;
+
         if tags_changed {
             activity_logs::append_field_updated(
-                &txn,
+                txn,
                 log_ctx.clone(),
                 "tags",
                 "标签",
@@ -421,9 +608,21 @@ impl TaskService {
             .await?;
         }
 
+        if blocked_by_changed {
+            activity_logs::append_field_updated(
+                txn,
+                log_ctx.clone(),
+                "blockedBy",
+                "前置任务",
+                previous_blocked_by_for_log,
+                next_blocked_by_for_log,
+            )
+            .await?;
+        }
+
         if links_changed {
             activity_logs::append_field_updated(
-                &txn,
+                txn,
                 log_ctx,
                 "links",
                 "关联链接",
@@ -441,11 +640,6 @@ impl TaskService {
             touched_project_ids.insert(project_id);
         }
 
-        for project_id in touched_project_ids {
-            stats::refresh_project_stats(&txn, &project_id, now).await?;
-        }
-
-        txn.commit().await.map_err(AppError::from)?;
-        Ok(())
+        Ok(touched_project_ids)
     }
 }
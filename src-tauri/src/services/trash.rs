@@ -0,0 +1,208 @@
+//! 回收站用例：彻底删除、清空回收站与按保留期自动清除。
+//!
+//! 约定：
+//! - 只清除已软删除的实体，未删除的 id 直接跳过
+//! - 项目连同整棵子树一起清除；子树里还有未删除的内容时不允许清除
+//! - 每清除一个实体都写墓碑，由同步把删除带到其他设备
+
+use std::collections::HashSet;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+
+use crate::db::now_ms;
+use crate::repos::{
+    project_repo::{query as project_query, ProjectRepo},
+    search_repo,
+    task_repo::query as task_query,
+    trash_repo,
+};
+use crate::types::{dto::TrashPurgeResultDto, error::AppError};
+
+const DAY_MS: i64 = 86_400_000;
+const MAX_RETENTION_DAYS: i64 = 3650;
+
+pub struct TrashService;
+
+impl TrashService {
+    /// 彻底删除回收站里的任务。
+    pub async fn purge_tasks(
+        conn: &DatabaseConnection,
+        ids: &[String],
+    ) -> Result<TrashPurgeResultDto, AppError> {
+        if ids.is_empty() {
+            return Err(AppError::Validation("请选择要彻底删除的任务".to_string()));
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let task_ids = task_query::find_deleted_by_ids(&txn, ids)
+            .await?
+            .into_iter()
+            .map(|task| task.id)
+            .collect::<Vec<_>>();
+        let result = purge_in(&txn, &task_ids, &[], now_ms()).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(result)
+    }
+
+    /// 彻底删除回收站里的项目及其整棵子树。
+    pub async fn purge_projects(
+        conn: &DatabaseConnection,
+        ids: &[String],
+    ) -> Result<TrashPurgeResultDto, AppError> {
+        if ids.is_empty() {
+            return Err(AppError::Validation("请选择要彻底删除的项目".to_string()));
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let mut project_ids = Vec::new();
+        for id in ids {
+            let project = project_query::find_by_id(&txn, id).await?;
+            if project.deleted_at.is_none() {
+                continue;
+            }
+            match collect_purgeable_subtree(&txn, id).await? {
+                Some(subtree_ids) => project_ids.extend(subtree_ids),
+                None => {
+                    return Err(AppError::Validation(format!(
+                        "项目「{}」下还有未删除的子项目或任务",
+                        project.title
+                    )))
+                }
+            }
+        }
+        let result = purge_projects_with_tasks(&txn, project_ids, now_ms()).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(result)
+    }
+
+    /// 清空回收站；子树里还有未删除内容的项目保留在回收站里。
+    pub async fn empty_trash(conn: &DatabaseConnection) -> Result<TrashPurgeResultDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let result = purge_deleted_before(&txn, None, now_ms()).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(result)
+    }
+
+    /// 清除删除时间早于保留期的回收站内容；保留天数为 0 时不做任何事。
+    pub async fn purge_expired(
+        conn: &DatabaseConnection,
+        now: i64,
+    ) -> Result<TrashPurgeResultDto, AppError> {
+        let days = trash_repo::read_retention_days(conn).await?;
+        if days <= 0 {
+            return Ok(TrashPurgeResultDto::default());
+        }
+
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let result = purge_deleted_before(&txn, Some(now - days * DAY_MS), now).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(result)
+    }
+
+    pub async fn get_retention_days(conn: &DatabaseConnection) -> Result<i64, AppError> {
+        trash_repo::read_retention_days(conn).await
+    }
+
+    pub async fn set_retention_days(conn: &DatabaseConnection, days: i64) -> Result<i64, AppError> {
+        if !(0..=MAX_RETENTION_DAYS).contains(&days) {
+            return Err(AppError::Validation(format!(
+                "回收站保留天数需在 0 到 {MAX_RETENTION_DAYS} 之间"
+            )));
+        }
+        trash_repo::write_retention_days(conn, days).await?;
+        Ok(days)
+    }
+}
+
+/// 清除删除时间早于 `before` 的任务与项目；`before` 为空时清除全部。
+async fn purge_deleted_before<C>(
+    txn: &C,
+    before: Option<i64>,
+    now: i64,
+) -> Result<TrashPurgeResultDto, AppError>
+where
+    C: ConnectionTrait,
+{
+    let task_ids = trash_repo::deleted_task_ids(txn, before).await?;
+    let mut project_ids = Vec::new();
+    let mut seen = HashSet::new();
+    for id in trash_repo::deleted_project_ids(txn, before).await? {
+        if seen.contains(&id) {
+            continue;
+        }
+        if let Some(subtree_ids) = collect_purgeable_subtree(txn, &id).await? {
+            for subtree_id in subtree_ids {
+                if seen.insert(subtree_id.clone()) {
+                    project_ids.push(subtree_id);
+                }
+            }
+        }
+    }
+
+    let mut result = purge_in(txn, &task_ids, &[], now).await?;
+    let project_result = purge_projects_with_tasks(txn, project_ids, now).await?;
+    result.purged_tasks += project_result.purged_tasks;
+    result.purged_projects += project_result.purged_projects;
+    Ok(result)
+}
+
+/// 子树里全是已删除内容时返回子树项目 id，否则返回 `None`。
+async fn collect_purgeable_subtree<C>(
+    conn: &C,
+    project_id: &str,
+) -> Result<Option<Vec<String>>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let subtree_ids = ProjectRepo::collect_subtree_ids(conn, project_id).await?;
+    if !project_query::find_not_deleted_by_ids(conn, &subtree_ids)
+        .await?
+        .is_empty()
+        || !task_query::find_not_deleted_by_project_ids(conn, &subtree_ids)
+            .await?
+            .is_empty()
+    {
+        return Ok(None);
+    }
+    Ok(Some(subtree_ids))
+}
+
+/// 清除项目，连同挂在这些项目下的（已删除）任务。
+async fn purge_projects_with_tasks<C>(
+    txn: &C,
+    project_ids: Vec<String>,
+    now: i64,
+) -> Result<TrashPurgeResultDto, AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut project_ids = project_ids;
+    let mut seen = HashSet::new();
+    project_ids.retain(|id| seen.insert(id.clone()));
+    let task_ids = trash_repo::task_ids_in_projects(txn, &project_ids).await?;
+    purge_in(txn, &task_ids, &project_ids, now).await
+}
+
+/// 硬删除并写墓碑，随后把这些实体从搜索索引里移除。
+async fn purge_in<C>(
+    txn: &C,
+    task_ids: &[String],
+    project_ids: &[String],
+    now: i64,
+) -> Result<TrashPurgeResultDto, AppError>
+where
+    C: ConnectionTrait,
+{
+    let purged_tasks = trash_repo::hard_delete_tasks(txn, task_ids).await?;
+    let purged_projects = trash_repo::hard_delete_projects(txn, project_ids, now).await?;
+    trash_repo::record_tombstones(txn, trash_repo::ENTITY_TASK, task_ids, now).await?;
+    trash_repo::record_tombstones(txn, trash_repo::ENTITY_PROJECT, project_ids, now).await?;
+
+    // 行已经不在了，重建索引等于删除对应条目。
+    search_repo::reindex_tasks(txn, task_ids).await?;
+    search_repo::reindex_projects(txn, project_ids).await?;
+    Ok(TrashPurgeResultDto {
+        purged_tasks,
+        purged_projects,
+    })
+}
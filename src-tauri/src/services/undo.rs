@@ -0,0 +1,159 @@
+//! 撤销 / 重做用例。
+//!
+//! 约定：
+//! - 一次操作就是共用同一个批次 id 的任务日志与项目日志，由各写用例在事务里生成
+//! - 撤销反转最近一次还没反转过的操作；反转本身也写出一批日志，重做就是再反转这一批
+//! - 撤销之后有了新操作，之前的撤销不再允许重做
+//! - 只要有一条日志对应的实体已被改过，整次撤销都不执行，并列出冲突
+//! - 所有回写都走 `TaskService` / `ProjectService` 的事务内入口，统计与索引随之刷新
+
+use std::collections::HashSet;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use uuid::Uuid;
+
+use crate::db::{
+    entities::{project_activity_logs, task_activity_logs},
+    now_ms,
+};
+use crate::repos::{
+    project_repo::activity_logs::ACTION_PROJECT_FIELD_UPDATED,
+    task_repo::activity_logs::ACTION_TASK_FIELD_UPDATED, undo_repo,
+};
+use crate::services::{ProjectService, TaskService};
+use crate::types::{dto::UndoResultDto, error::AppError};
+
+pub struct UndoService;
+
+impl UndoService {
+    /// 撤销最近一次操作。
+    pub async fn undo_last(conn: &DatabaseConnection) -> Result<UndoResultDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let batch_id = undo_repo::latest_undoable_batch(&txn)
+            .await?
+            .ok_or_else(|| AppError::Validation("没有可撤销的操作".to_string()))?;
+        let result = invert_batch(&txn, &batch_id, undo_repo::KIND_UNDO, None).await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(result)
+    }
+
+    /// 重做最近一次撤销。
+    pub async fn redo(conn: &DatabaseConnection) -> Result<UndoResultDto, AppError> {
+        let txn = conn.begin().await.map_err(AppError::from)?;
+        let record = undo_repo::latest_redoable_record(&txn)
+            .await?
+            .ok_or_else(|| AppError::Validation("没有可重做的操作".to_string()))?;
+        if undo_repo::has_fresh_batch_after(&txn, record.created_at).await? {
+            return Err(AppError::Validation(
+                "撤销之后已有新的操作，无法重做".to_string(),
+            ));
+        }
+        // 重做结果沿用原操作的名称，而不是撤销时写出的“恢复 / 删除”。
+        let result = invert_batch(
+            &txn,
+            &record.inverse_batch_id,
+            undo_repo::KIND_REDO,
+            Some(record.action_label.as_str()),
+        )
+        .await?;
+        txn.commit().await.map_err(AppError::from)?;
+        Ok(result)
+    }
+}
+
+/// 反转一个批次并记录下来；有冲突时不做任何写入。
+async fn invert_batch<C>(
+    txn: &C,
+    batch_id: &str,
+    kind: &str,
+    action_label: Option<&str>,
+) -> Result<UndoResultDto, AppError>
+where
+    C: ConnectionTrait,
+{
+    let task_logs = undo_repo::load_task_logs(txn, batch_id).await?;
+    let project_logs = undo_repo::load_project_logs(txn, batch_id).await?;
+    let batch_task_ids = task_logs
+        .iter()
+        .map(|log| log.task_id.clone())
+        .collect::<HashSet<_>>();
+
+    let task_plan = TaskService::plan_undo(txn, &task_logs).await?;
+    let project_plan = ProjectService::plan_undo(txn, &project_logs, &batch_task_ids).await?;
+    let conflicts = project_plan
+        .conflicts
+        .iter()
+        .chain(task_plan.conflicts.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+    if !conflicts.is_empty() {
+        let verb = if kind == undo_repo::KIND_REDO {
+            "重做"
+        } else {
+            "撤销"
+        };
+        return Err(AppError::Validation(format!(
+            "无法{verb}：{}",
+            conflicts.join("；")
+        )));
+    }
+
+    // 先恢复项目，任务才能回到可见的项目里；删除项目放在最后，避免任务回写落到已删除项目上。
+    let now = now_ms();
+    let inverse_batch_id = Uuid::new_v4().to_string();
+    ProjectService::apply_undo_restores_and_updates(txn, &project_plan, now, &inverse_batch_id)
+        .await?;
+    TaskService::apply_undo_restores_and_updates(txn, &task_plan, now, &inverse_batch_id).await?;
+    if !task_plan.delete_ids.is_empty() {
+        TaskService::delete_many_in(txn, &task_plan.delete_ids, now, Some(&inverse_batch_id))
+            .await?;
+    }
+    ProjectService::apply_undo_deletes(txn, &project_plan, now, &inverse_batch_id).await?;
+
+    let action_label = action_label
+        .map(str::to_string)
+        .unwrap_or_else(|| batch_label(&task_logs, &project_logs));
+    undo_repo::insert(
+        txn,
+        undo_repo::NewUndoRecord {
+            batch_id,
+            inverse_batch_id: &inverse_batch_id,
+            kind,
+            action_label: &action_label,
+            created_at: now,
+        },
+    )
+    .await?;
+
+    Ok(UndoResultDto {
+        batch_id: batch_id.to_string(),
+        inverse_batch_id,
+        action_label,
+        entity_count: task_plan.entity_count() + project_plan.entity_count(),
+        skipped_fields: project_plan
+            .skipped
+            .into_iter()
+            .chain(task_plan.skipped)
+            .collect(),
+    })
+}
+
+/// 操作名称优先取项目上的动作（创建、删除等），其次任务上的动作，最后才是字段更新。
+fn batch_label(
+    task_logs: &[task_activity_logs::Model],
+    project_logs: &[project_activity_logs::Model],
+) -> String {
+    project_logs
+        .iter()
+        .find(|log| log.action != ACTION_PROJECT_FIELD_UPDATED)
+        .map(|log| log.action_label.clone())
+        .or_else(|| {
+            task_logs
+                .iter()
+                .find(|log| log.action != ACTION_TASK_FIELD_UPDATED)
+                .map(|log| log.action_label.clone())
+        })
+        .or_else(|| project_logs.first().map(|log| log.action_label.clone()))
+        .or_else(|| task_logs.first().map(|log| log.action_label.clone()))
+        .unwrap_or_default()
+}
@@ -28,16 +28,31 @@ pub struct ProjectDto {
     pub priority: String,
     pub tags: Vec<String>,
     pub links: Vec<LinkDto>,
-    pub rank: i64,
+    pub rank: String,
     pub created_at: i64,
     pub updated_at: i64,
     pub archived_at: Option<i64>,
     pub deleted_at: Option<i64>,
     pub create_by: String,
+    /// 显式的生命周期状态：planned / active / paused / completed / cancelled
+    pub lifecycle_status: String,
+    pub start_at: Option<i64>,
+    pub due_at: Option<i64>,
+    /// 按任务计数推导的状态，仅作参考
     pub computed_status: String,
     pub todo_task_count: i64,
+    pub doing_task_count: i64,
+    pub waiting_task_count: i64,
+    pub someday_task_count: i64,
     pub done_task_count: i64,
     pub last_task_updated_at: Option<i64>,
+    /// 整棵子树（自身 + 全部后代项目）的任务汇总
+    pub subtree_task_count: i64,
+    pub subtree_done_task_count: i64,
+    /// 读取时已逾期的未完成任务数，只在项目列表查询时计算
+    pub subtree_overdue_task_count: i64,
+    pub subtree_last_activity_at: Option<i64>,
+    pub subtree_tracked_ms: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,7 +63,7 @@ pub struct TaskDto {
     pub project_id: Option<String>,
     pub title: String,
     pub note: Option<String>,
-    /// todo / done（展示层字符串，避免前端绑定后端枚举）
+    /// todo / doing / waiting / someday / done（展示层字符串，避免前端绑定后端枚举）
     pub status: String,
     /// completed / cancelled（仅 status=done 时有效）
     pub done_reason: Option<String>,
@@ -56,12 +71,16 @@ pub struct TaskDto {
     pub tags: Vec<String>, // 从关联表聚合填充
     /// 外部链接列表
     pub links: Vec<LinkDto>,
-    pub rank: i64,
+    pub rank: String,
     pub created_at: i64,
     pub updated_at: i64,
     pub completed_at: Option<i64>,
     /// 截止日期（时间戳毫秒）
     pub deadline_at: Option<i64>,
+    /// 计划处理时间（时间戳毫秒）
+    pub scheduled_at: Option<i64>,
+    /// 推迟到该时间之前不在默认列表中出现（时间戳毫秒）
+    pub defer_until: Option<i64>,
     /// 归档时间（时间戳毫秒）
     pub archived_at: Option<i64>,
     /// 软删除时间（时间戳毫秒）
@@ -70,6 +89,341 @@ pub struct TaskDto {
     pub custom_fields: Option<CustomFieldsDto>,
     /// 创建者
     pub create_by: String,
+    /// 重复规则（未设置表示非重复任务）
+    pub recurrence: Option<RecurrenceRuleDto>,
+    /// 所属重复系列 id
+    pub recurrence_series_id: Option<String>,
+    /// 所属里程碑 id
+    pub milestone_id: Option<String>,
+    /// 检查清单总项数（不含已删除）
+    pub checklist_total: i64,
+    /// 检查清单已完成项数
+    pub checklist_done: i64,
+    /// 前置任务 id 列表
+    pub blocked_by: Vec<String>,
+    /// 是否仍存在未完成的前置任务
+    pub blocked: bool,
+    /// 已结束计时记录的累计时长（毫秒）
+    pub tracked_ms: i64,
+}
+
+/// 完成任务后的派生结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompleteResultDto {
+    /// 因本次完成而解除阻塞的任务 id
+    pub unblocked_task_ids: Vec<String>,
+    /// 按重复规则生成的下一次任务 id
+    pub next_occurrence_task_id: Option<String>,
+}
+
+/// 快速录入的解析预览。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddPreviewDto {
+    pub title: String,
+    pub space_id: String,
+    pub project_id: Option<String>,
+    /// 命中项目的完整路径；未指定项目时为空（落到 space 默认项目）
+    pub project_path: Option<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<String>,
+    pub deadline_at: Option<i64>,
+    pub recurrence: Option<RecurrenceRuleDto>,
+}
+
+/// 快速录入结果；预览模式下 `task` 为空。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddResultDto {
+    pub preview: QuickAddPreviewDto,
+    pub task: Option<TaskDto>,
+}
+
+/// 批量更新中单个任务的结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateItemResultDto {
+    pub id: String,
+    pub ok: bool,
+    /// 失败时的错误码，与命令错误的 `code` 一致
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// 批量更新结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateResultDto {
+    /// 本批活动日志共用的批次 id；没有任何任务更新成功时为空
+    pub batch_id: Option<String>,
+    /// 成功更新的任务数
+    pub updated: usize,
+    /// 按请求顺序（去重后）逐条返回
+    pub results: Vec<BulkUpdateItemResultDto>,
+}
+
+/// 任务分页查询结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPageDto {
+    pub items: Vec<TaskDto>,
+    /// 下一页游标；为空表示已经是最后一页
+    pub next_cursor: Option<String>,
+}
+
+/// “今天”视图：各分组互不重复，按 逾期 → 今天截止 → 今天计划 → 进行中 的顺序归组。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodayViewDto {
+    /// 本地“今天”的起止时间（时间戳毫秒），`dayStart` 含、`dayEnd` 不含
+    pub day_start: i64,
+    pub day_end: i64,
+    /// 截止时间早于今天
+    pub overdue: Vec<TaskDto>,
+    /// 截止时间在今天
+    pub due_today: Vec<TaskDto>,
+    /// 计划时间在今天或更早
+    pub scheduled_today: Vec<TaskDto>,
+    /// 其余进行中的任务
+    pub doing: Vec<TaskDto>,
+}
+
+/// 时间范围过滤（时间戳毫秒），`from` 含、`to` 不含。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRangeDto {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+/// 任务排序键。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSortDto {
+    /// priority / rank / deadlineAt / scheduledAt / createdAt / updatedAt / completedAt / title
+    pub key: String,
+    /// asc / desc，默认 asc
+    pub direction: Option<String>,
+}
+
+/// 自定义字段过滤条件，按字段标题匹配。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldPredicateDto {
+    pub title: String,
+    /// eq / ne / contains / empty / notEmpty / includes / gt / gte / lt / lte
+    pub op: String,
+    pub value: Option<String>,
+}
+
+/// 按自定义字段取值分组的统计结果（每个项目 × 每个取值一行）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldAggregateDto {
+    pub project_id: Option<String>,
+    /// 为空表示任务没有该字段或取值为空
+    pub value: Option<String>,
+    pub count: i64,
+    /// 求和字段的数值合计；未指定求和字段或整组都没有取值时为空
+    pub sum: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItemDto {
+    pub id: String,
+    pub task_id: String,
+    pub title: String,
+    pub done: bool,
+    pub rank: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 任务计时记录。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryDto {
+    pub id: String,
+    pub task_id: String,
+    /// manual / pomodoro
+    pub mode: String,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+    pub paused_at: Option<i64>,
+    pub paused_ms: i64,
+    pub planned_ms: Option<i64>,
+    /// 有效时长：已结束取最终值，进行中按当前时间计算
+    pub elapsed_ms: i64,
+    /// 番茄钟剩余时长；manual 模式为空
+    pub remaining_ms: Option<i64>,
+    pub running: bool,
+    pub note: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 任务提醒。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskReminderDto {
+    pub id: String,
+    pub task_id: String,
+    /// 提前提醒的分钟数；0 表示在截止时刻提醒
+    pub offset_minutes: i64,
+    /// 按当前截止时间计算的计划提醒时刻；任务无截止时间时为空
+    pub fire_at: Option<i64>,
+    /// 下一次实际触发时刻（考虑稍后提醒）；已触发或已忽略时为空
+    pub next_fire_at: Option<i64>,
+    pub last_fired_at: Option<i64>,
+    pub snoozed_until: Option<i64>,
+    pub dismissed_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 调度器触发的一次提醒，供桌面通知与前端事件使用。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderFireDto {
+    pub reminder_id: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub deadline_at: i64,
+    pub offset_minutes: i64,
+    pub fired_at: i64,
+}
+
+/// 任务模板内容；文本字段支持 `{{date}}` / `{{project}}` / `{{space}}` 等占位符。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TaskTemplateBodyDto {
+    pub title: String,
+    pub note: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub links: Vec<LinkInputDto>,
+    pub custom_fields: Option<CustomFieldsDto>,
+    pub recurrence: Option<RecurrenceRuleDto>,
+    /// 截止时间相对实例化时刻的偏移分钟数
+    pub deadline_offset_minutes: Option<i64>,
+    /// 计划时间相对实例化时刻的偏移分钟数
+    pub scheduled_offset_minutes: Option<i64>,
+}
+
+/// 任务模板。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTemplateDto {
+    pub id: String,
+    pub name: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub body: TaskTemplateBodyDto,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 项目模板中的一个项目节点，带起始任务与子项目。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectTemplateNodeDto {
+    pub title: String,
+    pub note: Option<String>,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub links: Vec<LinkInputDto>,
+    pub tasks: Vec<TaskTemplateBodyDto>,
+    pub children: Vec<ProjectTemplateNodeDto>,
+}
+
+/// 项目模板。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTemplateDto {
+    pub id: String,
+    pub name: String,
+    pub root: ProjectTemplateNodeDto,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 项目里程碑。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMilestoneDto {
+    pub id: String,
+    pub project_id: String,
+    pub space_id: String,
+    pub title: String,
+    /// 目标日期（时间戳毫秒）
+    pub target_at: Option<i64>,
+    pub rank: i64,
+    /// 完成时间；为空表示未完成
+    pub completed_at: Option<i64>,
+    /// 挂在该里程碑下的任务数（不含已归档 / 已删除）
+    pub task_count: i64,
+    pub done_task_count: i64,
+    /// 完成进度，0 到 1
+    pub progress: f64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 项目计时汇总。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTimeRollupDto {
+    pub project_id: String,
+    /// 仅项目自身任务
+    pub own_ms: i64,
+    /// 项目及全部子项目任务
+    pub subtree_ms: i64,
+}
+
+/// 项目时间线上的一条记录。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTimelineEntryDto {
+    /// taskCompleted / taskLog / projectLog / note / snippet / diary
+    pub entry_type: String,
+    /// 对应记录的 id（任务完成时为任务 id）
+    pub entry_id: String,
+    pub occurred_at: i64,
+    /// 记录所属的项目（子树内的某一个）
+    pub project_id: String,
+    pub task_id: Option<String>,
+    pub title: String,
+    pub detail: Option<String>,
+}
+
+/// 项目时间线分页结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTimelinePageDto {
+    pub items: Vec<ProjectTimelineEntryDto>,
+    /// 下一页游标；为空表示已经是最后一页
+    pub next_cursor: Option<String>,
+}
+
+/// 排期有风险的项目。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRiskDto {
+    pub project_id: String,
+    pub title: String,
+    pub path: String,
+    pub lifecycle_status: String,
+    pub due_at: i64,
+    /// 以下计数都按整棵子树统计
+    pub task_count: i64,
+    pub open_task_count: i64,
+    /// 截止时间晚于项目截止时间的未完成任务数
+    pub late_task_count: i64,
+    /// dueSoon / lateTasks
+    pub reasons: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,6 +462,41 @@ pub struct CustomFieldItemDto {
     pub value: Option<String>,
 }
 
+/// 自定义字段定义。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldDefinitionDto {
+    pub id: String,
+    pub space_id: String,
+    /// 为空表示 space 级定义
+    pub project_id: Option<String>,
+    pub title: String,
+    /// text / number / date / select / multiSelect / checkbox / url
+    pub field_type: String,
+    /// 选择类字段的候选项
+    pub options: Vec<String>,
+    pub rank: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// RRULE 风格的任务重复规则。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceRuleDto {
+    /// daily / weekly / monthly
+    pub freq: String,
+    /// 间隔周期数，默认 1
+    pub interval: Option<i64>,
+    /// 仅 weekly 可用：MO / TU / WE / TH / FR / SA / SU
+    #[serde(default)]
+    pub by_weekday: Vec<String>,
+    /// 截止时间（时间戳毫秒），超过后不再生成
+    pub until: Option<i64>,
+    /// 系列总次数（含第一次）
+    pub count: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivityLogDto {
@@ -125,6 +514,32 @@ pub struct ActivityLogDto {
     pub space_id: String,
     pub project_id: Option<String>,
     pub project_name: String,
+    /// 同一次写操作的批次 id；历史数据为空
+    pub batch_id: Option<String>,
+}
+
+/// 撤销 / 重做结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoResultDto {
+    /// 被反转的操作批次
+    pub batch_id: String,
+    /// 反转时写出的日志批次；再反转它就是重做
+    pub inverse_batch_id: String,
+    /// 被反转操作的名称，例如“删除任务”
+    pub action_label: String,
+    /// 实际回写的任务与项目数
+    pub entity_count: usize,
+    /// 无法从日志还原、因此没有回写的字段
+    pub skipped_fields: Vec<String>,
+}
+
+/// 彻底删除结果。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashPurgeResultDto {
+    pub purged_tasks: usize,
+    pub purged_projects: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -201,3 +616,25 @@ pub struct AssetsMigrationStatusDto {
     pub done: bool,
     pub migrated_at: Option<i64>,
 }
+
+/// 全文搜索命中项。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHitDto {
+    /// task / project / note / snippet / diary
+    pub entity_type: String,
+    pub entity_id: String,
+    pub space_id: Option<String>,
+    pub project_id: Option<String>,
+    pub title: String,
+    /// 已做 HTML 转义，命中片段包在 `<mark>` 中
+    pub highlighted_title: String,
+    /// 正文里第一处命中附近的摘要，格式同 `highlighted_title`
+    pub highlighted_snippet: Option<String>,
+    pub tags: Vec<String>,
+    /// 相关度得分，越大越相关
+    pub score: f64,
+    pub deleted: bool,
+    pub archived: bool,
+    pub updated_at: i64,
+}
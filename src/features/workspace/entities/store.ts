@@ -1,6 +1,7 @@
 import { defineStore } from 'pinia'
 import { ref } from 'vue'
 import type { TaskStatus } from '@/infra/api/tasks'
+import { compareRank } from '@/shared/lib/rank'
 
 import {
 	createEmptyWorkspaceTaskIdsByStatus,
@@ -29,11 +30,11 @@ function compareWorkspaceTasks(left: WorkspaceEntityTask, right: WorkspaceEntity
 	if (left.status === 'done' && right.status === 'done') {
 		return (right.completedAt ?? 0) - (left.completedAt ?? 0)
 	}
-	return left.rank - right.rank
+	return compareRank(left.rank, right.rank)
 }
 
 function compareWorkspaceProjects(left: WorkspaceEntityProject, right: WorkspaceEntityProject): number {
-	if (left.rank !== right.rank) return left.rank - right.rank
+	if (left.rank !== right.rank) return compareRank(left.rank, right.rank)
 	return left.title.localeCompare(right.title, 'zh-CN')
 }
 
@@ -6,12 +6,13 @@ export async function deleteWorkspaceProject(projectId: string): Promise<void> {
 
 export async function reorderWorkspaceProject(
 	projectId: string,
-	newRank: number,
+	prevProjectId: string | null,
+	nextProjectId: string | null,
 	newParentId?: string | null,
-): Promise<void> {
-	await reorderProject(projectId, newRank, newParentId)
+): Promise<string> {
+	return await reorderProject(projectId, prevProjectId, nextProjectId, newParentId)
 }
 
-export async function rebalanceWorkspaceProjectRanks(projectIds: string[], step = 1024): Promise<void> {
-	await rebalanceProjectRanks(projectIds, step)
+export async function rebalanceWorkspaceProjectRanks(projectIds: string[]): Promise<void> {
+	await rebalanceProjectRanks(projectIds)
 }
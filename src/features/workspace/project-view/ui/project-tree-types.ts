@@ -4,7 +4,7 @@ export type ProjectTreeItem = {
 	label: string
 	icon: string
 	iconClass: string
-	rank: number
+	rank: string
 	createdAt: number
 	children?: ProjectTreeItem[]
 }
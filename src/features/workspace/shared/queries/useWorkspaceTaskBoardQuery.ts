@@ -2,6 +2,7 @@ import { useQuery } from '@pinia/colada'
 import { computed, type MaybeRefOrGetter, toValue } from 'vue'
 
 import { listTasks, type TaskStatus } from '@/infra/api/tasks'
+import { compareRank } from '@/shared/lib/rank'
 import { useStoneFlowQueryCache } from '@/shared/query'
 import { useWorkspaceEntityRepository } from '../../entities/repository'
 import { createWorkspaceTaskScopeKey } from '../../entities/indexes'
@@ -63,7 +64,7 @@ function sortTasksForScope(tasks: WorkspaceTask[], scope: WorkspaceTaskListScope
 		return [...tasks].sort((left, right) => (right.completedAt ?? 0) - (left.completedAt ?? 0))
 	}
 
-	return [...tasks].sort((left, right) => left.rank - right.rank)
+	return [...tasks].sort((left, right) => compareRank(left.rank, right.rank))
 }
 
 /**
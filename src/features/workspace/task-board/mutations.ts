@@ -16,10 +16,14 @@ export async function deleteWorkspaceTasks(taskIds: string[]): Promise<number> {
 	return await deleteTasks(taskIds)
 }
 
-export async function reorderWorkspaceTask(taskId: string, newRank: number): Promise<void> {
-	await reorderTask(taskId, newRank)
+export async function reorderWorkspaceTask(
+	taskId: string,
+	prevTaskId: string | null,
+	nextTaskId: string | null,
+): Promise<string> {
+	return await reorderTask(taskId, prevTaskId, nextTaskId)
 }
 
-export async function rebalanceWorkspaceTaskRanks(taskIds: string[], step = 1024): Promise<void> {
-	await rebalanceRanks(taskIds, step)
+export async function rebalanceWorkspaceTaskRanks(taskIds: string[]): Promise<void> {
+	await rebalanceRanks(taskIds)
 }
@@ -17,7 +17,7 @@ export type TrashProject = {
 	archivedAt: number | null
 	deletedAt: number | null
 	createBy: string
-	rank: number
+	rank: string
 	computedStatus: ProjectDto['computedStatus']
 	tags: string[]
 	links: ProjectDto['links']
@@ -33,7 +33,7 @@ export type TrashTask = {
 	doneReason: TaskDto['doneReason']
 	priority: TaskDto['priority']
 	tags: string[]
-	rank: number
+	rank: string
 	createdAt: number
 	updatedAt: number
 	completedAt: number | null
@@ -18,7 +18,7 @@ export type ProjectDto = {
 	archivedAt: number | null
 	deletedAt: number | null
 	createBy: string
-	rank: number
+	rank: string
 	computedStatus: ProjectComputedStatusValue
 	tags: string[]
 	links: LinkDto[]
@@ -34,7 +34,7 @@ export type CreateProjectArgs = {
 	parentId?: string | null
 	note?: string | null
 	priority?: ProjectPriorityValue | null
-	rank?: number | null
+	rank?: string | null
 	tags?: string[] | null
 	links?: LinkInput[] | null
 }
@@ -155,25 +155,32 @@ export async function unarchiveProject(projectId: string): Promise<void> {
 }
 
 /**
- * 更新项目的 rank（和可选的 parentId）用于拖拽排序
+ * 把项目移动到两个相邻同级项目之间（和可选的 parentId）用于拖拽排序，由后端计算并返回新的 rank
+ * @param prevProjectId 移动后紧挨在上方的同级项目（插入第一位时为 null）
+ * @param nextProjectId 移动后紧挨在下方的同级项目（插入最后时为 null）
  */
-export async function reorderProject(projectId: string, newRank: number, newParentId?: string | null): Promise<void> {
-	await tauriInvoke<void>('reorder_project', {
+export async function reorderProject(
+	projectId: string,
+	prevProjectId: string | null,
+	nextProjectId: string | null,
+	newParentId?: string | null,
+): Promise<string> {
+	return await tauriInvoke<string>('reorder_project', {
 		args: {
 			projectId,
-			newRank,
+			prevProjectId,
+			nextProjectId,
 			newParentId: newParentId !== undefined ? newParentId : undefined,
 		},
 	})
 }
 
 /**
- * 批量重排项目 rank（用于阈值触发的无感重排）
+ * 按给定顺序整体重排项目 rank（只在相邻项目之间没有空位时需要）
  * @param projectIds 按顺序排列的项目 ID 列表
- * @param step 步长，默认 1024
  */
-export async function rebalanceProjectRanks(projectIds: string[], step = 1024): Promise<void> {
+export async function rebalanceProjectRanks(projectIds: string[]): Promise<void> {
 	await tauriInvoke<void>('rebalance_project_ranks', {
-		args: { projectIds, step },
+		args: { projectIds },
 	})
 }
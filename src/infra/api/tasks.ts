@@ -41,7 +41,7 @@ export type TaskDto = {
 	doneReason: TaskDoneReason | null
 	priority: TaskPriorityValue
 	tags: string[]
-	rank: number
+	rank: string
 	createdAt: number
 	updatedAt: number
 	completedAt: number | null
@@ -126,7 +126,7 @@ export type UpdateTaskPatch = {
 	spaceId?: string
 	projectId?: string | null
 	deadlineAt?: number | null
-	rank?: number
+	rank?: string
 	links?: LinkInput[]
 	customFields?: CustomFields | null
 	archivedAt?: number | null
@@ -158,21 +158,22 @@ export async function restoreTasks(ids: string[]): Promise<number> {
 }
 
 /**
- * 更新单个任务的 rank
+ * 把任务移动到两个相邻任务之间，由后端计算并返回新的 rank
+ * @param prevTaskId 移动后紧挨在上方的任务（插入第一位时为 null）
+ * @param nextTaskId 移动后紧挨在下方的任务（插入最后时为 null）
  */
-export async function reorderTask(taskId: string, newRank: number): Promise<void> {
-	await tauriInvoke<void>('reorder_task', {
-		args: { taskId, newRank },
+export async function reorderTask(taskId: string, prevTaskId: string | null, nextTaskId: string | null): Promise<string> {
+	return await tauriInvoke<string>('reorder_task', {
+		args: { taskId, prevTaskId, nextTaskId },
 	})
 }
 
 /**
- * 批量重排任务 rank（用于阈值触发的无感重排）
+ * 按给定顺序整体重排任务 rank（只在相邻任务之间没有空位时需要）
  * @param taskIds 按顺序排列的任务 ID 列表
- * @param step 步长，默认 1024
  */
-export async function rebalanceRanks(taskIds: string[], step = 1024): Promise<void> {
+export async function rebalanceRanks(taskIds: string[]): Promise<void> {
 	await tauriInvoke<void>('rebalance_ranks', {
-		args: { taskIds, step },
+		args: { taskIds },
 	})
 }